@@ -1,4 +1,4 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
 
 use anyhow::Context;
 use ecolor::Color32;
@@ -9,36 +9,167 @@ use log::{debug, info};
 
 use crate::app::local_experiments::SingleMax;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::app::py_experiment::PyExperiment;
+use crate::app::{
+    about::UpdateCheck, autosave::Autosave, backups::Backups, file_watch::FileWatch,
+    py_experiment::PyExperiment, run_artifacts::RunArtifacts, screenshots::Screenshots,
+    variant_export::VariantExport,
+};
 
 use self::{
-    data_conversion::ConvertToSeries as _,
-    data_definition::{Data, DataLabel, DataPoint, DistanceCalculation, PointArray, Save as _},
+    add_point_dialog::AddPointDialog,
+    affine::AffineTransform,
+    batch_add::BatchAdd,
+    binned_view::BinnedView,
+    brush::{Brush, BrushStroke},
+    class_spread::ClassSpread,
+    convex_hulls::ConvexHulls,
+    coordinate_frames::CoordinateFrame,
+    data_definition::{
+        content_hash, CsvLoad, CsvMappings, Data, DataLabel, DataPoint, DataPoints, DataTimestamp,
+        DistanceCalculation, MatVariableNames, PendingCsvLoad, PointArray, Save as _,
+    },
+    data_generation::{GeneratorParams, GeneratorRegistry, PolarGenerator},
+    dedupe::Dedupe,
+    delete_picker::{DeleteTieBreak, PendingDelete},
+    display_precision::DisplayPrecision,
+    double_click::DoubleClickAction,
+    drag_move::MovingPoint,
+    drag_select::{DragSelect, SelectShape},
+    eraser::{Eraser, EraserStroke},
+    export_subset::ExportSubset,
+    external_scores::ExternalScores,
+    formula_edit::FormulaEdit,
+    grid_snap::GridSnap,
+    history_replay::HistoryPlayer,
+    jitter::Jitter,
+    kmeans::KMeansLabeling,
+    label_classes::LabelClasses,
+    large_load::LargeLoad,
+    legend_summary::LegendSummary,
     local_experiments::{
-        LocalExperiment, ModelInference, ModelInferenceConfig as _, ModelTrain as _,
-        ProximityScore, TrainResults, Trained, UnTrained,
+        IdleDebounce, LocalExperiment, ModelInference, ModelTrain as _, ProximityScore,
+        TrainResults, Trained, UnTrained,
     },
-    operational_state::{OperationOutcome, OperationalState, Payload},
-    plot_zoom_reset::StatePlotResetZoom,
+    marker_shapes::MarkerShapes,
+    mat_variables::MatVariablesDialog,
+    measure::Measurement,
+    mirror_placement::MirrorPlacement,
+    normalize::Normalize,
+    operational_state::{LoadProgress, OperationOutcome, OperationalState, Payload},
+    outlier_pruning::OutlierPruning,
+    overlay_cache::OverlayCache,
+    oversample::Oversample,
+    plot_zoom_reset::{StatePlotResetZoom, ZoomTarget},
     prediction_classification::{prediction_classification, Classification},
+    reference_layer::ReferenceLayer,
+    row_link::RowLink,
+    score_contours::ScoreContours,
+    score_delta::ReferenceScores,
+    score_heatmap::ScoreHeatmap,
+    selection::Selection,
+    series_visibility::{PlotSeries, SeriesVisibility},
+    sort_points::SortPoints,
+    stamp::Stamp,
     status_msg::StatusMsg,
+    subsample::Subsample,
+    table_add_row::TableAddRow,
+    table_columns::TableColumns,
+    table_keyboard::TableKeyboard,
+    train_test_split::TrainTestSplit,
     ui_blocks::OptionEditNumeric,
 };
 
-mod data_conversion;
-mod data_definition;
+mod about;
+mod add_point_dialog;
+mod affine;
+mod annotations;
+#[cfg(all(feature = "automation", not(target_arch = "wasm32")))]
+pub mod automation;
+#[cfg(not(target_arch = "wasm32"))]
+mod autosave;
+#[cfg(not(target_arch = "wasm32"))]
+mod backups;
+mod batch_add;
+mod binned_view;
+mod brush;
+mod class_spread;
+mod clipboard;
+mod convex_hulls;
+mod coordinate_frames;
+mod crosshair;
+pub(crate) mod data_definition;
+pub(crate) mod data_generation;
+mod dataset_settings;
+mod dedupe;
+mod delete_picker;
+mod display_precision;
 mod display_slice;
+mod double_click;
+mod drag_move;
+mod drag_select;
+mod eraser;
+mod export_results;
+mod export_subset;
+mod external_scores;
+#[cfg(not(target_arch = "wasm32"))]
+mod file_watch;
+mod formula_edit;
+mod generate_menu;
+mod grid_snap;
+mod history_replay;
+mod jitter;
+mod kmeans;
+mod label_classes;
+mod large_load;
+mod legend_summary;
 mod local_experiments;
+mod marker_shapes;
+mod mat_variables;
+mod measure;
+mod mirror_placement;
+mod normalize;
 mod operational_state;
+mod outlier_pruning;
+mod overlay_cache;
+mod oversample;
+mod plot_overlays;
 mod plot_zoom_reset;
+mod point_groups;
+mod point_tooltip;
 mod prediction_classification;
+mod presentation;
+mod project;
 #[cfg(not(target_arch = "wasm32"))]
 mod py_experiment;
+mod reference_layer;
+mod regions;
+mod row_link;
+#[cfg(not(target_arch = "wasm32"))]
+mod run_artifacts;
+mod score_contours;
+mod score_delta;
+mod score_heatmap;
+#[cfg(not(target_arch = "wasm32"))]
+mod screenshots;
+mod selection;
+mod selection_actions;
+mod series_visibility;
+mod settings_transfer;
+mod sort_points;
+mod stamp;
+mod startup;
 mod status_msg;
+mod subsample;
+mod table_add_row;
+mod table_cells;
+mod table_columns;
+mod table_keyboard;
+mod threshold_presets;
+mod train_test_split;
+mod trash;
 mod ui_blocks;
-
-// TODO 5: Add support for adding notes to plot (Separate save button for annotations or save only depending on if we can integrate them, easy to do on file for matlab but csv?)
-// TODO 5: Investigate supporting bounding boxes
+#[cfg(not(target_arch = "wasm32"))]
+mod variant_export;
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize, PartialEq)]
@@ -46,28 +177,151 @@ mod ui_blocks;
 pub struct DBV {
     /// Controls the size of the points
     marker_radius: f32,
+    display_precision: DisplayPrecision,
     color_normal: Color32,
     color_anom: Color32,
     color_results_false_negatives: Color32,
     color_results_false_positives: Color32,
     color_results_true_negatives: Color32,
     color_results_true_positives: Color32,
+    color_results_borderline: Color32,
+    color_selection: Color32,
+    marker_shapes: MarkerShapes,
+    label_classes: LabelClasses,
+    series_visibility: SeriesVisibility,
     data: Data,
     click_mode: ClickMode,
     primary_click_label: DataLabel,
+    /// When adding, the primary click uses the majority label of the nearest points instead
+    suggest_label_from_neighbors: bool,
+    /// Number of neighbors considered when suggesting a label
+    label_suggestion_k: usize,
+    delete_tie_break: DeleteTieBreak,
+    /// Distance from the cursor in screen points that a point can be to be picked for deletion
+    delete_pick_radius: f32,
+    #[serde(skip)]
+    pending_delete: Option<PendingDelete>,
     allow_boxed_zoom: bool,
     show_data_only: bool,
+    /// Set while presenting to what to restore when leaving presentation mode
+    #[serde(skip)]
+    show_data_only_before_presentation: Option<bool>,
     display_mode: DisplayMode,
+    binned_view: BinnedView,
     on_load_reset_plot_zoom: bool,
     show_plot_legend: bool,
+    show_point_tooltip: bool,
+    legend_summary: LegendSummary,
     show_plot_grid_lines: bool,
     show_plot_bounds: bool,
     show_points_color_picker: bool,
+    show_groups_window: bool,
+    show_group_outlines: bool,
+    show_trash_window: bool,
+    duplicate_offset: [f64; 2],
+    duplicate_should_flip_label: bool,
+    /// Number of copies made of each selected point
+    duplicate_count: usize,
+    jitter: Jitter,
+    normalize: Normalize,
+    formula_edit: FormulaEdit,
+    affine: AffineTransform,
+    show_affine_window: bool,
+    dedupe: Dedupe,
+    subsample: Subsample,
+    table_columns: TableColumns,
+    table_add_row: TableAddRow,
+    row_link: RowLink,
+    train_test_split: TrainTestSplit,
+    oversample: Oversample,
+    outlier_pruning: OutlierPruning,
+    show_outlier_pruning_window: bool,
+    kmeans: KMeansLabeling,
+    show_kmeans_window: bool,
+    sort_points: SortPoints,
+    grid_snap: GridSnap,
+    add_point_dialog: AddPointDialog,
+    batch_add: BatchAdd,
+    show_polar_grid: bool,
+    show_minimap: bool,
+    show_nearest_neighbors: bool,
+    show_density: bool,
+    show_voronoi: bool,
+    /// Calculate the overlays in the background after the data stops changing
+    precompute_overlays_when_idle: bool,
+    #[serde(skip)]
+    overlay_cache: OverlayCache,
+    show_score_heatmap: bool,
+    #[serde(skip)]
+    score_heatmap: ScoreHeatmap,
+    score_contours: ScoreContours,
+    /// Center requested by clicking on the mini-map that will be applied on the next frame
+    #[serde(skip)]
+    minimap_jump: Option<PointArray>,
+    polar_grid_spacing: f64,
+    /// Frames saved by the user (Built in frames are not included)
+    coordinate_frames: Vec<CoordinateFrame>,
+    #[serde(skip)]
+    coordinate_frame_name: String,
+    polar_generator: PolarGenerator,
+    /// Generators listed in the Generate menu (Includes any added by downstream builds)
+    #[serde(skip)]
+    generator_registry: GeneratorRegistry,
+    /// Last used parameters of the registered generators by name
+    generator_params: BTreeMap<String, GeneratorParams>,
+    /// Name of the registered generator whose window is open
+    #[serde(skip)]
+    open_generator: Option<String>,
+    /// Scores of a previous model to compare the current model against
+    reference_scores: Option<ReferenceScores>,
+    show_score_delta: bool,
+    #[serde(skip)]
+    show_polar_generator: bool,
+    /// Name typed for the next threshold preset to be saved
+    #[serde(skip)]
+    threshold_preset_name: String,
+    #[serde(skip)]
+    show_export_subset: bool,
+    #[serde(skip)]
+    export_subset: ExportSubset,
     shortcut_undo: KeyboardShortcut,
     shortcut_redo: KeyboardShortcut,
     #[cfg(not(target_arch = "wasm32"))]
     py_experiment: PyExperiment,
+    #[cfg(not(target_arch = "wasm32"))]
+    autosave: Autosave,
+    #[cfg(not(target_arch = "wasm32"))]
+    screenshots: Screenshots,
+    #[cfg(not(target_arch = "wasm32"))]
+    variant_export: VariantExport,
+    #[cfg(not(target_arch = "wasm32"))]
+    file_watch: FileWatch,
+    #[cfg(not(target_arch = "wasm32"))]
+    backups: Backups,
+    /// Most recently loaded or saved projects first
+    #[cfg(not(target_arch = "wasm32"))]
+    recent_projects: Vec<PathBuf>,
+    show_startup_chooser: bool,
+    #[serde(skip)]
+    is_startup_chooser_open: bool,
+    #[serde(skip)]
+    is_about_open: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    update_check: UpdateCheck,
+    #[cfg(not(target_arch = "wasm32"))]
+    run_artifacts: RunArtifacts,
+    /// Data found in the recovery file at startup waiting for the user to decide to restore it
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    recovered_data: Option<Data>,
     loc_experiment: LocalExperiment,
+    /// Column mappings selected for CSV files that are not in the layout used for saving
+    csv_mappings: CsvMappings,
+    /// Names of the variables used when loading and saving MAT-files
+    mat_variable_names: MatVariableNames,
+    /// The file the data was last saved to or loaded from (Used to revert)
+    #[cfg(not(target_arch = "wasm32"))]
+    last_saved_path: Option<PathBuf>,
     #[serde(skip)]
     should_show_reset_all_button: bool,
     #[serde(skip)]
@@ -75,44 +329,190 @@ pub struct DBV {
     #[serde(skip)]
     edit_history: OptionEditNumeric<u16>,
     #[serde(skip)]
+    edit_history_compression: OptionEditNumeric<u32>,
+    #[serde(skip)]
     plot_bounds: Option<PlotBounds>,
     #[serde(skip)]
     last_cursor_pos: Option<egui_plot::PlotPoint>,
     #[serde(skip)]
     state_reset_plot_zoom: StatePlotResetZoom,
     #[serde(skip)]
+    zoom_target: ZoomTarget,
+    #[serde(skip)]
     status_msg: StatusMsg,
     #[serde(skip)]
     op_state: OperationalState,
     #[serde(skip)]
     edit_point: Option<DuringEditPoint>,
+    #[serde(skip)]
+    table_keyboard: TableKeyboard,
+    double_click_action: DoubleClickAction,
+    /// Version of the data after the last primary click changed it
+    #[serde(skip)]
+    click_timestamp: Option<DataTimestamp>,
+    #[serde(skip)]
+    csv_mapping_dialog: Option<CsvMappingDialog>,
+    #[serde(skip)]
+    mat_variables_dialog: Option<MatVariablesDialog>,
+    /// Loads with more points than this need to be confirmed by the user
+    max_points_without_prompt: usize,
+    #[serde(skip)]
+    large_load: Option<LargeLoad>,
+    #[serde(skip)]
+    auto_retrain_debounce: IdleDebounce,
+    #[serde(skip)]
+    selection: Selection,
+    select_shape: SelectShape,
+    /// The selection shape being dragged
+    #[serde(skip)]
+    drag_select: Option<DragSelect>,
+    /// The point being dragged in move mode
+    #[serde(skip)]
+    moving_point: Option<MovingPoint>,
+    /// Index of the note being dragged
+    #[serde(skip)]
+    moving_annotation: Option<usize>,
+    /// Index of the note open in the editor
+    #[serde(skip)]
+    editing_annotation: Option<usize>,
+    show_annotations: bool,
+    show_crosshair: bool,
+    convex_hulls: ConvexHulls,
+    class_spread: ClassSpread,
+    #[serde(skip)]
+    history_player: Option<HistoryPlayer>,
+    #[serde(skip)]
+    measurement: Measurement,
+    mirror_placement: MirrorPlacement,
+    /// Color given to points by "Color Selection"
+    custom_point_color: Color32,
+    stamp: Stamp,
+    eraser: Eraser,
+    #[serde(skip)]
+    eraser_stroke: Option<EraserStroke>,
+    /// Corner where the drag adding a region started
+    #[serde(skip)]
+    drawing_region: Option<PointArray>,
+    show_regions_window: bool,
+    brush: Brush,
+    /// Points from another file scored by the model
+    #[serde(skip)]
+    external_scores: Option<ExternalScores>,
+    /// Points from another file drawn behind the data for comparison
+    reference_layer: ReferenceLayer,
+    /// The points painted by the brush drag in progress
+    #[serde(skip)]
+    brush_stroke: Option<BrushStroke>,
+    #[serde(skip)]
+    new_group_name: String,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Clone, Copy)]
 struct DuringEditPoint {
     index: usize,
     point: DataPoint,
 }
 
+#[derive(PartialEq, Debug)]
+struct CsvMappingDialog {
+    pending: PendingCsvLoad,
+    path: PathBuf,
+}
+
 #[derive(serde::Deserialize, serde::Serialize, PartialEq)]
+#[allow(clippy::enum_variant_names)] // Names are kept as is because they are used in the saved app state
 enum ClickMode {
     AddPoints,
     DeletePoints,
+    /// Drag a rectangle to select points
+    SelectPoints,
+    /// Drag a point to move it
+    MovePoints,
+    /// Drag to paint points
+    BrushPoints,
+    /// Click to place or edit a text note, drag to move it
+    Annotate,
+    /// Drag a rectangle to add a region
+    DrawRegion,
+    /// Click two locations to measure the distance between them
+    Measure,
+    /// Click to place a shape of points
+    Stamp,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, PartialEq)]
 enum DisplayMode {
     Plot,
+    /// Plot of the number of points in each bin instead of the points themselves
+    Bins,
     Table,
 }
 
 impl ClickMode {
-    /// Returns `true` if the click mode is [`DeletePoints`].
+    /// Returns `true` if the click mode is [`SelectPoints`].
+    ///
+    /// [`SelectPoints`]: ClickMode::SelectPoints
+    #[must_use]
+    fn is_select_points(&self) -> bool {
+        matches!(self, Self::SelectPoints)
+    }
+
+    /// Returns `true` if the click mode is [`MovePoints`].
+    ///
+    /// [`MovePoints`]: ClickMode::MovePoints
+    #[must_use]
+    fn is_move_points(&self) -> bool {
+        matches!(self, Self::MovePoints)
+    }
+
+    /// Returns `true` if the click mode is [`BrushPoints`].
+    ///
+    /// [`BrushPoints`]: ClickMode::BrushPoints
+    #[must_use]
+    fn is_brush_points(&self) -> bool {
+        matches!(self, Self::BrushPoints)
+    }
+
+    /// Returns `true` if the click mode is [`Annotate`].
+    ///
+    /// [`Annotate`]: ClickMode::Annotate
+    #[must_use]
+    fn is_annotate(&self) -> bool {
+        matches!(self, Self::Annotate)
+    }
+
+    /// Returns `true` if the click mode is [`DrawRegion`].
+    ///
+    /// [`DrawRegion`]: ClickMode::DrawRegion
+    #[must_use]
+    fn is_draw_region(&self) -> bool {
+        matches!(self, Self::DrawRegion)
+    }
+
+    /// Returns `true` if the click mode is [`Measure`].
     ///
-    /// [`DeletePoints`]: ClickMode::DeletePoints
+    /// [`Measure`]: ClickMode::Measure
     #[must_use]
-    fn is_delete_points(&self) -> bool {
-        matches!(self, Self::DeletePoints)
+    fn is_measure(&self) -> bool {
+        matches!(self, Self::Measure)
+    }
+
+    /// Returns `true` if the click mode is [`Stamp`].
+    ///
+    /// [`Stamp`]: ClickMode::Stamp
+    #[must_use]
+    fn is_stamp(&self) -> bool {
+        matches!(self, Self::Stamp)
+    }
+
+    /// Returns `true` if primary drags are used by the mode instead of panning the plot
+    #[must_use]
+    fn uses_primary_drag(&self) -> bool {
+        self.is_select_points()
+            || self.is_move_points()
+            || self.is_brush_points()
+            || self.is_annotate()
+            || self.is_draw_region()
     }
 }
 
@@ -120,21 +520,59 @@ impl Default for DBV {
     fn default() -> Self {
         Self {
             marker_radius: 8.0,
+            display_precision: Default::default(),
             color_normal: Color32::from_rgb(100, 150, 230),
             color_anom: Color32::from_rgb(200, 150, 70),
             color_results_false_negatives: Color32::from_rgb(255, 119, 0),
             color_results_false_positives: Color32::from_rgb(255, 165, 0),
             color_results_true_negatives: Color32::from_rgb(136, 136, 255),
             color_results_true_positives: Color32::from_rgb(0, 0, 255),
+            marker_shapes: Default::default(),
+            label_classes: Default::default(),
+            series_visibility: Default::default(),
+            color_results_borderline: Color32::from_rgb(255, 165, 0),
+            color_selection: Color32::from_rgb(255, 215, 0),
             data: Default::default(),
             click_mode: ClickMode::AddPoints,
             primary_click_label: DataLabel::Normal,
+            suggest_label_from_neighbors: false,
+            label_suggestion_k: 3,
+            delete_tie_break: Default::default(),
+            delete_pick_radius: 10.0,
+            pending_delete: Default::default(),
             allow_boxed_zoom: false,
             show_data_only: false,
+            show_data_only_before_presentation: None,
             display_mode: DisplayMode::Plot,
+            binned_view: Default::default(),
             #[cfg(not(target_arch = "wasm32"))]
             py_experiment: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            autosave: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            screenshots: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            variant_export: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            file_watch: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            backups: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            recent_projects: vec![],
+            show_startup_chooser: true,
+            is_startup_chooser_open: false,
+            is_about_open: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            update_check: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            run_artifacts: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            recovered_data: Default::default(),
             loc_experiment: Default::default(),
+            csv_mappings: Default::default(),
+            mat_variable_names: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            last_saved_path: Default::default(),
             should_show_reset_all_button: false,
             should_show_clear_history: false,
             edit_history: OptionEditNumeric::new(
@@ -144,16 +582,108 @@ impl Default for DBV {
                 0..=u16::MAX,
                 "Max History Size: ",
             ),
+            edit_history_compression: OptionEditNumeric::new(
+                "Change History Compression",
+                Data::DEFAULT_HISTORY_COMPRESS_THRESHOLD,
+                1_000,
+                1..=u32::MAX,
+                "Compress changes above (points): ",
+            ),
             plot_bounds: Default::default(),
             last_cursor_pos: Default::default(),
             state_reset_plot_zoom: Default::default(),
+            zoom_target: Default::default(),
             status_msg: Default::default(),
             op_state: Default::default(),
             on_load_reset_plot_zoom: true,
             edit_point: Default::default(),
+            table_keyboard: Default::default(),
+            double_click_action: Default::default(),
+            click_timestamp: None,
+            csv_mapping_dialog: Default::default(),
+            mat_variables_dialog: Default::default(),
+            reference_scores: Default::default(),
+            show_score_delta: false,
+            max_points_without_prompt: 1_000_000,
+            large_load: Default::default(),
+            auto_retrain_debounce: Default::default(),
+            selection: Default::default(),
+            select_shape: Default::default(),
+            drag_select: None,
+            moving_point: None,
+            moving_annotation: None,
+            editing_annotation: None,
+            show_annotations: true,
+            show_crosshair: false,
+            convex_hulls: Default::default(),
+            class_spread: Default::default(),
+            history_player: None,
+            measurement: Default::default(),
+            mirror_placement: Default::default(),
+            custom_point_color: Color32::from_rgb(255, 140, 0),
+            stamp: Default::default(),
+            eraser: Default::default(),
+            eraser_stroke: None,
+            drawing_region: None,
+            show_regions_window: false,
+            brush: Default::default(),
+            external_scores: None,
+            reference_layer: Default::default(),
+            brush_stroke: None,
+            new_group_name: Default::default(),
             show_plot_bounds: false,
             show_points_color_picker: false,
+            show_groups_window: false,
+            show_group_outlines: true,
+            show_trash_window: false,
+            duplicate_offset: [1.0, 0.0],
+            duplicate_should_flip_label: false,
+            duplicate_count: 1,
+            jitter: Default::default(),
+            normalize: Default::default(),
+            formula_edit: Default::default(),
+            affine: Default::default(),
+            show_affine_window: false,
+            dedupe: Default::default(),
+            subsample: Default::default(),
+            table_columns: Default::default(),
+            table_add_row: Default::default(),
+            row_link: Default::default(),
+            train_test_split: Default::default(),
+            oversample: Default::default(),
+            outlier_pruning: Default::default(),
+            show_outlier_pruning_window: false,
+            kmeans: Default::default(),
+            show_kmeans_window: false,
+            sort_points: Default::default(),
+            grid_snap: Default::default(),
+            add_point_dialog: Default::default(),
+            batch_add: Default::default(),
+            show_polar_grid: false,
+            show_minimap: false,
+            show_nearest_neighbors: false,
+            show_density: false,
+            show_voronoi: false,
+            precompute_overlays_when_idle: true,
+            overlay_cache: Default::default(),
+            show_score_heatmap: false,
+            score_heatmap: Default::default(),
+            score_contours: Default::default(),
+            minimap_jump: None,
+            polar_grid_spacing: 1.0,
+            coordinate_frames: vec![],
+            coordinate_frame_name: String::new(),
+            polar_generator: Default::default(),
+            show_polar_generator: false,
+            threshold_preset_name: String::new(),
+            generator_registry: Default::default(),
+            generator_params: Default::default(),
+            open_generator: None,
+            show_export_subset: false,
+            export_subset: Default::default(),
             show_plot_legend: true,
+            show_point_tooltip: true,
+            legend_summary: Default::default(),
             show_plot_grid_lines: true,
             shortcut_undo: egui::KeyboardShortcut::new(Modifiers::CTRL, egui::Key::Z),
             shortcut_redo: egui::KeyboardShortcut::new(Modifiers::CTRL, egui::Key::Y),
@@ -169,7 +699,8 @@ impl DBV {
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        if let Some(storage) = cc.storage {
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut result: Self = if let Some(storage) = cc.storage {
             info!("Storage found, loading...");
             if let Some(result) = eframe::get_value(storage, eframe::APP_KEY) {
                 info!("Loading app data succeeded");
@@ -181,11 +712,19 @@ impl DBV {
         } else {
             info!("Storage not found");
             Default::default()
-        }
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        result.check_for_recovery();
+
+        result.is_startup_chooser_open = result.show_startup_chooser;
+
+        result
     }
 
     fn panel_top(&mut self, ui: &mut egui::Ui) {
         self.ui_menu_main(ui);
+        self.ui_load_progress(ui);
         if !self.show_data_only {
             self.ui_instructions(ui);
 
@@ -224,50 +763,101 @@ impl DBV {
             self.color_results_false_positives = default.color_results_false_positives;
             self.color_results_true_negatives = default.color_results_true_negatives;
             self.color_results_true_positives = default.color_results_true_positives;
+            self.color_results_borderline = default.color_results_borderline;
+        }
+        if ui.button("Reset Shapes").clicked() {
+            self.marker_shapes = Default::default();
         }
         ui.horizontal(|ui| {
             ui.strong("Without Results");
             ui.separator();
             ui.label("Normal");
             ui.color_edit_button_srgba(&mut self.color_normal);
+            self.marker_shapes.normal.ui(ui, "id-shape-normal");
 
             ui.separator();
             ui.label("Anomaly");
             ui.color_edit_button_srgba(&mut self.color_anom);
+            self.marker_shapes.anomaly.ui(ui, "id-shape-anomaly");
         });
+        self.ui_label_classes(ui);
         ui.horizontal(|ui| {
             ui.strong("With Results");
 
             ui.separator();
             ui.label("TP");
             ui.color_edit_button_srgba(&mut self.color_results_true_positives);
+            self.marker_shapes
+                .true_positives
+                .ui(ui, "id-shape-true-positives");
 
             ui.separator();
             ui.label("FP");
             ui.color_edit_button_srgba(&mut self.color_results_false_positives);
+            self.marker_shapes
+                .false_positives
+                .ui(ui, "id-shape-false-positives");
 
             ui.separator();
             ui.label("TN");
             ui.color_edit_button_srgba(&mut self.color_results_true_negatives);
+            self.marker_shapes
+                .true_negatives
+                .ui(ui, "id-shape-true-negatives");
 
             ui.separator();
             ui.label("FN");
             ui.color_edit_button_srgba(&mut self.color_results_false_negatives);
+            self.marker_shapes
+                .false_negatives
+                .ui(ui, "id-shape-false-negatives");
+
+            ui.separator();
+            ui.label("Borderline");
+            ui.color_edit_button_srgba(&mut self.color_results_borderline);
+            self.marker_shapes.borderline.ui(ui, "id-shape-borderline");
         });
     }
 
     fn ui_click_mode_display(&mut self, ui: &mut egui::Ui) {
         let display_text = format!(
             // TODO 3: Add colors for ADD and DELETE
-            "Mode: Click to {} point {}",
+            "Mode: {} {}",
             match self.click_mode {
-                ClickMode::AddPoints => "ADD",
-                ClickMode::DeletePoints => "DELETE",
+                ClickMode::AddPoints => "Click to ADD point",
+                ClickMode::DeletePoints => "Click to DELETE point",
+                ClickMode::SelectPoints => "Drag to SELECT points",
+                ClickMode::MovePoints => "Drag a point to MOVE it",
+                ClickMode::BrushPoints => "Drag to PAINT points",
+                ClickMode::Annotate => "Click to place a NOTE",
+                ClickMode::DrawRegion => "Drag to draw a REGION",
+                ClickMode::Measure => "Click two places to MEASURE",
+                ClickMode::Stamp => "Click to STAMP a shape of points",
             },
-            if self.primary_click_label.is_normal() {
-                ""
+            if self.click_mode.is_select_points() {
+                "(Hold shift to add to the selection)".to_string()
+            } else if self.click_mode.is_annotate() {
+                "(Secondary Click deletes a note)".to_string()
+            } else if self.click_mode.is_measure() {
+                "(Secondary Click clears the measurement)".to_string()
+            } else if self.click_mode.is_draw_region() {
+                format!(
+                    "(New regions are labeled {})",
+                    self.label_classes.name(self.primary_click_label)
+                )
+            } else if self.click_mode.is_move_points() || self.click_mode.is_brush_points() {
+                String::new()
+            } else if self.suggest_label_from_neighbors && self.click_mode == ClickMode::AddPoints {
+                "(Primary Click uses label of nearest points)".to_string()
+            } else if self.primary_click_label.is_normal() {
+                String::new()
+            } else if self.primary_click_label.is_anomaly() {
+                "(Primary and Secondary Click Swapped)".to_string()
             } else {
-                "(Primary and Secondary Click Swapped)"
+                format!(
+                    "(Primary Click uses {})",
+                    self.label_classes.name(self.primary_click_label)
+                )
             }
         );
         if ui
@@ -283,8 +873,22 @@ impl DBV {
         ui.collapsing("Instructions", |ui| {
             ui.label("Primary click to add normal point (Usually left click)");
             ui.label("Secondary click to add anomaly point (Usually right click)");
-            ui.label("Middle click to switch between adding and removing points");
+            ui.label(
+                "Middle click to switch between adding, removing, selecting, moving and painting points, placing notes, drawing regions, measuring and stamping",
+            );
+            ui.label(
+                "When selecting, drag a rectangle or lasso around the points (Click to clear the selection)",
+            );
+            ui.label("When moving, press on a point and drag it to where it should be");
+            ui.label("When painting, drag to add points along the path (Undone as one step)");
+            ui.label("When placing notes, click a note to edit it and drag it to move it");
+            ui.label("When drawing regions, drag a rectangle then name it in the Regions window");
+            ui.label("When measuring, click the start then the end (The last measurement stays shown)");
+            ui.label("When stamping, click to place the shape of points (Undone as one step)");
             ui.label("Pan by dragging, or scroll (+ shift = horizontal).");
+            if self.show_crosshair {
+                ui.label("Ctrl / ⌘ + click to copy the coordinates under the crosshair");
+            }
             if self.allow_boxed_zoom {
                 ui.label("Box zooming: Right click to zoom in and zoom out using a selection.");
             }
@@ -300,66 +904,132 @@ impl DBV {
 
     fn ui_menu_options(&mut self, ui: &mut egui::Ui) {
         ui.menu_button("Options", |ui| {
+            self.ui_settings_transfer(ui);
+            ui.separator();
             ui.add(
                 egui::DragValue::new(&mut self.marker_radius)
                     .speed(0.1)
                     .clamp_range(0.0..=f64::INFINITY)
                     .prefix("Point Display Radius: "),
             );
+            self.display_precision.ui(ui);
             ui.checkbox(
                 &mut self.show_points_color_picker,
                 "Show Points Colors picker",
             );
             ui.separator();
-            let mut should_remove_on_click: bool = self.click_mode.is_delete_points();
-            ui.checkbox(&mut should_remove_on_click, "Should remove point on click");
-            self.click_mode = if should_remove_on_click {
-                ClickMode::DeletePoints
-            } else {
-                ClickMode::AddPoints
-            };
+            ui.horizontal(|ui| {
+                ui.label("Click mode");
+                ui.selectable_value(&mut self.click_mode, ClickMode::AddPoints, "Add");
+                ui.selectable_value(&mut self.click_mode, ClickMode::DeletePoints, "Remove");
+                ui.selectable_value(&mut self.click_mode, ClickMode::SelectPoints, "Select");
+                ui.selectable_value(&mut self.click_mode, ClickMode::MovePoints, "Move");
+                ui.selectable_value(&mut self.click_mode, ClickMode::BrushPoints, "Brush");
+                ui.selectable_value(&mut self.click_mode, ClickMode::Annotate, "Note");
+                ui.selectable_value(&mut self.click_mode, ClickMode::DrawRegion, "Region");
+                ui.selectable_value(&mut self.click_mode, ClickMode::Measure, "Measure");
+                ui.selectable_value(&mut self.click_mode, ClickMode::Stamp, "Stamp");
+                if self.click_mode.is_select_points() {
+                    ui.separator();
+                    self.ui_select_shape(ui);
+                }
+                if self.click_mode.is_brush_points() {
+                    ui.separator();
+                    self.ui_brush_options(ui);
+                }
+                if self.click_mode == ClickMode::AddPoints {
+                    ui.separator();
+                    self.ui_mirror_options(ui);
+                }
+                if self.click_mode == ClickMode::DeletePoints {
+                    ui.separator();
+                    self.ui_eraser_options(ui);
+                }
+                if self.click_mode.is_stamp() {
+                    ui.separator();
+                    self.ui_stamp_options(ui);
+                }
+                if self.click_mode.is_measure() {
+                    ui.separator();
+                    self.ui_btn_clear_measurement(ui);
+                }
+            });
 
-            let mut should_swap_normal_on_click = self.primary_click_label.is_anomaly();
-            ui.checkbox(
-                &mut should_swap_normal_on_click,
-                "Swap Click for Normal and Anomaly",
-            );
-            self.primary_click_label = if should_swap_normal_on_click {
-                DataLabel::Anomaly
-            } else {
-                DataLabel::Normal
-            };
+            ui.horizontal(|ui| {
+                ui.label("Primary click label");
+                self.label_classes.ui_combo(
+                    ui,
+                    "id-primary-click-label",
+                    &mut self.primary_click_label,
+                );
+                ui.label(format!(
+                    "(Secondary click uses {})",
+                    self.label_classes.name(self.secondary_click_label())
+                ));
+            });
 
-            // Handle setting rounding of new points
             ui.horizontal(|ui| {
-                let mut is_rounding_new_points_enabled = self.data.is_rounding_enabled();
                 ui.checkbox(
-                    &mut is_rounding_new_points_enabled,
-                    "Should round new points",
+                    &mut self.suggest_label_from_neighbors,
+                    "Suggest label from nearest points",
+                )
+                .on_hover_text(
+                    "Primary click adds the label most of the nearest points have and secondary click adds the other label. Ties use the normal click labels",
                 );
-                self.data
-                    .set_rounding_enabled(is_rounding_new_points_enabled);
-                if is_rounding_new_points_enabled {
-                    ui.separator();
-                    ui.label("Number of Decimal places: ");
-                    ui.add(egui::Slider::new(
-                        self.data.rounding_decimal_places_mut(),
-                        0..=Data::MAX_DECIMAL_PLACES,
-                    ));
+                if self.suggest_label_from_neighbors {
+                    ui.add(
+                        egui::DragValue::new(&mut self.label_suggestion_k)
+                            .clamp_range(1..=50)
+                            .prefix("k: "),
+                    );
                 }
             });
 
+            ui.menu_button("Dataset settings", |ui| self.ui_dataset_settings(ui))
+                .response
+                .on_hover_text("Saved in the project file instead of with the app's settings");
+            ui.menu_button("Coordinate frame", |ui| self.ui_coordinate_frames(ui))
+                .response
+                .on_hover_text("Sets the default view, bounds and rounding of new points and the generators' extents to a region");
+            ui.menu_button("MATLAB variable names", |ui| self.ui_mat_variable_names(ui))
+                .response
+                .on_hover_text("Files without these variables ask which variables to use");
+
             ui.checkbox(&mut self.allow_boxed_zoom, "Allow boxed zoom")
                 .on_hover_text("When enabled, instructions include an explanation");
 
             ui.checkbox(&mut self.show_plot_legend, "Show plot legend");
+            if self.show_plot_legend {
+                self.legend_summary.ui(ui);
+            }
+
+            ui.checkbox(&mut self.show_point_tooltip, "Show point details on hover");
 
             ui.checkbox(&mut self.show_plot_grid_lines, "Show plot grid lines");
+            ui.checkbox(&mut self.show_annotations, "Show notes")
+                .on_hover_text("Notes are always shown while placing them");
 
             ui.checkbox(&mut self.show_plot_bounds, "Show plot bounds");
 
             ui.checkbox(&mut self.on_load_reset_plot_zoom, "On load reset plot zoom");
 
+            self.ui_delete_tie_break_options(ui);
+
+            self.ui_double_click_options(ui);
+
+            self.ui_large_load_options(ui);
+
+            #[cfg(not(target_arch = "wasm32"))]
+            self.ui_autosave_options(ui);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.ui_screenshot_options(ui);
+
+            #[cfg(not(target_arch = "wasm32"))]
+            self.ui_file_watch_options(ui);
+
+            #[cfg(not(target_arch = "wasm32"))]
+            self.ui_backup_options(ui);
+
             ui.horizontal(|ui| {
                 ui.checkbox(
                     &mut self.should_show_reset_all_button,
@@ -373,6 +1043,34 @@ impl DBV {
         });
     }
 
+    fn ui_new_point_bounds(&mut self, ui: &mut egui::Ui) {
+        let bounds = &mut self.data.new_point_bounds;
+        egui::Grid::new("grid-new-point-bounds").show(ui, |ui| {
+            for (name, value) in [
+                ("x0 min", &mut bounds.x0_min),
+                ("x0 max", &mut bounds.x0_max),
+                ("x1 min", &mut bounds.x1_min),
+                ("x1 max", &mut bounds.x1_max),
+            ] {
+                let mut is_set = value.is_some();
+                ui.checkbox(&mut is_set, name);
+                match (is_set, value.as_mut()) {
+                    (true, Some(x)) => {
+                        ui.add(egui::DragValue::new(x).speed(0.1));
+                    }
+                    (true, None) => *value = Some(0.0),
+                    (false, _) => *value = None,
+                }
+                ui.end_row();
+            }
+        });
+        ui.add_enabled(
+            bounds.is_enabled(),
+            Checkbox::new(&mut bounds.should_clamp, "Clamp instead of rejecting"),
+        )
+        .on_hover_text("Moves clicks outside the bounds to the nearest allowed position");
+    }
+
     fn ui_undo_redo_with_options(&mut self, ui: &mut egui::Ui) {
         ui.add_enabled_ui(self.op_state.is_normal(), |ui| {
             self.ui_btn_undo_redo(ui);
@@ -394,7 +1092,18 @@ impl DBV {
                         .update(ui, self.data.max_history_size(), |value| {
                             self.data.set_history_size(value)
                         })
+                });
+                ui.horizontal(|ui| {
+                    self.edit_history_compression.update(
+                        ui,
+                        self.data.history_compress_threshold(),
+                        |value| self.data.set_history_compress_threshold(value),
+                    )
                 })
+                .response
+                .on_hover_text(
+                    "Large changes (e.g. big pastes or loads) are kept compressed in the history to save memory",
+                );
             });
         });
     }
@@ -437,14 +1146,17 @@ impl DBV {
             self.ui_btn_delete_all_points(ui);
             self.ui_btn_reset_plot_zoom(ui);
             if let Some(pos) = self.last_cursor_pos.as_ref() {
-                ui.label(format!("Last Pos: {:.3},{:.3}", pos.x, pos.y));
+                ui.label(format!(
+                    "Last Pos: {}",
+                    self.display_precision.format_pair([pos.x, pos.y])
+                ));
             }
             if self.show_plot_bounds {
                 if let Some(bounds) = self.plot_bounds {
                     ui.label(format!(
-                        "Plot bounds: min: {:.02?}, max: {:.02?}",
-                        bounds.min(),
-                        bounds.max()
+                        "Plot bounds: min: {}, max: {}",
+                        self.display_precision.format_pair(bounds.min()),
+                        self.display_precision.format_pair(bounds.max())
                     ));
                 }
             }
@@ -497,8 +1209,14 @@ impl DBV {
 
     fn panel_center(&mut self, ui: &mut egui::Ui) {
         match &self.display_mode {
-            DisplayMode::Plot => self.ui_plot(ui),
-            DisplayMode::Table => self.ui_table(ui),
+            DisplayMode::Plot | DisplayMode::Bins => self.ui_plot(ui),
+            DisplayMode::Table => {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.screenshots.plot_rect = None;
+                }
+                self.ui_table(ui)
+            }
         }
     }
     fn ui_plot(&mut self, ui: &mut egui::Ui) {
@@ -506,8 +1224,17 @@ impl DBV {
             .data_aspect(1.0)
             .min_size(egui::Vec2 { x: 100.0, y: 100.0 })
             .allow_boxed_zoom(self.allow_boxed_zoom)
+            // Dragging selects, moves, paints or erases points instead of panning in those modes
+            .allow_drag(!self.click_mode.uses_primary_drag() && !self.is_erasing())
             .allow_double_click_reset(false)
             .show_grid(self.show_plot_grid_lines);
+        let [x0_name, x1_name] = &self.data.settings.axis_labels;
+        if !x0_name.is_empty() {
+            markers_plot = markers_plot.x_axis_label(x0_name.as_str());
+        }
+        if !x1_name.is_empty() {
+            markers_plot = markers_plot.y_axis_label(x1_name.as_str());
+        }
 
         if self.show_plot_legend {
             markers_plot = markers_plot.legend(Legend::default());
@@ -518,17 +1245,62 @@ impl DBV {
             inner: pointer_coordinate,
             ..
         } = markers_plot.show(ui, |plot_ui| {
-            let markers = if let Some(model) = self.loc_inference_model() {
-                self.markers_w_results(model)
+            if self.show_polar_grid {
+                self.plot_polar_grid(plot_ui);
+            }
+            self.plot_score_heatmap(plot_ui);
+            self.plot_regions(plot_ui);
+            self.plot_overlays_from_cache(plot_ui);
+            self.plot_convex_hulls(plot_ui);
+            self.plot_reference_layer(plot_ui);
+            self.apply_minimap_jump(plot_ui);
+            if self.display_mode == DisplayMode::Bins {
+                self.plot_bins(plot_ui);
             } else {
-                self.markers_wo_results()
-            };
-            for marker in markers {
-                plot_ui.points(marker);
+                let markers = if let Some(markers) = self.markers_history_replay() {
+                    markers
+                } else if let Some(markers) = self.markers_score_delta() {
+                    markers
+                } else if let Some(model) = self.loc_inference_model() {
+                    self.markers_w_results(model)
+                } else {
+                    self.markers_wo_results()
+                };
+                for marker in markers {
+                    plot_ui.points(marker);
+                }
+            }
+            self.plot_external_scores(plot_ui);
+            self.plot_class_spread(plot_ui);
+            self.plot_group_and_selection_outlines(plot_ui);
+            self.plot_linked_row(plot_ui);
+            if self.display_mode != DisplayMode::Bins {
+                self.plot_locked_indicators(plot_ui);
+                self.plot_test_split_indicators(plot_ui);
+            }
+            self.plot_drag_select(plot_ui);
+            self.plot_drag_move(plot_ui);
+            self.plot_brush(plot_ui);
+            self.plot_eraser(plot_ui);
+            self.plot_annotations(plot_ui);
+            self.plot_measurement(plot_ui);
+            self.plot_mirror_guide(plot_ui);
+            self.plot_stamp_preview(plot_ui);
+            self.plot_affine_preview(plot_ui);
+            self.plot_outlier_pruning_preview(plot_ui);
+            self.plot_kmeans_centers(plot_ui);
+            self.plot_crosshair(plot_ui);
+            if self.display_mode != DisplayMode::Bins {
+                self.plot_delete_preview(plot_ui);
+                self.plot_point_tooltip(plot_ui);
             }
             if !self.state_reset_plot_zoom.is_stopped() {
-                self.state_reset_plot_zoom
-                    .step(plot_ui, self.data.get_points_min_max_w_margin())
+                let target = match self.zoom_target {
+                    ZoomTarget::AllPoints => self.data.get_points_min_max_w_margin(),
+                    ZoomTarget::Label(label) => self.data.get_label_min_max_w_margin(label),
+                    ZoomTarget::View(bounds) => bounds,
+                };
+                self.state_reset_plot_zoom.step(plot_ui, target)
             }
             self.plot_bounds = Some(plot_ui.plot_bounds());
             plot_ui.pointer_coordinate()
@@ -536,6 +1308,13 @@ impl DBV {
         if pointer_coordinate.is_some() {
             self.last_cursor_pos = pointer_coordinate;
         }
+        if self.show_minimap {
+            self.ui_minimap(ui, response.rect);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.screenshots.plot_rect = Some(response.rect);
+        }
 
         // Needs to have the option to use the last cursor position because on mobile the cursor position
         // doesn't persist after the finger is lifted which is when the click happens
@@ -549,157 +1328,71 @@ impl DBV {
             .max(ui.spacing().interact_size.y);
 
         let has_inference_model = self.loc_inference_model().is_some();
+        self.ui_table_add_row(ui);
+        self.ui_table_options(ui);
+        let nearest_distances = self.table_nearest_distances(ui.ctx());
+        let row_order = self.table_row_order(nearest_distances.as_deref());
+        // The column stays while the distances are recalculated so the layout does not jump
+        let can_show_nearest_distances =
+            self.data.points().len() <= TableColumns::NEAREST_DISTANCE_MAX_POINTS;
+        let columns = self
+            .table_columns
+            .shown_columns(has_inference_model, can_show_nearest_distances);
 
         let mut table_builder = TableBuilder::new(ui)
             .striped(true)
             .resizable(true)
-            .cell_layout(egui::Layout::left_to_right(egui::Align::LEFT))
-            .column(Column::auto())
-            .column(Column::auto())
-            .column(Column::auto())
-            .column(Column::auto())
-            .column(Column::auto());
-
-        if has_inference_model {
-            // Add columns for inference results
-            table_builder = table_builder
-                .column(Column::auto())
-                .column(Column::auto())
-                .column(Column::auto());
+            .cell_layout(egui::Layout::left_to_right(egui::Align::LEFT));
+        for _ in &columns {
+            table_builder = table_builder.column(Column::auto());
+        }
+        if let Some(row) = self
+            .row_link
+            .scroll_target(row_order.as_deref(), self.data.points().len())
+        {
+            let row = self.table_columns.show_row(row);
+            table_builder = table_builder.scroll_to_row(row, Some(egui::Align::Center));
         }
-        table_builder = table_builder.min_scrolled_height(0.0);
+        let visible_rows = self.table_columns.visible_rows(self.data.points().len());
+        table_builder = table_builder.sense(Sense::click()).min_scrolled_height(0.0);
 
         let table = table_builder.header(20.0, |mut header| {
-            header.col(|ui| {
-                ui.strong("row #");
-            });
-            header.col(|ui| {
-                ui.strong("x0");
-            });
-            header.col(|ui| {
-                ui.strong("x1");
-            });
-            header.col(|ui| {
-                ui.strong("label");
-            });
-            header.col(|ui| {
-                ui.strong(""); // Empty column for buttons
-            });
-            if has_inference_model {
-                header.col(|ui| {
-                    ui.strong("prediction");
-                });
-                header.col(|ui| {
-                    ui.strong("classification");
-                });
-                header.col(|ui| {
-                    ui.strong("score");
-                });
+            for &column in &columns {
+                header.col(|ui| self.ui_table_header(ui, column));
             }
         });
 
         table.body(|body| {
-            body.rows(text_height, self.data.points().len(), |mut row| {
-                let row_index = row.index();
+            body.rows(text_height, visible_rows.len(), |mut row| {
+                let display_index = visible_rows.start + row.index();
+                let row_index = match row_order.as_ref() {
+                    Some(order) => order.get(display_index).copied().unwrap_or(usize::MAX),
+                    None => display_index,
+                };
                 if row_index >= self.data.points().len(){
                     // This should only happen if the delete button was clicked and the last row was visible 
                     // but don't have a good way to keep track if it was clicked so just check if we get an illegal index
                     debug!("Stopping rows from being output on the assumption that the delete button was clicked");
                     return;
                 }
-                let point = self.data.points()[row_index];
-                let DataPoint { x0, x1, label } = point;
-                row.col(|ui| {
-                    ui.label(row_index.to_string());
-                });
-                match self.edit_point.as_mut() {
-                    Some(x) if x.index == row_index => {
-                        fn edit_num<Num: egui::emath::Numeric>(
-                            ui: &mut egui::Ui,
-                            value: &mut Num,
-                            rounding_decimal_places: Option<u8>,
-                        ) {
-                            let mut drag_value = egui::DragValue::new(value);
-                            if let Some(precision) = rounding_decimal_places {
-                                drag_value = drag_value.speed(0.1f64.powi(precision as i32));
-                            }
-                            ui.add(drag_value);
-                        }
-                        row.col(|ui| {
-                            edit_num(ui, &mut x.point.x0, self.data.rounding_decimal_places)
-                        });
-                        row.col(|ui| {
-                            edit_num(ui, &mut x.point.x1, self.data.rounding_decimal_places)
-                        });
-                        row.col(|ui| {
-                            egui::ComboBox::new("id-table-cell-label", "")
-                                .selected_text(x.point.label.to_string())
-                                .show_ui(ui, |ui| {
-                                    ui.selectable_value(
-                                        &mut x.point.label,
-                                        DataLabel::Normal,
-                                        DataLabel::Normal.to_string(),
-                                    );
-                                    ui.selectable_value(
-                                        &mut x.point.label,
-                                        DataLabel::Anomaly,
-                                        DataLabel::Anomaly.to_string(),
-                                    );
-                                });
-                        });
-                    }
-                    _ => {
-                        row.col(|ui| {
-                            ui.label(x0.to_string());
-                        });
-                        row.col(|ui| {
-                            ui.label(x1.to_string());
-                        });
-                        row.col(|ui| {
-                            ui.label(label.to_string());
-                        });
-                    }
+                row.set_selected(self.row_link.index == Some(row_index));
+                for &column in &columns {
+                    row.col(|ui| {
+                        self.ui_table_cell(
+                            ui,
+                            column,
+                            row_index,
+                            text_height,
+                            nearest_distances.as_deref(),
+                        )
+                    });
                 }
-                row.col(|ui| {
-                    if let Some(x) = self.edit_point.as_ref() {
-                        if x.index == row_index {
-                            if ui.button("Save").clicked() {
-                                self.data.edit(x.index, x.point);
-                                self.edit_point = None;
-                            }
-                            if ui.button("Cancel").clicked() {
-                                self.edit_point = None;
-                            }
-                        } else {
-                            // No buttons if not on the row being edited
-                        }
-                    } else if ui.button("Edit").clicked() {
-                        self.edit_point = Some(DuringEditPoint {
-                            index: row_index,
-                            point,
-                        });
-                    } else if ui.button("Delete").clicked() {
-                        debug!("Delete clicked on row_index: {row_index}");
-                        self.data.delete_by_index(row_index);
-                    }
-                });
-                if has_inference_model {
-                    if let Some(model) = self.loc_inference_model() {
-                        let predicted = model.prediction_on_training_data(row_index);
-                        let score = model.score_for_training_data(row_index);
-                        row.col(|ui| {
-                            ui.label(predicted.to_string());
-                        });
-                        row.col(|ui| {
-                            ui.label(prediction_classification(label, predicted).to_string());
-                        });
-                        row.col(|ui| {
-                            ui.label(score.to_string());
-                        });
-                    }
+                if row.response().clicked() {
+                    self.link_row(row_index);
                 }
             });
         });
+        self.handle_table_keys(ui.ctx(), row_order.as_deref(), &columns);
     }
 
     fn click_handler(
@@ -707,32 +1400,53 @@ impl DBV {
         response: &egui::Response,
         pointer_coordinate: Option<egui_plot::PlotPoint>,
     ) {
+        if self.crosshair_copy_on_click(response, pointer_coordinate) {
+            return;
+        }
+        if self.double_click_on(response, pointer_coordinate) {
+            return;
+        }
         if response.clicked() {
+            let timestamp_before = self.data.timestamp();
             match self.click_mode {
-                ClickMode::AddPoints => self.data.add(
-                    pointer_coordinate,
-                    self.primary_click_label,
-                    &mut self.status_msg,
-                ),
-                ClickMode::DeletePoints => self.data.delete(
-                    pointer_coordinate,
-                    self.primary_click_label,
-                    &mut self.status_msg,
-                ),
+                ClickMode::AddPoints => {
+                    self.add_on_click(pointer_coordinate, self.add_click_label(pointer_coordinate))
+                }
+                ClickMode::DeletePoints => {
+                    self.delete_on_click(response, pointer_coordinate, self.primary_click_label)
+                }
+                ClickMode::SelectPoints => {
+                    if !response.ctx.input(|i| i.modifiers.shift) {
+                        self.selection.clear();
+                    }
+                    self.link_point_on_click(response, pointer_coordinate);
+                }
+                ClickMode::MovePoints => (), // Points are only moved by dragging
+                ClickMode::BrushPoints => self.brush_dab_on_click(response, pointer_coordinate),
+                ClickMode::Annotate | ClickMode::DrawRegion => (), // Handled while drawing the plot
+                ClickMode::Measure => self.measure_on_click(pointer_coordinate, true),
+                ClickMode::Stamp => {
+                    self.stamp_on_click(pointer_coordinate, self.primary_click_label)
+                }
             }
+            self.click_timestamp =
+                (self.data.timestamp() != timestamp_before).then(|| self.data.timestamp());
         }
         if response.secondary_clicked() {
             match self.click_mode {
-                ClickMode::AddPoints => self.data.add(
-                    pointer_coordinate,
-                    self.secondary_click_label(),
-                    &mut self.status_msg,
-                ),
-                ClickMode::DeletePoints => self.data.delete(
+                ClickMode::AddPoints => self.add_on_click(
                     pointer_coordinate,
-                    self.secondary_click_label(),
-                    &mut self.status_msg,
+                    self.add_click_label(pointer_coordinate).opposite(),
                 ),
+                ClickMode::DeletePoints => {
+                    self.delete_on_click(response, pointer_coordinate, self.secondary_click_label())
+                }
+                ClickMode::SelectPoints | ClickMode::MovePoints | ClickMode::BrushPoints => (), // Secondary drag is used for boxed zoom
+                ClickMode::Annotate | ClickMode::DrawRegion => (), // Handled while drawing the plot
+                ClickMode::Measure => self.measure_on_click(pointer_coordinate, false),
+                ClickMode::Stamp => {
+                    self.stamp_on_click(pointer_coordinate, self.secondary_click_label())
+                }
             }
         }
         if response.middle_clicked() {
@@ -743,15 +1457,32 @@ impl DBV {
     fn toggle_click_mode(&mut self) {
         self.click_mode = match self.click_mode {
             ClickMode::AddPoints => ClickMode::DeletePoints,
-            ClickMode::DeletePoints => ClickMode::AddPoints,
+            ClickMode::DeletePoints => ClickMode::SelectPoints,
+            ClickMode::SelectPoints => ClickMode::MovePoints,
+            ClickMode::MovePoints => ClickMode::BrushPoints,
+            ClickMode::BrushPoints => ClickMode::Annotate,
+            ClickMode::Annotate => ClickMode::DrawRegion,
+            ClickMode::DrawRegion => ClickMode::Measure,
+            ClickMode::Measure => ClickMode::Stamp,
+            ClickMode::Stamp => ClickMode::AddPoints,
         }
     }
 
     fn secondary_click_label(&self) -> DataLabel {
-        match self.primary_click_label {
-            DataLabel::Normal => DataLabel::Anomaly,
-            DataLabel::Anomaly => DataLabel::Normal,
+        self.primary_click_label.opposite()
+    }
+
+    /// The label a primary click adds at the coordinate (The secondary click adds the opposite)
+    fn add_click_label(&self, pointer_coordinate: Option<egui_plot::PlotPoint>) -> DataLabel {
+        if !self.suggest_label_from_neighbors {
+            return self.primary_click_label;
         }
+        pointer_coordinate
+            .and_then(|coord| {
+                self.data
+                    .majority_label_near(coord.to_array(), self.label_suggestion_k)
+            })
+            .unwrap_or(self.primary_click_label)
     }
 
     fn ui_persistence(&mut self, ui: &mut egui::Ui) {
@@ -765,16 +1496,56 @@ impl DBV {
                 self.save_data(ui.ctx().clone());
                 ui.close_menu();
             }
+            self.ui_btn_export_subset(ui);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.ui_menu_variant_export(ui);
+            self.ui_btn_export_results(ui);
+            self.ui_btns_external_scores(ui);
+            self.ui_btns_reference_layer(ui);
+            if ui
+                .button("Copy Dataset Hash")
+                .on_hover_text(
+                    "Copies a hash of the points that identifies this exact version of the dataset",
+                )
+                .clicked()
+            {
+                let data_hash = self.data.content_hash();
+                self.status_msg
+                    .info(format!("Copied dataset hash {data_hash}"));
+                ui.output_mut(|o| o.copied_text = data_hash);
+                ui.close_menu();
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            self.ui_btn_revert_to_last_saved(ui);
         });
     }
 
     fn save_data(&mut self, ctx: egui::Context) {
-        debug_assert!(self.op_state.is_normal());
         let points = self.data.clone_points(); // Cloning seemed to be the most practical way I could think of to get a new copy to send into the closure
+        self.save_points(ctx, points, "Save as", Payload::Save);
+    }
+
+    /// Saves a subset of the points without changing which file the data is associated with
+    fn export_points(&mut self, ctx: egui::Context, points: DataPoints) {
+        self.save_points(ctx, points, "Export as", Payload::Export);
+    }
+
+    fn save_points(
+        &mut self,
+        ctx: egui::Context,
+        mut points: DataPoints,
+        title: &'static str,
+        to_payload: fn(PathBuf, String) -> Payload,
+    ) {
+        debug_assert!(self.op_state.is_normal());
+        self.label_classes.prepare_for_save(&mut points);
+        let mat_names = self.mat_variable_names.clone();
         #[cfg(not(target_arch = "wasm32"))]
         let data_dir = self.py_experiment.data_dir().cloned();
+        #[cfg(not(target_arch = "wasm32"))]
+        let (backups, mut status_msg) = (self.backups, self.status_msg.clone());
         self.op_state = OperationalState::Saving(execute(async move {
-            let dialog = rfd::AsyncFileDialog::new().set_title("Save as");
+            let dialog = rfd::AsyncFileDialog::new().set_title(title);
             #[cfg(not(target_arch = "wasm32"))]
             let dialog = if let Some(data_dir) = data_dir {
                 dialog.set_directory(data_dir)
@@ -789,12 +1560,23 @@ impl DBV {
                 return OperationOutcome::Cancelled;
             };
             let path = file_handle_to_path(&file);
+            #[cfg(not(target_arch = "wasm32"))]
+            match backups.backup_before_overwrite(&path) {
+                Ok(Some(backup_path)) => {
+                    status_msg.info(format!("Backup saved to {backup_path:?}"))
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    ctx.request_repaint();
+                    return OperationOutcome::Failed(e.context("save cancelled"));
+                }
+            }
             let result = match points
-                .save_to_file(&file)
+                .save_to_file(&file, &mat_names)
                 .await
                 .context("failed to save file")
             {
-                Ok(()) => OperationOutcome::Success(Payload::Save(path)),
+                Ok(()) => OperationOutcome::Success(to_payload(path, content_hash(&points))),
                 Err(e) => OperationOutcome::Failed(e),
             };
 
@@ -807,9 +1589,13 @@ impl DBV {
     fn load_data(&mut self, ctx: egui::Context) {
         debug_assert!(self.op_state.is_normal());
         let mut status_msg = self.status_msg.clone(); // Clone is cheap because type uses an arc internally
+        let csv_mappings = self.csv_mappings.clone();
+        let mat_names = self.mat_variable_names.clone();
         #[cfg(not(target_arch = "wasm32"))]
         let data_dir = self.py_experiment.data_dir().cloned();
-        self.op_state = OperationalState::Loading(execute(async move {
+        let progress = LoadProgress::default();
+        let progress_for_ui = progress.clone();
+        let promise = execute(async move {
             let dialog = rfd::AsyncFileDialog::new().set_title("Load data");
             #[cfg(not(target_arch = "wasm32"))]
             let dialog = if let Some(data_dir) = data_dir {
@@ -822,29 +1608,72 @@ impl DBV {
                 ctx.request_repaint();
                 return OperationOutcome::Cancelled;
             };
-            let path = file_handle_to_path(&file);
-            let result = match Data::load_from_file(&file).await.context("failed to load") {
-                Ok((loaded_data, load_msg)) => {
-                    if let Some(msg) = load_msg {
-                        status_msg.info(msg)
-                    }
-                    OperationOutcome::Success(Payload::Load { loaded_data, path })
-                }
-                Err(e) => OperationOutcome::Failed(e),
-            };
+            let result =
+                load_file(&file, &csv_mappings, &mat_names, &mut status_msg, &progress).await;
 
             ctx.request_repaint();
 
             result
-        }));
+        });
+        self.op_state = OperationalState::Loading(promise, progress_for_ui);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn ui_btn_revert_to_last_saved(&mut self, ui: &mut egui::Ui) {
+        let button = Button::new("Revert to last saved");
+        let response = if let Some(path) = self.last_saved_path.as_ref() {
+            ui.add(button).on_hover_text(format!(
+                "Reloads {path:?} (Can be undone like any other load)"
+            ))
+        } else {
+            ui.add_enabled(false, button)
+                .on_disabled_hover_text("No file has been saved or loaded yet")
+        };
+        if response.clicked() {
+            self.revert_to_last_saved(ui.ctx().clone());
+            ui.close_menu();
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn revert_to_last_saved(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let Some(path) = self.last_saved_path.clone() else {
+            self.status_msg
+                .error_display("Unable to revert. No file has been saved or loaded yet");
+            return;
+        };
+        self.load_from_path(path, ctx);
+    }
+
+    /// Loads the file at `path` without asking the user to pick it
+    fn load_from_path(&mut self, path: PathBuf, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let mut status_msg = self.status_msg.clone();
+        let csv_mappings = self.csv_mappings.clone();
+        let mat_names = self.mat_variable_names.clone();
+        let progress = LoadProgress::default();
+        let progress_for_ui = progress.clone();
+        let promise = execute(async move {
+            let file = rfd::FileHandle::from(path);
+            let result =
+                load_file(&file, &csv_mappings, &mat_names, &mut status_msg, &progress).await;
+
+            ctx.request_repaint();
+
+            result
+        });
+        self.op_state = OperationalState::Loading(promise, progress_for_ui);
     }
 
     fn ui_menu_main(&mut self, ui: &mut egui::Ui) {
         egui::menu::bar(ui, |ui| {
             self.ui_menu_file(ui);
             self.ui_menu_edit(ui);
+            self.ui_menu_generate(ui);
             self.ui_menu_view(ui);
             self.ui_menu_options(ui);
+            self.ui_menu_help(ui);
 
             ui.add_space(16.0);
             egui::widgets::global_dark_light_mode_buttons(ui);
@@ -854,7 +1683,34 @@ impl DBV {
     fn ui_menu_view(&mut self, ui: &mut egui::Ui) {
         ui.menu_button("View", |ui| {
             ui.checkbox(&mut self.show_data_only, "Show Data Only");
+            self.ui_btn_presentation_mode(ui);
+            self.ui_btn_show_groups(ui);
+            self.ui_btn_show_regions(ui);
+            self.ui_btn_show_trash(ui);
+            ui.checkbox(&mut self.show_minimap, "Show mini-map");
+            self.ui_crosshair_toggle(ui);
+            self.ui_menu_series_visibility(ui);
+            ui.menu_button("Overlays", |ui| {
+                self.ui_overlay_options(ui);
+                ui.separator();
+                self.ui_convex_hulls_toggle(ui);
+                self.ui_class_spread_toggle(ui);
+                self.ui_reference_layer_options(ui);
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.show_polar_grid, "Show polar grid");
+                if self.show_polar_grid {
+                    ui.add(
+                        egui::DragValue::new(&mut self.polar_grid_spacing)
+                            .speed(0.1)
+                            .clamp_range(0.01..=f64::INFINITY)
+                            .prefix("Ring spacing: "),
+                    );
+                }
+            });
             self.ui_btn_reset_plot_zoom(ui);
+            self.ui_btn_zoom_to_default_view(ui);
+            self.ui_btns_zoom_to_label(ui);
         });
     }
 
@@ -866,14 +1722,62 @@ impl DBV {
             )
             .clicked()
         {
-            self.state_reset_plot_zoom.start_reset();
+            self.start_zoom_reset(ZoomTarget::AllPoints);
             ui.close_menu();
         }
     }
 
+    fn ui_btns_zoom_to_label(&mut self, ui: &mut egui::Ui) {
+        for (text, label) in [
+            ("Zoom to Normals", DataLabel::Normal),
+            ("Zoom to Anomalies", DataLabel::Anomaly),
+        ] {
+            let has_points = self.data.points().iter().any(|point| point.label == label);
+            if ui
+                .add_enabled(
+                    has_points && self.state_reset_plot_zoom.is_stopped(),
+                    Button::new(text),
+                )
+                .clicked()
+            {
+                self.start_zoom_reset(ZoomTarget::Label(label));
+                ui.close_menu();
+            }
+        }
+    }
+
+    fn start_zoom_reset(&mut self, target: ZoomTarget) {
+        self.zoom_target = target;
+        self.state_reset_plot_zoom.start_reset();
+    }
+
     fn ui_menu_edit(&mut self, ui: &mut egui::Ui) {
         ui.menu_button("Edit", |ui| {
             self.ui_undo_redo_with_options(ui);
+            self.ui_btn_replay_history(ui);
+            self.ui_btn_add_point_dialog(ui);
+            self.ui_btn_paste_points(ui);
+            self.ui_btn_batch_add(ui);
+            self.ui_menu_duplicate_selection(ui);
+            self.ui_menu_relabel_selection(ui);
+            self.ui_btn_swap_labels(ui);
+            self.ui_btn_kmeans_labeling(ui);
+            self.ui_menu_color_selection(ui);
+            self.ui_menu_jitter(ui);
+            self.ui_menu_normalize(ui);
+            self.ui_menu_formula_edit(ui);
+            self.ui_btn_transform_points(ui);
+            self.ui_btn_delete_selection(ui);
+            self.ui_menu_remove_duplicates(ui);
+            self.ui_btn_prune_outliers(ui);
+            self.ui_btn_round_all_points(ui);
+            self.ui_menu_snap_to_grid(ui);
+            self.ui_menu_subsample(ui);
+            self.ui_menu_sort_points(ui);
+            self.ui_menu_oversample(ui);
+            self.ui_btns_training_exclusion(ui);
+            self.ui_menu_train_test_split(ui);
+            self.ui_btns_lock_selection(ui);
             if self.ui_btn_clear_status_msgs(ui) {
                 ui.close_menu();
             };
@@ -903,33 +1807,97 @@ impl DBV {
     }
 
     fn markers_wo_results(&self) -> Vec<Points> {
-        let normal_points = self.data_points_to_egui_points(
-            self.data.points().array_of_normal(),
-            "Normal",
-            MarkerShape::Plus,
-            self.color_normal,
+        let is_visible =
+            |label: DataLabel| self.series_visibility.is_visible(PlotSeries::Label(label));
+        let mut result = self.markers_by_label(self.data.points(), is_visible);
+        result.extend(
+            self.markers_custom_colors(self.data.points().iter().filter_map(|point| {
+                if !is_visible(point.label) {
+                    return None;
+                }
+                let shape = self.label_shape(point.label);
+                Some((point.to_array(), shape.into(), point.color?))
+            })),
         );
+        result
+    }
 
-        let anom_points = self.data_points_to_egui_points(
-            self.data.points().array_of_anom(),
-            "Anomalies ",
-            MarkerShape::Asterisk,
-            self.color_anom,
-        );
+    /// One series per label with the shape and color of the label (Normal and anomaly are always
+    /// included if visible so they stay in the legend)
+    fn markers_by_label(
+        &self,
+        points: &[DataPoint],
+        is_visible: impl Fn(DataLabel) -> bool,
+    ) -> Vec<Points> {
+        let mut by_label: BTreeMap<DataLabel, Vec<PointArray>> =
+            [(DataLabel::Normal, vec![]), (DataLabel::Anomaly, vec![])].into();
+        for point in points {
+            by_label
+                .entry(point.label)
+                .or_default()
+                .push(point.to_array());
+        }
+        by_label
+            .into_iter()
+            .filter(|(label, _)| is_visible(*label))
+            .map(|(label, points)| {
+                let name = match label {
+                    DataLabel::Anomaly => "Anomalies".to_string(),
+                    _ => self.label_classes.name(label),
+                };
+                self.data_points_to_egui_points(
+                    points,
+                    name,
+                    None,
+                    self.label_shape(label).into(),
+                    self.label_color(label),
+                )
+            })
+            .collect()
+    }
 
-        vec![normal_points, anom_points]
+    /// Markers for the points with a color set, drawn over the markers of their label or
+    /// classification with the same shape so the color set is what is seen
+    fn markers_custom_colors(
+        &self,
+        points: impl Iterator<Item = (PointArray, MarkerShape, Color32)>,
+    ) -> Vec<Points> {
+        let mut series: Vec<(MarkerShape, Color32, Vec<PointArray>)> = vec![];
+        for (position, shape, color) in points {
+            match series.iter_mut().find(|x| x.0 == shape && x.1 == color) {
+                Some(x) => x.2.push(position),
+                None => series.push((shape, color, vec![position])),
+            }
+        }
+        series
+            .into_iter()
+            .map(|(shape, color, points)| {
+                Points::new(points)
+                    .name("Custom color")
+                    .radius(self.marker_radius)
+                    .shape(shape)
+                    .color(color)
+            })
+            .collect()
     }
 
     fn data_points_to_egui_points<S: Display>(
         &self,
         point_arrays: Vec<PointArray>,
         name: S,
+        mean_score: Option<f64>,
         shape: MarkerShape,
         color: Color32,
     ) -> Points {
-        let len = point_arrays.len();
+        let name = self.legend_summary.series_name(
+            name,
+            point_arrays.len(),
+            self.data.points().len(),
+            mean_score,
+            &self.display_precision,
+        );
         Points::new(point_arrays)
-            .name(format!("{name} ({len})"))
+            .name(name)
             .radius(self.marker_radius)
             .shape(shape)
             .color(color)
@@ -941,7 +1909,8 @@ impl DBV {
             OperationalState::Normal => (), // All normal no action needed
             OperationalState::RunningPyExperiment(promise)
             | OperationalState::Saving(promise)
-            | OperationalState::Loading(promise)
+            | OperationalState::Loading(promise, _)
+            | OperationalState::Exporting(promise, _)
             | OperationalState::RunningLocExperiment(promise) => {
                 if promise.ready().is_some() {
                     let mut temp = OperationalState::default();
@@ -949,7 +1918,8 @@ impl DBV {
                     let owned_promise = match temp {
                         OperationalState::RunningPyExperiment(x)
                         | OperationalState::Saving(x)
-                        | OperationalState::Loading(x)
+                        | OperationalState::Loading(x, _)
+                        | OperationalState::Exporting(x, _)
                         | OperationalState::RunningLocExperiment(x) => x,
                         OperationalState::Normal => unreachable!(
                             "we matched to get into this code block so should still match"
@@ -964,27 +1934,50 @@ impl DBV {
                         OperationOutcome::Success(payload) => match payload {
                             Payload::PyRun => self.status_msg.info("Python Run succeeded"),
                             Payload::Load { loaded_data, path } => {
-                                self.data.replace_with_loaded_data(loaded_data);
-                                if self.on_load_reset_plot_zoom {
-                                    info!("Resetting plot zoom on load");
-                                    self.state_reset_plot_zoom.start_reset();
-                                } else {
-                                    info!(
-                                        "NOT resetting plot zoom on load because configured not to."
-                                    );
-                                }
-
+                                self.on_data_loaded(loaded_data, path)
+                            }
+                            Payload::LoadNeedsCsvMapping { pending, path } => {
+                                self.status_msg.info(
+                                    "CSV layout not recognized. Please select the columns to use",
+                                );
+                                self.csv_mapping_dialog = Some(CsvMappingDialog { pending, path });
+                            }
+                            Payload::LoadNeedsMatVariables { pending, path } => {
+                                self.prompt_for_mat_variables(pending, path)
+                            }
+                            Payload::Save(path, data_hash) => {
+                                self.status_msg.info(format!(
+                                    "Save successfully to {path:?} (Dataset hash: {data_hash})"
+                                ));
                                 #[cfg(not(target_arch = "wasm32"))]
-                                self.set_py_experiment_filename(path);
+                                {
+                                    self.last_saved_path = Some(path.clone());
+                                    self.set_py_experiment_filename(path);
+                                    // Our own write should not be reported as an outside change
+                                    crate::background_worker::mark_watched_file_seen();
+                                }
+                            }
+                            Payload::Export(path, data_hash) => self.status_msg.info(format!(
+                                "Exported successfully to {path:?} (Dataset hash: {data_hash})"
+                            )),
+                            Payload::LoadProject { project, path } => {
+                                self.on_project_loaded(*project, path)
+                            }
+                            Payload::ExportSettings(path) => self
+                                .status_msg
+                                .info(format!("Settings exported to {path:?}")),
+                            Payload::ImportSettings { settings, path } => {
+                                self.on_settings_imported(*settings, path)
                             }
-                            Payload::Save(path) => {
+                            Payload::SaveProject(path) => {
                                 self.status_msg
-                                    .info(format!("Save successfully to {path:?}"));
+                                    .info(format!("Project saved successfully to {path:?}"));
                                 #[cfg(not(target_arch = "wasm32"))]
-                                self.set_py_experiment_filename(path);
+                                self.remember_recent_project(path);
                             }
                             Payload::Train(results) => {
                                 self.status_msg.info("Model training completed");
+                                self.table_columns.invalidate_row_order();
                                 match &self.loc_experiment {
                                     LocalExperiment::None => self.status_msg.error_display(
                                         "failed to save training results. Type set to None",
@@ -1010,6 +2003,17 @@ impl DBV {
                                         )
                                     }
                                 }
+                                #[cfg(not(target_arch = "wasm32"))]
+                                self.save_run_artifacts();
+                            }
+                            Payload::ScoreExternal { parsed, path } => {
+                                self.on_external_points_loaded(parsed, path)
+                            }
+                            Payload::LoadReference { parsed, path } => {
+                                self.on_reference_points_loaded(parsed, path)
+                            }
+                            Payload::ExportVariants { folder, count } => {
+                                self.on_variants_exported(folder, count)
                             }
                         },
                         OperationOutcome::Failed(e) => self.status_msg.error_debug(e),
@@ -1019,10 +2023,132 @@ impl DBV {
         }
     }
 
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    fn on_data_loaded(&mut self, loaded_data: DataPoints, path: PathBuf) {
+        if loaded_data.len() > self.max_points_without_prompt {
+            self.prompt_for_large_load(loaded_data, path);
+            return;
+        }
+        self.finish_data_load(loaded_data, path);
+    }
+
+    /// Replaces the data without checking how many points were loaded
+    fn finish_data_load(&mut self, loaded_data: DataPoints, path: PathBuf) {
+        self.data.replace_with_loaded_data(loaded_data);
+        let added_classes = self.label_classes.add_missing(self.data.points());
+        if added_classes > 0 {
+            self.status_msg.info(format!(
+                "Added {added_classes} class(es) used by the loaded points"
+            ));
+        }
+        if self.on_load_reset_plot_zoom {
+            info!("Resetting plot zoom on load");
+            self.start_zoom_reset(ZoomTarget::AllPoints);
+        } else {
+            info!("NOT resetting plot zoom on load because configured not to.");
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.last_saved_path = Some(path.clone());
+            self.set_py_experiment_filename(path);
+            crate::background_worker::mark_watched_file_seen();
+        }
+    }
+
+    fn ui_csv_mapping_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.csv_mapping_dialog.as_mut() else {
+            return;
+        };
+        let mut is_open = true;
+        let mut should_load = false;
+        let mut should_cancel = false;
+        egui::Window::new("CSV Column Mapping")
+            .open(&mut is_open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!("File: {:?}", dialog.path));
+                ui.checkbox(
+                    &mut dialog.pending.mapping.has_headers,
+                    "First row contains headers",
+                );
+                let column_names = dialog.pending.column_names();
+                let mapping = &mut dialog.pending.mapping;
+                for (field_name, column) in [
+                    ("x0", &mut mapping.x0),
+                    ("x1", &mut mapping.x1),
+                    ("label", &mut mapping.label),
+                ] {
+                    egui::ComboBox::new(format!("id-csv-mapping-{field_name}"), field_name)
+                        .selected_text(column_names.get(*column).cloned().unwrap_or_default())
+                        .show_ui(ui, |ui| {
+                            for (i, name) in column_names.iter().enumerate() {
+                                ui.selectable_value(column, i, name);
+                            }
+                        });
+                }
+
+                ui.separator();
+                ui.strong("Preview");
+                egui::Grid::new("id-csv-mapping-preview")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for name in column_names.iter() {
+                            ui.strong(name);
+                        }
+                        ui.end_row();
+                        for record in dialog.pending.preview_rows(5) {
+                            for field in record.iter() {
+                                ui.label(field);
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Load").clicked() {
+                        should_load = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_load {
+            match dialog
+                .pending
+                .load()
+                .context("failed to load using selected columns")
+            {
+                Ok(loaded_data) => {
+                    let CsvMappingDialog { pending, path } = self
+                        .csv_mapping_dialog
+                        .take()
+                        .expect("dialog should be open to be able to load from it");
+                    self.csv_mappings
+                        .insert(pending.layout_key(), pending.mapping);
+                    self.on_data_loaded(loaded_data, path);
+                }
+                Err(e) => self.status_msg.error_debug(e),
+            }
+        } else if !is_open || should_cancel {
+            self.csv_mapping_dialog = None;
+        }
+    }
+
     fn ui_display_mode(&mut self, ui: &mut egui::Ui) {
         ui.label("Display Mode");
         ui.radio_value(&mut self.display_mode, DisplayMode::Plot, "Plot");
+        ui.radio_value(&mut self.display_mode, DisplayMode::Bins, "Bins")
+            .on_hover_text(
+                "Shows how many points of each label are in each area (For large datasets)",
+            );
         ui.radio_value(&mut self.display_mode, DisplayMode::Table, "Table");
+        if self.display_mode == DisplayMode::Bins {
+            self.ui_binned_view_options(ui);
+        }
     }
 
     fn ui_run_loc_experiment(&mut self, ui: &mut egui::Ui) {
@@ -1084,11 +2210,18 @@ impl DBV {
                             Button::new("Train Model"),
                             Self::train_model_wrapper,
                         );
-                        // TODO 4: Add colored background using predict_batch functionality (maybe use checkbox to control if it is enabled or not)
-                        //    Might be able to use a picture behind the plot with the colors as needed
-                        //    And just disable the plot background https://docs.rs/egui_plot/latest/egui_plot/struct.Plot.html#method.show_background
+                        if let Some(auto_retrain) = self.loc_experiment.auto_retrain_mut() {
+                            ui.checkbox(auto_retrain, "Auto retrain").on_hover_text(
+                                "Retrain automatically after the data stops changing",
+                            );
+                        }
+                        self.ui_score_heatmap_toggle(ui);
                         self.ui_loc_predict_config(ui);
                     });
+                    self.ui_score_delta(ui);
+                    self.ui_split_metrics(ui);
+                    #[cfg(not(target_arch = "wasm32"))]
+                    self.ui_run_artifacts(ui);
                 };
             }
         });
@@ -1116,7 +2249,7 @@ impl DBV {
         debug_assert!(self.op_state.is_normal());
         let mut status_msg = self.status_msg.clone(); // Clone is cheap because type uses an arc internally
         let points = self.data.clone_points();
-        let data_timestamp = self.data.timestamp();
+        let data_timestamp = self.data.training_timestamp();
         match &self.loc_experiment {
             LocalExperiment::None => unreachable!("We should never be trying to train None"),
             LocalExperiment::ProximityScoreTrained(x) => {
@@ -1190,65 +2323,112 @@ impl DBV {
         }
     }
 
+    /// Starts training if auto retrain is enabled and the data has changed since the last training
+    fn auto_retrain(&mut self, ctx: &egui::Context) {
+        let is_enabled = self.loc_experiment.auto_retrain_mut().is_some_and(|x| *x);
+        let data_timestamp = self.data.training_timestamp();
+        if !is_enabled
+            || !self.op_state.is_normal()
+            || self.data.is_empty()
+            || self.loc_experiment.is_at_timestamp(data_timestamp)
+        {
+            return;
+        }
+        if self.auto_retrain_debounce.poll(data_timestamp, ctx) {
+            info!("Auto retrain starting");
+            self.train_model_wrapper(ctx.clone());
+        }
+    }
+
     fn markers_w_results(&self, model: &dyn ModelInference) -> Vec<Points> {
-        let mut false_negatives = vec![];
-        let mut false_positives = vec![];
-        let mut true_negatives = vec![];
-        let mut true_positives = vec![];
+        // Points and their scores in each category
+        let mut false_negatives = (vec![], vec![]);
+        let mut false_positives = (vec![], vec![]);
+        let mut true_negatives = (vec![], vec![]);
+        let mut true_positives = (vec![], vec![]);
+        let mut borderline = (vec![], vec![]);
+        let mut custom_colors = vec![];
 
         // Sort each point into one of the categories
         for (i, point) in self.data.points().iter().enumerate() {
+            if !self
+                .series_visibility
+                .is_visible(self.point_series(i, Some(model)))
+            {
+                continue;
+            }
             let ground_truth = point.label;
             let predicted = model.prediction_on_training_data(i);
-            let point_array = point.to_array();
-            match prediction_classification(ground_truth, predicted) {
-                prediction_classification::Classification::FalseNegative => {
-                    false_negatives.push(point_array)
-                }
-                prediction_classification::Classification::FalsePositive => {
-                    false_positives.push(point_array)
-                }
-                prediction_classification::Classification::TrueNegative => {
-                    true_negatives.push(point_array)
-                }
-                prediction_classification::Classification::TruePositive => {
-                    true_positives.push(point_array)
+            let (category, shape) = if model.is_borderline_on_training_data(i) {
+                (&mut borderline, self.marker_shapes.borderline)
+            } else {
+                match prediction_classification(ground_truth, predicted) {
+                    prediction_classification::Classification::FalseNegative => {
+                        (&mut false_negatives, self.marker_shapes.false_negatives)
+                    }
+                    prediction_classification::Classification::FalsePositive => {
+                        (&mut false_positives, self.marker_shapes.false_positives)
+                    }
+                    prediction_classification::Classification::TrueNegative => {
+                        (&mut true_negatives, self.marker_shapes.true_negatives)
+                    }
+                    prediction_classification::Classification::TruePositive => {
+                        (&mut true_positives, self.marker_shapes.true_positives)
+                    }
                 }
+            };
+            if let Some(color) = point.color {
+                custom_colors.push((point.to_array(), shape.into(), color));
             }
+            category.0.push(point.to_array());
+            category.1.push(model.score_for_training_data(i));
         }
 
-        vec![
+        let mut result = vec![
             self.data_points_to_egui_points(
-                true_positives,
+                true_positives.0,
                 Classification::TruePositive,
-                MarkerShape::Asterisk,
+                legend_summary::mean(&true_positives.1),
+                self.marker_shapes.true_positives.into(),
                 self.color_results_true_positives,
             ),
             self.data_points_to_egui_points(
-                false_positives,
+                false_positives.0,
                 Classification::FalsePositive,
-                MarkerShape::Plus,
+                legend_summary::mean(&false_positives.1),
+                self.marker_shapes.false_positives.into(),
                 self.color_results_false_positives,
             ),
             self.data_points_to_egui_points(
-                true_negatives,
+                true_negatives.0,
                 Classification::TrueNegative,
-                MarkerShape::Plus,
+                legend_summary::mean(&true_negatives.1),
+                self.marker_shapes.true_negatives.into(),
                 self.color_results_true_negatives,
             ),
             self.data_points_to_egui_points(
-                false_negatives,
+                false_negatives.0,
                 Classification::FalseNegative,
-                MarkerShape::Asterisk,
+                legend_summary::mean(&false_negatives.1),
+                self.marker_shapes.false_negatives.into(),
                 self.color_results_false_negatives,
             ),
-        ]
+            self.data_points_to_egui_points(
+                borderline.0,
+                "Borderline",
+                legend_summary::mean(&borderline.1),
+                self.marker_shapes.borderline.into(),
+                self.color_results_borderline,
+            ),
+        ];
+        result.extend(self.markers_custom_colors(custom_colors.into_iter()));
+        result
     }
 
     fn ui_loc_predict_config(&mut self, ui: &mut egui::Ui) {
         if let Some(training_timestamp) = self.loc_experiment.data_timestamp_at_training() {
             ui.separator();
-            match training_timestamp.cmp(&self.data.timestamp()) {
+            match training_timestamp.cmp(&self.data.training_timestamp()) {
                 std::cmp::Ordering::Less => {
                     ui.label("Trained for older version of data (It's possible data may no longer be in the history)");
                 }
@@ -1262,14 +2442,12 @@ impl DBV {
                         LocalExperiment::None => unreachable!("we can't train None"),
                         LocalExperiment::ProximityScoreUntrained(..) => (), // It has no setting before training
                         LocalExperiment::ProximityScoreTrained(model) => {
-                            let config = model.predict_config_mut();
-                            ui.horizontal(|ui| {
-                                ui.label("Threshold: ");
-                                ui.add(egui::Slider::new(
-                                    &mut config.threshold,
-                                    config.min_score..=config.max_score,
-                                ));
-                                // TODO 4: Add button to set threshold to best value based on F1
+                            ui.vertical(|ui| {
+                                threshold_presets::ui_threshold_config(
+                                    ui,
+                                    model,
+                                    &mut self.threshold_preset_name,
+                                )
                             });
                         }
                         LocalExperiment::SingleMaxUntrained(..)
@@ -1283,7 +2461,10 @@ impl DBV {
     }
 
     fn loc_inference_model(&self) -> Option<&dyn ModelInference> {
-        if !self.loc_experiment.is_at_timestamp(self.data.timestamp()) {
+        if !self
+            .loc_experiment
+            .is_at_timestamp(self.data.training_timestamp())
+        {
             return None;
         }
         if let Some(result) = self.loc_experiment.model_inference() {
@@ -1296,6 +2477,12 @@ impl DBV {
     fn ui_menu_file(&mut self, ui: &mut egui::Ui) {
         ui.menu_button("File", |ui| {
             self.ui_persistence(ui);
+            ui.separator();
+            self.ui_project_persistence(ui);
+            if ui.button("Start Screen...").clicked() {
+                self.is_startup_chooser_open = true;
+                ui.close_menu();
+            }
             #[cfg(not(target_arch = "wasm32"))] // no File->Quit on web pages!
             if ui.button("Quit").clicked() {
                 ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
@@ -1304,6 +2491,35 @@ impl DBV {
     }
 }
 
+/// Loads the points from the file (Shared by the different ways of picking the file)
+async fn load_file(
+    file: &rfd::FileHandle,
+    csv_mappings: &CsvMappings,
+    mat_names: &MatVariableNames,
+    status_msg: &mut StatusMsg,
+    progress: &LoadProgress,
+) -> OperationOutcome {
+    let path = file_handle_to_path(file);
+    match Data::load_from_file(file, csv_mappings, mat_names, progress)
+        .await
+        .context("failed to load")
+    {
+        Ok((loaded_data, load_msg)) => {
+            if let Some(msg) = load_msg {
+                status_msg.info(msg)
+            }
+            OperationOutcome::Success(match loaded_data {
+                CsvLoad::Loaded(loaded_data) => Payload::Load { loaded_data, path },
+                CsvLoad::NeedsMapping(pending) => Payload::LoadNeedsCsvMapping { pending, path },
+                CsvLoad::NeedsMatVariables(pending) => {
+                    Payload::LoadNeedsMatVariables { pending, path }
+                }
+            })
+        }
+        Err(e) => OperationOutcome::Failed(e),
+    }
+}
+
 /// Convenience method to convert FileHandle to PathBuf to keep same code between WASM and Native.
 ///
 /// Paths are not used in the WASM code except for logging so set to the filename only
@@ -1321,13 +2537,36 @@ impl eframe::App for DBV {
         eframe::set_value(storage, eframe::APP_KEY, self);
     }
 
+    fn raw_input_hook(&mut self, _ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        self.take_table_tab_presses(raw_input);
+    }
+
     /// Called each time the UI needs repainting, which may be many times per second.
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.update_op_state();
+        self.auto_retrain(ctx);
+        self.precompute_overlays(ctx);
+        self.handle_paste_events(ctx);
+        self.handle_presentation_shortcut(ctx);
+        self.handle_add_point_shortcut(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle_screenshots(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.autosave();
+        #[cfg(not(target_arch = "wasm32"))]
+        self.watch_loaded_file(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_update_check(ctx);
+        self.selection.sync(&self.data);
+        if let Some(warning) = self.data.take_history_size_warning() {
+            self.status_msg.info(warning);
+        }
 
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            self.panel_top(ui);
-        });
+        if !self.is_presenting() {
+            egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+                self.panel_top(ui);
+            });
+        }
 
         if !self.show_data_only {
             egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
@@ -1339,6 +2578,34 @@ impl eframe::App for DBV {
             // The central panel the region left after adding TopPanel and BottomPanel
             self.panel_center(ui);
         });
+
+        self.ui_csv_mapping_dialog(ctx);
+        self.ui_mat_variables_dialog(ctx);
+        self.ui_large_load_dialog(ctx);
+        self.ui_pending_delete_popup(ctx);
+        self.ui_groups_window(ctx);
+        self.ui_annotation_editor(ctx);
+        self.ui_regions_window(ctx);
+        self.ui_history_replay_window(ctx);
+        self.ui_trash_window(ctx);
+        self.ui_affine_window(ctx);
+        self.ui_outlier_pruning_window(ctx);
+        self.ui_kmeans_window(ctx);
+        self.ui_add_point_dialog(ctx);
+        self.ui_batch_add_window(ctx);
+        self.ui_generator_windows(ctx);
+        self.ui_export_subset_window(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.ui_recovery_dialog(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.ui_file_changed_dialog(ctx);
+        self.ui_startup_chooser(ctx);
+        self.ui_about_window(ctx);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.autosave_on_exit();
     }
 }
 
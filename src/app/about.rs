@@ -0,0 +1,201 @@
+use crate::DBV;
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Settings for checking if a newer release has been published
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct UpdateCheck {
+    /// Check once each time the app starts (Off unless the user opts in)
+    pub is_enabled_at_startup: bool,
+    #[serde(skip)]
+    state: UpdateCheckState,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, PartialEq, Default)]
+enum UpdateCheckState {
+    #[default]
+    NotStarted,
+    /// Waiting on the background worker. Manual checks also report when already up to date
+    Checking {
+        is_manual: bool,
+    },
+    Finished(String),
+    /// The error from the last check (Checking again is still allowed)
+    Failed(String),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Returns `true` if the release tag (e.g. "v1.2.0") is a later version than `current`
+///
+/// Anything after a "-" (pre-release) is ignored and missing parts are treated as 0
+fn is_newer_version(tag: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .split('-')
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|x| x.parse().unwrap_or(0))
+            .collect()
+    };
+    let (mut tag, mut current) = (parse(tag), parse(current));
+    let len = tag.len().max(current.len());
+    tag.resize(len, 0);
+    current.resize(len, 0);
+    tag > current
+}
+
+impl DBV {
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    pub(super) fn ui_menu_help(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("Help", |ui| {
+            if ui.button("About...").clicked() {
+                self.is_about_open = true;
+                ui.close_menu();
+            }
+        });
+    }
+
+    pub(super) fn ui_about_window(&mut self, ctx: &egui::Context) {
+        let mut is_open = self.is_about_open;
+        egui::Window::new("About")
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading(crate::APP_NAME);
+                egui::Grid::new("grid-about").show(ui, |ui| {
+                    ui.label("Version");
+                    ui.label(Self::VERSION);
+                    ui.end_row();
+                    ui.label("Build");
+                    ui.label(format!(
+                        "{} ({} {})",
+                        if cfg!(debug_assertions) {
+                            "Debug"
+                        } else {
+                            "Release"
+                        },
+                        std::env::consts::OS,
+                        std::env::consts::ARCH
+                    ));
+                    ui.end_row();
+                    ui.label("Source");
+                    ui.hyperlink(crate::REPOSITORY_URL);
+                    ui.end_row();
+                });
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.checkbox(
+                            &mut self.update_check.is_enabled_at_startup,
+                            "Check for updates at startup",
+                        )
+                        .on_hover_text("Asks GitHub for the latest release (Nothing else is sent)");
+                        let is_checking =
+                            matches!(self.update_check.state, UpdateCheckState::Checking { .. });
+                        if ui
+                            .add_enabled(!is_checking, egui::Button::new("Check now"))
+                            .clicked()
+                        {
+                            self.start_update_check(ctx, true);
+                        }
+                        if is_checking {
+                            ui.spinner();
+                        }
+                    });
+                    match &self.update_check.state {
+                        UpdateCheckState::Finished(tag) => {
+                            if is_newer_version(tag, Self::VERSION) {
+                                ui.label(format!("Release {tag} is available"));
+                                ui.hyperlink_to("Open releases page", releases_url());
+                            } else {
+                                ui.label(format!("Up to date (Latest release is {tag})"));
+                            }
+                        }
+                        UpdateCheckState::Failed(error) => {
+                            ui.colored_label(
+                                ui.visuals().error_fg_color,
+                                format!("Unable to check for updates: {error}"),
+                            );
+                        }
+                        UpdateCheckState::NotStarted | UpdateCheckState::Checking { .. } => {}
+                    }
+                }
+            });
+        self.is_about_open = is_open;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_update_check(&mut self, ctx: &egui::Context, is_manual: bool) {
+        crate::background_worker::request_update_check(ctx);
+        self.update_check.state = UpdateCheckState::Checking { is_manual };
+    }
+
+    /// Starts the check at startup if enabled and reports the result once it is done
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn poll_update_check(&mut self, ctx: &egui::Context) {
+        match self.update_check.state {
+            UpdateCheckState::NotStarted => {
+                if self.update_check.is_enabled_at_startup {
+                    self.start_update_check(ctx, false);
+                }
+            }
+            UpdateCheckState::Checking { is_manual } => {
+                let Some(result) = crate::background_worker::take_update_check_result() else {
+                    return;
+                };
+                match result {
+                    Ok(tag) => {
+                        if is_newer_version(&tag, Self::VERSION) {
+                            self.status_msg.info(format!(
+                                "A newer release ({tag}) is available. See Help -> About"
+                            ));
+                        } else if is_manual {
+                            self.status_msg
+                                .info(format!("Up to date (Latest release is {tag})"));
+                        }
+                        self.update_check.state = UpdateCheckState::Finished(tag);
+                    }
+                    Err(e) => {
+                        let error = format!("{e:#}");
+                        if is_manual {
+                            self.status_msg
+                                .error_debug(e.context("failed to check for updates"));
+                        } else {
+                            log::warn!("failed to check for updates. Error: {e:?}");
+                        }
+                        // Allows trying again from the About window without repeating at startup
+                        self.update_check.state = UpdateCheckState::Failed(error);
+                    }
+                }
+            }
+            UpdateCheckState::Finished(_) | UpdateCheckState::Failed(_) => {}
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn releases_url() -> String {
+    format!("{}/releases", crate::REPOSITORY_URL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_release_versions() {
+        assert!(is_newer_version("v0.2.0", "0.1.0"));
+        assert!(is_newer_version("0.1.1", "0.1"));
+        assert!(is_newer_version("v1.0.0-beta", "0.9.9"));
+        assert!(!is_newer_version("v0.1.0", "0.1.0"));
+        assert!(!is_newer_version("v0.0.9", "0.1.0"));
+    }
+}
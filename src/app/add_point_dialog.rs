@@ -0,0 +1,96 @@
+use egui::{Button, Key, KeyboardShortcut, Modifiers};
+use egui_plot::PlotPoint;
+
+use crate::DBV;
+
+use super::data_definition::DataLabel;
+
+/// Values typed into the dialog for adding a point at exact coordinates
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct AddPointDialog {
+    #[serde(skip)]
+    pub is_open: bool,
+    pub coordinates: [f64; 2],
+    pub label: DataLabel,
+}
+
+impl Default for AddPointDialog {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            coordinates: [0.0, 0.0],
+            label: DataLabel::Normal,
+        }
+    }
+}
+
+impl AddPointDialog {
+    const SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::I);
+}
+
+impl DBV {
+    pub(super) fn ui_btn_add_point_dialog(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add(
+                Button::new("Add Point…")
+                    .shortcut_text(ui.ctx().format_shortcut(&AddPointDialog::SHORTCUT)),
+            )
+            .on_hover_text("Add a point by typing its coordinates")
+            .clicked()
+        {
+            self.add_point_dialog.is_open = true;
+            ui.close_menu();
+        }
+    }
+
+    pub(super) fn handle_add_point_shortcut(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|i| i.consume_shortcut(&AddPointDialog::SHORTCUT)) {
+            self.add_point_dialog.is_open = true;
+        }
+    }
+
+    pub(super) fn ui_add_point_dialog(&mut self, ctx: &egui::Context) {
+        if !self.add_point_dialog.is_open {
+            return;
+        }
+        let mut is_open = true;
+        let mut should_add = false;
+        egui::Window::new("Add Point")
+            .open(&mut is_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let dialog = &mut self.add_point_dialog;
+                egui::Grid::new("grid-add-point").show(ui, |ui| {
+                    for (i, value) in dialog.coordinates.iter_mut().enumerate() {
+                        ui.label(format!("x{i}"));
+                        let response = ui.add(egui::DragValue::new(value).speed(0.01));
+                        should_add |=
+                            response.lost_focus() && ui.input(|x| x.key_pressed(Key::Enter));
+                        ui.end_row();
+                    }
+                    ui.label("Label");
+                    self.label_classes
+                        .ui_combo(ui, "id-add-point-label", &mut dialog.label);
+                    ui.end_row();
+                });
+                should_add |= ui
+                    .add(Button::new("Add"))
+                    .on_hover_text(
+                        "Rounding and bounds for new points apply (Press Enter in a field to add)",
+                    )
+                    .clicked();
+            });
+        if should_add {
+            let [x0, x1] = self.add_point_dialog.coordinates;
+            self.data.add(
+                Some(PlotPoint::new(x0, x1)),
+                self.add_point_dialog.label,
+                &mut self.status_msg,
+            );
+        }
+        if !is_open {
+            self.add_point_dialog.is_open = false;
+        }
+    }
+}
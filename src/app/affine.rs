@@ -0,0 +1,226 @@
+use egui::Button;
+use egui_plot::{MarkerShape, PlotUi, Points};
+
+use crate::DBV;
+
+use super::data_definition::DataPoint;
+
+/// Point the rotation and scaling are done around
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum AffinePivot {
+    /// Mean of the points being transformed
+    #[default]
+    Centroid,
+    Origin,
+}
+
+/// Scales, then rotates, then translates points
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct AffineTransform {
+    pub scale: [f64; 2],
+    /// Counterclockwise in degrees
+    pub rotation: f64,
+    pub translation: [f64; 2],
+    pub pivot: AffinePivot,
+    /// Only the selected points are moved (All points if there is no selection)
+    pub is_selection_only: bool,
+}
+
+impl Default for AffineTransform {
+    fn default() -> Self {
+        Self {
+            scale: [1.0, 1.0],
+            rotation: 0.0,
+            translation: [0.0, 0.0],
+            pivot: Default::default(),
+            is_selection_only: true,
+        }
+    }
+}
+
+impl AffineTransform {
+    fn is_identity(&self) -> bool {
+        self.scale == [1.0, 1.0] && self.rotation == 0.0 && self.translation == [0.0, 0.0]
+    }
+
+    /// Resets the transform but keeps the options
+    fn reset(&mut self) {
+        *self = Self {
+            pivot: self.pivot,
+            is_selection_only: self.is_selection_only,
+            ..Default::default()
+        };
+    }
+
+    /// The points at the indices after the transform
+    fn apply(&self, points: &[DataPoint], indices: &[usize]) -> Vec<(usize, DataPoint)> {
+        let pivot = match self.pivot {
+            AffinePivot::Centroid if !indices.is_empty() => {
+                let count = indices.len() as f64;
+                [
+                    indices.iter().map(|&i| points[i].x0).sum::<f64>() / count,
+                    indices.iter().map(|&i| points[i].x1).sum::<f64>() / count,
+                ]
+            }
+            AffinePivot::Centroid | AffinePivot::Origin => [0.0, 0.0],
+        };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        indices
+            .iter()
+            .map(|&i| {
+                let mut point = points[i];
+                let x0 = (point.x0 - pivot[0]) * self.scale[0];
+                let x1 = (point.x1 - pivot[1]) * self.scale[1];
+                point.x0 = pivot[0] + x0 * cos - x1 * sin + self.translation[0];
+                point.x1 = pivot[1] + x0 * sin + x1 * cos + self.translation[1];
+                (i, point)
+            })
+            .collect()
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_btn_transform_points(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(!self.data.is_empty(), Button::new("Transform Points…"))
+            .clicked()
+        {
+            self.show_affine_window = true;
+            ui.close_menu();
+        }
+    }
+
+    /// Indices of the points the transform applies to
+    fn affine_indices(&self) -> Vec<usize> {
+        if self.affine.is_selection_only && !self.selection.is_empty() {
+            self.selection.indices().iter().copied().collect()
+        } else {
+            (0..self.data.points().len()).collect()
+        }
+    }
+
+    pub(super) fn ui_affine_window(&mut self, ctx: &egui::Context) {
+        if !self.show_affine_window {
+            return;
+        }
+        let mut is_open = true;
+        egui::Window::new("Transform Points")
+            .open(&mut is_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let affine = &mut self.affine;
+                egui::Grid::new("grid-affine").show(ui, |ui| {
+                    ui.label("Scale");
+                    ui.horizontal(|ui| {
+                        for (value, prefix) in affine.scale.iter_mut().zip(["x0: ", "x1: "]) {
+                            ui.add(egui::DragValue::new(value).speed(0.01).prefix(prefix));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Rotation (degrees)");
+                    ui.add(egui::DragValue::new(&mut affine.rotation).speed(0.5));
+                    ui.end_row();
+
+                    ui.label("Translation");
+                    ui.horizontal(|ui| {
+                        for (value, prefix) in affine.translation.iter_mut().zip(["x0: ", "x1: "]) {
+                            ui.add(egui::DragValue::new(value).speed(0.1).prefix(prefix));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Pivot");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut affine.pivot, AffinePivot::Centroid, "Centroid");
+                        ui.radio_value(&mut affine.pivot, AffinePivot::Origin, "Origin");
+                    });
+                    ui.end_row();
+                });
+                ui.add_enabled(
+                    !self.selection.is_empty(),
+                    egui::Checkbox::new(&mut self.affine.is_selection_only, "Selection only"),
+                );
+                ui.horizontal(|ui| {
+                    let count = self.affine_indices().len();
+                    if ui
+                        .add_enabled(
+                            !self.affine.is_identity() && count > 0,
+                            Button::new(format!("Apply to {count} points")),
+                        )
+                        .on_hover_text("The preview on the plot shows where the points will go")
+                        .clicked()
+                    {
+                        let edits = self
+                            .affine
+                            .apply(self.data.points(), &self.affine_indices());
+                        self.data.edit_many(edits);
+                        self.selection.keep_for(&self.data);
+                        self.affine.reset();
+                    }
+                    if ui.button("Reset").clicked() {
+                        self.affine.reset();
+                    }
+                });
+            });
+        if !is_open {
+            self.show_affine_window = false;
+        }
+    }
+
+    /// Shows where the points will be moved while the transform window is open
+    pub(super) fn plot_affine_preview(&self, plot_ui: &mut PlotUi) {
+        if !self.show_affine_window || self.affine.is_identity() {
+            return;
+        }
+        let positions: Vec<[f64; 2]> = self
+            .affine
+            .apply(self.data.points(), &self.affine_indices())
+            .into_iter()
+            .map(|(_, point)| [point.x0, point.x1])
+            .collect();
+        plot_ui.points(
+            Points::new(positions)
+                .name("Transform Preview")
+                .shape(MarkerShape::Circle)
+                .filled(false)
+                .radius(self.marker_radius)
+                .color(self.color_selection.gamma_multiply(0.6)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn rotates_around_centroid() {
+        let points = [
+            DataPoint::new(1.0, 0.0, DataLabel::Normal),
+            DataPoint::new(3.0, 0.0, DataLabel::Anomaly),
+            DataPoint::new(9.0, 9.0, DataLabel::Normal),
+        ];
+        let mut affine = AffineTransform {
+            rotation: 90.0,
+            scale: [2.0, 1.0],
+            translation: [0.0, 1.0],
+            ..Default::default()
+        };
+        assert!(!affine.is_identity());
+        let edits = affine.apply(&points, &[0, 1]);
+        let positions: Vec<_> = edits.iter().map(|(_, p)| [p.x0, p.x1]).collect();
+        for (actual, expected) in positions.iter().zip([[2.0, -1.0], [2.0, 3.0]]) {
+            assert!((actual[0] - expected[0]).abs() < 1e-9, "{positions:?}");
+            assert!((actual[1] - expected[1]).abs() < 1e-9, "{positions:?}");
+        }
+        assert_eq!(edits[1].1.label, DataLabel::Anomaly);
+
+        affine.reset();
+        assert!(affine.is_identity());
+        assert_eq!(affine.apply(&points, &[2])[0].1, points[2]);
+    }
+}
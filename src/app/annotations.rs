@@ -0,0 +1,130 @@
+use egui::{PointerButton, RichText};
+use egui_plot::{PlotPoint, PlotUi, Text};
+
+use crate::DBV;
+
+use super::ui_blocks::drag_start_position;
+
+impl DBV {
+    /// Distance in screen points from a note's anchor that still picks the note
+    const ANNOTATION_PICK_RADIUS: f64 = 12.0;
+    const NEW_ANNOTATION_TEXT: &'static str = "Note";
+
+    /// Draws the notes and, in note mode, places, moves and deletes them
+    pub(super) fn plot_annotations(&mut self, plot_ui: &mut PlotUi) {
+        let is_note_mode = self.click_mode.is_annotate();
+        if is_note_mode {
+            self.annotation_interactions(plot_ui);
+        } else {
+            self.moving_annotation = None;
+        }
+        if !self.show_annotations && !is_note_mode {
+            return;
+        }
+        let text_color = plot_ui.ctx().style().visuals.strong_text_color();
+        for (i, note) in self.data.annotations.iter().enumerate() {
+            let color = if self.editing_annotation == Some(i) {
+                self.color_selection
+            } else {
+                text_color
+            };
+            plot_ui.text(Text::new(
+                PlotPoint::new(note.position[0], note.position[1]),
+                RichText::new(&note.text).color(color),
+            ));
+        }
+    }
+
+    /// Primary click places a note or opens the one clicked for editing, dragging moves a note
+    /// and secondary click deletes it
+    fn annotation_interactions(&mut self, plot_ui: &mut PlotUi) {
+        let response = plot_ui.response().clone();
+        let radius = Self::ANNOTATION_PICK_RADIUS * plot_ui.plot_bounds().width()
+            / response.rect.width() as f64;
+        let pointer = plot_ui
+            .pointer_coordinate()
+            .or(self.last_cursor_pos)
+            .map(|p| [p.x, p.y]);
+
+        if response.drag_started_by(PointerButton::Primary) {
+            self.moving_annotation = drag_start_position(plot_ui)
+                .and_then(|press| self.data.annotations.nearest_within(press, radius));
+        }
+        if let Some(index) = self.moving_annotation {
+            if let (Some(note), Some(pointer)) = (self.data.annotations.get_mut(index), pointer) {
+                note.position = pointer;
+            }
+            if response.drag_stopped_by(PointerButton::Primary) {
+                self.moving_annotation = None;
+            }
+        }
+
+        let Some(pointer) = pointer else {
+            return;
+        };
+        if response.clicked() {
+            let index = self
+                .data
+                .annotations
+                .nearest_within(pointer, radius)
+                .unwrap_or_else(|| {
+                    self.data
+                        .annotations
+                        .add(Self::NEW_ANNOTATION_TEXT.to_string(), pointer)
+                });
+            self.editing_annotation = Some(index);
+        }
+        if response.secondary_clicked() {
+            match self.data.annotations.nearest_within(pointer, radius) {
+                Some(index) => self.remove_annotation(index),
+                None => self.status_msg.info("No note under the cursor to delete"),
+            }
+        }
+    }
+
+    fn remove_annotation(&mut self, index: usize) {
+        let Some(note) = self.data.annotations.remove(index) else {
+            return;
+        };
+        self.editing_annotation = match self.editing_annotation {
+            Some(i) if i == index => None,
+            Some(i) if i > index => Some(i - 1),
+            other => other,
+        };
+        self.status_msg
+            .info(format!("Deleted note {:?}", note.text));
+    }
+
+    pub(super) fn ui_annotation_editor(&mut self, ctx: &egui::Context) {
+        let Some(index) = self.editing_annotation else {
+            return;
+        };
+        let Some(note) = self.data.annotations.get_mut(index) else {
+            // Notes were replaced (e.g. a project was loaded)
+            self.editing_annotation = None;
+            return;
+        };
+        let mut is_open = true;
+        let mut should_delete = false;
+        egui::Window::new("Note")
+            .open(&mut is_open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut note.text)
+                        .desired_rows(2)
+                        .hint_text("Text shown on the plot"),
+                );
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut note.position[0]).prefix("x0: "));
+                    ui.add(egui::DragValue::new(&mut note.position[1]).prefix("x1: "));
+                });
+                should_delete = ui.button("Delete Note").clicked();
+            });
+        if should_delete {
+            self.remove_annotation(index);
+        } else if !is_open {
+            self.editing_annotation = None;
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Drives the app without a window so that runs can be scripted and tested end to end
+//!
+//! Each step runs the same code as the matching action in the UI and waits for it to finish.
+//!
+//! ```no_run
+//! use dbv::automation::{Automation, DataLabel, ModelKind};
+//!
+//! let mut app = Automation::new();
+//! app.load_file("data.csv").unwrap();
+//! app.add_point(1.0, 2.0, DataLabel::Anomaly).unwrap();
+//! app.select_model(ModelKind::ProximityScore);
+//! app.train().unwrap();
+//! app.export_image("figure.svg").unwrap();
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context as _};
+
+use crate::DBV;
+
+use super::{
+    data_definition::Save as _,
+    local_experiments::{LocalExperiment, ProximityScore, SingleMax},
+    operational_state::{OperationOutcome, OperationalState},
+};
+
+pub use super::data_definition::{DataLabel, DataPoint};
+
+/// The local models that can be selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    ProximityScore,
+    SingleMax,
+}
+
+/// An instance of the app that is controlled by method calls instead of the UI
+pub struct Automation {
+    app: DBV,
+    ctx: egui::Context,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl Default for Automation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Automation {
+    /// Starts with the default settings (Saved app state is not used so runs are reproducible)
+    pub fn new() -> Self {
+        let app = DBV {
+            // There is no one to answer the prompt
+            max_points_without_prompt: usize::MAX,
+            ..Default::default()
+        };
+        Self {
+            app,
+            ctx: Default::default(),
+            runtime: tokio::runtime::Runtime::new().expect("Unable to create Runtime"),
+        }
+    }
+
+    /// Replaces the points with the ones in the file
+    ///
+    /// Fails if the file would need the user to pick the columns or variables to use
+    pub fn load_file(&mut self, path: impl Into<PathBuf>) -> anyhow::Result<()> {
+        let path = path.into();
+        if !path.is_file() {
+            bail!("file not found: {path:?}");
+        }
+        self.run_operation(|app, ctx| app.load_from_path(path, ctx))?;
+        if self.app.csv_mapping_dialog.take().is_some() {
+            bail!("CSV layout not recognized. Expected columns x0, x1 and label");
+        }
+        if self.app.mat_variables_dialog.take().is_some() {
+            bail!(
+                "{:?} or {:?} not found in MAT-file",
+                self.app.mat_variable_names.points,
+                self.app.mat_variable_names.labels
+            );
+        }
+        Ok(())
+    }
+
+    /// Adds a point the same way as clicking on the plot (Can be undone)
+    pub fn add_point(&mut self, x0: f64, x1: f64, label: DataLabel) -> anyhow::Result<()> {
+        let count_before = self.app.data.points().len();
+        self.app.data.add(
+            Some(egui_plot::PlotPoint::new(x0, x1)),
+            label,
+            &mut self.app.status_msg,
+        );
+        if self.app.data.points().len() == count_before {
+            bail!(
+                "point ({x0}, {x1}) was not added: {}",
+                self.app.status_msg.msg()
+            );
+        }
+        Ok(())
+    }
+
+    /// Selects the model with its default settings (Replaces any trained model)
+    pub fn select_model(&mut self, kind: ModelKind) {
+        self.app.loc_experiment = match kind {
+            ModelKind::ProximityScore => {
+                LocalExperiment::ProximityScoreUntrained(ProximityScore::new())
+            }
+            ModelKind::SingleMax => LocalExperiment::SingleMaxUntrained(SingleMax::new()),
+        };
+    }
+
+    /// Trains the selected model on the current points
+    pub fn train(&mut self) -> anyhow::Result<()> {
+        if matches!(self.app.loc_experiment, LocalExperiment::None) {
+            bail!("no model selected");
+        }
+        self.run_operation(|app, ctx| app.train_model_wrapper(ctx))
+    }
+
+    /// Saves the points to the file using the format matching its extension
+    pub fn save(&mut self, path: impl Into<PathBuf>) -> anyhow::Result<()> {
+        let file = rfd::FileHandle::from(path.into());
+        let mut points = self.app.data.clone_points();
+        self.app.label_classes.prepare_for_save(&mut points);
+        self.runtime
+            .block_on(points.save_to_file(&file, &self.app.mat_variable_names))
+            .context("failed to save file")
+    }
+
+    /// Writes an SVG image of the points colored by the results if the model is trained on the
+    /// current points and by their labels otherwise
+    pub fn export_image(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.app.plot_snapshot_svg())
+            .with_context(|| format!("failed to write image to {path:?}"))
+    }
+
+    pub fn points(&self) -> &[DataPoint] {
+        self.app.data.points()
+    }
+
+    /// Returns `true` if the selected model is trained on the current points
+    pub fn is_trained(&self) -> bool {
+        self.app.loc_inference_model().is_some()
+    }
+
+    /// The messages that would have been shown in the status bar
+    pub fn status(&self) -> String {
+        self.app.status_msg.msg()
+    }
+
+    /// Starts the operation then waits for it to finish and applies the outcome like the UI does
+    fn run_operation(&mut self, start: impl FnOnce(&mut DBV, egui::Context)) -> anyhow::Result<()> {
+        debug_assert!(self.app.op_state.is_normal());
+        let _guard = self.runtime.enter();
+        start(&mut self.app, self.ctx.clone());
+        let error = match &self.app.op_state {
+            OperationalState::Normal => None,
+            OperationalState::RunningPyExperiment(promise)
+            | OperationalState::Saving(promise)
+            | OperationalState::Loading(promise, _)
+            | OperationalState::Exporting(promise, _)
+            | OperationalState::RunningLocExperiment(promise) => {
+                match promise.block_until_ready() {
+                    OperationOutcome::Failed(e) => Some(format!("{e:#}")),
+                    _ => None,
+                }
+            }
+        };
+        self.app.update_op_state();
+        match error {
+            Some(e) => bail!(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_train_and_export() {
+        let folder = tempfile::tempdir().unwrap();
+        let data_path = folder.path().join("data.csv");
+        let mut app = Automation::new();
+        for i in 0..10 {
+            app.add_point(f64::from(i), 0.0, DataLabel::Normal).unwrap();
+        }
+        app.add_point(5.0, 20.0, DataLabel::Anomaly).unwrap();
+        app.save(&data_path).unwrap();
+
+        let mut app = Automation::new();
+        app.load_file(&data_path).unwrap();
+        assert_eq!(app.points().len(), 11);
+        assert!(app.train().is_err());
+        app.select_model(ModelKind::ProximityScore);
+        app.train().unwrap();
+        assert!(app.is_trained());
+
+        let image_path = folder.path().join("plot.svg");
+        app.export_image(&image_path).unwrap();
+        let image = std::fs::read_to_string(image_path).unwrap();
+        assert_eq!(image.matches("<circle").count(), 11);
+        assert!(app.load_file(folder.path().join("missing.csv")).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context;
+use egui::Button;
+use log::info;
+use web_time::Instant;
+
+use crate::{background_worker, DBV};
+
+use super::{
+    data_definition::{Data, DataTimestamp},
+    plot_zoom_reset::ZoomTarget,
+};
+
+/// Settings for periodically writing a snapshot of the data to a recovery file
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct Autosave {
+    pub is_enabled: bool,
+    pub interval_secs: u16,
+    /// The version of the data last snapshotted and when
+    #[serde(skip)]
+    last_snapshot: Option<(DataTimestamp, Instant)>,
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self {
+            is_enabled: true,
+            interval_secs: 30,
+            last_snapshot: None,
+        }
+    }
+}
+
+impl Autosave {
+    const RECOVERY_FILENAME: &'static str = "recovery.ron";
+
+    /// Location of the recovery file (Next to where eframe stores the app state)
+    pub fn recovery_path() -> Option<PathBuf> {
+        eframe::storage_dir(crate::APP_NAME).map(|dir| dir.join(Self::RECOVERY_FILENAME))
+    }
+
+    /// Marks the data as already saved (Used on startup as that version is already persisted)
+    pub fn mark_saved(&mut self, timestamp: DataTimestamp) {
+        self.last_snapshot = Some((timestamp, Instant::now()));
+    }
+
+    /// Returns true if the data has changed since the last snapshot and the interval has passed
+    fn is_due(&self, timestamp: DataTimestamp) -> bool {
+        match self.last_snapshot {
+            Some((last_timestamp, _)) if last_timestamp == timestamp => false,
+            Some((_, at)) => at.elapsed() >= Duration::from_secs(self.interval_secs.into()),
+            None => true,
+        }
+    }
+
+    /// Reads the recovery file left behind if the app did not exit cleanly
+    pub fn read_recovery_file() -> anyhow::Result<Option<Data>> {
+        let Some(path) = Self::recovery_path() else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read recovery file at {path:?}"))?;
+        let data = ron::from_str(&text)
+            .with_context(|| format!("failed to parse recovery file at {path:?}"))?;
+        Ok(Some(data))
+    }
+
+    pub fn remove_recovery_file() {
+        if let Some(path) = Self::recovery_path() {
+            if path.exists() {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    log::error!("failed to remove recovery file at {path:?}. Error: {e}");
+                }
+            }
+        }
+    }
+}
+
+impl DBV {
+    /// Checks for a recovery file from a run that did not exit cleanly
+    pub(super) fn check_for_recovery(&mut self) {
+        self.autosave.mark_saved(self.data.timestamp());
+        match Autosave::read_recovery_file() {
+            Ok(Some(recovered)) if recovered.timestamp() != self.data.timestamp() => {
+                info!("Found recovery data that differs from the saved state");
+                self.recovered_data = Some(recovered);
+            }
+            Ok(Some(_)) => {
+                info!("Recovery data matches the saved state, removing it");
+                Autosave::remove_recovery_file();
+            }
+            Ok(None) => {}
+            Err(e) => self.status_msg.error_debug(e),
+        }
+    }
+
+    /// Queues a snapshot of the data for the background worker to write if one is due
+    pub(super) fn autosave(&mut self) {
+        if !self.autosave.is_enabled || self.recovered_data.is_some() {
+            // Don't overwrite the recovery file before the user decides what to do with it
+            return;
+        }
+        let timestamp = self.data.timestamp();
+        if !self.autosave.is_due(timestamp) {
+            return;
+        }
+        let Some(path) = Autosave::recovery_path() else {
+            return;
+        };
+        match ron::to_string(&self.data).context("failed to serialize data for autosave") {
+            Ok(contents) => background_worker::queue_autosave(path, contents),
+            Err(e) => self.status_msg.error_debug(e),
+        }
+        self.autosave.mark_saved(timestamp);
+    }
+
+    /// Called on a clean exit so the next launch does not offer recovery
+    pub(super) fn autosave_on_exit(&mut self) {
+        if let Some(path) = Autosave::recovery_path() {
+            background_worker::stop_autosave_and_remove(&path);
+        }
+    }
+
+    pub(super) fn ui_autosave_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.autosave.is_enabled, "Autosave for crash recovery");
+            if self.autosave.is_enabled {
+                ui.add(
+                    egui::DragValue::new(&mut self.autosave.interval_secs)
+                        .clamp_range(1..=3600)
+                        .prefix("Interval (s): "),
+                );
+            }
+        });
+    }
+
+    pub(super) fn ui_recovery_dialog(&mut self, ctx: &egui::Context) {
+        let Some(recovered) = self.recovered_data.as_ref() else {
+            return;
+        };
+        let mut should_restore = false;
+        let mut should_discard = false;
+        egui::Window::new("Recover Unsaved Data")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("The app did not exit cleanly last time.");
+                ui.label(format!(
+                    "Recovered data has {} points (Currently loaded data has {} points).",
+                    recovered.points().len(),
+                    self.data.points().len()
+                ));
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(Button::new("Restore"))
+                        .on_hover_text("Replaces the current data and its history")
+                        .clicked()
+                    {
+                        should_restore = true;
+                    }
+                    if ui.button("Discard").clicked() {
+                        should_discard = true;
+                    }
+                });
+            });
+        if should_restore {
+            if let Some(recovered) = self.recovered_data.take() {
+                self.data = recovered;
+                self.status_msg.info("Data restored from recovery file");
+                self.start_zoom_reset(ZoomTarget::AllPoints);
+            }
+        }
+        if should_discard {
+            self.recovered_data = None;
+            Autosave::remove_recovery_file();
+        }
+    }
+}
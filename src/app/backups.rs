@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::DBV;
+
+/// Settings for keeping copies of files before they are overwritten by a save
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(default)]
+pub struct Backups {
+    pub is_enabled: bool,
+    /// Number of backups kept for each file (Older ones are removed)
+    pub retention: u16,
+}
+
+impl Default for Backups {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            retention: 5,
+        }
+    }
+}
+
+impl Backups {
+    const MARKER: &'static str = ".bak-";
+
+    /// Copies the file to a timestamped backup next to it (if it exists) then removes the oldest
+    /// backups past the retention count. Returns the path of the backup if one was made
+    pub fn backup_before_overwrite(&self, path: &Path) -> anyhow::Result<Option<PathBuf>> {
+        if !self.is_enabled || !path.is_file() {
+            return Ok(None);
+        }
+        let backup_path = Self::backup_path(
+            path,
+            &chrono::Local::now().format("%Y%m%dT%H%M%S").to_string(),
+        );
+        std::fs::copy(path, &backup_path)
+            .with_context(|| format!("failed to create backup of {path:?} at {backup_path:?}"))?;
+        self.remove_old_backups(path)?;
+        Ok(Some(backup_path))
+    }
+
+    fn backup_path(path: &Path, timestamp: &str) -> PathBuf {
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(format!("{}{timestamp}", Self::MARKER));
+        path.with_file_name(file_name)
+    }
+
+    /// Returns the existing backups of the file oldest first
+    fn existing_backups(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let Some(file_name) = path.file_name().and_then(|x| x.to_str()) else {
+            return Ok(vec![]);
+        };
+        let prefix = format!("{file_name}{}", Self::MARKER);
+        let folder = match path.parent() {
+            Some(x) if !x.as_os_str().is_empty() => x,
+            _ => Path::new("."),
+        };
+        let mut result = vec![];
+        for entry in std::fs::read_dir(folder)
+            .with_context(|| format!("failed to list backups in {folder:?}"))?
+        {
+            let entry = entry.context("failed to read folder entry")?;
+            if entry
+                .file_name()
+                .to_str()
+                .is_some_and(|x| x.starts_with(&prefix))
+            {
+                result.push(entry.path());
+            }
+        }
+        // The timestamp format sorts in chronological order
+        result.sort();
+        Ok(result)
+    }
+
+    fn remove_old_backups(&self, path: &Path) -> anyhow::Result<()> {
+        let backups = Self::existing_backups(path)?;
+        let excess = backups.len().saturating_sub(self.retention.max(1).into());
+        for old in backups.into_iter().take(excess) {
+            std::fs::remove_file(&old)
+                .with_context(|| format!("failed to remove old backup {old:?}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_backup_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.backups.is_enabled,
+                "Backup files before overwriting",
+            )
+            .on_hover_text(
+                "Keeps timestamped copies next to the file (e.g. data.csv.bak-20240501T120000)",
+            );
+            if self.backups.is_enabled {
+                ui.add(
+                    egui::DragValue::new(&mut self.backups.retention)
+                        .clamp_range(1..=1000)
+                        .prefix("Keep: "),
+                );
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_newest_backups() {
+        let folder = tempfile::tempdir().unwrap();
+        let path = folder.path().join("data.csv");
+        let backups = Backups {
+            is_enabled: true,
+            retention: 2,
+        };
+        for timestamp in ["20240101T000000", "20240102T000000", "20240103T000000"] {
+            std::fs::write(Backups::backup_path(&path, timestamp), timestamp).unwrap();
+        }
+        std::fs::write(&path, "current").unwrap();
+        std::fs::write(folder.path().join("other.csv.bak-20240101T000000"), "").unwrap();
+
+        let created = backups.backup_before_overwrite(&path).unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(&created).unwrap(), "current");
+        assert_eq!(
+            Backups::existing_backups(&path).unwrap(),
+            [Backups::backup_path(&path, "20240103T000000"), created]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::data_definition::{parse_points_text, DataLabel, ParsedPoints};
+
+/// Text typed or pasted into the dialog for adding many points at once
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct BatchAdd {
+    #[serde(skip)]
+    pub is_open: bool,
+    #[serde(skip)]
+    text: String,
+    /// Used for lines that do not have a label
+    pub default_label: DataLabel,
+}
+
+impl Default for BatchAdd {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            text: String::new(),
+            default_label: DataLabel::Normal,
+        }
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_btn_batch_add(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .button("Add Points from Text…")
+            .on_hover_text("Type or paste lines of \"x0, x1, label\"")
+            .clicked()
+        {
+            self.batch_add.is_open = true;
+            ui.close_menu();
+        }
+    }
+
+    pub(super) fn ui_batch_add_window(&mut self, ctx: &egui::Context) {
+        if !self.batch_add.is_open {
+            return;
+        }
+        let mut is_open = true;
+        let mut should_add = false;
+        egui::Window::new("Add Points from Text")
+            .open(&mut is_open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label("One point per line as \"x0, x1, label\" (Tabs and semicolons also work)");
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.batch_add.text)
+                                .code_editor()
+                                .desired_rows(10)
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+                ui.horizontal(|ui| {
+                    ui.label("Label when missing");
+                    self.label_classes.ui_combo(
+                        ui,
+                        "id-batch-add-label",
+                        &mut self.batch_add.default_label,
+                    );
+                });
+                ui.horizontal(|ui| {
+                    should_add = ui
+                        .add_enabled(
+                            !self.batch_add.text.trim().is_empty(),
+                            Button::new("Add Points"),
+                        )
+                        .on_hover_text("Adds every valid line as a single undoable change")
+                        .clicked();
+                    if ui.button("Clear").clicked() {
+                        self.batch_add.text.clear();
+                    }
+                });
+            });
+        if should_add {
+            let ParsedPoints { points, errors } =
+                parse_points_text(&self.batch_add.text, self.batch_add.default_label);
+            for error in errors.iter() {
+                self.status_msg
+                    .error_display(format!("Unable to add point. {error}"));
+            }
+            if points.is_empty() {
+                self.status_msg.info("No points found to add");
+            } else {
+                let count = points.len();
+                self.data.add_many(points);
+                self.status_msg.info(format!("Added {count} points"));
+                if errors.is_empty() {
+                    self.batch_add.text.clear();
+                }
+            }
+        }
+        if !is_open {
+            self.batch_add.is_open = false;
+        }
+    }
+}
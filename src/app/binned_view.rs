@@ -0,0 +1,226 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ecolor::Color32;
+use egui::Stroke;
+use egui_plot::{PlotPoints, PlotUi, Polygon};
+
+use crate::DBV;
+
+use super::data_definition::{DataLabel, DataPoint, PointArray};
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum BinShape {
+    #[default]
+    Hexagon,
+    Square,
+}
+
+/// Settings for showing the points as counts per bin instead of as markers
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+#[serde(default)]
+pub struct BinnedView {
+    pub shape: BinShape,
+    /// Number of bins across the width of the visible area
+    pub bins_across: u32,
+}
+
+impl Default for BinnedView {
+    fn default() -> Self {
+        Self {
+            shape: Default::default(),
+            bins_across: 40,
+        }
+    }
+}
+
+/// Identifies a bin (Axial coordinates for hexagons and column and row for squares)
+type BinKey = (i64, i64);
+
+const SQRT_3: f64 = 1.732_050_807_568_877_2;
+
+impl BinShape {
+    /// Returns the bin containing the point where `size` is the side of the bin
+    fn bin_of(&self, [x, y]: PointArray, size: f64) -> BinKey {
+        match self {
+            BinShape::Square => ((x / size).floor() as i64, (y / size).floor() as i64),
+            BinShape::Hexagon => {
+                // Pointy top hexagons (See https://www.redblobgames.com/grids/hexagons/)
+                let q = (SQRT_3 / 3.0 * x - y / 3.0) / size;
+                let r = (2.0 / 3.0 * y) / size;
+                let s = -q - r;
+                let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+                let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+                if dq > dr && dq > ds {
+                    rq = -rr - rs;
+                } else if dr > ds {
+                    rr = -rq - rs;
+                }
+                (rq as i64, rr as i64)
+            }
+        }
+    }
+
+    fn center(&self, (a, b): BinKey, size: f64) -> PointArray {
+        let (a, b) = (a as f64, b as f64);
+        match self {
+            BinShape::Square => [(a + 0.5) * size, (b + 0.5) * size],
+            BinShape::Hexagon => [size * SQRT_3 * (a + b / 2.0), size * 1.5 * b],
+        }
+    }
+
+    fn outline(&self, key: BinKey, size: f64) -> Vec<PointArray> {
+        let [x, y] = self.center(key, size);
+        match self {
+            BinShape::Square => {
+                let half = size / 2.0;
+                vec![
+                    [x - half, y - half],
+                    [x + half, y - half],
+                    [x + half, y + half],
+                    [x - half, y + half],
+                ]
+            }
+            BinShape::Hexagon => (0..6)
+                .map(|i| {
+                    let angle = (60.0 * i as f64 - 30.0).to_radians();
+                    [x + size * angle.cos(), y + size * angle.sin()]
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Counts the points of each label in each bin
+fn bin_counts<'a>(
+    points: impl Iterator<Item = &'a DataPoint>,
+    shape: BinShape,
+    size: f64,
+) -> HashMap<BinKey, BTreeMap<DataLabel, usize>> {
+    let mut result: HashMap<BinKey, BTreeMap<DataLabel, usize>> = HashMap::new();
+    for point in points {
+        *result
+            .entry(shape.bin_of([point.x0, point.x1], size))
+            .or_default()
+            .entry(point.label)
+            .or_default() += 1;
+    }
+    result
+}
+
+impl DBV {
+    pub(super) fn ui_binned_view_options(&mut self, ui: &mut egui::Ui) {
+        let options = &mut self.binned_view;
+        ui.selectable_value(&mut options.shape, BinShape::Hexagon, "Hexagons");
+        ui.selectable_value(&mut options.shape, BinShape::Square, "Squares");
+        ui.add(
+            egui::DragValue::new(&mut options.bins_across)
+                .clamp_range(5..=200)
+                .prefix("Bins across: "),
+        );
+    }
+
+    /// Draws the number of points of each label in the bins covering the visible area (Darker
+    /// for more points). Hovering shows the counts of the bin under the pointer
+    pub(super) fn plot_bins(&self, plot_ui: &mut PlotUi) {
+        let bounds = plot_ui.plot_bounds();
+        let shape = self.binned_view.shape;
+        let across = f64::from(self.binned_view.bins_across.max(1));
+        let size = match shape {
+            BinShape::Square => bounds.width() / across,
+            BinShape::Hexagon => bounds.width() / across / SQRT_3,
+        };
+        if size <= 0.0 || !size.is_finite() {
+            return;
+        }
+        let counts = bin_counts(self.data.points().iter(), shape, size);
+        let mut max_counts: BTreeMap<DataLabel, usize> = BTreeMap::new();
+        for (&label, &count) in counts.values().flatten() {
+            let max = max_counts.entry(label).or_default();
+            *max = (*max).max(count);
+        }
+        let is_visible = |[x, y]: PointArray| {
+            (bounds.min()[0] - size..=bounds.max()[0] + size).contains(&x)
+                && (bounds.min()[1] - size..=bounds.max()[1] + size).contains(&y)
+        };
+
+        for (label, max) in max_counts {
+            let color = self.label_color(label);
+            // Log scale so a few crowded bins do not wash out the rest
+            let scale = (max as f64).ln_1p();
+            let name = format!("{} (Up to {max} per bin)", self.label_classes.name(label));
+            for (&key, bin) in counts.iter() {
+                let count = bin.get(&label).copied().unwrap_or_default();
+                if count == 0 || !is_visible(shape.center(key, size)) {
+                    continue;
+                }
+                let strength = 0.15 + 0.85 * ((count as f64).ln_1p() / scale) as f32;
+                plot_ui.polygon(
+                    Polygon::new(PlotPoints::new(shape.outline(key, size)))
+                        .name(&name)
+                        .stroke(Stroke::NONE)
+                        .fill_color(color_with_alpha(color, strength * 0.8)),
+                );
+            }
+        }
+
+        if !plot_ui.response().hovered() {
+            return;
+        }
+        let Some(pointer) = plot_ui.pointer_coordinate() else {
+            return;
+        };
+        let key = shape.bin_of([pointer.x, pointer.y], size);
+        let Some(bin) = counts.get(&key) else {
+            return;
+        };
+        plot_ui.polygon(
+            Polygon::new(PlotPoints::new(shape.outline(key, size)))
+                .stroke(Stroke::new(1.5, self.color_selection))
+                .fill_color(Color32::TRANSPARENT),
+        );
+        egui::show_tooltip_at_pointer(plot_ui.ctx(), egui::Id::new("bin-tooltip"), |ui| {
+            for (&label, count) in bin {
+                ui.label(format!("{}: {count}", self.label_classes.name(label)));
+            }
+        });
+    }
+}
+
+fn color_with_alpha(color: Color32, alpha: f32) -> Color32 {
+    let [r, g, b, _] = color.to_array();
+    Color32::from_rgba_unmultiplied(r, g, b, (alpha.clamp(0.0, 1.0) * 255.0) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_are_in_bin_around_them() {
+        let size = 0.7;
+        for shape in [BinShape::Hexagon, BinShape::Square] {
+            for point in [[0.0, 0.0], [1.3, -2.2], [-5.1, 4.05], [0.61, 0.35]] {
+                let center = shape.center(shape.bin_of(point, size), size);
+                let distance = (point[0] - center[0]).hypot(point[1] - center[1]);
+                // Hexagon corners and square corners are the farthest points from the center
+                let max_distance = match shape {
+                    BinShape::Hexagon => size,
+                    BinShape::Square => size * std::f64::consts::SQRT_2 / 2.0,
+                };
+                assert!(distance <= max_distance + 1e-9, "{shape:?} {point:?}");
+            }
+        }
+
+        let points = [
+            DataPoint::new(0.1, 0.1, DataLabel::Normal),
+            DataPoint::new(0.2, 0.1, DataLabel::Anomaly),
+            DataPoint::new(5.0, 5.0, DataLabel::Normal),
+        ];
+        let counts = bin_counts(points.iter(), BinShape::Hexagon, 1.0);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(
+            counts[&(0, 0)],
+            BTreeMap::from([(DataLabel::Normal, 1), (DataLabel::Anomaly, 1)])
+        );
+    }
+}
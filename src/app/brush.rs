@@ -0,0 +1,179 @@
+use egui::PointerButton;
+use egui_plot::{MarkerShape, PlotUi, Points};
+use rand::{rngs::StdRng, Rng as _};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoint, DataPoints, PointArray},
+    data_generation::rng_from_seed,
+    ui_blocks::{drag_start_position, ui_seed},
+};
+
+/// Settings for painting points by dragging in brush mode
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(default)]
+pub struct Brush {
+    /// Screen distance the pointer moves between dabs
+    pub spacing: f32,
+    /// Number of points added by each dab
+    pub points_per_dab: u8,
+    /// Screen radius the points of a dab are scattered in (All at the center if 0)
+    pub scatter: f32,
+    pub label: DataLabel,
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Self {
+            spacing: 20.0,
+            points_per_dab: 1,
+            scatter: 0.0,
+            label: DataLabel::Normal,
+        }
+    }
+}
+
+/// The points painted so far by the drag in progress (Added to the data on release)
+#[derive(Debug, PartialEq)]
+pub struct BrushStroke {
+    points: DataPoints,
+    last_dab: PointArray,
+    rng: StdRng,
+}
+
+impl Brush {
+    /// Returns the points of one dab centered at `center`
+    fn dab(&self, center: PointArray, units_per_screen_point: f64, rng: &mut StdRng) -> DataPoints {
+        let radius = self.scatter as f64 * units_per_screen_point;
+        (0..self.points_per_dab.max(1))
+            .map(|_| {
+                // sqrt spreads the points evenly over the area of the circle
+                let distance = radius * rng.gen::<f64>().sqrt();
+                let angle = rng.gen_range(0.0..std::f64::consts::TAU);
+                DataPoint::new(
+                    center[0] + distance * angle.cos(),
+                    center[1] + distance * angle.sin(),
+                    self.label,
+                )
+            })
+            .collect()
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_brush_options(&mut self, ui: &mut egui::Ui) {
+        self.label_classes
+            .ui_combo(ui, "id-brush-label", &mut self.brush.label);
+        ui.add(
+            egui::DragValue::new(&mut self.brush.spacing)
+                .clamp_range(1.0..=500.0)
+                .prefix("Spacing: "),
+        )
+        .on_hover_text("Distance on screen between dabs");
+        ui.add(
+            egui::DragValue::new(&mut self.brush.points_per_dab)
+                .clamp_range(1..=100)
+                .prefix("Points per dab: "),
+        );
+        ui.add(
+            egui::DragValue::new(&mut self.brush.scatter)
+                .clamp_range(0.0..=500.0)
+                .prefix("Scatter: "),
+        )
+        .on_hover_text("Radius on screen that the points of each dab are spread in");
+        ui_seed(ui, &mut self.data.settings.seed);
+    }
+
+    /// Derived from the dataset seed and the number of points so repeating the same actions
+    /// gives the same points without every dab having the same pattern
+    fn brush_rng(&self) -> StdRng {
+        rng_from_seed(
+            self.data
+                .settings
+                .seed
+                .wrapping_add(self.data.points().len() as u64),
+        )
+    }
+
+    /// Adds a single dab where the plot was clicked
+    pub(super) fn brush_dab_on_click(
+        &mut self,
+        response: &egui::Response,
+        pointer_coordinate: Option<egui_plot::PlotPoint>,
+    ) {
+        let (Some(center), Some(bounds)) = (pointer_coordinate, self.plot_bounds) else {
+            self.status_msg
+                .error_display("Unable to add points. Cursor not detected over the plot");
+            return;
+        };
+        let units_per_screen_point = bounds.width() / response.rect.width() as f64;
+        let mut rng = self.brush_rng();
+        let dab = self
+            .brush
+            .dab([center.x, center.y], units_per_screen_point, &mut rng);
+        self.data.add_many_in_bounds(dab, &mut self.status_msg);
+    }
+
+    /// Paints dabs along a primary drag in brush mode and adds them as a single event on release
+    pub(super) fn plot_brush(&mut self, plot_ui: &mut PlotUi) {
+        if !self.click_mode.is_brush_points() {
+            self.brush_stroke = None;
+            return;
+        }
+        let response = plot_ui.response().clone();
+        let units_per_screen_point = plot_ui.plot_bounds().width() / response.rect.width() as f64;
+        if response.drag_started_by(PointerButton::Primary) {
+            if let Some(start) = drag_start_position(plot_ui) {
+                let mut rng = self.brush_rng();
+                self.brush_stroke = Some(BrushStroke {
+                    points: self.brush.dab(start, units_per_screen_point, &mut rng),
+                    last_dab: start,
+                    rng,
+                });
+            }
+        }
+        let is_finished = response.drag_stopped_by(PointerButton::Primary);
+        let color = self.label_color(self.brush.label);
+        let Some(stroke) = self.brush_stroke.as_mut() else {
+            return;
+        };
+
+        if let Some(pointer) = plot_ui.pointer_coordinate() {
+            // Dabs are placed along the path so fast movements do not leave gaps
+            let spacing = (self.brush.spacing as f64 * units_per_screen_point).max(f64::EPSILON);
+            let [dx, dy] = [
+                pointer.x - stroke.last_dab[0],
+                pointer.y - stroke.last_dab[1],
+            ];
+            let distance = dx.hypot(dy);
+            let start = stroke.last_dab;
+            for step in 1..=(distance / spacing) as usize {
+                let fraction = step as f64 * spacing / distance;
+                stroke.last_dab = [start[0] + dx * fraction, start[1] + dy * fraction];
+                let dab = self
+                    .brush
+                    .dab(stroke.last_dab, units_per_screen_point, &mut stroke.rng);
+                stroke.points.extend(dab);
+            }
+        }
+
+        if is_finished {
+            if let Some(stroke) = self.brush_stroke.take() {
+                self.data
+                    .add_many_in_bounds(stroke.points, &mut self.status_msg);
+            }
+            return;
+        }
+
+        let preview: Vec<PointArray> = stroke.points.iter().map(|x| [x.x0, x.x1]).collect();
+        plot_ui.points(
+            Points::new(preview)
+                .name("Brush Stroke")
+                .radius(self.marker_radius)
+                .shape(MarkerShape::Circle)
+                .filled(false)
+                .color(color),
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeSet;
+
+use egui_plot::{Line, MarkerShape, PlotPoints, PlotUi, Points};
+
+use crate::DBV;
+
+use super::data_definition::{DataLabel, DataPoint, DataTimestamp, PointArray};
+
+/// Centroid and covariance ellipses of each label
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, PartialEq)]
+#[serde(default)]
+pub struct ClassSpread {
+    pub is_enabled: bool,
+    /// Spread of the points of each label for the data version
+    #[serde(skip)]
+    cache: Option<(DataTimestamp, Vec<(DataLabel, Spread)>)>,
+}
+
+/// Mean and covariance of a set of points
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Spread {
+    mean: PointArray,
+    /// Variance of x0, covariance and variance of x1
+    covariance: [f64; 3],
+}
+
+impl Spread {
+    /// Number of segments used to draw each ellipse
+    const ELLIPSE_SEGMENTS: usize = 96;
+
+    fn of<'a>(points: impl Iterator<Item = &'a DataPoint> + Clone) -> Option<Self> {
+        let n = points.clone().count() as f64;
+        if n == 0.0 {
+            return None;
+        }
+        let mean = points
+            .clone()
+            .fold([0.0, 0.0], |acc, p| [acc[0] + p.x0 / n, acc[1] + p.x1 / n]);
+        let covariance = points.fold([0.0; 3], |acc, p| {
+            let (d0, d1) = (p.x0 - mean[0], p.x1 - mean[1]);
+            [
+                acc[0] + d0 * d0 / n,
+                acc[1] + d0 * d1 / n,
+                acc[2] + d1 * d1 / n,
+            ]
+        });
+        Some(Self { mean, covariance })
+    }
+
+    /// Half lengths and direction of the first axis of the ellipse `sigmas` standard
+    /// deviations from the mean
+    fn axes(&self, sigmas: f64) -> ([f64; 2], f64) {
+        let [a, b, c] = self.covariance;
+        // Eigenvalues of the symmetric 2 by 2 matrix
+        let half_trace = (a + c) / 2.0;
+        let root = (((a - c) / 2.0).powi(2) + b * b).sqrt();
+        let (major, minor) = (half_trace + root, (half_trace - root).max(0.0));
+        let angle = 0.5 * (2.0 * b).atan2(a - c);
+        ([sigmas * major.sqrt(), sigmas * minor.sqrt()], angle)
+    }
+
+    fn ellipse(&self, sigmas: f64) -> Vec<PointArray> {
+        let ([major, minor], angle) = self.axes(sigmas);
+        let (sin, cos) = angle.sin_cos();
+        (0..=Self::ELLIPSE_SEGMENTS)
+            .map(|i| {
+                let t = std::f64::consts::TAU * i as f64 / Self::ELLIPSE_SEGMENTS as f64;
+                let (u, v) = (major * t.cos(), minor * t.sin());
+                [
+                    self.mean[0] + u * cos - v * sin,
+                    self.mean[1] + u * sin + v * cos,
+                ]
+            })
+            .collect()
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_class_spread_toggle(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(
+            &mut self.class_spread.is_enabled,
+            "Show centroids and spread",
+        )
+        .on_hover_text("Mean of each label with its 1σ and 2σ covariance ellipses");
+    }
+
+    pub(super) fn plot_class_spread(&mut self, plot_ui: &mut PlotUi) {
+        if !self.class_spread.is_enabled {
+            return;
+        }
+        let timestamp = self.data.timestamp();
+        if self.class_spread.cache.as_ref().map(|x| x.0) != Some(timestamp) {
+            let points = self.data.points();
+            let labels: BTreeSet<DataLabel> = points.iter().map(|p| p.label).collect();
+            let spreads = labels
+                .into_iter()
+                .filter_map(|label| {
+                    Some((
+                        label,
+                        Spread::of(points.iter().filter(|p| p.label == label))?,
+                    ))
+                })
+                .collect();
+            self.class_spread.cache = Some((timestamp, spreads));
+        }
+        let Some((_, spreads)) = self.class_spread.cache.as_ref() else {
+            return;
+        };
+        for &(label, spread) in spreads {
+            let name = format!("{} spread", self.label_classes.name(label));
+            let color = self.label_color(label);
+            for (sigmas, style) in [
+                (1.0, egui_plot::LineStyle::Solid),
+                (2.0, egui_plot::LineStyle::dashed_loose()),
+            ] {
+                plot_ui.line(
+                    Line::new(PlotPoints::new(spread.ellipse(sigmas)))
+                        .name(&name)
+                        .color(color)
+                        .style(style),
+                );
+            }
+            plot_ui.points(
+                Points::new(vec![spread.mean])
+                    .name(&name)
+                    .shape(MarkerShape::Cross)
+                    .radius(self.marker_radius * 2.5)
+                    .color(color),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::data_definition::DataLabel;
+
+    #[test]
+    fn spread_of_points_along_axes() {
+        let points = [
+            DataPoint::new(-2.0, 1.0, DataLabel::Normal),
+            DataPoint::new(2.0, 1.0, DataLabel::Normal),
+            DataPoint::new(0.0, 0.0, DataLabel::Normal),
+            DataPoint::new(0.0, 2.0, DataLabel::Normal),
+        ];
+        let spread = Spread::of(points.iter()).unwrap();
+        assert_eq!(spread.mean, [0.0, 1.0]);
+        assert_eq!(spread.covariance, [2.0, 0.0, 0.5]);
+        let ([major, minor], angle) = spread.axes(2.0);
+        assert!((major - 2.0 * 2f64.sqrt()).abs() < 1e-12);
+        assert!((minor - 2.0 * 0.5f64.sqrt()).abs() < 1e-12);
+        assert_eq!(angle, 0.0);
+        assert!(Spread::of(points[..0].iter()).is_none());
+    }
+}
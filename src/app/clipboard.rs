@@ -0,0 +1,105 @@
+use egui::{Button, Key, KeyboardShortcut, Modifiers};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{parse_points_text, ParsedPoints},
+    export_results::rows_csv,
+};
+
+impl DBV {
+    const SHORTCUT_PASTE: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::V);
+
+    pub(super) fn ui_btn_paste_points(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add(
+                Button::new("Paste Points")
+                    .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_PASTE)),
+            )
+            .on_hover_text("Appends lines of \"x0, x1, label\" (CSV or TSV) from the clipboard")
+            .clicked()
+        {
+            self.paste_points_from_clipboard();
+            ui.close_menu();
+        }
+    }
+
+    /// Copies the selected rows as CSV (With the results if the model is trained on the data)
+    pub(super) fn ui_btn_copy_rows(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(!self.selection.is_empty(), Button::new("Copy rows"))
+            .on_hover_text(
+                "Copies the selected rows as CSV including the results when there are any",
+            )
+            .on_disabled_hover_text("Select rows with the sel column first")
+            .clicked()
+        {
+            match rows_csv(
+                self.data.points(),
+                self.selection.indices().iter().copied(),
+                self.loc_inference_model(),
+            ) {
+                Ok(text) => {
+                    ui.output_mut(|o| o.copied_text = text);
+                    self.status_msg
+                        .info(format!("Copied {} rows", self.selection.len()));
+                }
+                Err(e) => self.status_msg.error_debug(e),
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn paste_points_from_clipboard(&mut self) {
+        use anyhow::Context as _;
+
+        match arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .context("failed to read text from the clipboard")
+        {
+            Ok(text) => self.paste_points(&text),
+            Err(e) => self.status_msg.error_debug(e),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn paste_points_from_clipboard(&mut self) {
+        self.status_msg.info(
+            "The browser only allows reading the clipboard on paste. Please use the keyboard shortcut",
+        );
+    }
+
+    /// Adds points for any paste events that were not consumed by a text field
+    pub(super) fn handle_paste_events(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return; // Pasting into a text field
+        }
+        let pasted_texts: Vec<String> = ctx.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                })
+                .collect()
+        });
+        for text in pasted_texts {
+            self.paste_points(&text);
+        }
+    }
+
+    fn paste_points(&mut self, text: &str) {
+        let ParsedPoints { points, errors } = parse_points_text(text, self.primary_click_label);
+        for error in errors {
+            self.status_msg
+                .error_display(format!("Unable to paste point. {error}"));
+        }
+        if points.is_empty() {
+            self.status_msg.info("No points found to paste");
+            return;
+        }
+        let count = points.len();
+        self.data.add_many(points);
+        self.status_msg.info(format!("Pasted {count} points"));
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use egui_plot::{Line, PlotPoints, PlotUi, Polygon};
+
+use crate::DBV;
+
+use super::data_definition::{DataLabel, DataTimestamp, DistanceCalculation as _, PointArray};
+
+/// Outline around all the points of each label
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, PartialEq)]
+#[serde(default)]
+pub struct ConvexHulls {
+    pub is_enabled: bool,
+    /// Hulls of the points of each label for the data version
+    #[serde(skip)]
+    cache: Option<(DataTimestamp, BTreeMap<DataLabel, Vec<PointArray>>)>,
+}
+
+/// Returns the corners of the smallest convex polygon containing all the points in
+/// counterclockwise order (Monotone chain)
+fn convex_hull(mut points: Vec<PointArray>) -> Vec<PointArray> {
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let mut lower = half_hull(points.iter());
+    let mut upper = half_hull(points.iter().rev());
+    // The last point of each half is the first of the other
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+/// Keeps each point only if it makes a left turn from the ones kept before it
+fn half_hull<'a>(points: impl Iterator<Item = &'a PointArray>) -> Vec<PointArray> {
+    let cross = |o: PointArray, a: PointArray, b: PointArray| {
+        (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+    };
+    let mut result: Vec<PointArray> = vec![];
+    for &point in points {
+        while result.len() >= 2
+            && cross(result[result.len() - 2], result[result.len() - 1], point) <= 0.0
+        {
+            result.pop();
+        }
+        result.push(point);
+    }
+    result
+}
+
+impl DBV {
+    pub(super) fn ui_convex_hulls_toggle(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.convex_hulls.is_enabled, "Show convex hulls")
+            .on_hover_text("Outline around all the points of each label to see where they overlap");
+    }
+
+    pub(super) fn plot_convex_hulls(&mut self, plot_ui: &mut PlotUi) {
+        if !self.convex_hulls.is_enabled {
+            return;
+        }
+        let timestamp = self.data.timestamp();
+        if self.convex_hulls.cache.as_ref().map(|x| x.0) != Some(timestamp) {
+            let mut by_label: BTreeMap<DataLabel, Vec<PointArray>> = BTreeMap::new();
+            for point in self.data.points() {
+                by_label
+                    .entry(point.label)
+                    .or_default()
+                    .push(point.to_array());
+            }
+            let hulls = by_label
+                .into_iter()
+                .map(|(label, points)| (label, convex_hull(points)))
+                .collect();
+            self.convex_hulls.cache = Some((timestamp, hulls));
+        }
+        let Some((_, hulls)) = self.convex_hulls.cache.as_ref() else {
+            return;
+        };
+        for (&label, hull) in hulls {
+            let name = format!("{} hull", self.label_classes.name(label));
+            let color = self.label_color(label);
+            if hull.len() >= 3 {
+                plot_ui.polygon(
+                    Polygon::new(PlotPoints::new(hull.clone()))
+                        .name(&name)
+                        .stroke(egui::Stroke::new(1.5, color))
+                        .fill_color(color.gamma_multiply(0.05)),
+                );
+            } else if hull.len() == 2 {
+                plot_ui.line(
+                    Line::new(PlotPoints::new(hull.clone()))
+                        .name(&name)
+                        .color(color),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hull_keeps_only_outer_corners() {
+        let points = vec![
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [0.5, 0.5],
+            [1.0, 1.0],
+            [0.0, 1.0],
+            [0.5, 0.0], // On an edge
+            [1.0, 1.0], // Duplicate
+        ];
+        assert_eq!(
+            convex_hull(points),
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]
+        );
+        assert_eq!(convex_hull(vec![[2.0, 2.0]]), vec![[2.0, 2.0]]);
+    }
+}
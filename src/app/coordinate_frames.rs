@@ -0,0 +1,195 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{NewPointBounds, PointArray},
+    data_generation::{ParamKind, ParamValue},
+    plot_zoom_reset::{MinMaxPair, ZoomTarget},
+};
+
+/// A region of the plane that new data is meant to be placed in
+///
+/// Applying it sets the default view, the bounds and rounding of new points and the extents of
+/// the generators
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone)]
+pub struct CoordinateFrame {
+    pub name: String,
+    pub min: PointArray,
+    pub max: PointArray,
+    /// Decimal places new points are snapped to (`None` leaves them as they are)
+    pub rounding_decimal_places: Option<u8>,
+}
+
+impl CoordinateFrame {
+    /// Frames that are always available
+    fn built_in() -> [Self; 2] {
+        [
+            Self {
+                name: "Unit square".to_string(),
+                min: [0.0, 0.0],
+                max: [1.0, 1.0],
+                rounding_decimal_places: Some(3),
+            },
+            Self {
+                name: "Centered [-1, 1]²".to_string(),
+                min: [-1.0, -1.0],
+                max: [1.0, 1.0],
+                rounding_decimal_places: Some(3),
+            },
+        ]
+    }
+
+    fn center(&self) -> PointArray {
+        [
+            (self.min[0] + self.max[0]) / 2.0,
+            (self.min[1] + self.max[1]) / 2.0,
+        ]
+    }
+
+    /// Value for a generator parameter that describes where the points go (`None` for others)
+    fn generator_param(&self, name: &str) -> Option<f64> {
+        Some(match name {
+            "Min x0" | "Start x0" => self.min[0],
+            "Min x1" | "Start x1" => self.min[1],
+            "Max x0" | "End x0" => self.max[0],
+            "Max x1" | "End x1" => self.max[1],
+            _ => return None,
+        })
+    }
+
+    fn description(&self) -> String {
+        let places = match self.rounding_decimal_places {
+            Some(places) => format!("Rounded to {places} decimal places"),
+            None => "Not rounded".to_string(),
+        };
+        format!(
+            "({}, {}) to ({}, {}). {places}",
+            self.min[0], self.min[1], self.max[0], self.max[1]
+        )
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_coordinate_frames(&mut self, ui: &mut egui::Ui) {
+        let mut to_apply = None;
+        for frame in CoordinateFrame::built_in() {
+            if ui
+                .button(&frame.name)
+                .on_hover_text(frame.description())
+                .clicked()
+            {
+                to_apply = Some(frame);
+            }
+        }
+        let mut to_remove = None;
+        for (i, frame) in self.coordinate_frames.iter().enumerate() {
+            let response = ui.button(&frame.name).on_hover_text(frame.description());
+            if response.clicked() {
+                to_apply = Some(frame.clone());
+            }
+            response.context_menu(|ui| {
+                if ui.button("Remove frame").clicked() {
+                    to_remove = Some(i);
+                    ui.close_menu();
+                }
+            });
+        }
+        if let Some(i) = to_remove {
+            self.coordinate_frames.remove(i);
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.coordinate_frame_name)
+                    .desired_width(80.0)
+                    .hint_text("Name"),
+            );
+            let name = self.coordinate_frame_name.trim();
+            if ui
+                .add_enabled(
+                    !name.is_empty() && self.plot_bounds.is_some(),
+                    Button::new("Save current view"),
+                )
+                .on_hover_text("Uses the current rounding of new points. Replaces any frame with the same name. Right click a frame to remove it")
+                .clicked()
+            {
+                let bounds = self.plot_bounds.expect("checked above");
+                let frame = CoordinateFrame {
+                    name: name.to_string(),
+                    min: bounds.min(),
+                    max: bounds.max(),
+                    rounding_decimal_places: self.data.rounding_decimal_places,
+                };
+                match self
+                    .coordinate_frames
+                    .iter_mut()
+                    .find(|x| x.name == frame.name)
+                {
+                    Some(existing) => *existing = frame,
+                    None => self.coordinate_frames.push(frame),
+                }
+                self.coordinate_frame_name.clear();
+            }
+        });
+
+        if let Some(frame) = to_apply {
+            self.apply_coordinate_frame(&frame);
+            ui.close_menu();
+        }
+    }
+
+    fn apply_coordinate_frame(&mut self, frame: &CoordinateFrame) {
+        let view = MinMaxPair {
+            min: frame.min,
+            max: frame.max,
+        };
+        self.data.settings.default_view = Some(view);
+        self.data.new_point_bounds = NewPointBounds {
+            x0_min: Some(frame.min[0]),
+            x0_max: Some(frame.max[0]),
+            x1_min: Some(frame.min[1]),
+            x1_max: Some(frame.max[1]),
+            should_clamp: self.data.new_point_bounds.should_clamp,
+        };
+        self.data.rounding_decimal_places = frame.rounding_decimal_places;
+
+        self.polar_generator.center = frame.center();
+        self.polar_generator.radius =
+            (frame.max[0] - frame.min[0]).min(frame.max[1] - frame.min[1]) / 2.0;
+        for name in self.generator_registry.names() {
+            let Some(generator) = self.generator_registry.get(name) else {
+                continue;
+            };
+            let specs = generator.params();
+            let params = self.generator_params.entry(name.to_string()).or_default();
+            params.fill_defaults(&specs);
+            for spec in specs {
+                if let (ParamKind::Number { min, max, .. }, Some(value)) =
+                    (&spec.kind, frame.generator_param(&spec.name))
+                {
+                    params.set(spec.name, ParamValue::Number(value.clamp(*min, *max)));
+                }
+            }
+        }
+
+        self.start_zoom_reset(ZoomTarget::View(view));
+        self.status_msg
+            .info(format!("Applied coordinate frame {:?}", frame.name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generator_params_follow_frame() {
+        let [unit, centered] = CoordinateFrame::built_in();
+        assert_eq!(unit.generator_param("Max x1"), Some(1.0));
+        assert_eq!(centered.generator_param("Start x0"), Some(-1.0));
+        assert_eq!(centered.generator_param("Noise x0"), None);
+        assert_eq!(centered.center(), [0.0, 0.0]);
+    }
+}
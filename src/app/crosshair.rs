@@ -0,0 +1,56 @@
+use egui_plot::{HLine, PlotPoint, PlotUi, Text, VLine};
+
+use crate::DBV;
+
+impl DBV {
+    /// Distance in screen points between the pointer and the coordinates label
+    const CROSSHAIR_LABEL_OFFSET: f64 = 8.0;
+
+    pub(super) fn ui_crosshair_toggle(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.show_crosshair, "Show crosshair")
+            .on_hover_text("Ctrl / ⌘ + click on the plot to copy the coordinates");
+    }
+
+    /// Draws lines through the pointer with its coordinates beside it
+    pub(super) fn plot_crosshair(&self, plot_ui: &mut PlotUi) {
+        if !self.show_crosshair || !plot_ui.response().hovered() {
+            return;
+        }
+        let Some(pointer) = plot_ui.pointer_coordinate() else {
+            return;
+        };
+        let color = plot_ui.ctx().style().visuals.weak_text_color();
+        plot_ui.hline(HLine::new(pointer.y).color(color));
+        plot_ui.vline(VLine::new(pointer.x).color(color));
+        let offset = Self::CROSSHAIR_LABEL_OFFSET * plot_ui.plot_bounds().width()
+            / plot_ui.response().rect.width() as f64;
+        plot_ui.text(
+            Text::new(
+                PlotPoint::new(pointer.x + offset, pointer.y + offset),
+                self.display_precision.format_pair([pointer.x, pointer.y]),
+            )
+            .anchor(egui::Align2::LEFT_BOTTOM)
+            .color(plot_ui.ctx().style().visuals.strong_text_color()),
+        );
+    }
+
+    /// Copies the coordinates if the click is meant for the crosshair (Returns `true` if it was)
+    pub(super) fn crosshair_copy_on_click(
+        &mut self,
+        response: &egui::Response,
+        pointer_coordinate: Option<PlotPoint>,
+    ) -> bool {
+        if !self.show_crosshair
+            || !response.clicked()
+            || !response.ctx.input(|i| i.modifiers.command)
+        {
+            return false;
+        }
+        if let Some(pos) = pointer_coordinate {
+            let text = self.display_precision.format_pair([pos.x, pos.y]);
+            self.status_msg.info(format!("Copied coordinates {text}"));
+            response.ctx.output_mut(|o| o.copied_text = text);
+        }
+        true
+    }
+}
@@ -1,19 +1,49 @@
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
 
 use anyhow::{bail, Context};
+use ecolor::Color32;
 use log::info;
 use rfd::FileHandle;
-use serde_repr::{Deserialize_repr, Serialize_repr};
 
+use self::bounds::BoundsCheck;
 use self::undo_manager::{
-    AddEventData, ClearEventData, DeleteEventData, EditEventData, Event, LoadEventData, UndoManager,
+    AddEventData, AddManyEventData, ClearEventData, DeleteEventData, DeleteManyEventData,
+    EditEventData, EditManyEventData, Event, LoadEventData, PointEdit, UndoManager,
 };
 
-use super::{plot_zoom_reset::MinMaxPair, status_msg::StatusMsg};
+use super::{operational_state::LoadProgress, plot_zoom_reset::MinMaxPair, status_msg::StatusMsg};
+pub use annotations::Annotations;
+pub use bounds::NewPointBounds;
+pub use content_hash::content_hash;
+pub use csv_mapping::{CsvLoad, CsvMappings, PendingCsvLoad};
+pub use dataset_settings::DatasetSettings;
+pub use groups::{GroupId, PointGroup, PointGroups};
+pub use history_replay::HistoryReplay;
+pub use mat_variables::{MatVariableNames, PendingMatLoad};
+pub use regions::{Region, Regions};
+pub use text_points::{parse_points_text, ParsedPoints};
+pub use trash::Trash;
 pub use undo_manager::DataTimestamp;
 
+mod annotations;
+mod arrow_ipc;
+mod bounds;
+mod chunked_reader;
+mod content_hash;
+mod csv_mapping;
+mod dataset_settings;
+mod groups;
+mod history_replay;
+mod jsonl;
+// Only used to list the variables in the file on native
+#[cfg_attr(not(any(target_arch = "wasm32", test)), allow(dead_code))]
+mod mat_v5;
+mod mat_variables;
 #[cfg(not(target_arch = "wasm32"))]
 mod matlab;
+mod regions;
+mod text_points;
+mod trash;
 mod undo_manager;
 
 pub type DataPoints = Vec<DataPoint>;
@@ -27,7 +57,25 @@ pub struct Data {
     points: DataPoints,
     /// Controls if / how many decimal places new points are rounded to
     pub rounding_decimal_places: Option<u8>,
+    /// Controls where new points are allowed to be added by clicking
+    #[serde(default)]
+    pub new_point_bounds: NewPointBounds,
     undo_manager: UndoManager,
+    /// Definitions of the groups that points can be assigned to (Only deleting a group is part of the history)
+    #[serde(default)]
+    pub groups: PointGroups,
+    /// Text notes shown on the plot
+    #[serde(default)]
+    pub annotations: Annotations,
+    /// Named rectangles used to relabel or select the points inside them
+    #[serde(default)]
+    pub regions: Regions,
+    /// Points removed while soft delete is enabled
+    #[serde(default)]
+    pub trash: Trash,
+    /// Settings such as axis names that belong to this dataset
+    #[serde(default)]
+    pub settings: DatasetSettings,
     /// Caches the value from `self.points`
     cached_points_min_max: Option<MinMaxPair>,
 }
@@ -36,7 +84,11 @@ pub trait Save {
     /// Saves the data to the file given
     ///
     /// ASSUMPTION: The parent folder of the file exists
-    async fn save_to_file(&self, file: &FileHandle) -> anyhow::Result<()>;
+    async fn save_to_file(
+        &self,
+        file: &FileHandle,
+        mat_names: &MatVariableNames,
+    ) -> anyhow::Result<()>;
 }
 
 pub type PointArray = [f64; 2];
@@ -66,11 +118,17 @@ impl Data {
     const DEFAULT_DECIMAL_PLACES_FOR_ROUNDING: u8 = 0;
     pub const MAX_DECIMAL_PLACES: u8 = 10;
     pub const DEFAULT_MAX_HISTORY: u16 = UndoManager::DEFAULT_MAX_HISTORY;
+    pub const DEFAULT_HISTORY_COMPRESS_THRESHOLD: u32 = UndoManager::DEFAULT_COMPRESS_THRESHOLD;
 
     pub fn points(&self) -> &[DataPoint] {
         &self.points
     }
 
+    /// Hash of the points used to identify this exact version of the dataset
+    pub fn content_hash(&self) -> String {
+        content_hash(&self.points)
+    }
+
     /// Creates a new copy of all the points
     pub fn clone_points(&self) -> DataPoints {
         self.points.clone()
@@ -92,6 +150,11 @@ impl Data {
         }
     }
 
+    /// Returns the number of decimal places new points are rounded to if rounding is enabled
+    pub fn rounding_decimal_places(&self) -> Option<u8> {
+        self.rounding_decimal_places
+    }
+
     /// Returns a reference to the value inside of the option. It will set it to default if it is none
     pub fn rounding_decimal_places_mut(&mut self) -> &mut u8 {
         self.rounding_decimal_places
@@ -102,10 +165,11 @@ impl Data {
         self.cached_points_min_max = None;
     }
 
-    fn get_closest_point(
+    /// Returns the index of the point closest to the target out of those the predicate accepts
+    pub fn get_closest_point(
         &self,
         target_coord: egui_plot::PlotPoint,
-        label: Option<DataLabel>,
+        predicate: impl Fn(usize, &DataPoint) -> bool,
     ) -> Option<usize> {
         let mut result = None;
         let mut min_distance = f64::INFINITY;
@@ -113,7 +177,7 @@ impl Data {
             .points
             .iter()
             .enumerate()
-            .filter(|(_, p)| label.is_none() || p.label == *label.as_ref().unwrap())
+            .filter(|(i, p)| predicate(*i, p))
         {
             let distance = target_coord.distance_to(data_point.to_array());
             if distance < min_distance {
@@ -124,6 +188,34 @@ impl Data {
         result
     }
 
+    /// Returns the most common label among the `k` closest points or `None` if there are no points
+    /// or the most common labels are tied
+    pub fn majority_label_near(&self, target_coord: PointArray, k: usize) -> Option<DataLabel> {
+        let mut distances: Vec<(f64, DataLabel)> = self
+            .points
+            .iter()
+            .map(|point| (point.distance_to(target_coord), point.label))
+            .collect();
+        let k = k.min(distances.len());
+        if k == 0 {
+            return None;
+        }
+        distances.select_nth_unstable_by(k - 1, |a, b| a.0.total_cmp(&b.0));
+        let mut votes = std::collections::BTreeMap::<DataLabel, usize>::new();
+        for (_, label) in &distances[..k] {
+            *votes.entry(*label).or_default() += 1;
+        }
+        let max_votes = *votes.values().max().expect("k is at least 1");
+        let mut winners = votes
+            .into_iter()
+            .filter(|(_, count)| *count == max_votes)
+            .map(|(label, _)| label);
+        match (winners.next(), winners.next()) {
+            (Some(label), None) => Some(label),
+            _ => None,
+        }
+    }
+
     pub fn add(
         &mut self,
         pointer_coordinate: Option<egui_plot::PlotPoint>,
@@ -131,14 +223,23 @@ impl Data {
         status_msg: &mut StatusMsg,
     ) {
         if let Some(pointer_coord) = pointer_coordinate {
+            let (x, y) = match self
+                .new_point_bounds
+                .check(pointer_coord.x, pointer_coord.y)
+            {
+                BoundsCheck::Inside => (pointer_coord.x, pointer_coord.y),
+                BoundsCheck::Clamped { x0, x1 } => {
+                    status_msg.info("Point was outside the allowed bounds and has been clamped");
+                    (x0, x1)
+                }
+                BoundsCheck::Rejected => {
+                    status_msg.error_display("Unable to add point. Outside of the allowed bounds");
+                    return;
+                }
+            };
             self.invalidate_cache();
-            let mut x = pointer_coord.x;
-            let mut y = pointer_coord.y;
-            if let Some(desired_decimal_places) = self.rounding_decimal_places {
-                let ten_pow = 10f64.powi(desired_decimal_places as _);
-                x = (x * ten_pow).round() / ten_pow;
-                y = (y * ten_pow).round() / ten_pow;
-            }
+            let x = self.round_new_coordinate(x);
+            let y = self.round_new_coordinate(y);
             let new_point = DataPoint::new(x, y, label);
             let event = Event::Add(AddEventData::new(new_point));
             self.undo_manager.add_undo(event);
@@ -148,6 +249,102 @@ impl Data {
         }
     }
 
+    /// Appends all the points as a single event (Rounding is applied the same as for [`Self::add`])
+    pub fn add_many(&mut self, mut points: DataPoints) {
+        if points.is_empty() {
+            return;
+        }
+        for point in points.iter_mut() {
+            point.x0 = self.round_new_coordinate(point.x0);
+            point.x1 = self.round_new_coordinate(point.x1);
+        }
+        self.append(points);
+    }
+
+    /// Same as [`Self::add_many`] but applies the new point bounds like [`Self::add`] does
+    pub fn add_many_in_bounds(&mut self, points: DataPoints, status_msg: &mut StatusMsg) {
+        let count = points.len();
+        let mut clamped_count = 0;
+        let in_bounds: DataPoints = points
+            .into_iter()
+            .filter_map(
+                |point| match self.new_point_bounds.check(point.x0, point.x1) {
+                    BoundsCheck::Inside => Some(point),
+                    BoundsCheck::Clamped { x0, x1 } => {
+                        clamped_count += 1;
+                        Some(DataPoint { x0, x1, ..point })
+                    }
+                    BoundsCheck::Rejected => None,
+                },
+            )
+            .collect();
+        if clamped_count > 0 {
+            status_msg.info(format!(
+                "{clamped_count} point(s) were outside the allowed bounds and have been clamped"
+            ));
+        }
+        let rejected_count = count - in_bounds.len();
+        if rejected_count > 0 {
+            status_msg.error_display(format!(
+                "Unable to add {rejected_count} point(s). Outside of the allowed bounds"
+            ));
+        }
+        self.add_many(in_bounds);
+    }
+
+    /// Appends `count` copies of the points at the indices as a single event and returns the
+    /// indices of the copies (Each copy is moved by `offset` from the one before it)
+    pub fn duplicate(
+        &mut self,
+        indices: impl IntoIterator<Item = usize>,
+        offset: [f64; 2],
+        should_flip_label: bool,
+        count: usize,
+    ) -> std::ops::Range<usize> {
+        let indices: Vec<usize> = indices.into_iter().collect();
+        let copies: DataPoints = (1..=count)
+            .flat_map(|n| indices.iter().map(move |&i| (n as f64, i)))
+            .map(|(n, i)| {
+                let mut point = self.points[i];
+                point.x0 += offset[0] * n;
+                point.x1 += offset[1] * n;
+                if should_flip_label {
+                    point.label = point.label.opposite();
+                }
+                point
+            })
+            .collect();
+        let start = self.points.len();
+        self.add_many(copies);
+        start..self.points.len()
+    }
+
+    /// Moves the points at the indices out of the trash and back into the data as a single event
+    pub fn restore_from_trash(&mut self, indices: &[usize]) {
+        let points = self.trash.take(indices);
+        if !points.is_empty() {
+            self.append(points);
+        }
+    }
+
+    /// Appends the points as they are as a single event
+    fn append(&mut self, points: DataPoints) {
+        self.invalidate_cache();
+        self.points.extend_from_slice(&points); // Actual add action
+        self.undo_manager
+            .add_undo(Event::AddMany(AddManyEventData::new(points)));
+    }
+
+    /// Applies the rounding setting for new points
+    fn round_new_coordinate(&self, value: f64) -> f64 {
+        if let Some(desired_decimal_places) = self.rounding_decimal_places {
+            let ten_pow = 10f64.powi(desired_decimal_places as _);
+            (value * ten_pow).round() / ten_pow
+        } else {
+            value
+        }
+    }
+
     pub fn edit(&mut self, index: usize, new_point: DataPoint) {
         self.invalidate_cache();
         let old_point = self
@@ -159,20 +356,252 @@ impl Data {
         *old_point = new_point; // Actual replacement action
     }
 
+    /// Moves the point to the coordinate as a single edit applying the same bounds and rounding
+    /// as [`Self::add`]
+    pub fn move_point(&mut self, index: usize, to: PointArray, status_msg: &mut StatusMsg) {
+        let (x0, x1) = match self.new_point_bounds.check(to[0], to[1]) {
+            BoundsCheck::Inside => (to[0], to[1]),
+            BoundsCheck::Clamped { x0, x1 } => {
+                status_msg.info("Point was moved outside the allowed bounds and has been clamped");
+                (x0, x1)
+            }
+            BoundsCheck::Rejected => {
+                status_msg.error_display("Unable to move point. Outside of the allowed bounds");
+                return;
+            }
+        };
+        let mut new_point = self.points[index];
+        new_point.x0 = self.round_new_coordinate(x0);
+        new_point.x1 = self.round_new_coordinate(x1);
+        if new_point != self.points[index] {
+            self.edit(index, new_point);
+        }
+    }
+
+    /// Replaces the points at the given indices as a single event (Edits that make no change are ignored)
+    pub fn edit_many(&mut self, edits: impl IntoIterator<Item = (usize, DataPoint)>) {
+        if let Some(point_edits) = self.apply_edits(edits) {
+            self.undo_manager
+                .add_undo(Event::EditMany(EditManyEventData::new(point_edits)));
+        }
+    }
+
+    /// Same as [`Self::edit_many`] but for edits that only change how the points look so they do
+    /// not invalidate a trained model
+    fn edit_many_appearance(&mut self, edits: impl IntoIterator<Item = (usize, DataPoint)>) {
+        if let Some(point_edits) = self.apply_edits(edits) {
+            let training_timestamp = self.training_timestamp();
+            self.undo_manager
+                .add_undo(Event::EditMany(EditManyEventData::new_appearance_only(
+                    point_edits,
+                    training_timestamp,
+                )));
+        }
+    }
+
+    /// Replaces the points and returns the changes made (`None` if nothing changed)
+    fn apply_edits(
+        &mut self,
+        edits: impl IntoIterator<Item = (usize, DataPoint)>,
+    ) -> Option<Vec<PointEdit>> {
+        let mut point_edits = vec![];
+        for (index, new_point) in edits {
+            let old_point = self
+                .points
+                .get_mut(index)
+                .expect("requires a valid point index");
+            if *old_point != new_point {
+                point_edits.push(PointEdit {
+                    index,
+                    old_point: *old_point,
+                    new_point,
+                });
+                *old_point = new_point; // Actual replacement action
+            }
+        }
+        if point_edits.is_empty() {
+            return None;
+        }
+        self.invalidate_cache();
+        Some(point_edits)
+    }
+
+    /// Applies the rounding setting to the points that already exist as a single event and returns
+    /// the number of points changed (Does nothing if rounding is off)
+    pub fn round_all_points(&mut self) -> usize {
+        if !self.is_rounding_enabled() {
+            return 0;
+        }
+        let edits: Vec<_> = self
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let mut point = *point;
+                point.x0 = self.round_new_coordinate(point.x0);
+                point.x1 = self.round_new_coordinate(point.x1);
+                (i, point)
+            })
+            .filter(|(i, point)| *point != self.points[*i])
+            .collect();
+        let count = edits.len();
+        self.edit_many(edits);
+        count
+    }
+
+    /// Assigns the points at the given indices to the group (or removes them from any group if `None`)
+    pub fn set_group(&mut self, indices: impl IntoIterator<Item = usize>, group: Option<GroupId>) {
+        let edits: Vec<_> = indices
+            .into_iter()
+            .map(|i| {
+                (
+                    i,
+                    DataPoint {
+                        group,
+                        ..self.points[i]
+                    },
+                )
+            })
+            .collect();
+        self.edit_many_appearance(edits);
+    }
+
+    /// Sets the label of the points at the given indices as a single undoable change
+    pub fn set_label(&mut self, indices: impl IntoIterator<Item = usize>, label: DataLabel) {
+        let edits: Vec<_> = indices
+            .into_iter()
+            .map(|i| {
+                (
+                    i,
+                    DataPoint {
+                        label,
+                        ..self.points[i]
+                    },
+                )
+            })
+            .collect();
+        self.edit_many(edits);
+    }
+
+    /// Sets if the points at the given indices are held out from training as a single undoable change
+    pub fn set_excluded_from_training(
+        &mut self,
+        indices: impl IntoIterator<Item = usize>,
+        value: bool,
+    ) {
+        let edits: Vec<_> = indices
+            .into_iter()
+            .map(|i| {
+                (
+                    i,
+                    DataPoint {
+                        is_excluded_from_training: value,
+                        ..self.points[i]
+                    },
+                )
+            })
+            .collect();
+        self.edit_many(edits);
+    }
+
+    /// Sets if the points at the given indices are protected from deletion as a single undoable
+    /// change
+    pub fn set_locked(&mut self, indices: impl IntoIterator<Item = usize>, is_locked: bool) {
+        let edits: Vec<_> = indices
+            .into_iter()
+            .map(|i| {
+                (
+                    i,
+                    DataPoint {
+                        is_locked,
+                        ..self.points[i]
+                    },
+                )
+            })
+            .collect();
+        self.edit_many_appearance(edits);
+    }
+
+    /// Sets the color shown for the points at the given indices as a single undoable change
+    /// (`None` goes back to the color of their label)
+    pub fn set_color(&mut self, indices: impl IntoIterator<Item = usize>, color: Option<Color32>) {
+        let edits: Vec<_> = indices
+            .into_iter()
+            .map(|i| {
+                (
+                    i,
+                    DataPoint {
+                        color,
+                        ..self.points[i]
+                    },
+                )
+            })
+            .collect();
+        self.edit_many_appearance(edits);
+    }
+
+    /// Returns the indices of the points in the group
+    pub fn indices_in_group(&self, group: GroupId) -> Vec<usize> {
+        self.points
+            .iter()
+            .enumerate()
+            .filter_map(|(i, point)| (point.group == Some(group)).then_some(i))
+            .collect()
+    }
+
+    /// Returns the name to display for the group (Empty if not in a group)
+    pub fn group_name(&self, group: Option<GroupId>) -> &str {
+        group
+            .and_then(|id| self.groups.get(id))
+            .map(|group| group.name.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Removes the group definition and removes its points from the group
+    pub fn delete_group(&mut self, group: GroupId) {
+        let Some(definition) = self.groups.remove(group) else {
+            return;
+        };
+        let edits: Vec<_> = self
+            .indices_in_group(group)
+            .into_iter()
+            .map(|i| {
+                (
+                    i,
+                    DataPoint {
+                        group: None,
+                        ..self.points[i]
+                    },
+                )
+            })
+            .collect();
+        // Recorded even if no points are in the group so undo brings back the definition
+        let point_edits = self.apply_edits(edits).unwrap_or_default();
+        let mut event_data =
+            EditManyEventData::new_appearance_only(point_edits, self.training_timestamp());
+        event_data.deleted_group = Some((group, definition));
+        self.undo_manager.add_undo(Event::EditMany(event_data));
+    }
+
+    /// Deletes the point closest to the pointer out of those the predicate accepts
     pub fn delete(
         &mut self,
         pointer_coordinate: Option<egui_plot::PlotPoint>,
-        label: DataLabel,
+        predicate: impl Fn(usize, &DataPoint) -> bool,
         status_msg: &mut StatusMsg,
     ) {
         let Some(pointer_coord) = pointer_coordinate else {
             status_msg.error_display("Unable to delete point. Cursor not detected over the plot");
             return;
         };
-        let index_closest_point = self.get_closest_point(pointer_coord, Some(label));
+        let index_closest_point = self.get_closest_point(pointer_coord, predicate);
 
         if let Some(index) = index_closest_point {
-            self.delete_by_index(index);
+            if self.points[index].is_locked {
+                status_msg.info("Nearest point is locked and was not deleted");
+            } else {
+                self.delete_by_index(index);
+            }
         } else {
             status_msg.info("No suitable point available for deleting");
         }
@@ -182,10 +611,16 @@ impl Data {
         self.points.is_empty()
     }
 
+    /// Deletes all the points except the locked ones
     pub fn clear_points(&mut self) {
+        if self.points.iter().any(|point| point.is_locked) {
+            self.delete_many(0..self.points.len());
+            return;
+        }
         self.invalidate_cache();
         let mut event_data = ClearEventData::new(vec![]);
         std::mem::swap(&mut self.points, &mut event_data.points); // Move points into event_data for possible restoration
+        event_data.is_trashed = self.trash.receive_many(&event_data.points);
         self.undo_manager.add_undo(Event::Clear(event_data));
     }
 
@@ -202,42 +637,51 @@ impl Data {
         if let Some(result) = self.cached_points_min_max {
             result
         } else {
-            let first_point = self.points.first();
-            let mut min_x0 = first_point.map(|x| x.x0).unwrap_or(-1.);
-            let mut max_x0 = first_point.map(|x| x.x0).unwrap_or(1.);
-            let mut min_x1 = first_point.map(|x| x.x1).unwrap_or(-1.);
-            let mut max_x1 = first_point.map(|x| x.x1).unwrap_or(1.);
-            for point in self.points.iter() {
-                min_x0 = point.x0.min(min_x0);
-                max_x0 = point.x0.max(max_x0);
-                min_x1 = point.x1.min(min_x1);
-                max_x1 = point.x1.max(max_x1);
-            }
-
-            // Handle case where there is no diff on a dimension
-            if (min_x0 - max_x0).abs() < f64::EPSILON {
-                min_x0 -= 1.;
-                max_x0 += 1.;
-            }
-            if (min_x1 - max_x1).abs() < f64::EPSILON {
-                min_x1 -= 1.;
-                max_x1 += 1.;
-            }
-
-            // Add Margin
-            (min_x0, max_x0) = Self::add_margin(min_x0, max_x0);
-            (min_x1, max_x1) = Self::add_margin(min_x1, max_x1);
-
-            let result = MinMaxPair {
-                min: [min_x0, min_x1],
-                max: [max_x0, max_x1],
-            };
+            let result = Self::min_max_w_margin(self.points.iter());
             self.cached_points_min_max = Some(result); // Store in cache
             info!("Points MinMax Calculated:  {result:?}");
             result
         }
     }
 
+    /// Same as [`Self::get_points_min_max_w_margin`] but only considers points with the label (Not cached)
+    pub fn get_label_min_max_w_margin(&self, label: DataLabel) -> MinMaxPair {
+        Self::min_max_w_margin(self.points.iter().filter(|point| point.label == label))
+    }
+
+    fn min_max_w_margin<'a>(mut points: impl Iterator<Item = &'a DataPoint>) -> MinMaxPair {
+        let first_point = points.next();
+        let mut min_x0 = first_point.map(|x| x.x0).unwrap_or(-1.);
+        let mut max_x0 = first_point.map(|x| x.x0).unwrap_or(1.);
+        let mut min_x1 = first_point.map(|x| x.x1).unwrap_or(-1.);
+        let mut max_x1 = first_point.map(|x| x.x1).unwrap_or(1.);
+        for point in points {
+            min_x0 = point.x0.min(min_x0);
+            max_x0 = point.x0.max(max_x0);
+            min_x1 = point.x1.min(min_x1);
+            max_x1 = point.x1.max(max_x1);
+        }
+
+        // Handle case where there is no diff on a dimension
+        if (min_x0 - max_x0).abs() < f64::EPSILON {
+            min_x0 -= 1.;
+            max_x0 += 1.;
+        }
+        if (min_x1 - max_x1).abs() < f64::EPSILON {
+            min_x1 -= 1.;
+            max_x1 += 1.;
+        }
+
+        // Add Margin
+        (min_x0, max_x0) = Self::add_margin(min_x0, max_x0);
+        (min_x1, max_x1) = Self::add_margin(min_x1, max_x1);
+
+        MinMaxPair {
+            min: [min_x0, min_x1],
+            max: [max_x0, max_x1],
+        }
+    }
+
     fn add_margin(min: f64, max: f64) -> (f64, f64) {
         let range = max - min;
         let new_range = range * Self::BOUNDARY_MARGIN;
@@ -272,6 +716,19 @@ impl Data {
                     );
                     self.points.pop().expect("should not be None");
                 }
+                Event::AddMany(event_data) => {
+                    let start = self
+                        .points
+                        .len()
+                        .checked_sub(event_data.points.len())
+                        .expect("should have at least as many points as were added");
+                    debug_assert_eq!(
+                        self.points[start..],
+                        event_data.points,
+                        "should be the last points added"
+                    );
+                    self.points.truncate(start);
+                }
                 Event::Edit(event_data) => {
                     debug_assert_eq!(
                         *self
@@ -283,9 +740,35 @@ impl Data {
                     );
                     *self.points.get_mut(event_data.index).unwrap() = event_data.old_point;
                 }
+                Event::EditMany(event_data) => {
+                    for edit in event_data.edits.iter().rev() {
+                        debug_assert_eq!(
+                            self.points[edit.index], edit.new_point,
+                            "current state should have the new_point in at the index specified"
+                        );
+                        self.points[edit.index] = edit.old_point;
+                    }
+                    if let Some((id, group)) = &event_data.deleted_group {
+                        self.groups.restore(*id, group.clone());
+                    }
+                }
                 Event::Delete(event_data) => {
                     debug_assert!(self.points.len() >= event_data.index, "index should be less than or equal to points length because it is supposed to be able to be inserted where it came from");
                     self.points.insert(event_data.index, event_data.point);
+                    if event_data.is_trashed {
+                        self.trash.take_back(&[event_data.point]);
+                    }
+                }
+                Event::DeleteMany(event_data) => {
+                    // Ascending order so each index is valid when it is inserted
+                    for &(index, point) in event_data.deleted.iter() {
+                        self.points.insert(index, point);
+                    }
+                    if event_data.is_trashed {
+                        let points: DataPoints =
+                            event_data.deleted.iter().map(|(_, point)| *point).collect();
+                        self.trash.take_back(&points);
+                    }
                 }
                 Event::Clear(event_data) => {
                     debug_assert!(
@@ -293,11 +776,18 @@ impl Data {
                         "should not have any points when undoing a clear"
                     );
                     std::mem::swap(&mut self.points, &mut event_data.points);
+                    if event_data.is_trashed {
+                        self.trash.take_back(&self.points);
+                    }
                 }
                 Event::Load(event_data) => {
                     std::mem::swap(&mut self.points, &mut event_data.points);
                 }
+                Event::Compressed(_) => {
+                    unreachable!("expanded by the undo manager before being returned")
+                }
             }
+            self.undo_manager.compact();
             // status_msg.add_msg(&format!("Undo: {event}")); // TODO 4: Decide if auto removal of status_msgs is worth implementing (leaving this off pending that)
         }
     }
@@ -311,6 +801,7 @@ impl Data {
             let event = self.undo_manager.redo();
             match event {
                 Event::Add(event_data) => self.points.push(event_data.point),
+                Event::AddMany(event_data) => self.points.extend_from_slice(&event_data.points),
                 Event::Edit(event_data) => {
                     debug_assert_eq!(
                         *self
@@ -322,12 +813,41 @@ impl Data {
                     );
                     *self.points.get_mut(event_data.index).unwrap() = event_data.new_point;
                 }
+                Event::EditMany(event_data) => {
+                    for edit in event_data.edits.iter() {
+                        debug_assert_eq!(
+                            self.points[edit.index], edit.old_point,
+                            "current state should have the old_point in at the index specified"
+                        );
+                        self.points[edit.index] = edit.new_point;
+                    }
+                    if let Some((id, _)) = &event_data.deleted_group {
+                        self.groups.remove(*id);
+                    }
+                }
                 Event::Delete(event_data) => {
                     debug_assert_eq!(
                         self.points[event_data.index], event_data.point,
                         "redoing a delete but point is not the same"
                     );
                     self.points.remove(event_data.index);
+                    if event_data.is_trashed {
+                        self.trash.put_back(&[event_data.point]);
+                    }
+                }
+                Event::DeleteMany(event_data) => {
+                    for &(index, point) in event_data.deleted.iter().rev() {
+                        debug_assert_eq!(
+                            self.points[index], point,
+                            "redoing a delete but point is not the same"
+                        );
+                        self.points.remove(index);
+                    }
+                    if event_data.is_trashed {
+                        let points: DataPoints =
+                            event_data.deleted.iter().map(|(_, point)| *point).collect();
+                        self.trash.put_back(&points);
+                    }
                 }
                 Event::Clear(event_data) => {
                     debug_assert!(
@@ -335,11 +855,18 @@ impl Data {
                         "should not have any points when redoing a clear"
                     );
                     std::mem::swap(&mut self.points, &mut event_data.points);
+                    if event_data.is_trashed {
+                        self.trash.put_back(&event_data.points);
+                    }
                 }
                 Event::Load(event_data) => {
                     std::mem::swap(&mut self.points, &mut event_data.points);
                 }
+                Event::Compressed(_) => {
+                    unreachable!("expanded by the undo manager before being returned")
+                }
             }
+            self.undo_manager.compact();
             // status_msg.add_msg(&format!("Redo: {event}")); // TODO 4: Decide if auto removal of status_msgs is worth implementing (leaving this off pending that)
         }
     }
@@ -356,6 +883,23 @@ impl Data {
         self.undo_manager.max_history_size()
     }
 
+    pub fn set_history_compress_threshold(&mut self, value: Option<u32>) {
+        self.undo_manager.set_compress_threshold(value);
+    }
+
+    pub fn history_compress_threshold(&self) -> Option<u32> {
+        self.undo_manager.compress_threshold()
+    }
+
+    /// Returns a warning if the last change was large enough to be compressed in the history
+    pub fn take_history_size_warning(&mut self) -> Option<String> {
+        let (count, size) = self.undo_manager.take_compression_report()?;
+        Some(format!(
+            "Change of {count} points stored compressed in the history ({:.1} MB). Clear the history to free the memory",
+            size as f64 / 1_000_000.0
+        ))
+    }
+
     /// Function replaces the data with the data passed in (also handles the history as needed)
     pub fn replace_with_loaded_data(&mut self, points: DataPoints) {
         self.invalidate_cache();
@@ -365,48 +909,87 @@ impl Data {
     }
 
     /// Returns the loaded data if loaded with an optional status message
+    ///
+    /// CSV files with a layout that is not known return [`CsvLoad::NeedsMapping`] and MAT-files
+    /// without the variables return [`CsvLoad::NeedsMatVariables`]
     pub async fn load_from_file(
         file: &FileHandle,
-    ) -> anyhow::Result<(DataPoints, Option<&'static str>)> {
+        csv_mappings: &CsvMappings,
+        mat_names: &MatVariableNames,
+        progress: &LoadProgress,
+    ) -> anyhow::Result<(CsvLoad, Option<&'static str>)> {
         let mut load_msg = None;
         let filename = file.file_name();
         let loaded_data = match &filename {
-            s if s.ends_with("mat") => Self::load_as_matlab(file)?,
-            s if s.ends_with("csv") => Self::load_as_csv(file)
+            s if s.ends_with("mat") => {
+                let bytes = file.read().await;
+                match PendingMatLoad::if_names_missing(bytes, mat_names) {
+                    Some(pending) => CsvLoad::NeedsMatVariables(pending),
+                    None => CsvLoad::Loaded(Self::load_as_matlab(file, mat_names).await?),
+                }
+            }
+            s if s.ends_with("jsonl") || s.ends_with("ndjson") => CsvLoad::Loaded(
+                jsonl::read_jsonl(&file.read().await, progress)
+                    .context("Failed to load from JSON Lines")?,
+            ),
+            s if is_arrow_file(s) => CsvLoad::Loaded(
+                arrow_ipc::read(&file.read().await).context("Failed to load from Arrow IPC")?,
+            ),
+            s if s.ends_with("csv") => Self::load_as_csv(file, csv_mappings, progress)
                 .await
                 .context("Failed to load from CSV")?,
             s => {
                 load_msg = Some("Extension not recognized. Attempted to load as CSV");
-                Self::load_as_csv(file).await.with_context(|| {
-                    format!("failed to load unrecognized file type as CSV. Filename: {s:?}")
-                })?
+                Self::load_as_csv(file, csv_mappings, progress)
+                    .await
+                    .with_context(|| {
+                        format!("failed to load unrecognized file type as CSV. Filename: {s:?}")
+                    })?
             }
         };
 
         Ok((loaded_data, load_msg))
     }
 
+    /// Uses a pure Rust implementation because matio is not available in WASM
     #[cfg(target_arch = "wasm32")]
-    fn save_as_matlab(_: &[DataPoint], _: &FileHandle) -> anyhow::Result<()> {
-        bail!("Saving to Matlab files is not supported in WASM")
+    async fn save_as_matlab(
+        points: &[DataPoint],
+        file: &FileHandle,
+        names: &MatVariableNames,
+    ) -> anyhow::Result<()> {
+        file.write(&mat_v5::write(points, names))
+            .await
+            .context("failed to write to FileHandle")
     }
 
+    /// Uses a pure Rust implementation because matio is not available in WASM
     #[cfg(target_arch = "wasm32")]
-    fn load_as_matlab(_: &FileHandle) -> anyhow::Result<DataPoints> {
-        bail!("Loading from Matlab files is not supported in WASM")
+    async fn load_as_matlab(
+        file: &FileHandle,
+        names: &MatVariableNames,
+    ) -> anyhow::Result<DataPoints> {
+        mat_v5::read(&file.read().await, names)
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    fn save_as_matlab(points: &[DataPoint], file: &FileHandle) -> anyhow::Result<()> {
+    async fn save_as_matlab(
+        points: &[DataPoint],
+        file: &FileHandle,
+        names: &MatVariableNames,
+    ) -> anyhow::Result<()> {
         use self::matlab::MatlabData;
 
         let data = MatlabData::from(points);
-        data.save_to_file(file.path())
+        data.save_to_file(file.path(), names)
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    fn load_as_matlab(file: &FileHandle) -> anyhow::Result<DataPoints> {
-        self::matlab::MatlabData::load_from_file(file.path())
+    async fn load_as_matlab(
+        file: &FileHandle,
+        names: &MatVariableNames,
+    ) -> anyhow::Result<DataPoints> {
+        self::matlab::MatlabData::load_from_file(file.path(), names)
     }
 
     async fn save_as_csv(points: &[DataPoint], file: &FileHandle) -> anyhow::Result<()> {
@@ -414,7 +997,7 @@ impl Data {
         let mut wtr = csv::Writer::from_writer(&mut write_buffer);
 
         for point in points.iter() {
-            wtr.serialize(point)?;
+            wtr.serialize(CsvPoint::from(point))?;
         }
 
         wtr.flush().context("failed flushing csv writer")?;
@@ -424,49 +1007,189 @@ impl Data {
             .context("failed to write to FileHandle")
     }
 
-    async fn load_as_csv(file: &FileHandle) -> anyhow::Result<DataPoints> {
+    /// NB: In WASM this still runs on the UI thread so the progress is only visible on native
+    async fn load_as_csv(
+        file: &FileHandle,
+        csv_mappings: &CsvMappings,
+        progress: &LoadProgress,
+    ) -> anyhow::Result<CsvLoad> {
         let text = file.read().await;
-        let mut reader = csv::Reader::from_reader(text.as_slice());
-        let mut result = vec![];
-        for record in reader.deserialize() {
-            let point: DataPoint = record?;
-            result.push(point);
-        }
-        Ok(result)
+        csv_mapping::read_csv(&text, csv_mappings, progress)
     }
 
     pub fn timestamp(&self) -> DataTimestamp {
         self.undo_manager.timestamp()
     }
 
+    /// Timestamp of the last change that affects training (Skips changes to only the color, lock
+    /// or group of points)
+    pub fn training_timestamp(&self) -> DataTimestamp {
+        self.undo_manager.training_timestamp()
+    }
+
+    /// Deletes the points at the indices as a single event (Indices that are out of range are ignored)
+    pub fn delete_many(&mut self, indices: impl IntoIterator<Item = usize>) {
+        let mut indices: Vec<usize> = indices
+            .into_iter()
+            .filter(|&i| i < self.points.len() && !self.points[i].is_locked)
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        match indices[..] {
+            [] => return,
+            [index] => return self.delete_by_index(index),
+            _ => {}
+        }
+        self.invalidate_cache();
+        let mut deleted: Vec<(usize, DataPoint)> = indices
+            .into_iter()
+            .rev()
+            .map(|i| (i, self.points.remove(i)))
+            .collect();
+        deleted.reverse();
+        let is_trashed = self.trash.receive_many(
+            &deleted
+                .iter()
+                .map(|(_, point)| *point)
+                .collect::<DataPoints>(),
+        );
+        let mut event_data = DeleteManyEventData::new(deleted);
+        event_data.is_trashed = is_trashed;
+        self.undo_manager.add_undo(Event::DeleteMany(event_data));
+    }
+
+    /// Returns the indices of the points within `radius` of the target ordered by distance
+    pub fn indices_within(
+        &self,
+        target_coord: PointArray,
+        radius: f64,
+        label: Option<DataLabel>,
+    ) -> Vec<usize> {
+        let mut result: Vec<(usize, f64)> = self
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| label.is_none() || Some(point.label) == label)
+            .map(|(i, point)| (i, point.distance_to(target_coord)))
+            .filter(|(_, distance)| *distance <= radius)
+            .collect();
+        result.sort_by(|a, b| a.1.total_cmp(&b.1));
+        result.into_iter().map(|(i, _)| i).collect()
+    }
+
+    /// Returns the index of the point nearest to the target if it is within `radius` (Only
+    /// considers the points the predicate accepts)
+    pub fn nearest_within(
+        &self,
+        target_coord: PointArray,
+        radius: f64,
+        predicate: impl Fn(usize) -> bool,
+    ) -> Option<usize> {
+        self.points
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| predicate(*i))
+            .map(|(i, point)| (i, point.distance_to(target_coord)))
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    /// Returns the indices of the points inside the rectangle with the given opposite corners
+    pub fn indices_in_rect(&self, corner: PointArray, opposite: PointArray) -> Vec<usize> {
+        let [min_x0, max_x0] = [corner[0].min(opposite[0]), corner[0].max(opposite[0])];
+        let [min_x1, max_x1] = [corner[1].min(opposite[1]), corner[1].max(opposite[1])];
+        self.points
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| {
+                (min_x0..=max_x0).contains(&point.x0) && (min_x1..=max_x1).contains(&point.x1)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the indices of the points inside the region
+    pub fn indices_in_region(&self, region: &Region) -> Vec<usize> {
+        self.points
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| region.contains(point.to_array()))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the indices of the points inside the polygon (Closed by joining the last vertex to the first)
+    pub fn indices_in_polygon(&self, polygon: &[PointArray]) -> Vec<usize> {
+        if polygon.len() < 3 {
+            return vec![];
+        }
+        self.points
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| is_inside_polygon(point.to_array(), polygon))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Deletes the point unless it is locked
     pub fn delete_by_index(&mut self, index: usize) {
+        if self.points[index].is_locked {
+            return;
+        }
         self.invalidate_cache();
         let removed_point = self.points.remove(index); // Actual delete action
-        self.undo_manager
-            .add_undo(Event::Delete(DeleteEventData::new(index, removed_point)));
+        let mut event_data = DeleteEventData::new(index, removed_point);
+        event_data.is_trashed = self.trash.receive_many(&[removed_point]);
+        self.undo_manager.add_undo(Event::Delete(event_data));
     }
 }
 
 impl Save for Data {
-    async fn save_to_file(&self, file: &FileHandle) -> anyhow::Result<()> {
-        self.points().save_to_file(file).await
+    async fn save_to_file(
+        &self,
+        file: &FileHandle,
+        mat_names: &MatVariableNames,
+    ) -> anyhow::Result<()> {
+        self.points().save_to_file(file, mat_names).await
     }
 }
 
 impl<T: AsRef<[DataPoint]>> Save for T {
-    async fn save_to_file(&self, file: &FileHandle) -> anyhow::Result<()> {
+    async fn save_to_file(
+        &self,
+        file: &FileHandle,
+        mat_names: &MatVariableNames,
+    ) -> anyhow::Result<()> {
         let filename = file.file_name();
         match &filename {
-            s if s.ends_with("mat") => Data::save_as_matlab(self.as_ref(), file),
+            s if s.ends_with("mat") => Data::save_as_matlab(self.as_ref(), file, mat_names).await,
             s if s.ends_with("csv") => Data::save_as_csv(self.as_ref(), file)
                 .await
                 .context("failed to save to CSV"),
-            _ => bail!("extension not recognized. Please use .csv or .mat. Filename: {file:?}"),
+            s if s.ends_with("jsonl") || s.ends_with("ndjson") => file
+                .write(&jsonl::write_jsonl(self.as_ref()))
+                .await
+                .context("failed to save to JSON Lines"),
+            s if is_arrow_file(s) => file
+                .write(&arrow_ipc::write(self.as_ref()))
+                .await
+                .context("failed to save to Arrow IPC"),
+            _ => bail!(
+                "extension not recognized. Please use .csv, .mat, .jsonl or .arrow. Filename: {file:?}"
+            ),
         }
         .context("failed to save")
     }
 }
 
+/// Arrow IPC files are also known as Feather V2 and the stream format uses `.arrows`
+fn is_arrow_file(filename: &str) -> bool {
+    [".arrow", ".arrows", ".feather", ".ipc"]
+        .iter()
+        .any(|x| filename.ends_with(x))
+}
+
 impl<T: AsRef<[DataPoint]>> DistanceCalculations for T {
     fn pairwise_distances(&self) -> Vec<Vec<f64>> {
         let points = self.as_ref();
@@ -482,6 +1205,24 @@ impl<T: AsRef<[DataPoint]>> DistanceCalculations for T {
     }
 }
 
+/// Even-odd rule test using a ray cast in the positive x0 direction
+fn is_inside_polygon(point: PointArray, polygon: &[PointArray]) -> bool {
+    let [x, y] = point;
+    let mut is_inside = false;
+    let mut previous = polygon[polygon.len() - 1];
+    for &current in polygon {
+        let ([x_a, y_a], [x_b, y_b]) = (current, previous);
+        if (y_a > y) != (y_b > y) {
+            let x_crossing = x_a + (y - y_a) * (x_b - x_a) / (y_b - y_a);
+            if x < x_crossing {
+                is_inside = !is_inside;
+            }
+        }
+        previous = current;
+    }
+    is_inside
+}
+
 impl DistanceCalculation for &DataPoint {
     fn to_array(&self) -> PointArray {
         [self.x0, self.x1]
@@ -506,11 +1247,23 @@ impl DistanceCalculation for egui_plot::PlotPoint {
     }
 }
 
-#[derive(Serialize_repr, Deserialize_repr, PartialEq, Clone, Copy, Debug)]
-#[repr(u8)]
+/// The class of a point (Stored as its index so files with only [`Normal`] and [`Anomaly`]
+/// points are the same as before classes were added)
+///
+/// [`Normal`]: DataLabel::Normal
+/// [`Anomaly`]: DataLabel::Anomaly
+#[derive(
+    serde::Deserialize, serde::Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug,
+)]
+#[serde(from = "u8", into = "u8")]
 pub enum DataLabel {
     Normal,
     Anomaly,
+    /// A user defined class
+    ///
+    /// ASSUMPTION: The index is at least [`DataLabel::FIRST_CLASS`] (Use [`DataLabel::from`] to
+    /// create labels from an index)
+    Class(u8),
 }
 
 impl DataLabel {
@@ -530,36 +1283,86 @@ impl DataLabel {
         matches!(self, Self::Anomaly)
     }
 
-    fn as_int(&self) -> u8 {
-        *self as u8
+    /// Index of the first user defined class
+    pub const FIRST_CLASS: u8 = 2;
+
+    /// Returns the other label (User defined classes are opposite of [`Normal`])
+    ///
+    /// [`Normal`]: DataLabel::Normal
+    #[must_use]
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Normal => Self::Anomaly,
+            Self::Anomaly | Self::Class(_) => Self::Normal,
+        }
+    }
+
+    /// Returns the label used by tools that only know normal and anomalous points (Every class
+    /// other than [`Normal`] is treated as an anomaly)
+    ///
+    /// [`Normal`]: DataLabel::Normal
+    #[must_use]
+    pub fn to_binary(&self) -> Self {
+        match self {
+            Self::Normal => Self::Normal,
+            Self::Anomaly | Self::Class(_) => Self::Anomaly,
+        }
+    }
+
+    pub fn as_int(&self) -> u8 {
+        match self {
+            Self::Normal => 0,
+            Self::Anomaly => 1,
+            Self::Class(x) => *x,
+        }
+    }
+
+    /// Converts a label stored as a number (Only whole numbers that fit in a [`u8`] are valid)
+    pub fn try_from_f64(value: f64) -> anyhow::Result<Self> {
+        if value.fract() == 0.0 && (0.0..=f64::from(u8::MAX)).contains(&value) {
+            Ok(Self::from(value as u8))
+        } else {
+            bail!(
+                "expected a whole number from 0 to {} as the label but found {value}",
+                u8::MAX
+            )
+        }
+    }
+}
+
+impl DataLabel {
+    /// The same text as `to_string` but only allocates for user defined classes
+    pub fn short_name(&self) -> Cow<'static, str> {
+        match self {
+            DataLabel::Normal => Cow::Borrowed("N"),
+            DataLabel::Anomaly => Cow::Borrowed("A"),
+            DataLabel::Class(x) => Cow::Owned(x.to_string()),
+        }
     }
 }
 
 impl Display for DataLabel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                DataLabel::Normal => "N",
-                DataLabel::Anomaly => "A",
-            }
-        )
+        write!(f, "{}", self.short_name())
     }
 }
 
-impl TryFrom<u8> for DataLabel {
-    type Error = anyhow::Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+impl From<u8> for DataLabel {
+    fn from(value: u8) -> Self {
         match value {
-            x if DataLabel::Normal.as_int() == x => Ok(DataLabel::Normal),
-            x if DataLabel::Anomaly.as_int() == x => Ok(DataLabel::Anomaly),
-            _ => bail!("unexpected value for DataLabel of {value}"),
+            0 => DataLabel::Normal,
+            1 => DataLabel::Anomaly,
+            x => DataLabel::Class(x),
         }
     }
 }
 
+impl From<DataLabel> for u8 {
+    fn from(value: DataLabel) -> Self {
+        value.as_int()
+    }
+}
+
 #[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Copy, Debug)]
 // TODO 4: Handle approximately equal (Use case prompting idea is for edits in the table)
 //      Reference library: https://jtempest.github.io/float_eq-rs/book/tutorials/basic_usage.html
@@ -567,6 +1370,38 @@ pub struct DataPoint {
     pub x0: f64,
     pub x1: f64,
     pub label: DataLabel,
+    #[serde(default)]
+    pub group: Option<GroupId>,
+    /// Held out when training but still shown and scored
+    #[serde(default)]
+    pub is_excluded_from_training: bool,
+    /// Not trained on and reported separately in the split metrics
+    #[serde(default)]
+    pub is_in_test_split: bool,
+    /// Shown instead of the color of its label or classification
+    #[serde(default)]
+    pub color: Option<Color32>,
+    /// Locked points are skipped when deleting
+    #[serde(default)]
+    pub is_locked: bool,
+}
+
+/// The fields of a [`DataPoint`] that are written to CSV files
+#[derive(serde::Serialize)]
+struct CsvPoint {
+    x0: f64,
+    x1: f64,
+    label: DataLabel,
+}
+
+impl From<&DataPoint> for CsvPoint {
+    fn from(value: &DataPoint) -> Self {
+        Self {
+            x0: value.x0,
+            x1: value.x1,
+            label: value.label,
+        }
+    }
 }
 
 impl Display for DataPoint {
@@ -576,8 +1411,22 @@ impl Display for DataPoint {
 }
 
 impl DataPoint {
-    fn new(x0: f64, x1: f64, label: DataLabel) -> Self {
-        Self { x0, x1, label }
+    pub fn new(x0: f64, x1: f64, label: DataLabel) -> Self {
+        Self {
+            x0,
+            x1,
+            label,
+            group: None,
+            is_excluded_from_training: false,
+            is_in_test_split: false,
+            color: None,
+            is_locked: false,
+        }
+    }
+
+    /// Trained on if it is in the train split and not held out
+    pub fn is_used_for_training(&self) -> bool {
+        !self.is_excluded_from_training && !self.is_in_test_split
     }
 }
 
@@ -593,23 +1442,356 @@ pub(crate) mod tests {
     }
 
     #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
-    pub(super) fn generate_data_points() -> DataPoints {
+    pub(crate) fn generate_data_points() -> DataPoints {
         (0..10)
             .map(|i| {
                 let i = i as f64;
-                DataPoint {
-                    x0: i,
-                    x1: i * 3.0,
-                    label: if i % 4.0 == 0.0 {
+                DataPoint::new(
+                    i,
+                    i * 3.0,
+                    if i % 4.0 == 0.0 {
                         DataLabel::Normal
                     } else {
                         DataLabel::Anomaly
                     },
-                }
+                )
             })
             .collect()
     }
 
+    #[test]
+    fn majority_label_uses_nearest_points() {
+        let mut data = Data::default();
+        data.add_many(vec![
+            DataPoint::new(0.0, 0.0, DataLabel::Normal),
+            DataPoint::new(1.0, 0.0, DataLabel::Normal),
+            DataPoint::new(0.0, 1.0, DataLabel::Anomaly),
+            DataPoint::new(10.0, 10.0, DataLabel::Anomaly),
+            DataPoint::new(11.0, 10.0, DataLabel::Anomaly),
+        ]);
+        assert_eq!(
+            data.majority_label_near([0.2, 0.2], 3),
+            Some(DataLabel::Normal)
+        );
+        assert_eq!(
+            data.majority_label_near([10.0, 9.0], 3),
+            Some(DataLabel::Anomaly)
+        );
+        assert_eq!(data.majority_label_near([0.2, 0.3], 2), None);
+        assert_eq!(Data::default().majority_label_near([0.0, 0.0], 3), None);
+    }
+
+    #[test]
+    fn appearance_edits_keep_training_timestamp() {
+        let mut data = Data::default();
+        data.add_many(generate_data_points());
+        let training_timestamp = data.training_timestamp();
+        data.set_color([0], Some(Color32::RED));
+        data.set_locked([1], true);
+        data.set_group([2], Some(1));
+        assert_ne!(data.timestamp(), training_timestamp);
+        assert_eq!(data.training_timestamp(), training_timestamp);
+        data.undo(&mut StatusMsg::default());
+        assert_eq!(data.training_timestamp(), training_timestamp);
+        data.set_label([0], DataLabel::Anomaly);
+        assert_ne!(data.training_timestamp(), training_timestamp);
+    }
+
+    #[test]
+    fn majority_label_counts_each_class() {
+        let mut data = Data::default();
+        data.add_many(vec![
+            DataPoint::new(0.0, 0.0, DataLabel::Class(2)),
+            DataPoint::new(1.0, 0.0, DataLabel::Class(2)),
+            DataPoint::new(0.0, 1.0, DataLabel::Normal),
+            DataPoint::new(1.0, 1.0, DataLabel::Class(3)),
+        ]);
+        assert_eq!(
+            data.majority_label_near([0.5, 0.5], 4),
+            Some(DataLabel::Class(2))
+        );
+        assert_eq!(data.majority_label_near([0.0, 1.0], 2), None);
+    }
+
+    #[test]
+    fn indices_in_rect_accepts_any_corners() {
+        let mut data = Data::default();
+        data.add_many(vec![
+            DataPoint::new(0.0, 0.0, DataLabel::Normal),
+            DataPoint::new(1.0, 2.0, DataLabel::Normal),
+            DataPoint::new(3.0, 1.0, DataLabel::Anomaly),
+        ]);
+        assert_eq!(data.indices_in_rect([2.0, 2.0], [-1.0, -1.0]), [0, 1]);
+        assert_eq!(data.indices_in_rect([1.0, 0.0], [3.0, 2.0]), [1, 2]);
+        assert!(data.indices_in_rect([4.0, 4.0], [5.0, 5.0]).is_empty());
+    }
+
+    #[test]
+    fn add_many_in_bounds_clamps_or_rejects() {
+        let mut data = Data::default();
+        let mut status_msg = StatusMsg::default();
+        data.new_point_bounds = NewPointBounds {
+            x0_max: Some(1.0),
+            ..Default::default()
+        };
+        let points = vec![
+            DataPoint::new(0.0, 0.0, DataLabel::Normal),
+            DataPoint::new(2.0, 0.0, DataLabel::Normal),
+        ];
+        data.add_many_in_bounds(points.clone(), &mut status_msg);
+        assert_eq!(data.points(), &points[..1]);
+
+        data.new_point_bounds.should_clamp = true;
+        data.add_many_in_bounds(points, &mut status_msg);
+        assert_eq!(data.points().len(), 3);
+        assert_eq!(data.points()[2].to_array(), [1.0, 0.0]);
+        data.undo(&mut status_msg);
+        assert_eq!(data.points().len(), 1);
+    }
+
+    #[test]
+    fn move_point_is_undone_in_one_step() {
+        let mut data = Data::default();
+        let mut status_msg = StatusMsg::default();
+        let original = DataPoint::new(1.0, 1.0, DataLabel::Anomaly);
+        data.add_many(vec![original]);
+        data.move_point(0, [4.0, -2.0], &mut status_msg);
+        assert_eq!(data.points()[0].to_array(), [4.0, -2.0]);
+        assert_eq!(data.points()[0].label, DataLabel::Anomaly);
+        data.undo(&mut status_msg);
+        assert_eq!(data.points(), [original]);
+    }
+
+    #[test]
+    fn indices_in_polygon_handles_concave_shapes() {
+        let mut data = Data::default();
+        data.add_many(vec![
+            DataPoint::new(1.0, 1.0, DataLabel::Normal),
+            DataPoint::new(2.0, 3.0, DataLabel::Normal),
+            DataPoint::new(3.0, 1.0, DataLabel::Anomaly),
+            DataPoint::new(5.0, 5.0, DataLabel::Anomaly),
+        ]);
+        // U shape with the opening at the top between x0 = 1.5 and 2.5
+        let u_shape = [
+            [0.0, 0.0],
+            [4.0, 0.0],
+            [4.0, 4.0],
+            [2.5, 4.0],
+            [2.5, 2.0],
+            [1.5, 2.0],
+            [1.5, 4.0],
+            [0.0, 4.0],
+        ];
+        assert_eq!(data.indices_in_polygon(&u_shape), [0, 2]);
+        assert!(data.indices_in_polygon(&u_shape[..2]).is_empty());
+    }
+
+    #[test]
+    fn delete_many_is_one_event() {
+        let mut data = Data::default();
+        let points = generate_data_points();
+        data.add_many(points.clone());
+        data.delete_many([7, 2, 4, 2, 100]);
+        let expected: DataPoints = points
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| ![2, 4, 7].contains(i))
+            .map(|(_, point)| *point)
+            .collect();
+        assert_eq!(data.points(), expected);
+
+        let mut status_msg = StatusMsg::default();
+        data.undo(&mut status_msg);
+        assert_eq!(data.points(), points);
+        data.redo(&mut status_msg);
+        assert_eq!(data.points(), expected);
+    }
+
+    #[test]
+    fn round_all_points_is_undoable() {
+        let mut data = Data::default();
+        let points = vec![
+            DataPoint::new(1.234, 5.0, DataLabel::Normal),
+            DataPoint::new(2.0, -0.456, DataLabel::Anomaly),
+            DataPoint::new(3.0, 4.0, DataLabel::Normal),
+        ];
+        data.add_many(points.clone());
+        assert_eq!(data.round_all_points(), 0);
+        data.set_rounding_enabled(true);
+        *data.rounding_decimal_places_mut() = 1;
+        assert_eq!(data.round_all_points(), 2);
+        assert_eq!(data.points()[0].x0, 1.2);
+        assert_eq!(data.points()[1].x1, -0.5);
+
+        data.undo(&mut StatusMsg::default());
+        assert_eq!(data.points(), points);
+    }
+
+    #[test]
+    fn large_changes_are_compressed_in_history() {
+        let mut data = Data::default();
+        data.set_history_compress_threshold(Some(3));
+        let mut points = generate_data_points();
+        points[1].group = Some(7);
+        points[2].is_excluded_from_training = true;
+        points[2].is_in_test_split = true;
+        points[3].color = Some(Color32::from_rgba_unmultiplied(1, 2, 3, 4));
+        points[4].is_locked = true;
+        data.add_many(points.clone());
+        assert!(data.take_history_size_warning().is_some());
+        assert!(data.take_history_size_warning().is_none());
+        data.delete_many([1, 2, 5, 8]);
+        let after_delete = data.clone_points();
+
+        let mut status_msg = StatusMsg::default();
+        data.undo(&mut status_msg);
+        assert_eq!(data.points(), points);
+        data.undo(&mut status_msg);
+        assert!(data.points().is_empty());
+        data.redo(&mut status_msg);
+        data.redo(&mut status_msg);
+        assert_eq!(data.points(), after_delete);
+    }
+
+    #[test]
+    fn locked_points_are_not_deleted() {
+        let mut data = Data::default();
+        data.add_many(generate_data_points());
+        let count = data.points().len();
+        data.set_locked([1, 3], true);
+        let locked = [data.points()[1], data.points()[3]];
+
+        data.delete_many([0, 1, 2]);
+        assert_eq!(data.points().len(), count - 2);
+        data.delete_by_index(0);
+        assert_eq!(data.points()[0], locked[0]);
+        data.clear_points();
+        assert_eq!(data.points(), locked);
+
+        let mut status_msg = StatusMsg::default();
+        data.undo(&mut status_msg);
+        assert_eq!(data.points().len(), count - 2);
+    }
+
+    #[test]
+    fn group_assignment_is_undoable() {
+        let mut data = Data::default();
+        data.add_many(generate_data_points());
+        let id = data.groups.add("cluster".to_string()).unwrap();
+        data.set_group([1, 3], Some(id));
+        assert_eq!(data.indices_in_group(id), vec![1, 3]);
+
+        let mut status_msg = StatusMsg::default();
+        data.undo(&mut status_msg);
+        assert!(data.indices_in_group(id).is_empty());
+        data.redo(&mut status_msg);
+        assert_eq!(data.indices_in_group(id), vec![1, 3]);
+
+        data.delete_group(id);
+        assert!(data.groups.get(id).is_none());
+        assert!(data.points().iter().all(|point| point.group.is_none()));
+
+        // A new group must not take the id of the deleted one or undo would put the points in it
+        let new_id = data.groups.add("other".to_string()).unwrap();
+        assert_ne!(new_id, id);
+        data.undo(&mut status_msg);
+        assert_eq!(data.groups.get(id).unwrap().name, "cluster");
+        assert_eq!(data.indices_in_group(id), vec![1, 3]);
+        assert!(data.indices_in_group(new_id).is_empty());
+        data.redo(&mut status_msg);
+        assert!(data.groups.get(id).is_none());
+        assert!(data.indices_in_group(id).is_empty());
+    }
+
+    #[test]
+    fn duplicate_with_offset_is_one_event() {
+        let mut data = Data::default();
+        data.add_many(generate_data_points());
+        let copies = data.duplicate([0, 1], [1.0, -2.0], true, 1);
+        assert_eq!(copies, 10..12);
+        assert_eq!(
+            data.points()[copies].to_vec(),
+            vec![
+                DataPoint::new(1.0, -2.0, DataLabel::Anomaly),
+                DataPoint::new(2.0, 1.0, DataLabel::Normal)
+            ]
+        );
+
+        data.undo(&mut StatusMsg::default());
+        assert_eq!(data.points().len(), 10);
+
+        let copies = data.duplicate([1], [1.0, 0.0], false, 3);
+        assert_eq!(copies, 10..13);
+        assert_eq!(
+            data.points()[12],
+            DataPoint::new(4.0, 3.0, DataLabel::Anomaly)
+        );
+        data.undo(&mut StatusMsg::default());
+        assert_eq!(data.points().len(), 10);
+    }
+
+    #[test]
+    fn nearest_within_picks_closest_in_radius() {
+        let mut data = Data::default();
+        data.add_many(generate_data_points());
+        assert_eq!(data.nearest_within([1.2, 3.0], 1.0, |_| true), Some(1));
+        assert_eq!(data.nearest_within([1.6, 4.5], 2.0, |_| true), Some(2));
+        assert_eq!(data.nearest_within([1.5, 10.0], 1.0, |_| true), None);
+    }
+
+    #[test]
+    fn soft_delete_and_restore() {
+        let mut data = Data::default();
+        let points = generate_data_points();
+        data.add_many(points.clone());
+        data.delete_by_index(2);
+        assert!(
+            data.trash.is_empty(),
+            "trash should only be used when enabled"
+        );
+
+        data.trash.is_enabled = true;
+        data.delete_by_index(2);
+        data.delete_by_index(0);
+        assert_eq!(data.trash.points(), &[points[3], points[0]]);
+
+        data.restore_from_trash(&[1]);
+        assert_eq!(data.trash.points(), &[points[3]]);
+        assert_eq!(data.points().last(), Some(&points[0]));
+    }
+
+    #[test]
+    fn undoing_soft_delete_takes_points_out_of_trash() {
+        let mut data = Data::default();
+        let points = generate_data_points();
+        data.add_many(points.clone());
+        data.trash.is_enabled = true;
+        let mut status_msg = StatusMsg::default();
+
+        data.delete_by_index(1);
+        data.delete_many([2, 4]);
+        assert_eq!(data.trash.len(), 3);
+        data.undo(&mut status_msg);
+        data.undo(&mut status_msg);
+        assert!(data.trash.is_empty());
+        assert_eq!(data.points(), &points[..]);
+
+        // Restoring after the undo must not add the points a second time
+        data.restore_from_trash(&[0]);
+        assert_eq!(data.points(), &points[..]);
+
+        data.redo(&mut status_msg);
+        assert_eq!(data.trash.points(), &[points[1]]);
+        data.undo(&mut status_msg);
+
+        data.clear_points();
+        assert_eq!(data.trash.points(), &points[..]);
+        data.undo(&mut status_msg);
+        assert!(data.trash.is_empty());
+        data.redo(&mut status_msg);
+        assert_eq!(data.trash.points(), &points[..]);
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[ignore = "Needs to write to disk and tests code that doesn't change often"]
     #[tokio::test]
@@ -621,22 +1803,34 @@ pub(crate) mod tests {
         let file = FileHandle::from(path.to_path_buf());
 
         Data::save_as_csv(&expected, &file).await.unwrap();
-        let actual = Data::load_as_csv(&file).await.unwrap();
+        let CsvLoad::Loaded(actual) =
+            Data::load_as_csv(&file, &Default::default(), &Default::default())
+                .await
+                .unwrap()
+        else {
+            panic!("saved layout should load without a mapping");
+        };
         assert_eq!(actual, expected);
     }
 
     #[cfg(not(target_arch = "wasm32"))]
     #[ignore = "Needs to write to disk and tests code that doesn't change often"]
-    #[test]
-    fn save_load_from_disk_as_matlab() {
+    #[tokio::test]
+    async fn save_load_from_disk_as_matlab() {
         let expected = generate_data_points();
         let temp_file = tempfile::NamedTempFile::new().unwrap();
         let path = temp_file.path();
         println!("Using temp file at: {path:?}");
         let file = FileHandle::from(path.to_path_buf());
 
-        Data::save_as_matlab(&expected, &file).unwrap();
-        let actual = Data::load_as_matlab(&file).unwrap();
+        let names = MatVariableNames {
+            points: "data".to_string(),
+            labels: "labels".to_string(),
+        };
+        Data::save_as_matlab(&expected, &file, &names)
+            .await
+            .unwrap();
+        let actual = Data::load_as_matlab(&file, &names).await.unwrap();
         assert_eq!(actual, expected);
     }
 }
@@ -0,0 +1,70 @@
+use super::{DistanceCalculation, PointArray};
+
+/// A text note placed on the plot
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Debug)]
+pub struct Annotation {
+    pub text: String,
+    /// Plot coordinate the text is centered on
+    pub position: PointArray,
+}
+
+impl DistanceCalculation for Annotation {
+    fn to_array(&self) -> PointArray {
+        self.position
+    }
+}
+
+/// Notes used to document regions of the plot (Changes are not part of the history)
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Default, Debug)]
+pub struct Annotations {
+    items: Vec<Annotation>,
+}
+
+impl Annotations {
+    /// Adds a note and returns its index
+    pub fn add(&mut self, text: String, position: PointArray) -> usize {
+        self.items.push(Annotation { text, position });
+        self.items.len() - 1
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Annotation> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Annotation> {
+        self.items.get_mut(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Annotation> {
+        self.items.iter()
+    }
+
+    /// Returns the index of the note anchored nearest to the target if it is within `radius`
+    pub fn nearest_within(&self, target: PointArray, radius: f64) -> Option<usize> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(i, note)| (i, note.distance_to(target)))
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_note_is_found_and_removed() {
+        let mut notes = Annotations::default();
+        notes.add("first".to_string(), [0.0, 0.0]);
+        let second = notes.add("second".to_string(), [1.0, 1.0]);
+        assert_eq!(notes.nearest_within([0.9, 0.8], 0.5), Some(second));
+        assert_eq!(notes.nearest_within([5.0, 5.0], 0.5), None);
+
+        assert_eq!(notes.remove(second).unwrap().text, "second");
+        assert!(notes.remove(second).is_none());
+        assert_eq!(notes.iter().count(), 1);
+    }
+}
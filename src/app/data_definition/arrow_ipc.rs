@@ -0,0 +1,689 @@
+use anyhow::{bail, Context};
+
+use super::{DataLabel, DataPoint, DataPoints};
+
+const MAGIC: &[u8; 6] = b"ARROW1";
+const CONTINUATION: u32 = 0xFFFF_FFFF;
+const METADATA_VERSION_V5: i16 = 4;
+
+// Message header types (See Message.fbs in the Arrow format specification)
+const HEADER_SCHEMA: u8 = 1;
+const HEADER_RECORD_BATCH: u8 = 3;
+
+// Field types (See Schema.fbs in the Arrow format specification)
+const TYPE_NULL: u8 = 1;
+const TYPE_INT: u8 = 2;
+const TYPE_FLOATING_POINT: u8 = 3;
+const TYPE_BOOL: u8 = 6;
+
+const PRECISION_SINGLE: i16 = 1;
+const PRECISION_DOUBLE: i16 = 2;
+
+/// Names of the columns used for the fields of [`DataPoint`]
+const COLUMN_NAMES: [&str; 3] = ["x0", "x1", "label"];
+
+/// Size of the FieldNode and Buffer structs in a RecordBatch
+const STRUCT_SIZE: usize = 16;
+/// Size of the Block struct in the footer
+const BLOCK_SIZE: usize = 24;
+
+/// Writes the points as an Arrow IPC file (Feather V2) with a single record batch of `x0`, `x1`
+/// (float64) and `label` (uint8) without compression
+pub fn write(points: &[DataPoint]) -> Vec<u8> {
+    let mut body = vec![];
+    let mut buffers = vec![];
+    let mut push_buffer = |data: &[u8]| {
+        buffers.extend_from_slice(&(body.len() as i64).to_le_bytes());
+        buffers.extend_from_slice(&(data.len() as i64).to_le_bytes());
+        body.extend_from_slice(data);
+        body.resize(body.len() + padding_for(data.len()), 0);
+    };
+    let x0: Vec<u8> = points.iter().flat_map(|p| p.x0.to_le_bytes()).collect();
+    let x1: Vec<u8> = points.iter().flat_map(|p| p.x1.to_le_bytes()).collect();
+    let labels: Vec<u8> = points.iter().map(|p| p.label.as_int()).collect();
+    for values in [x0, x1, labels] {
+        push_buffer(&[]); // No validity bitmap because there are no nulls
+        push_buffer(&values);
+    }
+    let n = points.len() as i64;
+    let nodes: Vec<u8> = (0..COLUMN_NAMES.len())
+        .flat_map(|_| n.to_le_bytes().into_iter().chain(0i64.to_le_bytes()))
+        .collect();
+    let record_batch = Fb::Table(vec![
+        Some(Fb::I64(n)),
+        Some(Fb::Structs(STRUCT_SIZE, nodes)),
+        Some(Fb::Structs(STRUCT_SIZE, buffers)),
+    ]);
+
+    let mut result = MAGIC.to_vec();
+    result.extend_from_slice(&[0; 2]);
+    write_message(&mut result, HEADER_SCHEMA, schema(), &[]);
+    let batch_block = write_message(&mut result, HEADER_RECORD_BATCH, record_batch, &body);
+    result.extend_from_slice(&CONTINUATION.to_le_bytes());
+    result.extend_from_slice(&0u32.to_le_bytes()); // End of stream
+
+    let footer = Fb::Table(vec![
+        Some(Fb::I16(METADATA_VERSION_V5)),
+        Some(schema()),
+        Some(Fb::Structs(BLOCK_SIZE, vec![])),
+        Some(Fb::Structs(BLOCK_SIZE, batch_block)),
+    ]);
+    let footer = finish(&footer);
+    result.extend_from_slice(&footer);
+    result.extend_from_slice(&(footer.len() as i32).to_le_bytes());
+    result.extend_from_slice(MAGIC);
+    result
+}
+
+/// Reads the `x0`, `x1` and `label` columns from an Arrow IPC file or stream (Uncompressed only)
+pub fn read(bytes: &[u8]) -> anyhow::Result<DataPoints> {
+    let mut rest = if bytes.starts_with(MAGIC) {
+        &bytes[8..]
+    } else {
+        bytes // Stream format does not have the magic number
+    };
+    let mut columns = None;
+    let mut result = vec![];
+    while let Some((Message { metadata, body }, next)) = next_message(rest)? {
+        match metadata.u8(1)? {
+            HEADER_SCHEMA => {
+                let schema = metadata
+                    .table(2)?
+                    .context("schema message missing schema")?;
+                columns = Some(find_columns(&schema)?);
+            }
+            HEADER_RECORD_BATCH => {
+                let columns = columns
+                    .as_ref()
+                    .context("record batch found before the schema")?;
+                let batch = metadata
+                    .table(2)?
+                    .context("record batch message missing record batch")?;
+                read_batch(&batch, body, columns, &mut result)?;
+            }
+            _ => {} // Dictionaries are only used by columns that are not loaded
+        }
+        rest = next;
+    }
+    if columns.is_none() {
+        bail!("no schema found in Arrow file");
+    }
+    Ok(result)
+}
+
+fn padding_for(len: usize) -> usize {
+    (8 - len % 8) % 8
+}
+
+fn schema() -> Fb {
+    let field = |name: &str, type_type: u8, type_table: Fb| {
+        Fb::Table(vec![
+            Some(Fb::String(name.to_string())),
+            Some(Fb::Bool(false)),
+            Some(Fb::U8(type_type)),
+            Some(type_table),
+            None,
+            Some(Fb::Tables(vec![])),
+        ])
+    };
+    let double = || Fb::Table(vec![Some(Fb::I16(PRECISION_DOUBLE))]);
+    let uint8 = Fb::Table(vec![Some(Fb::I32(8)), Some(Fb::Bool(false))]);
+    Fb::Table(vec![
+        Some(Fb::I16(0)), // Little endian
+        Some(Fb::Tables(vec![
+            field(COLUMN_NAMES[0], TYPE_FLOATING_POINT, double()),
+            field(COLUMN_NAMES[1], TYPE_FLOATING_POINT, double()),
+            field(COLUMN_NAMES[2], TYPE_INT, uint8),
+        ])),
+    ])
+}
+
+/// Appends an encapsulated message and returns the Block struct used to find it from the footer
+fn write_message(out: &mut Vec<u8>, header_type: u8, header: Fb, body: &[u8]) -> Vec<u8> {
+    let message = Fb::Table(vec![
+        Some(Fb::I16(METADATA_VERSION_V5)),
+        Some(Fb::U8(header_type)),
+        Some(header),
+        Some(Fb::I64(body.len() as i64)),
+    ]);
+    let mut metadata = finish(&message);
+    metadata.resize(metadata.len() + padding_for(metadata.len()), 0);
+    let offset = out.len();
+    out.extend_from_slice(&CONTINUATION.to_le_bytes());
+    out.extend_from_slice(&(metadata.len() as i32).to_le_bytes());
+    out.extend_from_slice(&metadata);
+    out.extend_from_slice(body);
+
+    let mut block = vec![];
+    block.extend_from_slice(&(offset as i64).to_le_bytes());
+    block.extend_from_slice(&(metadata.len() as i32 + 8).to_le_bytes());
+    block.extend_from_slice(&[0; 4]);
+    block.extend_from_slice(&(body.len() as i64).to_le_bytes());
+    block
+}
+
+/// An encapsulated message in an Arrow IPC file
+struct Message<'a> {
+    metadata: FbTable<'a>,
+    body: &'a [u8],
+}
+
+/// Splits off the next message returning it and the remaining bytes or `None` at the end of the
+/// stream
+fn next_message(bytes: &[u8]) -> anyhow::Result<Option<(Message<'_>, &[u8])>> {
+    if bytes.len() < 4 {
+        return Ok(None);
+    }
+    let mut start: usize = 4;
+    let mut len = read_u32(bytes, 0)?;
+    if len == CONTINUATION {
+        len = read_u32(bytes, 4)?;
+        start = 8;
+    }
+    if len == 0 {
+        return Ok(None);
+    }
+    let metadata_end = start
+        .checked_add(len as usize)
+        .context("message metadata length is too large")?;
+    let metadata = bytes
+        .get(start..metadata_end)
+        .context("unexpected end of file while reading message metadata")?;
+    let metadata = FbTable::root(metadata)?;
+    let body_len = usize::try_from(metadata.i64(3)?).context("invalid message body length")?;
+    let body = bytes
+        .get(metadata_end..metadata_end.saturating_add(body_len))
+        .context("unexpected end of file while reading message body")?;
+    Ok(Some((
+        Message { metadata, body },
+        &bytes[metadata_end + body_len..],
+    )))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColumnType {
+    Int { bit_width: i32, is_signed: bool },
+    Float { precision: i16 },
+    Bool,
+}
+
+/// Where to find a column in the nodes and buffers of each record batch
+#[derive(Debug, Clone, Copy)]
+struct Column {
+    node: usize,
+    /// Index of the validity buffer (The values buffer is the next one)
+    buffer: usize,
+    column_type: ColumnType,
+}
+
+/// Finds the columns for each of [`COLUMN_NAMES`] (Names are not case sensitive)
+fn find_columns(schema: &FbTable<'_>) -> anyhow::Result<[Column; 3]> {
+    let mut result = [None; 3];
+    let mut found_names = vec![];
+    let mut node = 0;
+    let mut buffer = 0;
+    for field in schema.tables(1)? {
+        let name = field.string(0)?.unwrap_or_default();
+        found_names.push(name.to_string());
+        let type_type = field.u8(2)?;
+        if let Some(i) = COLUMN_NAMES
+            .iter()
+            .position(|x| x.eq_ignore_ascii_case(name))
+        {
+            if field.table(4)?.is_some() {
+                bail!("dictionary encoded column {name:?} is not supported");
+            }
+            let type_table = field
+                .table(3)?
+                .with_context(|| format!("column {name:?} missing type"))?;
+            let column_type = match type_type {
+                TYPE_INT => ColumnType::Int {
+                    bit_width: type_table.i32(0)?,
+                    is_signed: type_table.bool(1)?,
+                },
+                TYPE_FLOATING_POINT => ColumnType::Float {
+                    precision: type_table.i16(0)?,
+                },
+                TYPE_BOOL => ColumnType::Bool,
+                _ => bail!("column {name:?} has unsupported type {type_type}. Expected a number"),
+            };
+            result[i] = Some(Column {
+                node,
+                buffer,
+                column_type,
+            });
+        }
+        count_nodes_and_buffers(&field, &mut node, &mut buffer)?;
+    }
+    let mut columns = vec![];
+    for (column, name) in result.into_iter().zip(COLUMN_NAMES) {
+        columns.push(column.with_context(|| {
+            format!("column {name:?} not found. Columns found: {found_names:?}")
+        })?);
+    }
+    Ok(columns.try_into().expect("one for each name"))
+}
+
+/// Adds the number of nodes and buffers the field and its children use in each record batch
+fn count_nodes_and_buffers(
+    field: &FbTable<'_>,
+    nodes: &mut usize,
+    buffers: &mut usize,
+) -> anyhow::Result<()> {
+    *nodes += 1;
+    if field.table(4)?.is_some() {
+        *buffers += 2; // Dictionary encoded columns store the validity and the indices
+        return Ok(());
+    }
+    let type_type = field.u8(2)?;
+    *buffers += match type_type {
+        TYPE_NULL => 0,
+        // Struct and FixedSizeList
+        13 | 16 => 1,
+        // Int, FloatingPoint, Bool, Decimal, Date, Time, Timestamp, Interval, List,
+        // FixedSizeBinary, Map, Duration and LargeList
+        TYPE_INT | TYPE_FLOATING_POINT | TYPE_BOOL | 7..=12 | 15 | 17 | 18 | 21 => 2,
+        // Binary, Utf8, LargeBinary and LargeUtf8
+        4 | 5 | 19 | 20 => 3,
+        _ => bail!("unsupported column type {type_type} in Arrow file"),
+    };
+    for child in field.tables(5)? {
+        count_nodes_and_buffers(&child, nodes, buffers)?;
+    }
+    Ok(())
+}
+
+fn read_batch(
+    batch: &FbTable<'_>,
+    body: &[u8],
+    columns: &[Column; 3],
+    result: &mut DataPoints,
+) -> anyhow::Result<()> {
+    if batch.table(3)?.is_some() {
+        bail!("compressed Arrow files are not supported. Please save without compression");
+    }
+    let n = usize::try_from(batch.i64(0)?).context("invalid record batch length")?;
+    let nodes = batch.structs(1, STRUCT_SIZE)?;
+    let buffers = batch.structs(2, STRUCT_SIZE)?;
+    let mut values = vec![];
+    for (column, name) in columns.iter().zip(COLUMN_NAMES) {
+        let node = nodes
+            .get(column.node)
+            .with_context(|| format!("record batch missing node for column {name:?}"))?;
+        if read_i64(node, 8)? > 0 {
+            bail!("column {name:?} has missing values which are not supported");
+        }
+        let buffer = buffers
+            .get(column.buffer + 1)
+            .with_context(|| format!("record batch missing buffer for column {name:?}"))?;
+        let offset = usize::try_from(read_i64(buffer, 0)?)?;
+        let len = usize::try_from(read_i64(buffer, 8)?)?;
+        let data = offset
+            .checked_add(len)
+            .and_then(|end| body.get(offset..end))
+            .with_context(|| format!("buffer for column {name:?} is outside of the body"))?;
+        values.push(
+            to_f64s(column.column_type, data, n)
+                .with_context(|| format!("failed to read values of column {name:?}"))?,
+        );
+    }
+    let [x0, x1, labels]: [Vec<f64>; 3] = values.try_into().expect("one for each column");
+    result.reserve(n);
+    for ((x0, x1), label) in x0.into_iter().zip(x1).zip(labels) {
+        let label = DataLabel::try_from_f64(label)?;
+        result.push(DataPoint::new(x0, x1, label));
+    }
+    Ok(())
+}
+
+/// Converts the first `n` values in the buffer to f64
+fn to_f64s(column_type: ColumnType, bytes: &[u8], n: usize) -> anyhow::Result<Vec<f64>> {
+    macro_rules! convert {
+        ($t:ty) => {{
+            let size = std::mem::size_of::<$t>();
+            let Some(byte_count) = n.checked_mul(size).filter(|x| *x <= bytes.len()) else {
+                bail!(
+                    "expected {n} values but buffer only has {} bytes",
+                    bytes.len()
+                );
+            };
+            bytes[..byte_count]
+                .chunks_exact(size)
+                .map(|x| <$t>::from_le_bytes(x.try_into().expect("chunk size matches type")) as f64)
+                .collect()
+        }};
+    }
+    Ok(match column_type {
+        ColumnType::Int {
+            bit_width,
+            is_signed,
+        } => match (bit_width, is_signed) {
+            (8, true) => convert!(i8),
+            (8, false) => convert!(u8),
+            (16, true) => convert!(i16),
+            (16, false) => convert!(u16),
+            (32, true) => convert!(i32),
+            (32, false) => convert!(u32),
+            (64, true) => convert!(i64),
+            (64, false) => convert!(u64),
+            _ => bail!("unsupported integer bit width {bit_width}"),
+        },
+        ColumnType::Float { precision } => match precision {
+            PRECISION_SINGLE => convert!(f32),
+            PRECISION_DOUBLE => convert!(f64),
+            _ => bail!("half precision floats are not supported"),
+        },
+        ColumnType::Bool => {
+            if bytes.len() < n.div_ceil(8) {
+                bail!(
+                    "expected {n} values but buffer only has {} bytes",
+                    bytes.len()
+                );
+            }
+            (0..n)
+                .map(|i| f64::from((bytes[i / 8] >> (i % 8)) & 1))
+                .collect()
+        }
+    })
+}
+
+fn read_bytes<const N: usize>(bytes: &[u8], pos: usize) -> anyhow::Result<[u8; N]> {
+    Ok(bytes
+        .get(pos..pos + N)
+        .with_context(|| format!("unexpected end of data reading {N} bytes at {pos}"))?
+        .try_into()
+        .expect("slice has length N"))
+}
+
+fn read_u32(bytes: &[u8], pos: usize) -> anyhow::Result<u32> {
+    read_bytes(bytes, pos).map(u32::from_le_bytes)
+}
+
+fn read_i64(bytes: &[u8], pos: usize) -> anyhow::Result<i64> {
+    read_bytes(bytes, pos).map(i64::from_le_bytes)
+}
+
+/// A value to be written into a flatbuffer by [`finish`]
+///
+/// Only the parts of flatbuffers needed for the Arrow metadata are supported
+enum Fb {
+    /// Fields in order of their id (`None` for fields left at their default)
+    Table(Vec<Option<Fb>>),
+    Bool(bool),
+    U8(u8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    String(String),
+    Tables(Vec<Fb>),
+    /// Vector of structs with 8 byte alignment given as the size of each struct and their
+    /// concatenated bytes
+    Structs(usize, Vec<u8>),
+}
+
+impl Fb {
+    /// Size when stored in a table (Vectors, strings and tables are stored as an offset)
+    fn inline_size(&self) -> usize {
+        match self {
+            Fb::Bool(_) | Fb::U8(_) => 1,
+            Fb::I16(_) => 2,
+            Fb::I32(_) => 4,
+            Fb::I64(_) => 8,
+            Fb::Table(_) | Fb::String(_) | Fb::Tables(_) | Fb::Structs(..) => 4,
+        }
+    }
+}
+
+/// Writes a flatbuffer with `root` as the root table
+///
+/// Objects are written after the objects that refer to them because offsets are unsigned
+fn finish(root: &Fb) -> Vec<u8> {
+    let mut buf = vec![0; 4];
+    let pos = write_object(&mut buf, root);
+    buf[0..4].copy_from_slice(&(pos as u32).to_le_bytes());
+    buf
+}
+
+/// Adds padding until the length is `remainder` more than a multiple of `alignment`
+fn align(buf: &mut Vec<u8>, alignment: usize, remainder: usize) {
+    while buf.len() % alignment != remainder {
+        buf.push(0);
+    }
+}
+
+/// Writes `child` and sets the offset at `slot` to point to it
+fn write_child(buf: &mut Vec<u8>, slot: usize, child: &Fb) {
+    let pos = write_object(buf, child);
+    buf[slot..slot + 4].copy_from_slice(&((pos - slot) as u32).to_le_bytes());
+}
+
+/// Writes a value that is referred to by an offset and returns its position
+fn write_object(buf: &mut Vec<u8>, value: &Fb) -> usize {
+    match value {
+        Fb::Table(fields) => write_table(buf, fields),
+        Fb::String(s) => {
+            align(buf, 4, 0);
+            let pos = buf.len();
+            buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            buf.extend_from_slice(s.as_bytes());
+            buf.push(0);
+            pos
+        }
+        Fb::Tables(items) => {
+            align(buf, 4, 0);
+            let pos = buf.len();
+            buf.extend_from_slice(&(items.len() as u32).to_le_bytes());
+            buf.resize(buf.len() + 4 * items.len(), 0);
+            for (i, item) in items.iter().enumerate() {
+                write_child(buf, pos + 4 + 4 * i, item);
+            }
+            pos
+        }
+        Fb::Structs(size, bytes) => {
+            align(buf, 8, 4); // So the structs after the length are aligned
+            let pos = buf.len();
+            buf.extend_from_slice(&((bytes.len() / size) as u32).to_le_bytes());
+            buf.extend_from_slice(bytes);
+            pos
+        }
+        Fb::Bool(_) | Fb::U8(_) | Fb::I16(_) | Fb::I32(_) | Fb::I64(_) => {
+            unreachable!("scalars are only stored inside of tables")
+        }
+    }
+}
+
+fn write_table(buf: &mut Vec<u8>, fields: &[Option<Fb>]) -> usize {
+    align(buf, 2, 0);
+    let vtable = buf.len();
+    buf.resize(vtable + 4 + 2 * fields.len(), 0);
+    align(buf, 8, 4); // So 8 byte fields after the vtable offset are aligned
+    let table = buf.len();
+    buf.extend_from_slice(&((table - vtable) as i32).to_le_bytes());
+
+    // Largest fields first to keep them aligned
+    let mut order: Vec<(usize, &Fb)> = fields
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| Some((i, x.as_ref()?)))
+        .collect();
+    order.sort_by_key(|(_, x)| std::cmp::Reverse(x.inline_size()));
+    let mut children = vec![];
+    for (i, field) in order {
+        align(buf, field.inline_size(), 0);
+        let field_offset = (buf.len() - table) as u16;
+        buf[vtable + 4 + 2 * i..vtable + 6 + 2 * i].copy_from_slice(&field_offset.to_le_bytes());
+        match field {
+            Fb::Bool(x) => buf.push(u8::from(*x)),
+            Fb::U8(x) => buf.push(*x),
+            Fb::I16(x) => buf.extend_from_slice(&x.to_le_bytes()),
+            Fb::I32(x) => buf.extend_from_slice(&x.to_le_bytes()),
+            Fb::I64(x) => buf.extend_from_slice(&x.to_le_bytes()),
+            Fb::Table(_) | Fb::String(_) | Fb::Tables(_) | Fb::Structs(..) => {
+                children.push((buf.len(), field));
+                buf.extend_from_slice(&[0; 4]);
+            }
+        }
+    }
+    let vtable_len = (4 + 2 * fields.len()) as u16;
+    let table_len = (buf.len() - table) as u16;
+    buf[vtable..vtable + 2].copy_from_slice(&vtable_len.to_le_bytes());
+    buf[vtable + 2..vtable + 4].copy_from_slice(&table_len.to_le_bytes());
+
+    for (slot, child) in children {
+        write_child(buf, slot, child);
+    }
+    table
+}
+
+/// A table in a flatbuffer being read
+struct FbTable<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    vtable: usize,
+    vtable_len: usize,
+}
+
+impl<'a> FbTable<'a> {
+    fn root(buf: &'a [u8]) -> anyhow::Result<Self> {
+        Self::at(buf, read_u32(buf, 0)? as usize)
+    }
+
+    fn at(buf: &'a [u8], pos: usize) -> anyhow::Result<Self> {
+        let soffset = i32::from_le_bytes(read_bytes(buf, pos)?);
+        let vtable = usize::try_from(pos as i64 - i64::from(soffset))
+            .context("invalid flatbuffer vtable offset")?;
+        let vtable_len = u16::from_le_bytes(read_bytes(buf, vtable)?) as usize;
+        Ok(Self {
+            buf,
+            pos,
+            vtable,
+            vtable_len,
+        })
+    }
+
+    /// Returns the position of the field or `None` if it is not present
+    fn field_pos(&self, id: usize) -> anyhow::Result<Option<usize>> {
+        let entry = 4 + 2 * id;
+        if entry + 2 > self.vtable_len {
+            return Ok(None);
+        }
+        let offset = u16::from_le_bytes(read_bytes(self.buf, self.vtable + entry)?);
+        Ok((offset != 0).then_some(self.pos + offset as usize))
+    }
+
+    /// Reads a scalar field (Fields that are not present have a default value of 0)
+    fn scalar<const N: usize>(&self, id: usize) -> anyhow::Result<[u8; N]> {
+        match self.field_pos(id)? {
+            Some(pos) => read_bytes(self.buf, pos),
+            None => Ok([0; N]),
+        }
+    }
+
+    fn bool(&self, id: usize) -> anyhow::Result<bool> {
+        Ok(self.u8(id)? != 0)
+    }
+
+    fn u8(&self, id: usize) -> anyhow::Result<u8> {
+        self.scalar(id).map(u8::from_le_bytes)
+    }
+
+    fn i16(&self, id: usize) -> anyhow::Result<i16> {
+        self.scalar(id).map(i16::from_le_bytes)
+    }
+
+    fn i32(&self, id: usize) -> anyhow::Result<i32> {
+        self.scalar(id).map(i32::from_le_bytes)
+    }
+
+    fn i64(&self, id: usize) -> anyhow::Result<i64> {
+        self.scalar(id).map(i64::from_le_bytes)
+    }
+
+    /// Returns the position that the offset stored in the field points to
+    fn follow(&self, id: usize) -> anyhow::Result<Option<usize>> {
+        let Some(pos) = self.field_pos(id)? else {
+            return Ok(None);
+        };
+        Ok(Some(pos + read_u32(self.buf, pos)? as usize))
+    }
+
+    fn table(&self, id: usize) -> anyhow::Result<Option<FbTable<'a>>> {
+        self.follow(id)?
+            .map(|pos| Self::at(self.buf, pos))
+            .transpose()
+    }
+
+    fn string(&self, id: usize) -> anyhow::Result<Option<&'a str>> {
+        let Some(pos) = self.follow(id)? else {
+            return Ok(None);
+        };
+        let len = read_u32(self.buf, pos)? as usize;
+        let bytes = self
+            .buf
+            .get(pos + 4..pos + 4 + len)
+            .context("flatbuffer string out of bounds")?;
+        std::str::from_utf8(bytes)
+            .map(Some)
+            .context("flatbuffer string is not valid UTF-8")
+    }
+
+    fn tables(&self, id: usize) -> anyhow::Result<Vec<FbTable<'a>>> {
+        let Some(pos) = self.follow(id)? else {
+            return Ok(vec![]);
+        };
+        let len = read_u32(self.buf, pos)? as usize;
+        (0..len)
+            .map(|i| {
+                let slot = pos + 4 + 4 * i;
+                Self::at(self.buf, slot + read_u32(self.buf, slot)? as usize)
+            })
+            .collect()
+    }
+
+    fn structs(&self, id: usize, size: usize) -> anyhow::Result<Vec<&'a [u8]>> {
+        let Some(pos) = self.follow(id)? else {
+            return Ok(vec![]);
+        };
+        let len = read_u32(self.buf, pos)? as usize;
+        let bytes = self
+            .buf
+            .get(pos + 4..pos + 4 + len * size)
+            .context("flatbuffer vector out of bounds")?;
+        Ok(bytes.chunks_exact(size).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::app::data_definition::tests::generate_data_points;
+
+    #[test]
+    fn round_trip() {
+        let expected = generate_data_points();
+        let actual = read(&write(&expected)).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn reads_stream_format() {
+        let expected = generate_data_points();
+        let file = write(&expected);
+        // The stream format is the file without the magic number and footer
+        let actual = read(&file[8..]).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn value_count_too_large_for_buffer() {
+        let bytes = [0; 16];
+        let double = ColumnType::Float {
+            precision: PRECISION_DOUBLE,
+        };
+        assert!(to_f64s(double, &bytes, usize::MAX).is_err());
+        assert!(to_f64s(ColumnType::Bool, &bytes, usize::MAX).is_err());
+        assert_eq!(to_f64s(double, &bytes, 2).unwrap(), [0.0, 0.0]);
+    }
+}
@@ -0,0 +1,68 @@
+/// Optional limits on where new points can be added by clicking
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Default, Debug, Clone, Copy)]
+pub struct NewPointBounds {
+    pub x0_min: Option<f64>,
+    pub x0_max: Option<f64>,
+    pub x1_min: Option<f64>,
+    pub x1_max: Option<f64>,
+    /// If points outside should be moved to the nearest allowed position instead of being rejected
+    pub should_clamp: bool,
+}
+
+/// Result of checking a position against the bounds
+#[derive(Debug, PartialEq)]
+pub enum BoundsCheck {
+    Inside,
+    Clamped { x0: f64, x1: f64 },
+    Rejected,
+}
+
+impl NewPointBounds {
+    pub fn is_enabled(&self) -> bool {
+        self.x0_min.is_some()
+            || self.x0_max.is_some()
+            || self.x1_min.is_some()
+            || self.x1_max.is_some()
+    }
+
+    pub fn check(&self, x0: f64, x1: f64) -> BoundsCheck {
+        let clamped_x0 = clamp(x0, self.x0_min, self.x0_max);
+        let clamped_x1 = clamp(x1, self.x1_min, self.x1_max);
+        if clamped_x0 == x0 && clamped_x1 == x1 {
+            BoundsCheck::Inside
+        } else if self.should_clamp {
+            BoundsCheck::Clamped {
+                x0: clamped_x0,
+                x1: clamped_x1,
+            }
+        } else {
+            BoundsCheck::Rejected
+        }
+    }
+}
+
+fn clamp(value: f64, min: Option<f64>, max: Option<f64>) -> f64 {
+    let value = min.map_or(value, |min| value.max(min));
+    max.map_or(value, |max| value.min(max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_against_partial_bounds() {
+        let mut bounds = NewPointBounds {
+            x0_min: Some(0.0),
+            x1_max: Some(10.0),
+            ..Default::default()
+        };
+        assert_eq!(bounds.check(5.0, -100.0), BoundsCheck::Inside);
+        assert_eq!(bounds.check(-1.0, 5.0), BoundsCheck::Rejected);
+        bounds.should_clamp = true;
+        assert_eq!(
+            bounds.check(-1.0, 11.0),
+            BoundsCheck::Clamped { x0: 0.0, x1: 10.0 }
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use crate::app::operational_state::LoadProgress;
+
+/// Number of records read between updates of the progress
+const RECORDS_PER_CHUNK: usize = 10_000;
+
+/// Collects the records returned by `read_next` until it returns `None`, updating the progress
+/// after each chunk of records. Shared by the formats that are read one record at a time.
+///
+/// `read_next` returns the record and the number of bytes of the input consumed so far
+pub fn read_chunked<T>(
+    total_bytes: usize,
+    progress: &LoadProgress,
+    mut read_next: impl FnMut() -> anyhow::Result<Option<(T, usize)>>,
+) -> anyhow::Result<Vec<T>> {
+    progress.set_total(total_bytes);
+    let mut result = vec![];
+    loop {
+        let mut bytes_read = 0;
+        for _ in 0..RECORDS_PER_CHUNK {
+            let Some((record, position)) = read_next()? else {
+                progress.set_processed(total_bytes);
+                return Ok(result);
+            };
+            result.push(record);
+            bytes_read = position;
+        }
+        progress.set_processed(bytes_read);
+    }
+}
@@ -0,0 +1,45 @@
+use sha1::{Digest as _, Sha1};
+
+use super::DataPoint;
+
+/// Returns a hex encoded SHA-1 hash of the coordinates and labels of the points in order.
+///
+/// Groups and other metadata are not included so the hash stays the same for any file format the
+/// points are saved in
+pub fn content_hash(points: &[DataPoint]) -> String {
+    let mut hasher = Sha1::new();
+    for point in points {
+        hasher.update(point.x0.to_le_bytes());
+        hasher.update(point.x1.to_le_bytes());
+        hasher.update([point.label.as_int()]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::{tests::generate_data_points, DataLabel};
+
+    use super::*;
+
+    #[test]
+    fn hash_changes_with_content() {
+        let mut points = generate_data_points();
+        let expected = content_hash(&points);
+        assert_eq!(content_hash(&points), expected);
+        assert_eq!(expected.len(), 40);
+
+        points[0].label = points[0].label.opposite();
+        assert_ne!(content_hash(&points), expected);
+        points[0].label = points[0].label.opposite();
+        points[0].group = None;
+        assert_eq!(content_hash(&points), expected);
+
+        points.push(DataPoint::new(0.0, 0.0, DataLabel::Normal));
+        assert_ne!(content_hash(&points), expected);
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Context};
+
+use crate::app::operational_state::LoadProgress;
+
+use super::{chunked_reader::read_chunked, DataLabel, DataPoint, DataPoints, PendingMatLoad};
+
+/// Mappings selected by the user keyed on the layout of the CSV file they were selected for
+pub type CsvMappings = HashMap<String, CsvColumnMapping>;
+
+/// Specifies which columns of a CSV file hold each of the fields of a [`DataPoint`]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct CsvColumnMapping {
+    pub has_headers: bool,
+    pub x0: usize,
+    pub x1: usize,
+    pub label: usize,
+}
+
+/// Outcome of reading a file that may need the user to say how to interpret it
+pub enum CsvLoad {
+    Loaded(DataPoints),
+    NeedsMapping(PendingCsvLoad),
+    /// Only for MAT-files but shares this type so all loads return the same thing
+    NeedsMatVariables(PendingMatLoad),
+}
+
+/// A CSV file that has been read but cannot be converted without the user selecting the columns to use
+#[derive(Debug, PartialEq)]
+pub struct PendingCsvLoad {
+    records: Vec<csv::StringRecord>,
+    /// The mapping that will be used to do the conversion (Starts as a best guess)
+    pub mapping: CsvColumnMapping,
+}
+
+impl CsvColumnMapping {
+    /// Headers used when saving to CSV
+    const NATIVE_HEADERS: [&'static str; 3] = ["x0", "x1", "label"];
+
+    fn native() -> Self {
+        Self {
+            has_headers: true,
+            x0: 0,
+            x1: 1,
+            label: 2,
+        }
+    }
+
+    /// Converts the records into points using this mapping
+    fn apply(&self, records: &[csv::StringRecord]) -> anyhow::Result<DataPoints> {
+        let skip = if self.has_headers { 1 } else { 0 };
+        let mut result = Vec::with_capacity(records.len().saturating_sub(skip));
+        for (i, record) in records.iter().enumerate().skip(skip) {
+            let get_field = |column: usize| {
+                record.get(column).with_context(|| {
+                    format!("row {} does not have a column at index {column}", i + 1)
+                })
+            };
+            let x0 = parse_coordinate(get_field(self.x0)?)
+                .with_context(|| format!("invalid x0 on row {}", i + 1))?;
+            let x1 = parse_coordinate(get_field(self.x1)?)
+                .with_context(|| format!("invalid x1 on row {}", i + 1))?;
+            let label = parse_label(get_field(self.label)?)
+                .with_context(|| format!("invalid label on row {}", i + 1))?;
+            result.push(DataPoint::new(x0, x1, label));
+        }
+        Ok(result)
+    }
+}
+
+impl PendingCsvLoad {
+    /// Key used to identify files with the same layout
+    pub fn layout_key(&self) -> String {
+        layout_key(&self.records)
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.records.first().map(|x| x.len()).unwrap_or_default()
+    }
+
+    /// Returns the names to show the user for each column
+    pub fn column_names(&self) -> Vec<String> {
+        (0..self.column_count())
+            .map(|i| match self.records.first().and_then(|x| x.get(i)) {
+                Some(header) if self.mapping.has_headers => format!("{i}: {header}"),
+                _ => format!("Column {i}"),
+            })
+            .collect()
+    }
+
+    /// Returns up to `count` rows of data (excluding the header if it is present)
+    pub fn preview_rows(&self, count: usize) -> impl Iterator<Item = &csv::StringRecord> {
+        let skip = if self.mapping.has_headers { 1 } else { 0 };
+        self.records.iter().skip(skip).take(count)
+    }
+
+    /// Converts the records using the current mapping
+    pub fn load(&self) -> anyhow::Result<DataPoints> {
+        self.mapping.apply(&self.records)
+    }
+}
+
+/// Reads the CSV text and converts it into points if the layout is known
+///
+/// Progress is reported in bytes of `text` parsed
+pub fn read_csv(
+    text: &[u8],
+    mappings: &CsvMappings,
+    progress: &LoadProgress,
+) -> anyhow::Result<CsvLoad> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text);
+    let mut record = csv::StringRecord::new();
+    let records = read_chunked(text.len(), progress, || {
+        let has_record = reader
+            .read_record(&mut record)
+            .context("failed to read CSV record")?;
+        Ok(has_record.then(|| (record.clone(), reader.position().byte() as usize)))
+    })?;
+
+    let Some(first) = records.first() else {
+        return Ok(CsvLoad::Loaded(vec![]));
+    };
+    if first.len() < 3 {
+        bail!(
+            "at least 3 columns are required (x0, x1 and label) but found {}",
+            first.len()
+        );
+    }
+
+    if *first == CsvColumnMapping::NATIVE_HEADERS[..] {
+        return CsvColumnMapping::native()
+            .apply(&records)
+            .map(CsvLoad::Loaded);
+    }
+
+    if let Some(mapping) = mappings.get(&layout_key(&records)) {
+        return mapping.apply(&records).map(CsvLoad::Loaded);
+    }
+
+    let mapping = guess_mapping(first);
+    Ok(CsvLoad::NeedsMapping(PendingCsvLoad { records, mapping }))
+}
+
+/// A row is assumed to be a header row if none of its fields are numeric
+fn is_header_row(record: &csv::StringRecord) -> bool {
+    record.iter().all(|field| field.parse::<f64>().is_err())
+}
+
+fn layout_key(records: &[csv::StringRecord]) -> String {
+    match records.first() {
+        Some(first) if is_header_row(first) => first.iter().collect::<Vec<_>>().join(","),
+        Some(first) => format!("[{} unnamed columns]", first.len()),
+        None => String::new(),
+    }
+}
+
+/// Attempts to find the columns based on common header names and falls back to using the first
+/// two columns as coordinates and the last column as the label
+fn guess_mapping(first: &csv::StringRecord) -> CsvColumnMapping {
+    let has_headers = is_header_row(first);
+    let last = first.len() - 1;
+    let mut result = CsvColumnMapping {
+        has_headers,
+        x0: 0,
+        x1: 1,
+        label: last,
+    };
+    if has_headers {
+        let find = |names: &[&str]| {
+            first
+                .iter()
+                .position(|header| names.iter().any(|x| header.eq_ignore_ascii_case(x)))
+        };
+        if let Some(i) = find(&["x0", "x"]) {
+            result.x0 = i;
+        }
+        if let Some(i) = find(&["x1", "y"]) {
+            result.x1 = i;
+        }
+        if let Some(i) = find(&["label", "class", "target", "anomaly", "outlier"]) {
+            result.label = i;
+        }
+    }
+    result
+}
+
+fn parse_coordinate(field: &str) -> anyhow::Result<f64> {
+    field
+        .parse()
+        .with_context(|| format!("unable to convert {field:?} to a number"))
+}
+
+/// Accepts the numeric representation as well as the display text of the labels
+pub(super) fn parse_label(field: &str) -> anyhow::Result<DataLabel> {
+    if let Ok(value) = field.parse::<f64>() {
+        return DataLabel::try_from_f64(value);
+    }
+    match field.to_ascii_lowercase().as_str() {
+        "n" | "normal" | "false" => Ok(DataLabel::Normal),
+        "a" | "anomaly" | "anom" | "outlier" | "true" => Ok(DataLabel::Anomaly),
+        _ => bail!("unable to convert {field:?} to a label. Expected a number like 0 or 1"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn native_layout_loads_without_mapping() {
+        let text = b"x0,x1,label\n1.0,2.0,0\n3.5,4.0,1\n";
+        let CsvLoad::Loaded(actual) =
+            read_csv(text, &Default::default(), &Default::default()).unwrap()
+        else {
+            panic!("native layout should not need a mapping");
+        };
+        assert_eq!(
+            actual,
+            vec![
+                DataPoint::new(1.0, 2.0, DataLabel::Normal),
+                DataPoint::new(3.5, 4.0, DataLabel::Anomaly)
+            ]
+        );
+    }
+
+    #[test]
+    fn extra_columns_need_mapping_until_stored() {
+        let text = b"id,class,x,y\n7,A,1.0,2.0\n";
+        let CsvLoad::NeedsMapping(pending) =
+            read_csv(text, &Default::default(), &Default::default()).unwrap()
+        else {
+            panic!("unknown layout should need a mapping");
+        };
+        let expected_mapping = CsvColumnMapping {
+            has_headers: true,
+            x0: 2,
+            x1: 3,
+            label: 1,
+        };
+        assert_eq!(pending.mapping, expected_mapping);
+        let expected_points = vec![DataPoint::new(1.0, 2.0, DataLabel::Anomaly)];
+        assert_eq!(pending.load().unwrap(), expected_points);
+
+        let mappings = CsvMappings::from([(pending.layout_key(), pending.mapping)]);
+        let CsvLoad::Loaded(actual) = read_csv(text, &mappings, &Default::default()).unwrap()
+        else {
+            panic!("stored mapping should be used");
+        };
+        assert_eq!(actual, expected_points);
+    }
+}
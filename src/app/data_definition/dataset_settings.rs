@@ -0,0 +1,15 @@
+use crate::app::plot_zoom_reset::MinMaxPair;
+
+/// Settings that only make sense for the dataset they were set on. They are stored with the data
+/// so that they are saved in the project file and replaced when another project is loaded.
+/// (Rounding of new points and their bounds are also per dataset but predate this struct)
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Default, Debug)]
+#[serde(default)]
+pub struct DatasetSettings {
+    /// Names shown on the plot axes for x0 and x1 (Empty means no name is shown)
+    pub axis_labels: [String; 2],
+    /// The part of the plot to show when the project is loaded instead of fitting all the points
+    pub default_view: Option<MinMaxPair>,
+    /// Used by every tool that uses random numbers so results can be reproduced
+    pub seed: u64,
+}
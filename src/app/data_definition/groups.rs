@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use ecolor::Color32;
+
+/// Identifies a group. Ids are never reused so the history cannot confuse a deleted group with a new one
+pub type GroupId = u16;
+
+/// A named set of points independent of their label
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Debug)]
+pub struct PointGroup {
+    pub name: String,
+    pub color: Color32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Default, Debug)]
+pub struct PointGroups {
+    groups: BTreeMap<GroupId, PointGroup>,
+    /// Id given to the next group created (Only increases)
+    #[serde(default)]
+    next_id: GroupId,
+}
+
+impl PointGroups {
+    /// Colors assigned to new groups in rotation
+    const PALETTE: [Color32; 6] = [
+        Color32::from_rgb(230, 25, 75),
+        Color32::from_rgb(60, 180, 75),
+        Color32::from_rgb(145, 30, 180),
+        Color32::from_rgb(70, 240, 240),
+        Color32::from_rgb(240, 50, 230),
+        Color32::from_rgb(128, 128, 0),
+    ];
+
+    /// Creates a new group and returns its id
+    pub fn add(&mut self, name: String) -> anyhow::Result<GroupId> {
+        // Groups saved before `next_id` existed only have the ids in use to go by
+        let id = match self.groups.last_key_value() {
+            Some((last_id, _)) => self.next_id.max(
+                last_id
+                    .checked_add(1)
+                    .context("no more group ids are available")?,
+            ),
+            None => self.next_id,
+        };
+        self.next_id = id
+            .checked_add(1)
+            .context("no more group ids are available")?;
+        let color = Self::PALETTE[id as usize % Self::PALETTE.len()];
+        self.groups.insert(id, PointGroup { name, color });
+        Ok(id)
+    }
+
+    /// Removes the definition of the group (Does not change the points)
+    pub(super) fn remove(&mut self, id: GroupId) -> Option<PointGroup> {
+        self.groups.remove(&id)
+    }
+
+    /// Puts back a definition taken by [`Self::remove`]
+    pub(super) fn restore(&mut self, id: GroupId, group: PointGroup) {
+        debug_assert!(
+            id < self.next_id,
+            "only ids that were given out can be restored"
+        );
+        self.groups.insert(id, group);
+    }
+
+    pub fn get(&self, id: GroupId) -> Option<&PointGroup> {
+        self.groups.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: GroupId) -> Option<&mut PointGroup> {
+        self.groups.get_mut(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (GroupId, &PointGroup)> {
+        self.groups.iter().map(|(id, group)| (*id, group))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
@@ -0,0 +1,110 @@
+use crate::app::status_msg::StatusMsg;
+
+use super::{Data, DataPoint};
+
+/// A copy of the points and their history that can be stepped through to show how the points
+/// were built (Starts before the oldest change kept in the history)
+#[derive(PartialEq)]
+pub struct HistoryReplay {
+    /// Only the points and history are used
+    scratch: Data,
+    step: usize,
+    step_count: usize,
+}
+
+impl HistoryReplay {
+    pub fn new(data: &Data) -> Self {
+        let mut scratch = Data {
+            points: data.points.clone(),
+            undo_manager: data.undo_manager.clone(),
+            ..Default::default()
+        };
+        // Only changes that can be undone are replayed
+        scratch.undo_manager.clear_redo();
+        let step_count = scratch.undo_manager.undo_count();
+        let mut result = Self {
+            scratch,
+            step: step_count,
+            step_count,
+        };
+        result.seek(0);
+        result
+    }
+
+    pub fn points(&self) -> &[DataPoint] {
+        &self.scratch.points
+    }
+
+    /// Number of changes applied so far
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn step_count(&self) -> usize {
+        self.step_count
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.step == self.step_count
+    }
+
+    /// Description of the last change applied
+    pub fn last_change(&self) -> Option<String> {
+        (self.step > 0)
+            .then(|| self.scratch.undo_manager.peek_undo())
+            .flatten()
+            .map(|event| event.to_string())
+    }
+
+    /// Applies or undoes changes until `step` of them are applied
+    pub fn seek(&mut self, step: usize) {
+        let step = step.min(self.step_count);
+        // Messages are only for the "No history" cases that the bounds prevent
+        let mut status_msg = StatusMsg::default();
+        while self.step < step {
+            self.scratch.redo(&mut status_msg);
+            self.step += 1;
+        }
+        while self.step > step {
+            self.scratch.undo(&mut status_msg);
+            self.step -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui_plot::PlotPoint;
+
+    use super::*;
+    use crate::app::data_definition::DataLabel;
+
+    #[test]
+    fn replay_steps_through_changes() {
+        let mut data = Data::default();
+        let mut status_msg = StatusMsg::default();
+        data.add(
+            Some(PlotPoint::new(1.0, 1.0)),
+            DataLabel::Normal,
+            &mut status_msg,
+        );
+        data.add(
+            Some(PlotPoint::new(2.0, 2.0)),
+            DataLabel::Anomaly,
+            &mut status_msg,
+        );
+        data.delete_by_index(0);
+
+        let mut replay = HistoryReplay::new(&data);
+        assert_eq!(replay.step_count(), 3);
+        assert!(replay.points().is_empty());
+        assert!(replay.last_change().is_none());
+        replay.seek(2);
+        assert_eq!(replay.points().len(), 2);
+        assert!(replay.last_change().is_some());
+        replay.seek(10);
+        assert!(replay.is_at_end());
+        assert_eq!(replay.points(), data.points());
+        assert_eq!(data.points().len(), 1, "original is not changed");
+    }
+}
@@ -0,0 +1,129 @@
+//! JSON Lines files where each line is an object for one point e.g. `{"x0": 1.5, "x1": -2, "label": 0}`.
+//!
+//! Other keys are ignored so extra fields (like timestamps) can be included by the program that
+//! produced the file.
+
+use anyhow::{bail, Context};
+use serde_json::Value;
+
+use crate::app::operational_state::LoadProgress;
+
+use super::{
+    chunked_reader::read_chunked, csv_mapping::parse_label, DataLabel, DataPoint, DataPoints,
+};
+
+/// Writes one object per point with the same field names used for CSV files
+pub fn write_jsonl(points: &[DataPoint]) -> Vec<u8> {
+    let mut result = String::new();
+    for point in points {
+        result.push_str(&format!(
+            "{{\"x0\":{:?},\"x1\":{:?},\"label\":{}}}\n",
+            point.x0,
+            point.x1,
+            point.label.as_int()
+        ));
+    }
+    result.into_bytes()
+}
+
+/// Reads the points from the lines of the text (Blank lines are skipped)
+pub fn read_jsonl(text: &[u8], progress: &LoadProgress) -> anyhow::Result<DataPoints> {
+    let text = std::str::from_utf8(text).context("JSON Lines file is not valid UTF-8")?;
+    let mut lines = text.split_inclusive('\n').enumerate();
+    let mut position = 0;
+    read_chunked(text.len(), progress, || {
+        for (i, line) in lines.by_ref() {
+            position += line.len();
+            if line.trim().is_empty() {
+                continue;
+            }
+            let point = parse_point(line).with_context(|| format!("invalid line {}", i + 1))?;
+            return Ok(Some((point, position)));
+        }
+        Ok(None)
+    })
+}
+
+fn parse_point(line: &str) -> anyhow::Result<DataPoint> {
+    let object: serde_json::Map<String, Value> =
+        serde_json::from_str(line).context("expected a JSON object")?;
+    let number = |key: &str| match object.get(key) {
+        Some(value) => value
+            .as_f64()
+            .with_context(|| format!("{key} must be a number")),
+        None => bail!("missing {key}"),
+    };
+    let (x0, x1) = (number("x0")?, number("x1")?);
+    let label = match object.get("label").context("missing label")? {
+        Value::Number(x) => DataLabel::try_from_f64(x.as_f64().context("label is out of range")?)?,
+        Value::String(x) => parse_label(x)?,
+        Value::Bool(x) => parse_label(&x.to_string())?,
+        Value::Null => bail!("label must not be null"),
+        Value::Array(_) | Value::Object(_) => bail!("label must be a number, text or a boolean"),
+    };
+    Ok(DataPoint::new(x0, x1, label))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::app::data_definition::tests::generate_data_points;
+
+    #[test]
+    fn round_trip() {
+        let expected = generate_data_points();
+        let actual = read_jsonl(&write_jsonl(&expected), &Default::default()).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn accepts_extra_fields_and_label_text() {
+        let text = br#"{"time": "2024-01-01T00:00:00", "x0": 1, "x1": -2.5e0, "label": "anomaly", "ok": true}
+
+{ "label" : false , "x1" : 3 , "x0" : 4 }
+"#;
+        let actual = read_jsonl(text, &Default::default()).unwrap();
+        assert_eq!(
+            actual,
+            vec![
+                DataPoint::new(1.0, -2.5, DataLabel::Anomaly),
+                DataPoint::new(4.0, 3.0, DataLabel::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_line_of_error() {
+        let text = b"{\"x0\": 1, \"x1\": 2, \"label\": 0}\n{\"x0\": 1, \"label\": 0}\n";
+        let error = read_jsonl(text, &Default::default()).unwrap_err();
+        assert_eq!(format!("{error:#}"), "invalid line 2: missing x1");
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        let text = br#"{"x0": 1, "x1": 2, "label": "\u0061"}"#;
+        let actual = read_jsonl(text, &Default::default()).unwrap();
+        assert_eq!(actual, vec![DataPoint::new(1.0, 2.0, DataLabel::Anomaly)]);
+
+        // Surrogate pairs are combined into one character
+        let text = br#"{"x0": 1, "x1": 2, "label": "\ud83d\ude00"}"#;
+        let error = read_jsonl(text, &Default::default()).unwrap_err();
+        assert!(format!("{error:#}").contains("\"\u{1f600}\""), "{error:#}");
+
+        let text = br#"{"x0": 1, "x1": 2, "label": "\u61"}"#;
+        assert!(read_jsonl(text, &Default::default()).is_err());
+    }
+
+    #[test]
+    fn rejects_numbers_that_are_not_json() {
+        for x0 in ["inf", "NaN", "+1", "-inf", "1."] {
+            let text = format!(r#"{{"x0": {x0}, "x1": 2, "label": 0}}"#);
+            assert!(
+                read_jsonl(text.as_bytes(), &Default::default()).is_err(),
+                "{x0} was accepted"
+            );
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use std::io::Read as _;
+
+use anyhow::{bail, Context};
+
+use super::{DataLabel, DataPoint, DataPoints, MatVariableNames};
+
+// Data types used in the tags of data elements (See MAT-File Format documentation)
+const MI_INT8: u32 = 1;
+const MI_UINT8: u32 = 2;
+const MI_INT16: u32 = 3;
+const MI_UINT16: u32 = 4;
+const MI_INT32: u32 = 5;
+const MI_UINT32: u32 = 6;
+const MI_SINGLE: u32 = 7;
+const MI_DOUBLE: u32 = 9;
+const MI_INT64: u32 = 12;
+const MI_UINT64: u32 = 13;
+const MI_MATRIX: u32 = 14;
+const MI_COMPRESSED: u32 = 15;
+
+// Array classes
+const MX_DOUBLE_CLASS: u8 = 6;
+const MX_UINT8_CLASS: u8 = 9;
+const MX_UINT64_CLASS: u8 = 15;
+
+const HEADER_LEN: usize = 128;
+const COMPLEX_FLAG: u32 = 0x0800;
+
+/// A numeric variable read from a MAT file with its values converted to f64 (Column major order)
+#[derive(Debug, PartialEq)]
+struct Variable {
+    name: String,
+    dims: Vec<usize>,
+    values: Vec<f64>,
+}
+
+/// Writes the points as a Level 5 MAT-file with the points (n x 2 double) and labels (n x 1 uint8)
+/// the same as the native version does
+pub fn write(points: &[DataPoint], names: &MatVariableNames) -> Vec<u8> {
+    let mut result = header();
+    let n = points.len();
+    let mut x = Vec::with_capacity(n * 2 * 8);
+    for value in points
+        .iter()
+        .map(|p| p.x0)
+        .chain(points.iter().map(|p| p.x1))
+    {
+        x.extend_from_slice(&value.to_le_bytes());
+    }
+    write_matrix(
+        &mut result,
+        &names.points,
+        MX_DOUBLE_CLASS,
+        MI_DOUBLE,
+        [n, 2],
+        &x,
+    );
+    let y: Vec<u8> = points.iter().map(|p| p.label.as_int()).collect();
+    write_matrix(
+        &mut result,
+        &names.labels,
+        MX_UINT8_CLASS,
+        MI_UINT8,
+        [n, 1],
+        &y,
+    );
+    result
+}
+
+/// Reads the points and labels variables from a Level 5 MAT-file (Compressed or uncompressed)
+pub fn read(bytes: &[u8], names: &MatVariableNames) -> anyhow::Result<DataPoints> {
+    let variables = read_variables(bytes)?;
+    let x = find_variable(&variables, &names.points)?;
+    let y = find_variable(&variables, &names.labels)?;
+    if x.dims.len() != 2 || x.dims[1] != 2 {
+        bail!(
+            "expected {:?} to have 2 columns but dimensions were {:?}",
+            names.points,
+            x.dims
+        );
+    }
+    let n = x.dims[0];
+    if y.values.len() != n {
+        bail!(
+            "expected the same number of values in {:?} as rows in {:?}. Found {} and {n} rows",
+            names.labels,
+            names.points,
+            y.values.len()
+        );
+    }
+    y.values
+        .iter()
+        .enumerate()
+        .map(|(i, &label)| {
+            let label = DataLabel::try_from_f64(label)?;
+            Ok(DataPoint::new(x.values[i], x.values[i + n], label))
+        })
+        .collect()
+}
+
+/// Returns the names of the numeric variables in the file in the order they are stored
+pub fn variable_names(bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+    Ok(read_variables(bytes)?.into_iter().map(|x| x.name).collect())
+}
+
+fn read_variables(bytes: &[u8]) -> anyhow::Result<Vec<Variable>> {
+    if bytes.len() < HEADER_LEN {
+        bail!("file too short to be a MAT-file");
+    }
+    match &bytes[126..128] {
+        b"IM" => {}
+        b"MI" => bail!("big endian MAT-files are not supported"),
+        _ => bail!("not a Level 5 MAT-file (Files saved with -v7.3 are not supported)"),
+    }
+    let mut variables = vec![];
+    read_elements(&bytes[HEADER_LEN..], &mut variables)?;
+    Ok(variables)
+}
+
+fn find_variable<'a>(variables: &'a [Variable], name: &str) -> anyhow::Result<&'a Variable> {
+    variables
+        .iter()
+        .find(|x| x.name == name)
+        .with_context(|| format!("variable {name:?} not found in MAT-file"))
+}
+
+fn header() -> Vec<u8> {
+    // Uses web_time because `chrono::Local::now` is not available in WASM
+    let time_stamp = web_time::SystemTime::UNIX_EPOCH
+        .elapsed()
+        .expect("expected date on system to be after the epoch")
+        .as_secs();
+    let created_on = chrono::DateTime::from_timestamp(time_stamp as i64, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local)
+        .format("%a %b %e %H:%M:%S %Y");
+    let text = format!("MATLAB 5.0 MAT-file, Platform: dbv, Created on: {created_on}");
+    let mut result = text.into_bytes();
+    result.resize(116, b' ');
+    result.extend_from_slice(&[0; 8]); // Subsystem data offset (none)
+    result.extend_from_slice(&0x0100u16.to_le_bytes()); // Version
+    result.extend_from_slice(b"IM"); // Endian indicator
+    debug_assert_eq!(result.len(), HEADER_LEN);
+    result
+}
+
+fn padding_for(len: usize) -> usize {
+    (8 - len % 8) % 8
+}
+
+/// Appends a data element (Uses the small element format if the data fits in 4 bytes)
+fn write_element(out: &mut Vec<u8>, data_type: u32, data: &[u8]) {
+    if data.len() <= 4 {
+        out.extend_from_slice(&((data.len() as u32) << 16 | data_type).to_le_bytes());
+        out.extend_from_slice(data);
+        out.resize(out.len() + 4 - data.len(), 0);
+    } else {
+        out.extend_from_slice(&data_type.to_le_bytes());
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+        out.resize(out.len() + padding_for(data.len()), 0);
+    }
+}
+
+fn write_matrix(
+    out: &mut Vec<u8>,
+    name: &str,
+    class: u8,
+    data_type: u32,
+    dims: [usize; 2],
+    data: &[u8],
+) {
+    let mut body = vec![];
+    let flags = [u32::from(class), 0];
+    write_element(
+        &mut body,
+        MI_UINT32,
+        &flags
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>(),
+    );
+    write_element(
+        &mut body,
+        MI_INT32,
+        &dims
+            .iter()
+            .flat_map(|&x| (x as i32).to_le_bytes())
+            .collect::<Vec<_>>(),
+    );
+    write_element(&mut body, MI_INT8, name.as_bytes());
+    write_element(&mut body, data_type, data);
+    out.extend_from_slice(&MI_MATRIX.to_le_bytes());
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend_from_slice(&body);
+}
+
+/// Splits off the next data element returning its type, data and the remaining bytes
+fn next_element(bytes: &[u8]) -> anyhow::Result<(u32, &[u8], &[u8])> {
+    if bytes.len() < 8 {
+        bail!("unexpected end of file while reading a data element tag");
+    }
+    let first = u32::from_le_bytes(bytes[0..4].try_into().expect("length checked above"));
+    if first >> 16 != 0 {
+        // Small data element format
+        let size = (first >> 16) as usize;
+        if size > 4 {
+            bail!("invalid small data element size {size}");
+        }
+        return Ok((first & 0xFFFF, &bytes[4..4 + size], &bytes[8..]));
+    }
+    let size = u32::from_le_bytes(bytes[4..8].try_into().expect("length checked above")) as usize;
+    let data_end = 8 + size;
+    if bytes.len() < data_end {
+        bail!("unexpected end of file while reading a data element of {size} bytes");
+    }
+    // Compressed elements are not padded
+    let next = if first == MI_COMPRESSED {
+        data_end
+    } else {
+        (data_end + padding_for(size)).min(bytes.len())
+    };
+    Ok((first, &bytes[8..data_end], &bytes[next..]))
+}
+
+fn read_elements(mut bytes: &[u8], variables: &mut Vec<Variable>) -> anyhow::Result<()> {
+    while !bytes.is_empty() {
+        let (data_type, data, rest) = next_element(bytes)?;
+        match data_type {
+            MI_COMPRESSED => {
+                let mut decompressed = vec![];
+                flate2::read::ZlibDecoder::new(data)
+                    .read_to_end(&mut decompressed)
+                    .context("failed to decompress MAT-file data element")?;
+                read_elements(&decompressed, variables)?;
+            }
+            MI_MATRIX => {
+                if let Some(variable) = read_matrix(data)? {
+                    variables.push(variable);
+                }
+            }
+            _ => {} // Not a variable, skip
+        }
+        bytes = rest;
+    }
+    Ok(())
+}
+
+/// Reads a numeric matrix. Returns `None` for other kinds of variables (cells, structs, etc.)
+fn read_matrix(data: &[u8]) -> anyhow::Result<Option<Variable>> {
+    if data.is_empty() {
+        return Ok(None); // Empty matrices can be written with no sub elements
+    }
+    let (_, flags, rest) = next_element(data).context("failed to read array flags")?;
+    if flags.len() < 4 {
+        bail!("array flags too short");
+    }
+    let flags = u32::from_le_bytes(flags[0..4].try_into().expect("length checked above"));
+    let class = (flags & 0xFF) as u8;
+    if !(MX_DOUBLE_CLASS..=MX_UINT64_CLASS).contains(&class) {
+        return Ok(None);
+    }
+    let (_, dims, rest) = next_element(rest).context("failed to read dimensions")?;
+    let dims = parse_dims(dims)?;
+    let (_, name, rest) = next_element(rest).context("failed to read array name")?;
+    let name = String::from_utf8_lossy(name).into_owned();
+    if flags & COMPLEX_FLAG != 0 {
+        bail!("complex values are not supported for variable {name:?}");
+    }
+    let (data_type, real, _) = next_element(rest)
+        .with_context(|| format!("failed to read values of variable {name:?}"))?;
+    let values = to_f64s(data_type, real)
+        .with_context(|| format!("failed to convert values of variable {name:?}"))?;
+    let expected_len = dims
+        .iter()
+        .try_fold(1usize, |acc, &x| acc.checked_mul(x))
+        .with_context(|| format!("dimensions {dims:?} of variable {name:?} are too large"))?;
+    if values.len() != expected_len {
+        bail!(
+            "variable {name:?} has {} values but dimensions {dims:?}",
+            values.len()
+        );
+    }
+    Ok(Some(Variable { name, dims, values }))
+}
+
+/// Reads the dimensions, rejecting negative values
+fn parse_dims(bytes: &[u8]) -> anyhow::Result<Vec<usize>> {
+    bytes
+        .chunks_exact(4)
+        .map(|x| {
+            let dim = i32::from_le_bytes(x.try_into().expect("chunks are 4 bytes"));
+            usize::try_from(dim).with_context(|| format!("invalid dimension {dim}"))
+        })
+        .collect()
+}
+
+/// Converts the values to f64 (MATLAB may store values in a smaller type than the array class)
+fn to_f64s(data_type: u32, bytes: &[u8]) -> anyhow::Result<Vec<f64>> {
+    macro_rules! convert {
+        ($t:ty) => {
+            bytes
+                .chunks_exact(std::mem::size_of::<$t>())
+                .map(|x| <$t>::from_le_bytes(x.try_into().expect("chunk size matches type")) as f64)
+                .collect()
+        };
+    }
+    Ok(match data_type {
+        MI_INT8 => convert!(i8),
+        MI_UINT8 => convert!(u8),
+        MI_INT16 => convert!(i16),
+        MI_UINT16 => convert!(u16),
+        MI_INT32 => convert!(i32),
+        MI_UINT32 => convert!(u32),
+        MI_SINGLE => convert!(f32),
+        MI_DOUBLE => convert!(f64),
+        MI_INT64 => convert!(i64),
+        MI_UINT64 => convert!(u64),
+        _ => bail!("unsupported data type {data_type}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::app::data_definition::tests::generate_data_points;
+
+    #[test]
+    fn round_trip() {
+        let expected = generate_data_points();
+        let names = MatVariableNames::default();
+        let actual = read(&write(&expected, &names), &names).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_negative_dims() {
+        let bytes: Vec<u8> = [2i32, -1].iter().flat_map(|x| x.to_le_bytes()).collect();
+        assert!(parse_dims(&bytes).is_err());
+    }
+
+    #[test]
+    fn reads_compressed_elements() {
+        let expected = generate_data_points();
+        let names = MatVariableNames::default();
+        let uncompressed = write(&expected, &names);
+        let mut result = uncompressed[..HEADER_LEN].to_vec();
+        let mut rest = &uncompressed[HEADER_LEN..];
+        while !rest.is_empty() {
+            let (_, data, next) = next_element(rest).unwrap();
+            let element = &rest[..data.len() + 8];
+            let mut encoder =
+                flate2::write::ZlibEncoder::new(vec![], flate2::Compression::default());
+            encoder.write_all(element).unwrap();
+            let compressed = encoder.finish().unwrap();
+            result.extend_from_slice(&MI_COMPRESSED.to_le_bytes());
+            result.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+            result.extend_from_slice(&compressed);
+            rest = next;
+        }
+        assert_eq!(read(&result, &names).unwrap(), expected);
+    }
+}
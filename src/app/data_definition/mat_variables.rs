@@ -0,0 +1,92 @@
+use super::{mat_v5, DataPoints};
+
+/// Names of the variables that hold the points and labels in MAT-files
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Debug)]
+#[serde(default)]
+pub struct MatVariableNames {
+    /// n x 2 matrix of the coordinates
+    pub points: String,
+    /// n x 1 vector of 0 (Normal), 1 (Anomaly) or the index of a user defined class
+    pub labels: String,
+}
+
+impl Default for MatVariableNames {
+    fn default() -> Self {
+        Self {
+            points: "X".to_string(),
+            labels: "y".to_string(),
+        }
+    }
+}
+
+/// A MAT-file that does not have the expected variables and needs the user to select which to use
+#[derive(Debug, PartialEq)]
+pub struct PendingMatLoad {
+    bytes: Vec<u8>,
+    /// Names of the numeric variables found in the file
+    pub available: Vec<String>,
+    /// The names that will be used to do the conversion (Starts as a best guess)
+    pub names: MatVariableNames,
+}
+
+impl PendingMatLoad {
+    /// Returns `None` if the file has the variables or the variables in the file could not be
+    /// listed (The error is left for the actual load to report)
+    pub(super) fn if_names_missing(bytes: Vec<u8>, names: &MatVariableNames) -> Option<Self> {
+        let available = mat_v5::variable_names(&bytes).ok()?;
+        let has_name = |name: &String| available.contains(name);
+        if (has_name(&names.points) && has_name(&names.labels)) || available.is_empty() {
+            return None;
+        }
+        let guess = |preferred: &String, skip: Option<&String>| {
+            if has_name(preferred) {
+                return preferred.clone();
+            }
+            available
+                .iter()
+                .find(|x| Some(*x) != skip)
+                .unwrap_or(&available[0])
+                .clone()
+        };
+        let points = guess(&names.points, None);
+        let labels = guess(&names.labels, Some(&points));
+        Some(Self {
+            bytes,
+            available,
+            names: MatVariableNames { points, labels },
+        })
+    }
+
+    /// Converts the variables using the current names
+    ///
+    /// Uses the pure Rust reader on all platforms because only files it was able to list the
+    /// variables of end up pending
+    pub fn load(&self) -> anyhow::Result<DataPoints> {
+        mat_v5::read(&self.bytes, &self.names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::app::data_definition::tests::generate_data_points;
+
+    #[test]
+    fn missing_names_need_selection() {
+        let expected = generate_data_points();
+        let saved_names = MatVariableNames {
+            points: "data".to_string(),
+            labels: "labels".to_string(),
+        };
+        let bytes = mat_v5::write(&expected, &saved_names);
+        assert!(PendingMatLoad::if_names_missing(bytes.clone(), &saved_names).is_none());
+
+        let pending = PendingMatLoad::if_names_missing(bytes, &Default::default())
+            .expect("default names are not in the file");
+        assert_eq!(pending.available, ["data", "labels"]);
+        assert_eq!(pending.names, saved_names);
+        assert_eq!(pending.load().unwrap(), expected);
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::{anyhow, bail, Context};
 use matio_rs::{Mat, MatArray, MatFile, MatioError, MayBeFrom};
 
-use super::{DataLabel, DataPoint, DataPoints};
+use super::{DataLabel, DataPoint, DataPoints, MatVariableNames};
 
 #[derive(Debug, Default)]
 pub struct MatlabData {
@@ -20,81 +20,66 @@ impl MatlabData {
         result
     }
 
-    fn X(&self) -> Result<Mat<'_>, MatioError> {
+    fn X<'a>(&'a self, name: &str) -> Result<Mat<'a>, MatioError> {
         let arr = MatArray::new(&self.x, vec![self.x.len() as u64 / 2, 2]);
-        Mat::maybe_from("X", arr)
+        Mat::maybe_from(name, arr)
     }
-    fn y(&self) -> Result<Mat<'_>, MatioError> {
+    fn y<'a>(&'a self, name: &str) -> Result<Mat<'a>, MatioError> {
         let arr = MatArray::new(&self.y, vec![self.y.len() as u64, 1]);
-        Mat::maybe_from("y", arr)
+        Mat::maybe_from(name, arr)
     }
 
-    pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> anyhow::Result<()> {
+    pub fn save_to_file<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        names: &MatVariableNames,
+    ) -> anyhow::Result<()> {
         let mat_file = matio_rs::MatFile::save(path)?;
-        mat_file.write(
-            self.X()
-                .map_err(|e| anyhow!("matlab convert X failed with error: {e}"))?,
-        );
-        mat_file.write(
-            self.y()
-                .map_err(|e| anyhow!("matlab convert y failed with error: {e}"))?,
-        );
+        mat_file
+            .write(self.X(&names.points).map_err(|e| {
+                anyhow!("matlab convert {:?} failed with error: {e}", names.points)
+            })?);
+        mat_file
+            .write(self.y(&names.labels).map_err(|e| {
+                anyhow!("matlab convert {:?} failed with error: {e}", names.labels)
+            })?);
         Ok(())
     }
 
-    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<DataPoints, anyhow::Error> {
+    pub fn load_from_file<P: AsRef<std::path::Path>>(
+        path: P,
+        names: &MatVariableNames,
+    ) -> Result<DataPoints, anyhow::Error> {
         let mat_file = MatFile::load(path)?;
-        let x: Vec<f64> = mat_file.var("X")?;
-        let y: Vec<u8> = match mat_file.var::<&str, Vec<u8>>("y") {
+        let x: Vec<f64> = mat_file.var(names.points.as_str())?;
+        let y: Vec<u8> = match mat_file.var::<&str, Vec<u8>>(&names.labels) {
             Ok(val) => val,
             Err(e) => {
                 if let MatioError::TypeMismatch(_var_name, _expected, found_type) = &e {
                     match &found_type[..] {
                         "DOUBLE" => mat_file
-                            .var::<&str, Vec<f64>>("y")
+                            .var::<&str, Vec<f64>>(&names.labels)
                             .context("Error said to expect f64")?
                             .into_iter()
-                            .map(|x| {
-                                if x == 1.0 {
-                                    Ok(1u8)
-                                } else if x == 0.0 {
-                                    Ok(0u8)
-                                } else {
-                                    bail!("Only expected 1 or 0 but found {x}")
-                                }
-                            })
+                            .map(|x| DataLabel::try_from_f64(x).map(|label| label.as_int()))
                             .collect::<Result<Vec<_>, _>>()?,
                         "INT32" => mat_file
-                            .var::<&str, Vec<i32>>("y")
+                            .var::<&str, Vec<i32>>(&names.labels)
                             .context("Error said to expect i32")?
                             .into_iter()
-                            .map(|x| {
-                                if x == 1 {
-                                    Ok(1u8)
-                                } else if x == 0 {
-                                    Ok(0u8)
-                                } else {
-                                    bail!("Only expected 1 or 0 but found {x}")
-                                }
-                            })
+                            .map(label_from_int)
                             .collect::<Result<Vec<_>, _>>()?,
                         "INT64" => mat_file
-                            .var::<&str, Vec<i64>>("y")
+                            .var::<&str, Vec<i64>>(&names.labels)
                             .context("Error said to expect i64")?
                             .into_iter()
-                            .map(|x| {
-                                if x == 1 {
-                                    Ok(1u8)
-                                } else if x == 0 {
-                                    Ok(0u8)
-                                } else {
-                                    bail!("Only expected 1 or 0 but found {x}")
-                                }
-                            })
+                            .map(label_from_int)
                             .collect::<Result<Vec<_>, _>>()?,
                         _ => {
-                            return Err(anyhow::Error::new(e)
-                                .context("Currently Unsupported Type for \"y\""))
+                            return Err(anyhow::Error::new(e).context(format!(
+                                "Currently Unsupported Type for {:?}",
+                                names.labels
+                            )))
                         }
                     }
                 } else {
@@ -119,6 +104,16 @@ impl MatlabData {
     }
 }
 
+/// Accepts any label index that fits in a [`u8`] (Class labels are saved as their index)
+fn label_from_int<T: Copy + std::fmt::Display + TryInto<u8>>(value: T) -> anyhow::Result<u8> {
+    value.try_into().map_err(|_| {
+        anyhow!(
+            "expected a whole number from 0 to {} as the label but found {value}",
+            u8::MAX
+        )
+    })
+}
+
 impl From<&[DataPoint]> for MatlabData {
     fn from(points: &[DataPoint]) -> Self {
         let points_len = points.len();
@@ -147,12 +142,11 @@ impl TryFrom<&MatlabData> for DataPoints {
         let points_len = value.y.len();
         let mut result = Vec::with_capacity(points_len);
         for i in 0..points_len {
-            result.push(DataPoint {
-                x0: value.x[i],
-                x1: value.x[i + points_len],
-                label: DataLabel::try_from(value.y[i])
-                    .context("unable to convert number to data label")?,
-            });
+            result.push(DataPoint::new(
+                value.x[i],
+                value.x[i + points_len],
+                DataLabel::from(value.y[i]),
+            ));
         }
         Ok(result)
     }
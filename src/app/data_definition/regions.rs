@@ -0,0 +1,100 @@
+use super::{DataLabel, PointArray};
+
+/// A named rectangle on the plot with the label the points inside are meant to have
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Debug)]
+pub struct Region {
+    pub name: String,
+    pub label: DataLabel,
+    /// Corner with the lowest coordinates
+    pub min: PointArray,
+    /// Corner with the highest coordinates
+    pub max: PointArray,
+}
+
+impl Region {
+    /// Creates the region spanned by two opposite corners given in any order
+    pub fn from_corners(
+        name: String,
+        label: DataLabel,
+        corner: PointArray,
+        opposite: PointArray,
+    ) -> Self {
+        Self {
+            name,
+            label,
+            min: [corner[0].min(opposite[0]), corner[1].min(opposite[1])],
+            max: [corner[0].max(opposite[0]), corner[1].max(opposite[1])],
+        }
+    }
+
+    pub fn contains(&self, [x0, x1]: PointArray) -> bool {
+        (self.min[0]..=self.max[0]).contains(&x0) && (self.min[1]..=self.max[1]).contains(&x1)
+    }
+
+    /// Corners in drawing order
+    pub fn outline(&self) -> Vec<PointArray> {
+        vec![
+            self.min,
+            [self.max[0], self.min[1]],
+            self.max,
+            [self.min[0], self.max[1]],
+        ]
+    }
+}
+
+/// Regions drawn on the plot (Changes are not part of the history)
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Default, Debug)]
+pub struct Regions {
+    items: Vec<Region>,
+}
+
+impl Regions {
+    pub fn add(&mut self, region: Region) {
+        self.items.push(region);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Region> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Region> {
+        self.items.get(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.items.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Region> {
+        self.items.iter_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_from_any_corners_contains_inside() {
+        let region = Region::from_corners(
+            "box".to_string(),
+            DataLabel::Anomaly,
+            [2.0, -1.0],
+            [0.0, 1.0],
+        );
+        assert_eq!(region.min, [0.0, -1.0]);
+        assert_eq!(region.max, [2.0, 1.0]);
+        assert!(region.contains([1.0, 0.0]));
+        assert!(region.contains([2.0, 1.0]));
+        assert!(!region.contains([2.5, 0.0]));
+        assert!(!region.contains([1.0, -1.5]));
+    }
+}
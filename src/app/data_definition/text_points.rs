@@ -0,0 +1,80 @@
+use anyhow::{bail, Context};
+
+use super::{csv_mapping::parse_label, DataLabel, DataPoint, DataPoints};
+
+/// Points found in text along with a description of each line that could not be converted
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedPoints {
+    pub points: DataPoints,
+    pub errors: Vec<String>,
+}
+
+/// Parses text with one point per line in the form "x0, x1, label" (Tabs and semicolons are also
+/// accepted as separators so that cells copied from a spreadsheet work)
+///
+/// If the label is omitted `default_label` is used. A first line without any numbers is treated as a header and skipped.
+pub fn parse_points_text(text: &str, default_label: DataLabel) -> ParsedPoints {
+    let mut result = ParsedPoints::default();
+    let mut is_first_line = true;
+    for (line_number, line) in text.lines().enumerate().map(|(i, x)| (i + 1, x.trim())) {
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split([',', '\t', ';'])
+            .map(|field| field.trim())
+            .collect();
+        if is_first_line && fields.iter().all(|field| field.parse::<f64>().is_err()) {
+            is_first_line = false;
+            continue; // Skip header
+        }
+        is_first_line = false;
+        match parse_fields(&fields, default_label) {
+            Ok(point) => result.points.push(point),
+            Err(e) => result.errors.push(format!("Line {line_number}: {e:#}")),
+        }
+    }
+    result
+}
+
+fn parse_fields(fields: &[&str], default_label: DataLabel) -> anyhow::Result<DataPoint> {
+    let (x0, x1, label) = match fields {
+        [x0, x1] => (x0, x1, None),
+        [x0, x1, label] => (x0, x1, Some(label)),
+        _ => bail!("expected 2 or 3 values but found {}", fields.len()),
+    };
+    let x0 = x0
+        .parse()
+        .with_context(|| format!("unable to convert {x0:?} to a number"))?;
+    let x1 = x1
+        .parse()
+        .with_context(|| format!("unable to convert {x1:?} to a number"))?;
+    let label = match label {
+        Some(label) => parse_label(label)?,
+        None => default_label,
+    };
+    Ok(DataPoint::new(x0, x1, label))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn mixed_separators_and_errors() {
+        let text = "x0\tx1\tlabel\r\n1\t2\t1\r\n\r\n3.5, 4, N\r\n5;6\r\nabc,1,0\r\n";
+        let actual = parse_points_text(text, DataLabel::Anomaly);
+        assert_eq!(
+            actual.points,
+            vec![
+                DataPoint::new(1.0, 2.0, DataLabel::Anomaly),
+                DataPoint::new(3.5, 4.0, DataLabel::Normal),
+                DataPoint::new(5.0, 6.0, DataLabel::Anomaly),
+            ]
+        );
+        assert_eq!(actual.errors.len(), 1);
+        assert!(actual.errors[0].starts_with("Line 6:"));
+    }
+}
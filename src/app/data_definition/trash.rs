@@ -0,0 +1,73 @@
+use super::{DataPoint, DataPoints};
+
+/// Holds points that were deleted while soft delete is enabled so they can be restored later.
+/// Undoing a delete takes its points back out of the trash and redoing it puts them back
+/// (Clearing the history does not empty it).
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Default, Debug)]
+pub struct Trash {
+    /// If deleted points should be moved into the trash
+    pub is_enabled: bool,
+    points: DataPoints,
+}
+
+impl Trash {
+    /// Stores the points if soft delete is enabled and returns if they were stored
+    pub(super) fn receive_many(&mut self, points: &[DataPoint]) -> bool {
+        if self.is_enabled {
+            self.points.extend_from_slice(points);
+        }
+        self.is_enabled
+    }
+
+    /// Stores the points again when the delete that trashed them is redone
+    pub(super) fn put_back(&mut self, points: &[DataPoint]) {
+        self.points.extend_from_slice(points);
+    }
+
+    /// Removes the most recent copy of each of the points when the delete that trashed them is
+    /// undone (Points no longer in the trash are skipped as they were restored or purged)
+    pub(super) fn take_back(&mut self, points: &[DataPoint]) {
+        for point in points.iter().rev() {
+            if let Some(i) = self.points.iter().rposition(|x| x == point) {
+                self.points.remove(i);
+            }
+        }
+    }
+
+    /// Removes and returns the points at the indices (Indices that are out of range are ignored)
+    pub(super) fn take(&mut self, indices: &[usize]) -> DataPoints {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.retain(|&i| i < self.points.len());
+        let mut result: DataPoints = indices
+            .into_iter()
+            .rev()
+            .map(|i| self.points.remove(i))
+            .collect();
+        result.reverse(); // Keep the order they were deleted in
+        result
+    }
+
+    /// Permanently removes the points at the indices
+    pub fn purge(&mut self, indices: &[usize]) {
+        self.take(indices);
+    }
+
+    /// Permanently removes all the points
+    pub fn empty(&mut self) {
+        self.points.clear();
+    }
+
+    pub fn points(&self) -> &[DataPoint] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
@@ -1,17 +1,24 @@
 use std::fmt::Display;
 
-use self::{dequeue::Deque, stack::Stack};
+use self::{compressed::CompressedEvent, dequeue::Deque, stack::Stack};
 
-use super::{DataPoint, DataPoints};
+use super::{DataPoint, DataPoints, GroupId, PointGroup};
 
+mod compressed;
 mod dequeue;
 mod stack;
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
 pub struct UndoManager {
     max_history_size: Option<u16>,
+    /// Events with more points than this are stored compressed (`None` to never compress)
+    #[serde(default = "UndoManager::default_compress_threshold")]
+    compress_threshold: Option<u32>,
     undo_events: Deque<Event>,
     redo_events: Stack<Event>,
+    /// Number of points and compressed size in bytes of the last new event that was compressed
+    #[serde(skip)]
+    compression_report: Option<(usize, usize)>,
 }
 
 #[derive(
@@ -38,34 +45,104 @@ impl Default for UndoManager {
     fn default() -> Self {
         Self {
             max_history_size: Some(Self::DEFAULT_MAX_HISTORY),
+            compress_threshold: Self::default_compress_threshold(),
             undo_events: Default::default(),
             redo_events: Default::default(),
+            compression_report: None,
         }
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
 pub enum Event {
     Add(AddEventData),
+    AddMany(AddManyEventData),
     Edit(EditEventData),
+    EditMany(EditManyEventData),
     Delete(DeleteEventData),
+    DeleteMany(DeleteManyEventData),
     Clear(ClearEventData),
     Load(LoadEventData),
+    /// A large event stored compressed (Expanded again before it is undone or redone)
+    Compressed(CompressedEvent),
 }
 
 impl Event {
     pub fn timestamp(&self) -> DataTimestamp {
         match self {
             Event::Add(x) => x.timestamp,
+            Event::AddMany(x) => x.timestamp,
             Event::Edit(x) => x.timestamp,
+            Event::EditMany(x) => x.timestamp,
             Event::Delete(x) => x.timestamp,
+            Event::DeleteMany(x) => x.timestamp,
             Event::Clear(x) => x.timestamp,
             Event::Load(x) => x.timestamp,
+            Event::Compressed(x) => x.inner().timestamp(),
+        }
+    }
+
+    /// The timestamp of the last change that affects training as of this event
+    pub fn training_timestamp(&self) -> DataTimestamp {
+        match self {
+            Event::EditMany(x) => x.appearance_only_since.unwrap_or(x.timestamp),
+            Event::Compressed(x) => x.inner().training_timestamp(),
+            other => other.timestamp(),
+        }
+    }
+
+    /// Number of points held by the events that can hold many points
+    fn point_count(&self) -> usize {
+        match self {
+            Event::AddMany(x) => x.points.len(),
+            Event::DeleteMany(x) => x.deleted.len(),
+            Event::Clear(x) => x.points.len(),
+            Event::Load(x) => x.points.len(),
+            Event::Compressed(x) => x.point_count(),
+            Event::Add(_) | Event::Edit(_) | Event::EditMany(_) | Event::Delete(_) => 0,
+        }
+    }
+
+    /// Takes the points out of the event along with their index (Only deletes use the index)
+    ///
+    /// # PANICS
+    /// If the event is not one that holds many points
+    fn take_points(&mut self) -> Vec<(usize, DataPoint)> {
+        let points = match self {
+            Event::DeleteMany(x) => return std::mem::take(&mut x.deleted),
+            Event::AddMany(x) => std::mem::take(&mut x.points),
+            Event::Clear(x) => std::mem::take(&mut x.points),
+            Event::Load(x) => std::mem::take(&mut x.points),
+            _ => unreachable!("only called on events that hold many points"),
+        };
+        points.into_iter().enumerate().collect()
+    }
+
+    /// Puts back the points taken by [`Self::take_points`]
+    fn put_points(&mut self, records: Vec<(usize, DataPoint)>) {
+        let points = match self {
+            Event::DeleteMany(x) => {
+                x.deleted = records;
+                return;
+            }
+            Event::AddMany(x) => &mut x.points,
+            Event::Clear(x) => &mut x.points,
+            Event::Load(x) => &mut x.points,
+            _ => unreachable!("only called on events that hold many points"),
+        };
+        *points = records.into_iter().map(|(_, point)| point).collect();
+    }
+
+    /// Returns the event with its points uncompressed
+    fn expanded(self) -> Self {
+        match self {
+            Event::Compressed(x) => x.expand(),
+            other => other,
         }
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
 pub struct AddEventData {
     pub point: DataPoint,
     timestamp: DataTimestamp,
@@ -79,7 +156,21 @@ impl AddEventData {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
+pub struct AddManyEventData {
+    pub points: DataPoints,
+    timestamp: DataTimestamp,
+}
+impl AddManyEventData {
+    pub(crate) fn new(points: DataPoints) -> Self {
+        Self {
+            points,
+            timestamp: DataTimestamp::now(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
 pub struct EditEventData {
     pub new_point: DataPoint,
     pub old_point: DataPoint,
@@ -97,11 +188,55 @@ impl EditEventData {
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
+pub struct EditManyEventData {
+    /// Edits in the order they were applied
+    pub edits: Vec<PointEdit>,
+    timestamp: DataTimestamp,
+    /// Set if the edits only change how the points look (Holds the training timestamp from
+    /// before the edits so they do not invalidate a trained model)
+    #[serde(default)]
+    appearance_only_since: Option<DataTimestamp>,
+    /// The definition of the group removed along with the edits (Restored on undo)
+    #[serde(default)]
+    pub deleted_group: Option<(GroupId, PointGroup)>,
+}
+impl EditManyEventData {
+    pub(crate) fn new(edits: Vec<PointEdit>) -> Self {
+        Self {
+            edits,
+            timestamp: DataTimestamp::now(),
+            appearance_only_since: None,
+            deleted_group: None,
+        }
+    }
+
+    pub(crate) fn new_appearance_only(
+        edits: Vec<PointEdit>,
+        training_timestamp: DataTimestamp,
+    ) -> Self {
+        Self {
+            appearance_only_since: Some(training_timestamp),
+            ..Self::new(edits)
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
+pub struct PointEdit {
+    pub index: usize,
+    pub old_point: DataPoint,
+    pub new_point: DataPoint,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
 pub struct DeleteEventData {
     pub index: usize,
     pub point: DataPoint,
     timestamp: DataTimestamp,
+    /// If the point was also moved into the trash (Taken back out on undo)
+    #[serde(default)]
+    pub is_trashed: bool,
 }
 impl DeleteEventData {
     pub(crate) fn new(index: usize, removed_point: DataPoint) -> Self {
@@ -109,25 +244,50 @@ impl DeleteEventData {
             index,
             point: removed_point,
             timestamp: DataTimestamp::now(),
+            is_trashed: false,
         }
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
+pub struct DeleteManyEventData {
+    /// The points removed with the index they were at before the delete (Sorted by index)
+    pub deleted: Vec<(usize, DataPoint)>,
+    timestamp: DataTimestamp,
+    /// If the points were also moved into the trash (Taken back out on undo)
+    #[serde(default)]
+    pub is_trashed: bool,
+}
+impl DeleteManyEventData {
+    pub(crate) fn new(deleted: Vec<(usize, DataPoint)>) -> Self {
+        debug_assert!(deleted.windows(2).all(|x| x[0].0 < x[1].0));
+        Self {
+            deleted,
+            timestamp: DataTimestamp::now(),
+            is_trashed: false,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
 pub struct ClearEventData {
     pub points: DataPoints,
     timestamp: DataTimestamp,
+    /// If the points were also moved into the trash (Taken back out on undo)
+    #[serde(default)]
+    pub is_trashed: bool,
 }
 impl ClearEventData {
     pub(crate) fn new(points: DataPoints) -> Self {
         Self {
             points,
             timestamp: DataTimestamp::now(),
+            is_trashed: false,
         }
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
 pub struct LoadEventData {
     pub points: DataPoints,
     timestamp: DataTimestamp,
@@ -145,10 +305,14 @@ impl Display for Event {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Event::Add(data) => data.fmt(f),
+            Event::AddMany(data) => data.fmt(f),
             Event::Edit(data) => data.fmt(f),
+            Event::EditMany(data) => data.fmt(f),
             Event::Delete(data) => data.fmt(f),
+            Event::DeleteMany(data) => data.fmt(f),
             Event::Clear(data) => data.fmt(f),
             Event::Load(data) => data.fmt(f),
+            Event::Compressed(data) => data.inner().fmt(f),
         }
     }
 }
@@ -159,6 +323,12 @@ impl Display for AddEventData {
     }
 }
 
+impl Display for AddManyEventData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Add {} Points", self.points.len())
+    }
+}
+
 impl Display for EditEventData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -169,12 +339,28 @@ impl Display for EditEventData {
     }
 }
 
+impl Display for EditManyEventData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((_, group)) = &self.deleted_group {
+            write!(f, "Delete Group {:?}", group.name)
+        } else {
+            write!(f, "Edit {} Points", self.edits.len())
+        }
+    }
+}
+
 impl Display for DeleteEventData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Delete Point: {} at index: {}", self.point, self.index)
     }
 }
 
+impl Display for DeleteManyEventData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Delete {} Points", self.deleted.len())
+    }
+}
+
 impl Display for ClearEventData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Clear of Points")
@@ -189,6 +375,51 @@ impl Display for LoadEventData {
 
 impl UndoManager {
     pub const DEFAULT_MAX_HISTORY: u16 = 200;
+    pub const DEFAULT_COMPRESS_THRESHOLD: u32 = 50_000;
+
+    fn default_compress_threshold() -> Option<u32> {
+        Some(Self::DEFAULT_COMPRESS_THRESHOLD)
+    }
+
+    pub fn compress_threshold(&self) -> Option<u32> {
+        self.compress_threshold
+    }
+
+    /// Only applies to changes made after it is set
+    pub fn set_compress_threshold(&mut self, value: Option<u32>) {
+        self.compress_threshold = value;
+    }
+
+    /// Returns the number of points and compressed size of the last new event that was
+    /// compressed if it has not been taken yet
+    pub fn take_compression_report(&mut self) -> Option<(usize, usize)> {
+        self.compression_report.take()
+    }
+
+    /// Replaces the event with a compressed version if it holds more points than the threshold
+    fn compress_if_large(threshold: Option<u32>, event: &mut Event) -> Option<&CompressedEvent> {
+        let threshold = threshold?;
+        if matches!(event, Event::Compressed(_)) || event.point_count() <= threshold as usize {
+            return None;
+        }
+        // Placeholder that is replaced right away
+        let uncompressed = std::mem::replace(event, Event::Load(LoadEventData::new(vec![])));
+        *event = Event::Compressed(CompressedEvent::new(uncompressed));
+        match event {
+            Event::Compressed(x) => Some(x),
+            _ => unreachable!("just set"),
+        }
+    }
+
+    /// Compresses the events that were expanded to be undone or redone again
+    pub fn compact(&mut self) {
+        for event in [self.undo_events.peek_mut(), self.redo_events.peek()]
+            .into_iter()
+            .flatten()
+        {
+            Self::compress_if_large(self.compress_threshold, event);
+        }
+    }
     pub fn max_history_size(&self) -> Option<u16> {
         self.max_history_size
     }
@@ -207,6 +438,10 @@ impl UndoManager {
         self.redo_events.clear();
     }
 
+    pub fn clear_redo(&mut self) {
+        self.redo_events.clear();
+    }
+
     pub fn is_undo_empty(&self) -> bool {
         self.undo_events.is_empty()
     }
@@ -219,8 +454,22 @@ impl UndoManager {
         self.is_undo_empty() && self.is_redo_empty()
     }
 
-    pub fn add_undo(&mut self, event: Event) {
+    /// Number of changes that can be undone
+    pub fn undo_count(&self) -> usize {
+        self.undo_events.len()
+    }
+
+    /// The change that would be undone next
+    pub fn peek_undo(&self) -> Option<&Event> {
+        self.undo_events.peek()
+    }
+
+    pub fn add_undo(&mut self, mut event: Event) {
         self.redo_events.clear();
+        if let Some(compressed) = Self::compress_if_large(self.compress_threshold, &mut event) {
+            self.compression_report =
+                Some((compressed.point_count(), compressed.compressed_size()));
+        }
         self.undo_events.push(event);
         if let Some(max_size) = self.max_history_size {
             if self.undo_events.len() > max_size as usize {
@@ -240,7 +489,8 @@ impl UndoManager {
         let event = self
             .undo_events
             .pop()
-            .expect("should not be empty if called");
+            .expect("should not be empty if called")
+            .expanded();
         self.redo_events.push(event);
         self.redo_events
             .peek()
@@ -254,7 +504,8 @@ impl UndoManager {
         let event = self
             .redo_events
             .pop()
-            .expect("should not be empty if called");
+            .expect("should not be empty if called")
+            .expanded();
         self.undo_events.push(event);
         self.undo_events
             .peek_mut()
@@ -270,4 +521,10 @@ impl UndoManager {
             DataTimestamp::epoch()
         }
     }
+
+    pub(crate) fn training_timestamp(&self) -> DataTimestamp {
+        self.undo_events
+            .peek()
+            .map_or(DataTimestamp::epoch(), Event::training_timestamp)
+    }
 }
@@ -0,0 +1,115 @@
+use std::io::{Read as _, Write as _};
+
+use ecolor::Color32;
+
+use crate::app::data_definition::{DataLabel, DataPoint};
+
+use super::Event;
+
+/// A large event with its points packed into compressed chunks so the history does not hold a
+/// second full size copy of a big change
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
+pub struct CompressedEvent {
+    /// The event with its points taken out
+    event: Box<Event>,
+    /// Each chunk holds up to [`Self::CHUNK_SIZE`] points (with their indices) deflated separately
+    chunks: Vec<Vec<u8>>,
+    len: usize,
+}
+
+impl CompressedEvent {
+    const CHUNK_SIZE: usize = 65_536;
+    /// Index (u64), x0 and x1 (f64), label, has group, group (u16), excluded flag, has color,
+    /// color (RGBA), locked flag and test split flag
+    const RECORD_SIZE: usize = 8 + 8 + 8 + 1 + 1 + 2 + 1 + 1 + 4 + 1 + 1;
+
+    /// Packs the points of the event
+    ///
+    /// ASSUMPTION: The event is one of those that hold many points (See [`Event::take_points`])
+    pub fn new(mut event: Event) -> Self {
+        let records = event.take_points();
+        let chunks = records
+            .chunks(Self::CHUNK_SIZE)
+            .map(|chunk| {
+                let mut bytes = Vec::with_capacity(chunk.len() * Self::RECORD_SIZE);
+                for record in chunk {
+                    encode_record(record, &mut bytes);
+                }
+                let mut encoder =
+                    flate2::write::DeflateEncoder::new(vec![], flate2::Compression::fast());
+                encoder
+                    .write_all(&bytes)
+                    .expect("writing to a Vec should not fail");
+                encoder.finish().expect("writing to a Vec should not fail")
+            })
+            .collect();
+        Self {
+            event: Box::new(event),
+            chunks,
+            len: records.len(),
+        }
+    }
+
+    /// Unpacks the points back into the event
+    pub fn expand(self) -> Event {
+        let mut records = Vec::with_capacity(self.len);
+        let mut bytes = vec![];
+        for chunk in self.chunks.iter() {
+            bytes.clear();
+            flate2::read::DeflateDecoder::new(chunk.as_slice())
+                .read_to_end(&mut bytes)
+                .expect("should be able to decompress the chunks this created");
+            records.extend(bytes.chunks_exact(Self::RECORD_SIZE).map(decode_record));
+        }
+        debug_assert_eq!(records.len(), self.len);
+        let mut event = *self.event;
+        event.put_points(records);
+        event
+    }
+
+    pub fn inner(&self) -> &Event {
+        &self.event
+    }
+
+    pub fn point_count(&self) -> usize {
+        self.len
+    }
+
+    /// Number of bytes used by the compressed points
+    pub fn compressed_size(&self) -> usize {
+        self.chunks.iter().map(Vec::len).sum()
+    }
+}
+
+fn encode_record((index, point): &(usize, DataPoint), bytes: &mut Vec<u8>) {
+    bytes.extend((*index as u64).to_le_bytes());
+    bytes.extend(point.x0.to_le_bytes());
+    bytes.extend(point.x1.to_le_bytes());
+    bytes.push(point.label.as_int());
+    bytes.push(point.group.is_some().into());
+    bytes.extend(point.group.unwrap_or_default().to_le_bytes());
+    bytes.push(point.is_excluded_from_training.into());
+    bytes.push(point.color.is_some().into());
+    bytes.extend(point.color.unwrap_or_default().to_array());
+    bytes.push(point.is_locked.into());
+    bytes.push(point.is_in_test_split.into());
+}
+
+fn decode_record(bytes: &[u8]) -> (usize, DataPoint) {
+    let f64_at = |i: usize| f64::from_le_bytes(bytes[i..i + 8].try_into().expect("8 bytes"));
+    let index = u64::from_le_bytes(bytes[0..8].try_into().expect("8 bytes")) as usize;
+    let label = DataLabel::from(bytes[24]);
+    let group = u16::from_le_bytes([bytes[26], bytes[27]]);
+    let point = DataPoint {
+        x0: f64_at(8),
+        x1: f64_at(16),
+        label,
+        group: (bytes[25] != 0).then_some(group),
+        is_excluded_from_training: bytes[28] != 0,
+        color: (bytes[29] != 0)
+            .then(|| Color32::from_rgba_premultiplied(bytes[30], bytes[31], bytes[32], bytes[33])),
+        is_locked: bytes[34] != 0,
+        is_in_test_split: bytes[35] != 0,
+    };
+    (index, point)
+}
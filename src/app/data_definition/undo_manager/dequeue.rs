@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
 pub struct Deque<T> {
     data: VecDeque<T>,
 }
@@ -1,4 +1,4 @@
-#[derive(serde::Deserialize, serde::Serialize, PartialEq)]
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
 pub struct Stack<T> {
     data: Vec<T>,
 }
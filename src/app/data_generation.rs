@@ -0,0 +1,26 @@
+use rand::{rngs::StdRng, Rng as _, SeedableRng as _};
+
+pub use polar::{PolarGenerator, PolarShape};
+pub use registry::{
+    DataGenerator, GeneratorParams, GeneratorRegistry, ParamKind, ParamSpec, ParamValue,
+};
+
+mod clusters;
+mod polar;
+mod registry;
+mod structured;
+
+/// Creates the random number generator used by the generators so results are reproducible from the seed
+pub(super) fn rng_from_seed(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Samples from a normal distribution with mean 0 and the standard deviation (Box-Muller transform)
+pub(super) fn sample_normal(rng: &mut StdRng, std_dev: f64) -> f64 {
+    if std_dev <= 0.0 {
+        return 0.0;
+    }
+    let u1: f64 = 1.0 - rng.gen::<f64>(); // Exclude 0 to avoid ln(0)
+    let u2: f64 = rng.gen();
+    std_dev * (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
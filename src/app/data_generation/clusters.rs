@@ -0,0 +1,183 @@
+use std::f64::consts::PI;
+
+use rand::Rng as _;
+
+use crate::app::data_definition::{DataLabel, DataPoint, DataPoints};
+
+use super::{rng_from_seed, sample_normal, DataGenerator, GeneratorParams, ParamSpec};
+
+/// Round clusters of points around centers placed at random in a rectangle
+pub(super) struct GaussianBlobsGenerator;
+
+impl GaussianBlobsGenerator {
+    /// Limited so that each cluster can have its own label parameter
+    const MAX_CLUSTERS: i64 = 8;
+
+    fn label_param_name(cluster: usize) -> String {
+        format!("Label of cluster {}", cluster + 1)
+    }
+}
+
+impl DataGenerator for GaussianBlobsGenerator {
+    fn name(&self) -> &str {
+        "Gaussian Blobs"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        let mut result = vec![
+            ParamSpec::integer("Number of clusters", 3, 1, Self::MAX_CLUSTERS),
+            ParamSpec::integer("Points per cluster", 50, 1, 100_000),
+            ParamSpec::number("Std dev", 1.0, 0.0, f64::INFINITY, 0.05),
+            ParamSpec::number("Min x0", -10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Max x0", 10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Min x1", -10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Max x1", 10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+        ];
+        result.extend(
+            (0..Self::MAX_CLUSTERS as usize)
+                .map(|i| ParamSpec::label(Self::label_param_name(i), DataLabel::Normal)),
+        );
+        result
+    }
+
+    fn generate(&self, params: &GeneratorParams, seed: u64) -> DataPoints {
+        let mut rng = rng_from_seed(seed);
+        let clusters = params
+            .integer("Number of clusters")
+            .clamp(1, Self::MAX_CLUSTERS) as usize;
+        let points_per_cluster = params.integer("Points per cluster").max(1) as usize;
+        let std_dev = params.number("Std dev");
+        let x0_range = [params.number("Min x0"), params.number("Max x0")];
+        let x1_range = [params.number("Min x1"), params.number("Max x1")];
+        // Centers are placed within the rectangle, the points may spill out of it
+        let mut sample_in = |range: [f64; 2]| {
+            if range[0] < range[1] {
+                rng.gen_range(range[0]..=range[1])
+            } else {
+                range[0]
+            }
+        };
+        let centers: Vec<[f64; 2]> = (0..clusters)
+            .map(|_| [sample_in(x0_range), sample_in(x1_range)])
+            .collect();
+        let mut result = Vec::with_capacity(clusters * points_per_cluster);
+        for (i, center) in centers.into_iter().enumerate() {
+            let label = params.label(&Self::label_param_name(i));
+            for _ in 0..points_per_cluster {
+                result.push(DataPoint::new(
+                    center[0] + sample_normal(&mut rng, std_dev),
+                    center[1] + sample_normal(&mut rng, std_dev),
+                    label,
+                ));
+            }
+        }
+        result
+    }
+}
+
+/// Two interleaving half circles (Upper one opens downwards and the lower one upwards)
+pub(super) struct TwoMoonsGenerator;
+
+impl TwoMoonsGenerator {
+    const ANOMALOUS_MOON: [&'static str; 3] = ["None", "Upper", "Lower"];
+}
+
+impl DataGenerator for TwoMoonsGenerator {
+    fn name(&self) -> &str {
+        "Two Moons"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("Number of points", 200, 2, 100_000),
+            ParamSpec::number("Noise (std dev)", 0.1, 0.0, f64::INFINITY, 0.01),
+            ParamSpec::number("Radius", 5.0, 0.0, f64::INFINITY, 0.1),
+            ParamSpec::number("Center x0", 0.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Center x1", 0.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::choice("Anomalous moon", &Self::ANOMALOUS_MOON, 2),
+        ]
+    }
+
+    fn generate(&self, params: &GeneratorParams, seed: u64) -> DataPoints {
+        let mut rng = rng_from_seed(seed);
+        let count = params.integer("Number of points").max(2) as usize;
+        let noise = params.number("Noise (std dev)");
+        let radius = params.number("Radius");
+        let center = [params.number("Center x0"), params.number("Center x1")];
+        let anomalous = params.choice("Anomalous moon");
+        let label_of = |moon: usize| {
+            if anomalous == moon {
+                DataLabel::Anomaly
+            } else {
+                DataLabel::Normal
+            }
+        };
+        // The upper moon gets the extra point if the count is odd
+        let upper_count = count.div_ceil(2);
+        (0..count)
+            .map(|i| {
+                let (is_upper, i, moon_count) = if i < upper_count {
+                    (true, i, upper_count)
+                } else {
+                    (false, i - upper_count, count - upper_count)
+                };
+                let angle = PI * i as f64 / (moon_count.max(2) - 1) as f64;
+                // Centered so that the pair of moons is symmetric around the center
+                let (x0, x1, moon) = if is_upper {
+                    (angle.cos() - 0.5, angle.sin() - 0.25, 1)
+                } else {
+                    (0.5 - angle.cos(), 0.25 - angle.sin(), 2)
+                };
+                DataPoint::new(
+                    center[0] + radius * x0 + sample_normal(&mut rng, noise),
+                    center[1] + radius * x1 + sample_normal(&mut rng, noise),
+                    label_of(moon),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ParamValue;
+    use super::*;
+
+    #[test]
+    fn clusters_are_labeled_and_reproducible() {
+        let mut params = GeneratorParams::default();
+        params.fill_defaults(&GaussianBlobsGenerator.params());
+        params.set("Number of clusters", ParamValue::Integer(2));
+        params.set("Points per cluster", ParamValue::Integer(20));
+        params.set("Std dev", ParamValue::Number(0.0));
+        params.set("Label of cluster 2", ParamValue::Label(DataLabel::Anomaly));
+
+        let points = GaussianBlobsGenerator.generate(&params, 7);
+        assert_eq!(points.len(), 40);
+        assert_eq!(points, GaussianBlobsGenerator.generate(&params, 7));
+        assert!(points[..20]
+            .iter()
+            .all(|p| p.label == DataLabel::Normal && p.x0 == points[0].x0 && p.x1 == points[0].x1));
+        assert!(points[20..].iter().all(|p| p.label == DataLabel::Anomaly
+            && (-10.0..=10.0).contains(&p.x0)
+            && (-10.0..=10.0).contains(&p.x1)));
+    }
+
+    #[test]
+    fn moons_interleave() {
+        let mut params = GeneratorParams::default();
+        params.fill_defaults(&TwoMoonsGenerator.params());
+        params.set("Number of points", ParamValue::Integer(5));
+        params.set("Noise (std dev)", ParamValue::Number(0.0));
+        params.set("Radius", ParamValue::Number(1.0));
+
+        let points = TwoMoonsGenerator.generate(&params, 0);
+        let labels: Vec<DataLabel> = points.iter().map(|p| p.label).collect();
+        assert_eq!(labels[..3], [DataLabel::Normal; 3]);
+        assert_eq!(labels[3..], [DataLabel::Anomaly; 2]);
+        // Top of the upper moon and the ends of the lower moon
+        assert!((points[1].x0 + 0.5).abs() < 1e-9 && (points[1].x1 - 0.75).abs() < 1e-9);
+        assert_eq!([points[3].x0, points[3].x1], [-0.5, 0.25]);
+        assert!((points[4].x0 - 1.5).abs() < 1e-9 && (points[4].x1 - 0.25).abs() < 1e-9);
+    }
+}
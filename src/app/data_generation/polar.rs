@@ -0,0 +1,125 @@
+use std::f64::consts::TAU;
+
+use rand::Rng as _;
+
+use crate::app::data_definition::{DataLabel, DataPoint, DataPoints};
+
+use super::{rng_from_seed, sample_normal};
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PolarShape {
+    /// Points around a circle
+    Ring,
+    /// Points along an Archimedean spiral from the inner radius to the radius
+    Spiral,
+    /// Points spread over the area between the inner radius and the radius within the angles
+    Sector,
+}
+
+/// Generates points described in polar coordinates around a center
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Debug)]
+pub struct PolarGenerator {
+    pub shape: PolarShape,
+    pub center: [f64; 2],
+    pub count: usize,
+    pub radius: f64,
+    /// Start radius of a spiral or inner radius of a sector
+    pub inner_radius: f64,
+    /// Angle in degrees measured counterclockwise from the positive x0 axis
+    pub start_angle: f64,
+    /// Angle in degrees (Not used for spirals)
+    pub end_angle: f64,
+    /// Number of times a spiral goes around the center
+    pub turns: f64,
+    /// Standard deviation of the noise added to the radius of each point
+    pub radial_noise: f64,
+    pub label: DataLabel,
+    /// Set to the dataset seed when generating from the menu
+    pub seed: u64,
+}
+
+impl Default for PolarGenerator {
+    fn default() -> Self {
+        Self {
+            shape: PolarShape::Ring,
+            center: [0.0, 0.0],
+            count: 100,
+            radius: 10.0,
+            inner_radius: 0.0,
+            start_angle: 0.0,
+            end_angle: 360.0,
+            turns: 2.0,
+            radial_noise: 0.5,
+            label: DataLabel::Normal,
+            seed: 0,
+        }
+    }
+}
+
+impl PolarGenerator {
+    pub fn generate(&self) -> DataPoints {
+        let mut rng = rng_from_seed(self.seed);
+        let start = self.start_angle.to_radians();
+        let end = self.end_angle.to_radians();
+        (0..self.count)
+            .map(|i| {
+                // Fraction of the way through the points, used to space them evenly
+                let t = if self.count > 1 {
+                    i as f64 / (self.count - 1) as f64
+                } else {
+                    0.0
+                };
+                let (r, theta) = match self.shape {
+                    PolarShape::Ring => (self.radius, start + (end - start) * t),
+                    PolarShape::Spiral => (
+                        self.inner_radius + (self.radius - self.inner_radius) * t,
+                        start + TAU * self.turns * t,
+                    ),
+                    PolarShape::Sector => {
+                        // Square root keeps the density even over the area
+                        let inner_sq = self.inner_radius.powi(2);
+                        let outer_sq = self.radius.powi(2);
+                        let r = (inner_sq + (outer_sq - inner_sq) * rng.gen::<f64>()).sqrt();
+                        (r, start + (end - start) * rng.gen::<f64>())
+                    }
+                };
+                let r = r + sample_normal(&mut rng, self.radial_noise);
+                DataPoint::new(
+                    self.center[0] + r * theta.cos(),
+                    self.center[1] + r * theta.sin(),
+                    self.label,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_without_noise_is_at_radius() {
+        let generator = PolarGenerator {
+            center: [1.0, -1.0],
+            count: 12,
+            radial_noise: 0.0,
+            ..Default::default()
+        };
+        let points = generator.generate();
+        assert_eq!(points.len(), 12);
+        for point in points {
+            let distance = (point.x0 - 1.0).hypot(point.x1 + 1.0);
+            assert!((distance - generator.radius).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_points() {
+        let generator = PolarGenerator {
+            shape: PolarShape::Sector,
+            ..Default::default()
+        };
+        assert_eq!(generator.generate(), generator.generate());
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+
+use rand::Rng as _;
+
+use crate::app::data_definition::{DataLabel, DataPoint, DataPoints};
+
+use super::{
+    clusters::{GaussianBlobsGenerator, TwoMoonsGenerator},
+    rng_from_seed,
+    structured::{GridGenerator, LineGenerator},
+};
+
+/// A source of points that can be listed in the Generate menu
+///
+/// Implement this and pass it to [`crate::DBV::register_generator`] to add a generator without
+/// changing the UI code. The window for it is built from [`DataGenerator::params`].
+pub trait DataGenerator: Send + Sync {
+    /// Shown in the menu and used as the key to remember the parameter values (Should be unique)
+    fn name(&self) -> &str;
+
+    /// Describes the parameters the user can set before generating
+    fn params(&self) -> Vec<ParamSpec>;
+
+    /// Creates the points. The same parameters and seed should always give the same points
+    fn generate(&self, params: &GeneratorParams, seed: u64) -> DataPoints;
+}
+
+/// Description of a single parameter of a [`DataGenerator`]
+#[derive(Clone, Debug)]
+pub struct ParamSpec {
+    pub name: String,
+    pub kind: ParamKind,
+    pub default: ParamValue,
+}
+
+impl ParamSpec {
+    pub fn number(name: impl Into<String>, default: f64, min: f64, max: f64, speed: f64) -> Self {
+        Self {
+            name: name.into(),
+            kind: ParamKind::Number { min, max, speed },
+            default: ParamValue::Number(default),
+        }
+    }
+
+    pub fn integer(name: impl Into<String>, default: i64, min: i64, max: i64) -> Self {
+        Self {
+            name: name.into(),
+            kind: ParamKind::Integer { min, max },
+            default: ParamValue::Integer(default),
+        }
+    }
+
+    /// `default` is the index into `options`
+    pub fn choice(name: impl Into<String>, options: &[&str], default: usize) -> Self {
+        Self {
+            name: name.into(),
+            kind: ParamKind::Choice(options.iter().map(|x| x.to_string()).collect()),
+            default: ParamValue::Choice(default),
+        }
+    }
+
+    pub fn label(name: impl Into<String>, default: DataLabel) -> Self {
+        Self {
+            name: name.into(),
+            kind: ParamKind::Label,
+            default: ParamValue::Label(default),
+        }
+    }
+}
+
+/// Controls which widget is used to edit a parameter
+#[derive(Clone, Debug)]
+pub enum ParamKind {
+    Number {
+        min: f64,
+        max: f64,
+        speed: f64,
+    },
+    Integer {
+        min: i64,
+        max: i64,
+    },
+    /// Names of the options
+    Choice(Vec<String>),
+    Label,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Copy, Debug)]
+pub enum ParamValue {
+    Number(f64),
+    Integer(i64),
+    /// Index of the selected option
+    Choice(usize),
+    Label(DataLabel),
+}
+
+/// Values of the parameters of a generator by name
+#[derive(serde::Deserialize, serde::Serialize, Default, PartialEq, Clone, Debug)]
+#[serde(default)]
+pub struct GeneratorParams {
+    values: BTreeMap<String, ParamValue>,
+}
+
+impl GeneratorParams {
+    /// Adds the default for any parameter that is missing or no longer has the right type
+    pub fn fill_defaults(&mut self, specs: &[ParamSpec]) {
+        for spec in specs {
+            let value = self.values.entry(spec.name.clone()).or_insert(spec.default);
+            if std::mem::discriminant(value) != std::mem::discriminant(&spec.default) {
+                *value = spec.default;
+            }
+        }
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut ParamValue> {
+        self.values.get_mut(name)
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: ParamValue) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Returns 0 if the parameter is missing or not a number
+    pub fn number(&self, name: &str) -> f64 {
+        match self.values.get(name) {
+            Some(ParamValue::Number(x)) => *x,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns 0 if the parameter is missing or not an integer
+    pub fn integer(&self, name: &str) -> i64 {
+        match self.values.get(name) {
+            Some(ParamValue::Integer(x)) => *x,
+            _ => 0,
+        }
+    }
+
+    /// Returns the first option if the parameter is missing or not a choice
+    pub fn choice(&self, name: &str) -> usize {
+        match self.values.get(name) {
+            Some(ParamValue::Choice(x)) => *x,
+            _ => 0,
+        }
+    }
+
+    /// Returns [`DataLabel::Normal`] if the parameter is missing or not a label
+    pub fn label(&self, name: &str) -> DataLabel {
+        match self.values.get(name) {
+            Some(ParamValue::Label(x)) => *x,
+            _ => DataLabel::Normal,
+        }
+    }
+}
+
+/// The generators listed in the Generate menu in the order they were registered
+pub struct GeneratorRegistry {
+    generators: Vec<Box<dyn DataGenerator>>,
+}
+
+impl Default for GeneratorRegistry {
+    /// Starts with the generators that ship with the app
+    fn default() -> Self {
+        let mut result = Self { generators: vec![] };
+        result.register(UniformGenerator);
+        result.register(GridGenerator);
+        result.register(LineGenerator);
+        result.register(GaussianBlobsGenerator);
+        result.register(TwoMoonsGenerator);
+        result
+    }
+}
+
+impl PartialEq for GeneratorRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        self.names().eq(other.names())
+    }
+}
+
+impl std::fmt::Debug for GeneratorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl GeneratorRegistry {
+    /// Adds the generator to the end of the list or replaces one with the same name
+    pub fn register(&mut self, generator: impl DataGenerator + 'static) {
+        let generator = Box::new(generator);
+        match self
+            .generators
+            .iter_mut()
+            .find(|x| x.name() == generator.name())
+        {
+            Some(existing) => *existing = generator,
+            None => self.generators.push(generator),
+        }
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.generators.iter().map(|x| x.name())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn DataGenerator> {
+        self.generators
+            .iter()
+            .find(|x| x.name() == name)
+            .map(|x| x.as_ref())
+    }
+}
+
+/// Points spread evenly over a rectangle
+struct UniformGenerator;
+
+impl DataGenerator for UniformGenerator {
+    fn name(&self) -> &str {
+        "Uniform"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("Number of points", 100, 1, 100_000),
+            ParamSpec::number("Min x0", -10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Max x0", 10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Min x1", -10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Max x1", 10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::label("Label", DataLabel::Normal),
+        ]
+    }
+
+    fn generate(&self, params: &GeneratorParams, seed: u64) -> DataPoints {
+        let mut rng = rng_from_seed(seed);
+        let count = params.integer("Number of points").max(0) as usize;
+        let x0_range = [params.number("Min x0"), params.number("Max x0")];
+        let x1_range = [params.number("Min x1"), params.number("Max x1")];
+        let label = params.label("Label");
+        (0..count)
+            .map(|_| {
+                let x0 = x0_range[0] + (x0_range[1] - x0_range[0]) * rng.gen::<f64>();
+                let x1 = x1_range[0] + (x1_range[1] - x1_range[0]) * rng.gen::<f64>();
+                DataPoint::new(x0, x1, label)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_generator_uses_defaults() {
+        let registry = GeneratorRegistry::default();
+        let generator = registry.get("Uniform").expect("built in generator");
+        let mut params = GeneratorParams::default();
+        params.set("Label", ParamValue::Integer(1)); // Wrong type should be replaced
+        params.fill_defaults(&generator.params());
+
+        let points = generator.generate(&params, 7);
+        assert_eq!(points.len(), 100);
+        assert!(points
+            .iter()
+            .all(|p| (-10.0..=10.0).contains(&p.x0) && (-10.0..=10.0).contains(&p.x1)));
+        assert!(points.iter().all(|p| p.label == DataLabel::Normal));
+        assert_eq!(points, generator.generate(&params, 7));
+    }
+}
@@ -0,0 +1,151 @@
+use rand::{rngs::StdRng, Rng as _};
+
+use crate::app::data_definition::{DataLabel, DataPoint, DataPoints};
+
+use super::{rng_from_seed, DataGenerator, GeneratorParams, ParamSpec};
+
+/// Parameters for the noise added independently on each axis
+fn noise_params() -> [ParamSpec; 2] {
+    [
+        ParamSpec::number("Noise x0", 0.0, 0.0, f64::INFINITY, 0.01),
+        ParamSpec::number("Noise x1", 0.0, 0.0, f64::INFINITY, 0.01),
+    ]
+}
+
+/// Moves the point by up to the noise amplitude of each axis in either direction
+fn add_noise(point: [f64; 2], params: &GeneratorParams, rng: &mut StdRng) -> [f64; 2] {
+    let mut result = point;
+    for (value, name) in result.iter_mut().zip(["Noise x0", "Noise x1"]) {
+        let amplitude = params.number(name);
+        if amplitude > 0.0 {
+            *value += rng.gen_range(-amplitude..=amplitude);
+        }
+    }
+    result
+}
+
+/// Points on a regular lattice covering a rectangle
+pub(super) struct GridGenerator;
+
+impl DataGenerator for GridGenerator {
+    fn name(&self) -> &str {
+        "Grid"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        let mut result = vec![
+            ParamSpec::integer("Columns", 10, 1, 1_000),
+            ParamSpec::integer("Rows", 10, 1, 1_000),
+            ParamSpec::number("Min x0", -10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Max x0", 10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Min x1", -10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Max x1", 10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+        ];
+        result.extend(noise_params());
+        result.push(ParamSpec::label("Label", DataLabel::Normal));
+        result
+    }
+
+    fn generate(&self, params: &GeneratorParams, seed: u64) -> DataPoints {
+        let mut rng = rng_from_seed(seed);
+        let columns = params.integer("Columns").max(1) as usize;
+        let rows = params.integer("Rows").max(1) as usize;
+        let x0_range = [params.number("Min x0"), params.number("Max x0")];
+        let x1_range = [params.number("Min x1"), params.number("Max x1")];
+        let label = params.label("Label");
+        // A single column or row is placed at the start of the range
+        let step =
+            |range: [f64; 2], count: usize| (range[1] - range[0]) / (count.max(2) - 1) as f64;
+        let (x0_step, x1_step) = (step(x0_range, columns), step(x1_range, rows));
+        let mut result = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                let point = [
+                    x0_range[0] + column as f64 * x0_step,
+                    x1_range[0] + row as f64 * x1_step,
+                ];
+                let [x0, x1] = add_noise(point, params, &mut rng);
+                result.push(DataPoint::new(x0, x1, label));
+            }
+        }
+        result
+    }
+}
+
+/// Points evenly spaced along a line segment
+pub(super) struct LineGenerator;
+
+impl DataGenerator for LineGenerator {
+    fn name(&self) -> &str {
+        "Line"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        let mut result = vec![
+            ParamSpec::integer("Number of points", 50, 1, 100_000),
+            ParamSpec::number("Start x0", -10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("Start x1", -10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("End x0", 10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+            ParamSpec::number("End x1", 10.0, f64::NEG_INFINITY, f64::INFINITY, 0.1),
+        ];
+        result.extend(noise_params());
+        result.push(ParamSpec::label("Label", DataLabel::Normal));
+        result
+    }
+
+    fn generate(&self, params: &GeneratorParams, seed: u64) -> DataPoints {
+        let mut rng = rng_from_seed(seed);
+        let count = params.integer("Number of points").max(1) as usize;
+        let start = [params.number("Start x0"), params.number("Start x1")];
+        let end = [params.number("End x0"), params.number("End x1")];
+        let label = params.label("Label");
+        (0..count)
+            .map(|i| {
+                let fraction = i as f64 / (count.max(2) - 1) as f64;
+                let point = [
+                    start[0] + (end[0] - start[0]) * fraction,
+                    start[1] + (end[1] - start[1]) * fraction,
+                ];
+                let [x0, x1] = add_noise(point, params, &mut rng);
+                DataPoint::new(x0, x1, label)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ParamValue;
+    use super::*;
+
+    fn params_for(generator: &dyn DataGenerator) -> GeneratorParams {
+        let mut result = GeneratorParams::default();
+        result.fill_defaults(&generator.params());
+        result
+    }
+
+    #[test]
+    fn noise_is_applied_per_axis() {
+        let mut params = params_for(&GridGenerator);
+        let lattice = GridGenerator.generate(&params, 0);
+        assert_eq!(lattice.len(), 100);
+        assert_eq!(lattice[0].x0, -10.0);
+        assert_eq!(lattice[99].x0, 10.0);
+
+        params.set("Noise x0", ParamValue::Number(0.5));
+        let jittered = GridGenerator.generate(&params, 0);
+        assert!(lattice
+            .iter()
+            .zip(jittered.iter())
+            .all(|(a, b)| { a.x1 == b.x1 && (a.x0 - b.x0).abs() <= 0.5 }));
+        assert!(lattice
+            .iter()
+            .zip(jittered.iter())
+            .any(|(a, b)| a.x0 != b.x0));
+
+        let mut params = params_for(&LineGenerator);
+        params.set("Noise x1", ParamValue::Number(0.25));
+        let line = LineGenerator.generate(&params, 0);
+        assert!(line.iter().all(|p| (p.x1 - p.x0).abs() <= 0.25));
+    }
+}
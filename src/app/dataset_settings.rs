@@ -0,0 +1,111 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::{data_definition::Data, plot_zoom_reset::ZoomTarget, ui_blocks::ui_seed};
+
+impl DBV {
+    pub(super) fn ui_btn_round_all_points(&mut self, ui: &mut egui::Ui) {
+        let text = match self.data.rounding_decimal_places() {
+            Some(decimal_places) => format!("Round All Points to {decimal_places} Decimal Places"),
+            None => "Round All Points".to_string(),
+        };
+        if ui
+            .add_enabled(
+                self.data.is_rounding_enabled() && !self.data.is_empty(),
+                Button::new(text),
+            )
+            .on_hover_text("Applies the rounding for new points to the existing points")
+            .on_disabled_hover_text(
+                "Requires rounding of new points to be turned on in the options",
+            )
+            .clicked()
+        {
+            let count = self.data.round_all_points();
+            self.selection.keep_for(&self.data);
+            self.status_msg.info(format!("Rounded {count} points"));
+            ui.close_menu();
+        }
+    }
+
+    pub(super) fn ui_dataset_settings(&mut self, ui: &mut egui::Ui) {
+        // Handle setting rounding of new points
+        ui.horizontal(|ui| {
+            let mut is_rounding_new_points_enabled = self.data.is_rounding_enabled();
+            ui.checkbox(
+                &mut is_rounding_new_points_enabled,
+                "Should round new points",
+            );
+            self.data
+                .set_rounding_enabled(is_rounding_new_points_enabled);
+            if is_rounding_new_points_enabled {
+                ui.separator();
+                ui.label("Number of Decimal places: ");
+                ui.add(egui::Slider::new(
+                    self.data.rounding_decimal_places_mut(),
+                    0..=Data::MAX_DECIMAL_PLACES,
+                ));
+            }
+        });
+
+        ui.menu_button("Bounds for new points", |ui| self.ui_new_point_bounds(ui));
+
+        ui.horizontal(|ui| {
+            ui_seed(ui, &mut self.data.settings.seed);
+        });
+
+        ui.separator();
+        egui::Grid::new("grid-axis-labels").show(ui, |ui| {
+            let [x0_name, x1_name] = &mut self.data.settings.axis_labels;
+            ui.label("x0 axis name");
+            ui.text_edit_singleline(x0_name);
+            ui.end_row();
+            ui.label("x1 axis name");
+            ui.text_edit_singleline(x1_name);
+            ui.end_row();
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.plot_bounds.is_some(), Button::new("Use Current View"))
+                .on_hover_text("Shown instead of all the points when the project is loaded")
+                .clicked()
+            {
+                self.data.settings.default_view = self.plot_bounds.map(Into::into);
+            }
+            if ui
+                .add_enabled(
+                    self.data.settings.default_view.is_some(),
+                    Button::new("Clear Default View"),
+                )
+                .clicked()
+            {
+                self.data.settings.default_view = None;
+            }
+        });
+        if let Some(view) = &self.data.settings.default_view {
+            ui.label(format!(
+                "Default view: {} to {}",
+                self.display_precision.format_pair(view.min),
+                self.display_precision.format_pair(view.max)
+            ));
+        }
+    }
+
+    pub(super) fn ui_btn_zoom_to_default_view(&mut self, ui: &mut egui::Ui) {
+        let Some(view) = self.data.settings.default_view else {
+            return;
+        };
+        if ui
+            .add_enabled(
+                self.state_reset_plot_zoom.is_stopped(),
+                Button::new("Zoom to Default View"),
+            )
+            .clicked()
+        {
+            self.start_zoom_reset(ZoomTarget::View(view));
+            ui.close_menu();
+        }
+    }
+}
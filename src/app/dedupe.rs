@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use egui::Button;
+
+use crate::DBV;
+
+use super::data_definition::DataPoint;
+
+/// Settings for finding points that are the same as an earlier point
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct Dedupe {
+    /// Coordinates within the tolerance of each other count as equal
+    pub is_approximate: bool,
+    /// Largest difference allowed on each axis when approximate
+    pub tolerance: f64,
+    /// Points with different labels at the same place are also duplicates
+    pub should_ignore_labels: bool,
+}
+
+impl Default for Dedupe {
+    fn default() -> Self {
+        Self {
+            is_approximate: false,
+            tolerance: 1e-6,
+            should_ignore_labels: false,
+        }
+    }
+}
+
+impl Dedupe {
+    /// Indices of the points that duplicate a point with a lower index (The first is kept)
+    fn duplicate_indices(&self, points: &[DataPoint]) -> Vec<usize> {
+        let tolerance = if self.is_approximate {
+            self.tolerance.max(0.0)
+        } else {
+            0.0
+        };
+        let is_match = |a: &DataPoint, b: &DataPoint| {
+            (a.x0 - b.x0).abs() <= tolerance
+                && (a.x1 - b.x1).abs() <= tolerance
+                && (self.should_ignore_labels || a.label == b.label)
+        };
+        // Kept points by cell so only nearby cells need to be checked
+        let mut kept: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        let mut result = vec![];
+        for (i, point) in points.iter().enumerate() {
+            let cell = if tolerance > 0.0 {
+                (
+                    (point.x0 / tolerance).floor() as i64,
+                    (point.x1 / tolerance).floor() as i64,
+                )
+            } else {
+                // Adding 0.0 makes -0.0 and 0.0 the same cell
+                (
+                    (point.x0 + 0.0).to_bits() as i64,
+                    (point.x1 + 0.0).to_bits() as i64,
+                )
+            };
+            let reach = if tolerance > 0.0 { 1 } else { 0 };
+            let is_duplicate = (-reach..=reach).any(|d0| {
+                (-reach..=reach).any(|d1| {
+                    kept.get(&(cell.0.wrapping_add(d0), cell.1.wrapping_add(d1)))
+                        .is_some_and(|cell| cell.iter().any(|&j| is_match(&points[j], point)))
+                })
+            });
+            if is_duplicate {
+                result.push(i);
+            } else {
+                kept.entry(cell).or_default().push(i);
+            }
+        }
+        result
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_menu_remove_duplicates(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Remove Duplicates", |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.dedupe.is_approximate, "Approximately equal");
+                    ui.add_enabled(
+                        self.dedupe.is_approximate,
+                        egui::DragValue::new(&mut self.dedupe.tolerance)
+                            .speed(0.001)
+                            .clamp_range(0.0..=f64::INFINITY)
+                            .prefix("tolerance: "),
+                    );
+                });
+                ui.checkbox(&mut self.dedupe.should_ignore_labels, "Ignore labels")
+                    .on_hover_text("Points at the same place are duplicates even if their labels differ");
+                if ui
+                    .add(Button::new("Remove"))
+                    .on_hover_text(
+                        "The first of each set of duplicates is kept (Locked points are not removed)",
+                    )
+                    .clicked()
+                {
+                    let duplicates = self.dedupe.duplicate_indices(self.data.points());
+                    let count_before = self.data.points().len();
+                    self.data.delete_many(duplicates);
+                    self.selection.keep_for(&self.data);
+                    self.status_msg.info(format!(
+                        "Removed {} duplicate points",
+                        count_before - self.data.points().len()
+                    ));
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn keeps_the_first_of_each_duplicate() {
+        let points = [
+            DataPoint::new(0.0, 1.0, DataLabel::Normal),
+            DataPoint::new(-0.0, 1.0, DataLabel::Normal),
+            DataPoint::new(0.0, 1.0, DataLabel::Anomaly),
+            DataPoint::new(0.0, 1.0 + 1e-9, DataLabel::Normal),
+            DataPoint::new(5.0, 5.0, DataLabel::Normal),
+        ];
+        let mut dedupe = Dedupe::default();
+        assert_eq!(dedupe.duplicate_indices(&points), [1]);
+        dedupe.should_ignore_labels = true;
+        assert_eq!(dedupe.duplicate_indices(&points), [1, 2]);
+        dedupe.is_approximate = true;
+        assert_eq!(dedupe.duplicate_indices(&points), [1, 2, 3]);
+    }
+}
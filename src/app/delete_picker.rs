@@ -0,0 +1,226 @@
+use egui::{Button, Pos2};
+use egui_plot::{MarkerShape, PlotPoint, PlotUi, Points};
+
+use crate::DBV;
+
+use super::data_definition::{DataLabel, DataTimestamp, DistanceCalculation as _, PointArray};
+
+/// What to do when a delete click has more than one point within the pick radius
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DeleteTieBreak {
+    /// Delete the closest point without asking
+    Nearest,
+    /// Show a popup listing the points to choose from
+    #[default]
+    Ask,
+    /// Delete every point within the pick radius
+    DeleteAll,
+}
+
+impl DeleteTieBreak {
+    const ALL: [Self; 3] = [Self::Nearest, Self::Ask, Self::DeleteAll];
+
+    fn description(&self) -> &'static str {
+        match self {
+            DeleteTieBreak::Nearest => "Delete nearest",
+            DeleteTieBreak::Ask => "Ask which to delete",
+            DeleteTieBreak::DeleteAll => "Delete all under cursor",
+        }
+    }
+}
+
+/// Points under the cursor waiting for the user to choose which to delete
+#[derive(PartialEq, Debug)]
+pub struct PendingDelete {
+    /// Version of the data the indices are for
+    timestamp: DataTimestamp,
+    /// Ordered by distance from the click
+    indices: Vec<usize>,
+    /// Where the popup is shown
+    screen_pos: Pos2,
+}
+
+impl DBV {
+    pub(super) fn ui_delete_tie_break_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("When deleting with multiple points under the cursor");
+            egui::ComboBox::from_id_source("id-delete-tie-break")
+                .selected_text(self.delete_tie_break.description())
+                .show_ui(ui, |ui| {
+                    for value in DeleteTieBreak::ALL {
+                        ui.selectable_value(&mut self.delete_tie_break, value, value.description());
+                    }
+                });
+            ui.add(
+                egui::DragValue::new(&mut self.delete_pick_radius)
+                    .clamp_range(1.0..=100.0)
+                    .prefix("Pick radius (screen points): "),
+            );
+        });
+    }
+
+    /// Returns the shown points with the label that a delete click at the coordinate would remove
+    /// (or offer to remove). More than one only if several are within the pick radius
+    fn delete_targets(
+        &self,
+        coord: PlotPoint,
+        label: DataLabel,
+        units_per_screen_point: Option<f64>,
+    ) -> Vec<usize> {
+        let mut candidates = match units_per_screen_point {
+            Some(units) if self.delete_tie_break != DeleteTieBreak::Nearest => {
+                self.data.indices_within(
+                    [coord.x, coord.y],
+                    self.delete_pick_radius as f64 * units,
+                    Some(label),
+                )
+            }
+            _ => vec![],
+        };
+        candidates.retain(|&i| self.is_point_shown(i));
+        if candidates.len() < 2 {
+            self.data
+                .get_closest_point(coord, |i, p| p.label == label && self.is_point_shown(i))
+                .into_iter()
+                .collect()
+        } else {
+            candidates
+        }
+    }
+
+    /// Deletes the point(s) under the cursor according to the tie-breaking setting
+    pub(super) fn delete_on_click(
+        &mut self,
+        response: &egui::Response,
+        pointer_coordinate: Option<egui_plot::PlotPoint>,
+        label: DataLabel,
+    ) {
+        let candidates = match pointer_coordinate {
+            Some(coord) => self.delete_targets(
+                coord,
+                label,
+                self.plot_bounds
+                    .map(|bounds| bounds.width() / response.rect.width() as f64),
+            ),
+            None => vec![],
+        };
+        if candidates.len() < 2 {
+            // Nothing ambiguous, keep the original nearest point behaviour
+            let nearest = candidates.first().copied();
+            self.data.delete(
+                pointer_coordinate,
+                |i, _| Some(i) == nearest,
+                &mut self.status_msg,
+            );
+            return;
+        }
+        match self.delete_tie_break {
+            DeleteTieBreak::Nearest => unreachable!("no candidates are found for nearest"),
+            DeleteTieBreak::Ask => {
+                self.pending_delete = Some(PendingDelete {
+                    timestamp: self.data.timestamp(),
+                    indices: candidates,
+                    screen_pos: response
+                        .interact_pointer_pos()
+                        .unwrap_or(response.rect.center()),
+                })
+            }
+            DeleteTieBreak::DeleteAll => self.data.delete_many(candidates),
+        }
+    }
+
+    pub(super) fn ui_pending_delete_popup(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.pending_delete else {
+            return;
+        };
+        if pending.timestamp != self.data.timestamp() {
+            // Indices are no longer valid
+            self.pending_delete = None;
+            return;
+        }
+        let mut to_delete = None;
+        let mut should_cancel = false;
+        let mut is_open = true;
+        egui::Window::new("Points Under Cursor")
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .fixed_pos(pending.screen_pos)
+            .show(ctx, |ui| {
+                egui::Grid::new("grid-pending-delete")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for &i in pending.indices.iter() {
+                            let point = self.data.points()[i];
+                            ui.label(format!(
+                                "{} {}",
+                                self.display_precision.format_pair([point.x0, point.x1]),
+                                point.label
+                            ));
+                            if ui.button("Delete").clicked() {
+                                to_delete = Some(vec![i]);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(Button::new(format!("Delete All {}", pending.indices.len())))
+                        .clicked()
+                    {
+                        to_delete = Some(pending.indices.clone());
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+        if let Some(indices) = to_delete {
+            self.data.delete_many(indices);
+            self.pending_delete = None;
+        } else if !is_open || should_cancel {
+            self.pending_delete = None;
+        }
+    }
+
+    /// Rings the points a primary (large ring) or secondary (small ring) click would delete
+    pub(super) fn plot_delete_preview(&self, plot_ui: &mut PlotUi) {
+        if self.click_mode != super::ClickMode::DeletePoints
+            || !plot_ui.response().hovered()
+            || self.pending_delete.is_some()
+        {
+            return;
+        }
+        let Some(pointer) = plot_ui.pointer_coordinate() else {
+            return;
+        };
+        let units_per_screen_point =
+            plot_ui.plot_bounds().width() / plot_ui.response().rect.width() as f64;
+        for (label, name, radius_factor) in [
+            (self.primary_click_label, "Deleted by primary click", 2.4),
+            (
+                self.secondary_click_label(),
+                "Deleted by secondary click",
+                1.7,
+            ),
+        ] {
+            let targets: Vec<PointArray> = self
+                .delete_targets(pointer, label, Some(units_per_screen_point))
+                .into_iter()
+                .map(|i| self.data.points()[i].to_array())
+                .collect();
+            if targets.is_empty() {
+                continue;
+            }
+            let color = self.label_color(label);
+            plot_ui.points(
+                Points::new(targets)
+                    .name(name)
+                    .shape(MarkerShape::Circle)
+                    .filled(false)
+                    .radius(self.marker_radius * radius_factor)
+                    .color(color),
+            );
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::Write as _;
+
+/// Controls how many decimal places are shown for numbers in the UI (Stored values are not changed)
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Copy, Debug)]
+pub struct DisplayPrecision {
+    /// `None` shows the full precision
+    pub decimal_places: Option<u8>,
+}
+
+impl Default for DisplayPrecision {
+    fn default() -> Self {
+        Self {
+            decimal_places: Some(3),
+        }
+    }
+}
+
+impl DisplayPrecision {
+    pub const MAX_DECIMAL_PLACES: u8 = 15;
+
+    pub fn format(&self, value: f64) -> String {
+        let mut text = String::new();
+        self.format_into(&mut text, value);
+        text
+    }
+
+    /// Replaces the contents of `text` with the value (Reuses the allocation of `text`)
+    pub fn format_into(&self, text: &mut String, value: f64) {
+        text.clear();
+        let result = match self.decimal_places {
+            Some(places) => write!(text, "{value:.*}", places as usize),
+            None => write!(text, "{value}"),
+        };
+        result.expect("writing to a String does not fail");
+    }
+
+    /// Formats a pair of values as `(x0, x1)`
+    pub fn format_pair(&self, [x0, x1]: [f64; 2]) -> String {
+        format!("({}, {})", self.format(x0), self.format(x1))
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut is_limited = self.decimal_places.is_some();
+            ui.checkbox(&mut is_limited, "Limit displayed decimal places")
+                .on_hover_text("Only changes how values are shown, not the values stored");
+            match (is_limited, self.decimal_places.as_mut()) {
+                (true, Some(places)) => {
+                    ui.add(egui::Slider::new(places, 0..=Self::MAX_DECIMAL_PLACES));
+                }
+                (true, None) => self.decimal_places = Self::default().decimal_places,
+                (false, _) => self.decimal_places = None,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_respects_setting() {
+        let mut precision = DisplayPrecision::default();
+        assert_eq!(precision.format(1.23456), "1.235");
+        precision.decimal_places = Some(0);
+        assert_eq!(precision.format_pair([1.6, -2.4]), "(2, -2)");
+        precision.decimal_places = None;
+        assert_eq!(precision.format(1.23456), "1.23456");
+    }
+}
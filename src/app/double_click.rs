@@ -0,0 +1,119 @@
+use egui_plot::PlotPoint;
+
+use crate::DBV;
+
+use super::{
+    plot_zoom_reset::{MinMaxPair, ZoomTarget},
+    DuringEditPoint,
+};
+
+/// What a primary double click on the plot does
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DoubleClickAction {
+    #[default]
+    Nothing,
+    /// Starts editing the nearest point in the table
+    EditNearest,
+    /// Gives the nearest point the opposite label
+    ToggleLabel,
+    /// Centers the plot on the cursor and zooms in
+    ZoomToCursor,
+}
+
+impl DoubleClickAction {
+    const ALL: [Self; 4] = [
+        Self::Nothing,
+        Self::EditNearest,
+        Self::ToggleLabel,
+        Self::ZoomToCursor,
+    ];
+
+    /// Screen distance from the cursor that the nearest point must be within
+    const PICK_RADIUS: f64 = 10.0;
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::Nothing => "Nothing",
+            Self::EditNearest => "Edit nearest point",
+            Self::ToggleLabel => "Toggle label of nearest point",
+            Self::ZoomToCursor => "Zoom to cursor",
+        }
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_double_click_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Double click on the plot");
+            egui::ComboBox::from_id_source("id-double-click-action")
+                .selected_text(self.double_click_action.description())
+                .show_ui(ui, |ui| {
+                    for value in DoubleClickAction::ALL {
+                        ui.selectable_value(
+                            &mut self.double_click_action,
+                            value,
+                            value.description(),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text("What the first click of the double click changed is undone first");
+        });
+    }
+
+    /// Returns `true` if the double click was handled (The clicks should then be ignored)
+    pub(super) fn double_click_on(
+        &mut self,
+        response: &egui::Response,
+        pointer_coordinate: Option<PlotPoint>,
+    ) -> bool {
+        if !response.double_clicked() || self.double_click_action == DoubleClickAction::Nothing {
+            return false;
+        }
+        // The first click was handled as a single click so take back what it did
+        if self.click_timestamp.take() == Some(self.data.timestamp()) {
+            self.data.undo(&mut self.status_msg);
+        }
+        self.pending_delete = None;
+
+        let Some(coord) = pointer_coordinate else {
+            return true;
+        };
+        let Some(bounds) = self.plot_bounds else {
+            return true;
+        };
+        let units_per_screen_point = bounds.width() / response.rect.width() as f64;
+        let nearest = self.data.nearest_within(
+            [coord.x, coord.y],
+            DoubleClickAction::PICK_RADIUS * units_per_screen_point,
+            |i| self.is_point_shown(i),
+        );
+        match (self.double_click_action, nearest) {
+            (DoubleClickAction::Nothing, _) => unreachable!("checked above"),
+            (DoubleClickAction::EditNearest | DoubleClickAction::ToggleLabel, None) => {
+                self.status_msg.info("No point under the cursor")
+            }
+            (DoubleClickAction::EditNearest, Some(index)) => {
+                self.edit_point = Some(DuringEditPoint {
+                    index,
+                    point: self.data.points()[index],
+                });
+                self.status_msg
+                    .info(format!("Editing row {index} in the table"));
+            }
+            (DoubleClickAction::ToggleLabel, Some(index)) => {
+                let label = self.data.points()[index].label.opposite();
+                self.data.set_label([index], label);
+                self.selection.keep_for(&self.data);
+            }
+            (DoubleClickAction::ZoomToCursor, _) => {
+                let [half_width, half_height] = [bounds.width() / 4.0, bounds.height() / 4.0];
+                self.start_zoom_reset(ZoomTarget::View(MinMaxPair {
+                    min: [coord.x - half_width, coord.y - half_height],
+                    max: [coord.x + half_width, coord.y + half_height],
+                }));
+            }
+        }
+        true
+    }
+}
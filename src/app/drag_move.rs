@@ -0,0 +1,88 @@
+use egui::PointerButton;
+use egui_plot::{Line, MarkerShape, PlotPoints, PlotUi, Points};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataTimestamp, DistanceCalculation as _, PointArray},
+    ui_blocks::drag_start_position,
+};
+
+/// A point picked up in move mode and where it would be dropped
+#[derive(Debug, PartialEq)]
+pub struct MovingPoint {
+    index: usize,
+    position: PointArray,
+    /// Data version the index refers to (The move is dropped if the data changes)
+    timestamp: DataTimestamp,
+}
+
+impl DBV {
+    /// Picks up the point nearest to where a primary drag starts and moves it on release
+    ///
+    /// The move is recorded as a single edit so it can be undone in one step
+    pub(super) fn plot_drag_move(&mut self, plot_ui: &mut PlotUi) {
+        if !self.click_mode.is_move_points() {
+            self.moving_point = None;
+            return;
+        }
+        let response = plot_ui.response().clone();
+        if response.drag_started_by(PointerButton::Primary) {
+            let press = drag_start_position(plot_ui);
+            let units_per_screen_point =
+                plot_ui.plot_bounds().width() / response.rect.width() as f64;
+            self.moving_point = press.and_then(|press| {
+                // Hidden points are skipped so only what is seen can be picked up
+                let index = self.data.nearest_within(
+                    press,
+                    self.marker_radius as f64 * units_per_screen_point,
+                    |i| self.is_point_shown(i),
+                )?;
+                Some(MovingPoint {
+                    index,
+                    position: self.data.points()[index].to_array(),
+                    timestamp: self.data.timestamp(),
+                })
+            });
+            if self.moving_point.is_none() {
+                self.status_msg.info("No point under the cursor to move");
+            }
+        }
+        let is_finished = response.drag_stopped_by(PointerButton::Primary);
+        let Some(moving) = self.moving_point.as_mut() else {
+            return;
+        };
+        if moving.timestamp != self.data.timestamp() {
+            // Changed by something else during the drag (e.g. undo) so the index may be stale
+            self.moving_point = None;
+            return;
+        }
+        if let Some(pointer) = plot_ui.pointer_coordinate() {
+            moving.position = [pointer.x, pointer.y];
+        }
+
+        if is_finished {
+            let MovingPoint {
+                index, position, ..
+            } = *moving;
+            self.moving_point = None;
+            self.data.move_point(index, position, &mut self.status_msg);
+            return;
+        }
+
+        let start = self.data.points()[moving.index].to_array();
+        plot_ui.line(
+            Line::new(PlotPoints::new(vec![start, moving.position]))
+                .name("Moving Point")
+                .color(self.color_selection),
+        );
+        plot_ui.points(
+            Points::new(vec![moving.position])
+                .name("Moving Point")
+                .radius(self.marker_radius)
+                .shape(MarkerShape::Circle)
+                .filled(false)
+                .color(self.color_selection),
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use egui::PointerButton;
+use egui_plot::{Line, PlotPoints, PlotUi, Polygon};
+
+use crate::DBV;
+
+use super::data_definition::PointArray;
+
+/// The shape drawn by dragging when in select mode
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum SelectShape {
+    #[default]
+    Rectangle,
+    /// Freehand outline that is closed by joining the end to the start
+    Lasso,
+}
+
+/// The selection shape being dragged in plot coordinates
+#[derive(Debug, PartialEq)]
+pub enum DragSelect {
+    Rectangle { start: PointArray },
+    Lasso(Vec<PointArray>),
+}
+
+impl DBV {
+    pub(super) fn ui_select_shape(&mut self, ui: &mut egui::Ui) {
+        ui.selectable_value(&mut self.select_shape, SelectShape::Rectangle, "Rectangle");
+        ui.selectable_value(&mut self.select_shape, SelectShape::Lasso, "Lasso");
+    }
+
+    /// Tracks a primary drag in select mode and selects the points inside the shape on release
+    ///
+    /// Holding shift when the drag ends adds to the selection instead of replacing it
+    pub(super) fn plot_drag_select(&mut self, plot_ui: &mut PlotUi) {
+        if !self.click_mode.is_select_points() {
+            self.drag_select = None;
+            return;
+        }
+        let response = plot_ui.response();
+        let pointer = plot_ui
+            .pointer_coordinate()
+            .or(self.last_cursor_pos)
+            .map(|x| [x.x, x.y]);
+        if response.drag_started_by(PointerButton::Primary) {
+            self.drag_select = pointer.map(|start| match self.select_shape {
+                SelectShape::Rectangle => DragSelect::Rectangle { start },
+                SelectShape::Lasso => DragSelect::Lasso(vec![start]),
+            });
+        }
+        let is_finished = response.drag_stopped_by(PointerButton::Primary);
+        let Some(drag_select) = self.drag_select.as_mut() else {
+            return;
+        };
+
+        let outline = match drag_select {
+            DragSelect::Rectangle { start } => {
+                let start = *start;
+                let end = pointer.unwrap_or(start);
+                vec![start, [end[0], start[1]], end, [start[0], end[1]]]
+            }
+            DragSelect::Lasso(path) => {
+                if let Some(pointer) = pointer {
+                    if path.last() != Some(&pointer) {
+                        path.push(pointer);
+                    }
+                }
+                path.clone()
+            }
+        };
+
+        if is_finished {
+            let mut indices = match self.drag_select.take() {
+                Some(DragSelect::Rectangle { .. }) => {
+                    self.data.indices_in_rect(outline[0], outline[2])
+                }
+                _ => self.data.indices_in_polygon(&outline),
+            };
+            if plot_ui.ctx().input(|i| i.modifiers.shift) {
+                self.selection.sync(&self.data);
+                indices.extend(self.selection.indices().iter().copied());
+            }
+            self.selection.set(&self.data, indices);
+            return;
+        }
+
+        if matches!(self.drag_select, Some(DragSelect::Lasso(_))) {
+            // Drawn as a line because the polygon fill does not work for concave shapes
+            let mut closed = outline;
+            closed.extend(closed.first().copied());
+            plot_ui.line(
+                Line::new(PlotPoints::new(closed))
+                    .name("Selection Lasso")
+                    .color(self.color_selection),
+            );
+        } else {
+            plot_ui.polygon(
+                Polygon::new(PlotPoints::new(outline))
+                    .name("Selection Rectangle")
+                    .stroke(egui::Stroke::new(1.0, self.color_selection))
+                    .fill_color(self.color_selection.gamma_multiply(0.1)),
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::BTreeSet;
+
+use egui::PointerButton;
+use egui_plot::{Line, MarkerShape, PlotPoints, PlotUi, Points};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataTimestamp, DistanceCalculation as _, PointArray},
+    ui_blocks::drag_start_position,
+};
+
+/// Settings for deleting points by dragging in delete mode
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(default)]
+pub struct Eraser {
+    /// Dragging erases instead of panning the plot
+    pub is_enabled: bool,
+    /// Screen distance from the path that points are erased within
+    pub radius: f32,
+}
+
+impl Default for Eraser {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            radius: 15.0,
+        }
+    }
+}
+
+/// The points swept by the drag in progress (Deleted on release)
+#[derive(Debug, PartialEq)]
+pub struct EraserStroke {
+    indices: BTreeSet<usize>,
+    last_position: PointArray,
+    /// Data version the indices refer to (The stroke is dropped if the data changes)
+    timestamp: DataTimestamp,
+}
+
+impl DBV {
+    pub(super) fn ui_eraser_options(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.eraser.is_enabled, "Erase by dragging")
+            .on_hover_text("Deletes every point near the path of the drag as one undoable change");
+        if self.eraser.is_enabled {
+            ui.add(
+                egui::DragValue::new(&mut self.eraser.radius)
+                    .clamp_range(1.0..=500.0)
+                    .prefix("Radius: "),
+            )
+            .on_hover_text("Distance on screen from the path");
+        }
+    }
+
+    /// Returns `true` if primary drags erase points instead of panning the plot
+    pub(super) fn is_erasing(&self) -> bool {
+        self.eraser.is_enabled && self.click_mode == super::ClickMode::DeletePoints
+    }
+
+    /// Collects the points near a primary drag and deletes them on release
+    pub(super) fn plot_eraser(&mut self, plot_ui: &mut PlotUi) {
+        if !self.is_erasing() {
+            self.eraser_stroke = None;
+            return;
+        }
+        let response = plot_ui.response().clone();
+        let radius = self.eraser.radius as f64 * plot_ui.plot_bounds().width()
+            / response.rect.width() as f64;
+        let pointer = plot_ui.pointer_coordinate().map(|x| [x.x, x.y]);
+        if response.drag_started_by(PointerButton::Primary) {
+            self.eraser_stroke = drag_start_position(plot_ui).map(|start| EraserStroke {
+                indices: Default::default(),
+                last_position: start,
+                timestamp: self.data.timestamp(),
+            });
+        }
+        let is_finished = response.drag_stopped_by(PointerButton::Primary);
+
+        // Taken out during the update so hidden points can be skipped while adding to it
+        if let Some(mut stroke) = self.eraser_stroke.take() {
+            if stroke.timestamp != self.data.timestamp() {
+                // Changed by something else during the drag (e.g. undo) so the indices may be stale
+                return;
+            }
+            // Samples the path so fast movements do not skip points
+            let end = pointer.unwrap_or(stroke.last_position);
+            let start = stroke.last_position;
+            let distance = (end[0] - start[0]).hypot(end[1] - start[1]);
+            let steps = (distance / (radius / 2.0).max(f64::EPSILON))
+                .ceil()
+                .max(1.0) as usize;
+            for step in 0..=steps {
+                let t = step as f64 / steps as f64;
+                let position = [
+                    start[0] + (end[0] - start[0]) * t,
+                    start[1] + (end[1] - start[1]) * t,
+                ];
+                stroke.indices.extend(
+                    self.data
+                        .indices_within(position, radius, None)
+                        .into_iter()
+                        .filter(|&i| self.is_point_shown(i)),
+                );
+            }
+            stroke.last_position = end;
+
+            if is_finished {
+                let indices = stroke.indices;
+                let count_before = self.data.points().len();
+                self.data.delete_many(indices);
+                let count = count_before - self.data.points().len();
+                self.status_msg.info(format!("Erased {count} point(s)"));
+                return;
+            }
+
+            let erased: Vec<PointArray> = stroke
+                .indices
+                .iter()
+                .map(|&i| self.data.points()[i].to_array())
+                .collect();
+            plot_ui.points(
+                Points::new(erased)
+                    .name("Erasing")
+                    .shape(MarkerShape::Cross)
+                    .radius(self.marker_radius * 1.5)
+                    .color(self.color_selection),
+            );
+            self.eraser_stroke = Some(stroke);
+        }
+
+        if let Some([x, y]) = pointer {
+            plot_ui.line(
+                Line::new(PlotPoints::from_parametric_callback(
+                    |t| (x + radius * t.cos(), y + radius * t.sin()),
+                    0.0..=std::f64::consts::TAU,
+                    64,
+                ))
+                .name("Eraser")
+                .color(self.color_selection),
+            );
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use anyhow::Context as _;
+use egui::Button;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoint},
+    execute, file_handle_to_path,
+    local_experiments::ModelInference,
+    operational_state::{OperationOutcome, OperationalState, Payload},
+    prediction_classification::prediction_classification,
+};
+
+/// A row of the results export (The point along with the model's output for it)
+#[derive(serde::Serialize)]
+struct ResultRow {
+    x0: f64,
+    x1: f64,
+    label: DataLabel,
+    score: f64,
+    prediction: DataLabel,
+    classification: String,
+}
+
+/// Writes the points with the scores and predictions of the model as CSV
+///
+/// ASSUMPTION: The model was trained on `points`
+pub(super) fn results_csv(
+    points: &[DataPoint],
+    model: &dyn ModelInference,
+) -> anyhow::Result<Vec<u8>> {
+    let mut write_buffer = Vec::new();
+    let mut wtr = csv::Writer::from_writer(&mut write_buffer);
+    for (i, point) in points.iter().enumerate() {
+        let prediction = model.prediction_on_training_data(i);
+        wtr.serialize(ResultRow {
+            x0: point.x0,
+            x1: point.x1,
+            label: point.label,
+            score: model.score_for_training_data(i),
+            prediction,
+            classification: prediction_classification(point.label, prediction).to_string(),
+        })?;
+    }
+    wtr.flush().context("failed flushing csv writer")?;
+    drop(wtr);
+    Ok(write_buffer)
+}
+
+/// A row copied from the table (Without results)
+#[derive(serde::Serialize)]
+struct CopiedRow {
+    row: usize,
+    x0: f64,
+    x1: f64,
+    label: DataLabel,
+}
+
+/// A row copied from the table along with the model's output for it
+#[derive(serde::Serialize)]
+struct CopiedResultRow {
+    row: usize,
+    x0: f64,
+    x1: f64,
+    label: DataLabel,
+    score: f64,
+    prediction: DataLabel,
+    classification: String,
+}
+
+/// Writes the rows at the indices as CSV including the results if there is a model
+///
+/// ASSUMPTION: The model was trained on `points`
+pub(super) fn rows_csv(
+    points: &[DataPoint],
+    indices: impl IntoIterator<Item = usize>,
+    model: Option<&dyn ModelInference>,
+) -> anyhow::Result<String> {
+    let mut write_buffer = Vec::new();
+    let mut wtr = csv::Writer::from_writer(&mut write_buffer);
+    for row in indices {
+        let point = points[row];
+        match model {
+            Some(model) => {
+                let prediction = model.prediction_on_training_data(row);
+                wtr.serialize(CopiedResultRow {
+                    row,
+                    x0: point.x0,
+                    x1: point.x1,
+                    label: point.label,
+                    score: model.score_for_training_data(row),
+                    prediction,
+                    classification: prediction_classification(point.label, prediction).to_string(),
+                })?
+            }
+            None => wtr.serialize(CopiedRow {
+                row,
+                x0: point.x0,
+                x1: point.x1,
+                label: point.label,
+            })?,
+        }
+    }
+    wtr.flush().context("failed flushing csv writer")?;
+    drop(wtr);
+    String::from_utf8(write_buffer).context("csv was not valid UTF-8")
+}
+
+impl DBV {
+    pub(super) fn ui_btn_export_results(&mut self, ui: &mut egui::Ui) {
+        let button = Button::new("Export Results...");
+        let response = if self.loc_inference_model().is_some() {
+            ui.add(button)
+                .on_hover_text("Saves the points with the score, prediction and classification from the trained model as CSV")
+        } else {
+            ui.add_enabled(false, button)
+                .on_disabled_hover_text("Requires a model trained on the current data")
+        };
+        if response.clicked() {
+            self.export_results(ui.ctx().clone());
+            ui.close_menu();
+        }
+    }
+
+    fn export_results(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let Some(model) = self.loc_inference_model() else {
+            self.status_msg
+                .error_display("Unable to export results. No model trained on the current data");
+            return;
+        };
+        let bytes = match results_csv(self.data.points(), model)
+            .context("failed to convert results to CSV")
+        {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.status_msg.error_debug(e);
+                return;
+            }
+        };
+        let data_hash = self.data.content_hash();
+        self.op_state = OperationalState::Saving(execute(async move {
+            let dialog = rfd::AsyncFileDialog::new()
+                .set_title("Export Results as")
+                .add_filter("CSV", &["csv"]);
+            #[cfg(target_arch = "wasm32")]
+            let dialog = dialog.set_file_name("results.csv");
+            let Some(file) = dialog.save_file().await else {
+                // user canceled
+                ctx.request_repaint();
+                return OperationOutcome::Cancelled;
+            };
+            let path = file_handle_to_path(&file);
+            let result = match file.write(&bytes).await.context("failed to export results") {
+                Ok(()) => OperationOutcome::Success(Payload::Export(path, data_hash)),
+                Err(e) => OperationOutcome::Failed(e),
+            };
+
+            ctx.request_repaint();
+
+            result
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::{DataTimestamp, PointArray};
+
+    use super::*;
+
+    /// Predicts anomaly for points with a score above 0.5
+    struct FixedScores(Vec<f64>);
+
+    impl ModelInference for FixedScores {
+        fn data_timestamp_at_training(&self) -> DataTimestamp {
+            unimplemented!("not needed for export")
+        }
+
+        fn prediction_on_training_data(&self, index: usize) -> DataLabel {
+            self.prediction_for_score(self.0[index])
+        }
+
+        fn score_for_training_data(&self, index: usize) -> f64 {
+            self.0[index]
+        }
+
+        fn score(&self, _point: PointArray, _training_points: &[DataPoint]) -> f64 {
+            unimplemented!("not needed for export")
+        }
+
+        fn prediction_for_score(&self, score: f64) -> DataLabel {
+            if score > 0.5 {
+                DataLabel::Anomaly
+            } else {
+                DataLabel::Normal
+            }
+        }
+
+        fn decision_threshold(&self) -> f64 {
+            0.5
+        }
+    }
+
+    #[test]
+    fn results_include_model_output() {
+        let points = [
+            DataPoint::new(1.0, 2.0, DataLabel::Normal),
+            DataPoint::new(3.0, 4.0, DataLabel::Normal),
+            DataPoint::new(5.0, 6.0, DataLabel::Anomaly),
+        ];
+        let model = FixedScores(vec![0.25, 0.75, 1.0]);
+        let actual = String::from_utf8(results_csv(&points, &model).unwrap()).unwrap();
+        let expected = "x0,x1,label,score,prediction,classification\n\
+                        1.0,2.0,0,0.25,0,TN\n\
+                        3.0,4.0,0,0.75,1,FP\n\
+                        5.0,6.0,1,1.0,1,TP\n";
+        assert_eq!(actual, expected);
+
+        assert_eq!(
+            rows_csv(&points, [2], Some(&model)).unwrap(),
+            "row,x0,x1,label,score,prediction,classification\n2,5.0,6.0,1,1.0,1,TP\n"
+        );
+        assert_eq!(
+            rows_csv(&points, [0, 1], None).unwrap(),
+            "row,x0,x1,label\n0,1.0,2.0,0\n1,3.0,4.0,0\n"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Display;
+
+use egui::Button;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoints},
+    prediction_classification::{prediction_classification, Classification},
+};
+
+/// The points to include when exporting part of the data
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum ExportSubset {
+    #[default]
+    Selection,
+    Label(DataLabel),
+    /// Requires a trained model that is up to date with the data
+    Classification(Classification),
+}
+
+impl ExportSubset {
+    const ALL: [Self; 7] = [
+        Self::Selection,
+        Self::Label(DataLabel::Normal),
+        Self::Label(DataLabel::Anomaly),
+        Self::Classification(Classification::FalsePositive),
+        Self::Classification(Classification::FalseNegative),
+        Self::Classification(Classification::TruePositive),
+        Self::Classification(Classification::TrueNegative),
+    ];
+}
+
+impl Display for ExportSubset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportSubset::Selection => write!(f, "Selected points"),
+            ExportSubset::Label(label) => write!(f, "Only {label}"),
+            ExportSubset::Classification(classification) => write!(f, "Only {classification}"),
+        }
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_btn_export_subset(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(
+                self.op_state.is_normal() && !self.data.is_empty(),
+                Button::new("Export Subset..."),
+            )
+            .clicked()
+        {
+            self.show_export_subset = true;
+            ui.close_menu();
+        }
+    }
+
+    /// Returns the points in the subset or `None` if the subset is not available
+    fn subset_points(&self, subset: ExportSubset) -> Option<DataPoints> {
+        let points = self.data.points();
+        match subset {
+            ExportSubset::Selection => Some(
+                self.selection
+                    .indices()
+                    .iter()
+                    .filter_map(|&i| points.get(i).copied())
+                    .collect(),
+            ),
+            ExportSubset::Label(label) => Some(
+                points
+                    .iter()
+                    .filter(|point| point.label == label)
+                    .copied()
+                    .collect(),
+            ),
+            ExportSubset::Classification(classification) => {
+                let model = self.loc_inference_model()?;
+                Some(
+                    points
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, point)| {
+                            prediction_classification(
+                                point.label,
+                                model.prediction_on_training_data(*i),
+                            ) == classification
+                        })
+                        .map(|(_, point)| *point)
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    pub(super) fn ui_export_subset_window(&mut self, ctx: &egui::Context) {
+        if !self.show_export_subset {
+            return;
+        }
+        let mut is_open = true;
+        let mut to_export = None;
+        egui::Window::new("Export Subset")
+            .open(&mut is_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                for subset in ExportSubset::ALL {
+                    let count = self.subset_points(subset).map(|x| x.len());
+                    let text = match count {
+                        Some(count) => format!("{subset} ({count})"),
+                        None => format!("{subset} (Requires an up to date trained model)"),
+                    };
+                    ui.add_enabled_ui(count.is_some(), |ui| {
+                        ui.radio_value(&mut self.export_subset, subset, text);
+                    });
+                }
+                let points = self.subset_points(self.export_subset).unwrap_or_default();
+                if ui
+                    .add_enabled(
+                        !points.is_empty() && self.op_state.is_normal(),
+                        Button::new("Export..."),
+                    )
+                    .clicked()
+                {
+                    to_export = Some(points);
+                }
+            });
+        if let Some(points) = to_export {
+            self.export_points(ctx.clone(), points);
+            is_open = false;
+        }
+        if !is_open {
+            self.show_export_subset = false;
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use egui::Button;
+use egui_plot::{MarkerShape, PlotUi, Points};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{
+        content_hash, parse_points_text, DataLabel, DataPoint, DataPoints, DataTimestamp,
+        ParsedPoints, PointArray,
+    },
+    execute, file_handle_to_path,
+    local_experiments::ModelInference,
+    operational_state::{OperationOutcome, OperationalState, Payload},
+};
+
+/// Points from another file scored by the model without being added to the data
+#[derive(Debug, PartialEq)]
+pub struct ExternalScores {
+    path: PathBuf,
+    points: DataPoints,
+    scores: Vec<f64>,
+    /// Training state of the model the scores came from (Scored again if the model is retrained)
+    model_timestamp: DataTimestamp,
+}
+
+/// A row of the external scores export
+#[derive(serde::Serialize)]
+struct ExternalRow {
+    x0: f64,
+    x1: f64,
+    score: f64,
+    prediction: DataLabel,
+}
+
+impl ExternalScores {
+    fn new(
+        path: PathBuf,
+        points: DataPoints,
+        model: &dyn ModelInference,
+        training_points: &[DataPoint],
+    ) -> Self {
+        let scores = points
+            .iter()
+            .map(|x| model.score([x.x0, x.x1], training_points))
+            .collect();
+        Self {
+            path,
+            points,
+            scores,
+            model_timestamp: model.data_timestamp_at_training(),
+        }
+    }
+
+    fn to_csv(&self, model: &dyn ModelInference) -> anyhow::Result<Vec<u8>> {
+        let mut write_buffer = Vec::new();
+        let mut wtr = csv::Writer::from_writer(&mut write_buffer);
+        for (point, &score) in self.points.iter().zip(self.scores.iter()) {
+            wtr.serialize(ExternalRow {
+                x0: point.x0,
+                x1: point.x1,
+                score,
+                prediction: model.prediction_for_score(score),
+            })?;
+        }
+        wtr.flush().context("failed flushing csv writer")?;
+        drop(wtr);
+        Ok(write_buffer)
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_btns_external_scores(&mut self, ui: &mut egui::Ui) {
+        let has_model = self.loc_inference_model().is_some();
+        if ui
+            .add_enabled(has_model, Button::new("Score External File..."))
+            .on_hover_text("Scores the points in a CSV file (label optional) with the trained model without adding them to the data")
+            .on_disabled_hover_text("Requires a model trained on the current data")
+            .clicked()
+        {
+            self.load_external_points(ui.ctx().clone());
+            ui.close_menu();
+        }
+        if self.external_scores.is_none() {
+            return;
+        }
+        if ui
+            .add_enabled(has_model, Button::new("Export External Scores..."))
+            .on_disabled_hover_text("Requires a model trained on the current data")
+            .clicked()
+        {
+            self.export_external_scores(ui.ctx().clone());
+            ui.close_menu();
+        }
+        if ui.button("Clear External Scores").clicked() {
+            self.external_scores = None;
+            ui.close_menu();
+        }
+    }
+
+    fn load_external_points(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let progress = Default::default();
+        self.op_state = OperationalState::Loading(
+            execute(async move {
+                let Some(file) = rfd::AsyncFileDialog::new()
+                    .set_title("Score External File")
+                    .add_filter("CSV", &["csv", "txt"])
+                    .pick_file()
+                    .await
+                else {
+                    // user canceled
+                    ctx.request_repaint();
+                    return OperationOutcome::Cancelled;
+                };
+                let text = String::from_utf8_lossy(&file.read().await).into_owned();
+                let parsed = parse_points_text(&text, DataLabel::Normal);
+                ctx.request_repaint();
+                OperationOutcome::Success(Payload::ScoreExternal {
+                    parsed,
+                    path: file_handle_to_path(&file),
+                })
+            }),
+            progress,
+        );
+    }
+
+    /// Scores the points loaded from the external file with the current model
+    pub(super) fn on_external_points_loaded(&mut self, parsed: ParsedPoints, path: PathBuf) {
+        let ParsedPoints { points, errors } = parsed;
+        for error in errors {
+            self.status_msg
+                .error_display(format!("Unable to read external point. {error}"));
+        }
+        if points.is_empty() {
+            self.status_msg
+                .error_display(format!("No points found in {path:?}"));
+            return;
+        }
+        let Some(model) = self.loc_inference_model() else {
+            self.status_msg.error_display(
+                "Unable to score external points. No model trained on the current data",
+            );
+            return;
+        };
+        let scores = ExternalScores::new(path, points, model, self.data.points());
+        let anomaly_count = scores
+            .scores
+            .iter()
+            .filter(|&&x| model.prediction_for_score(x) == DataLabel::Anomaly)
+            .count();
+        self.status_msg.info(format!(
+            "Scored {} external points from {:?} ({anomaly_count} predicted anomalies)",
+            scores.points.len(),
+            scores.path
+        ));
+        self.external_scores = Some(scores);
+    }
+
+    fn export_external_scores(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let (Some(model), Some(external)) =
+            (self.loc_inference_model(), self.external_scores.as_ref())
+        else {
+            self.status_msg.error_display(
+                "Unable to export external scores. No model trained on the current data",
+            );
+            return;
+        };
+        let bytes = match external
+            .to_csv(model)
+            .context("failed to convert external scores to CSV")
+        {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.status_msg.error_debug(e);
+                return;
+            }
+        };
+        let data_hash = content_hash(&external.points);
+        self.op_state = OperationalState::Saving(execute(async move {
+            let dialog = rfd::AsyncFileDialog::new()
+                .set_title("Export External Scores as")
+                .add_filter("CSV", &["csv"]);
+            #[cfg(target_arch = "wasm32")]
+            let dialog = dialog.set_file_name("external_scores.csv");
+            let Some(file) = dialog.save_file().await else {
+                // user canceled
+                ctx.request_repaint();
+                return OperationOutcome::Cancelled;
+            };
+            let path = file_handle_to_path(&file);
+            let result = match file
+                .write(&bytes)
+                .await
+                .context("failed to export external scores")
+            {
+                Ok(()) => OperationOutcome::Success(Payload::Export(path, data_hash)),
+                Err(e) => OperationOutcome::Failed(e),
+            };
+
+            ctx.request_repaint();
+
+            result
+        }));
+    }
+
+    /// Draws the external points by their prediction (Hidden while the model is not trained on
+    /// the current data)
+    pub(super) fn plot_external_scores(&mut self, plot_ui: &mut PlotUi) {
+        let Some(external) = self.external_scores.as_mut() else {
+            return;
+        };
+        let Some(model) = self.loc_experiment.model_inference() else {
+            return;
+        };
+        if !self
+            .loc_experiment
+            .is_at_timestamp(self.data.training_timestamp())
+        {
+            return;
+        }
+        if external.model_timestamp != model.data_timestamp_at_training() {
+            *external = ExternalScores::new(
+                std::mem::take(&mut external.path),
+                std::mem::take(&mut external.points),
+                model,
+                self.data.points(),
+            );
+        }
+        let (mut normal, mut anomalies): (Vec<PointArray>, Vec<PointArray>) = (vec![], vec![]);
+        for (point, &score) in external.points.iter().zip(external.scores.iter()) {
+            match model.prediction_for_score(score) {
+                DataLabel::Normal => normal.push([point.x0, point.x1]),
+                DataLabel::Anomaly | DataLabel::Class(_) => anomalies.push([point.x0, point.x1]),
+            }
+        }
+        for (points, name, color) in [
+            (normal, "External Normal", self.color_normal),
+            (anomalies, "External Anomalies", self.color_anom),
+        ] {
+            let len = points.len();
+            plot_ui.points(
+                Points::new(points)
+                    .name(format!("{name} ({len})"))
+                    .radius(self.marker_radius)
+                    .shape(MarkerShape::Square)
+                    .color(color),
+            );
+        }
+    }
+}
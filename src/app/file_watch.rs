@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use crate::{background_worker, DBV};
+
+/// Settings for reloading the loaded file when another program changes it
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct FileWatch {
+    pub is_enabled: bool,
+    /// Reload as soon as a change is found instead of asking first
+    pub should_reload_without_asking: bool,
+    /// The file that changed waiting for the user to decide to reload it
+    #[serde(skip)]
+    changed_path: Option<PathBuf>,
+}
+
+impl DBV {
+    /// Keeps the background worker watching the last loaded or saved file and reacts to changes
+    pub(super) fn watch_loaded_file(&mut self, ctx: &egui::Context) {
+        let path = if self.file_watch.is_enabled {
+            self.last_saved_path.as_deref()
+        } else {
+            None
+        };
+        background_worker::watch_file(path, ctx);
+        let Some(changed_path) = background_worker::take_watched_file_change() else {
+            return;
+        };
+        if self.file_watch.should_reload_without_asking && self.op_state.is_normal() {
+            self.status_msg
+                .info(format!("{changed_path:?} changed on disk, reloading"));
+            self.revert_to_last_saved(ctx.clone());
+        } else {
+            self.file_watch.changed_path = Some(changed_path);
+        }
+    }
+
+    pub(super) fn ui_file_watch_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.file_watch.is_enabled, "Watch loaded file")
+                .on_hover_text(
+                    "Offers to reload the last loaded or saved file when it changes on disk",
+                );
+            if self.file_watch.is_enabled {
+                ui.checkbox(
+                    &mut self.file_watch.should_reload_without_asking,
+                    "Reload without asking",
+                );
+            }
+        });
+    }
+
+    pub(super) fn ui_file_changed_dialog(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.file_watch.changed_path.as_ref() else {
+            return;
+        };
+        let mut should_reload = false;
+        let mut should_ignore = false;
+        egui::Window::new("File Changed")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("{path:?} was changed by another program."));
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(self.op_state.is_normal(), egui::Button::new("Reload"))
+                        .on_hover_text("Can be undone like any other load")
+                        .clicked()
+                    {
+                        should_reload = true;
+                    }
+                    if ui.button("Ignore").clicked() {
+                        should_ignore = true;
+                    }
+                });
+            });
+        if should_reload {
+            self.file_watch.changed_path = None;
+            self.revert_to_last_saved(ctx.clone());
+        }
+        if should_ignore {
+            self.file_watch.changed_path = None;
+        }
+    }
+}
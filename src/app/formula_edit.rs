@@ -0,0 +1,435 @@
+use anyhow::{bail, Context as _};
+use egui::Button;
+
+use crate::DBV;
+
+use super::data_definition::DataPoint;
+
+/// A coordinate that an expression can read or write
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum FormulaColumn {
+    #[default]
+    X0,
+    X1,
+}
+
+impl FormulaColumn {
+    const ALL: [Self; 2] = [Self::X0, Self::X1];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::X0 => "x0",
+            Self::X1 => "x1",
+        }
+    }
+
+    fn get(&self, point: &DataPoint) -> f64 {
+        match self {
+            Self::X0 => point.x0,
+            Self::X1 => point.x1,
+        }
+    }
+
+    fn set(&self, point: &mut DataPoint, value: f64) {
+        match self {
+            Self::X0 => point.x0 = value,
+            Self::X1 => point.x1 = value,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Function {
+    Abs,
+    Sqrt,
+    Exp,
+    Ln,
+    Log10,
+    Sin,
+    Cos,
+    Round,
+    Floor,
+    Ceil,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "abs" => Self::Abs,
+            "sqrt" => Self::Sqrt,
+            "exp" => Self::Exp,
+            "ln" => Self::Ln,
+            "log10" => Self::Log10,
+            "sin" => Self::Sin,
+            "cos" => Self::Cos,
+            "round" => Self::Round,
+            "floor" => Self::Floor,
+            "ceil" => Self::Ceil,
+            _ => return None,
+        })
+    }
+
+    fn apply(&self, x: f64) -> f64 {
+        match self {
+            Self::Abs => x.abs(),
+            Self::Sqrt => x.sqrt(),
+            Self::Exp => x.exp(),
+            Self::Ln => x.ln(),
+            Self::Log10 => x.log10(),
+            Self::Sin => x.sin(),
+            Self::Cos => x.cos(),
+            Self::Round => x.round(),
+            Self::Floor => x.floor(),
+            Self::Ceil => x.ceil(),
+        }
+    }
+}
+
+/// A parsed arithmetic expression
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Number(f64),
+    Column(FormulaColumn),
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, char, Box<Expr>),
+    Call(Function, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, point: &DataPoint) -> f64 {
+        match self {
+            Self::Number(x) => *x,
+            Self::Column(column) => column.get(point),
+            Self::Neg(x) => -x.eval(point),
+            Self::Binary(a, op, b) => {
+                let (a, b) = (a.eval(point), b.eval(point));
+                match op {
+                    '+' => a + b,
+                    '-' => a - b,
+                    '*' => a * b,
+                    '/' => a / b,
+                    '^' => a.powf(b),
+                    _ => unreachable!("only operators above are parsed"),
+                }
+            }
+            Self::Call(function, x) => function.apply(x.eval(point)),
+        }
+    }
+}
+
+/// Recursive descent parser (`+ -` then `* /` then unary minus then `^` (right associative))
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(text: &'a str) -> anyhow::Result<Expr> {
+        let mut parser = Self { text, position: 0 };
+        let expr = parser.sum()?;
+        parser.skip_spaces();
+        if parser.position < text.len() {
+            bail!("unexpected {:?} at {}", parser.rest(), parser.position + 1);
+        }
+        Ok(expr)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes the character if it is next
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_spaces();
+        if self.rest().starts_with(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn sum(&mut self) -> anyhow::Result<Expr> {
+        let mut result = self.product()?;
+        loop {
+            let op = if self.eat('+') {
+                '+'
+            } else if self.eat('-') {
+                '-'
+            } else {
+                return Ok(result);
+            };
+            result = Expr::Binary(Box::new(result), op, Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> anyhow::Result<Expr> {
+        let mut result = self.unary()?;
+        loop {
+            let op = if self.eat('*') {
+                '*'
+            } else if self.eat('/') {
+                '/'
+            } else {
+                return Ok(result);
+            };
+            result = Expr::Binary(Box::new(result), op, Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> anyhow::Result<Expr> {
+        if self.eat('-') {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> anyhow::Result<Expr> {
+        let base = self.atom()?;
+        if self.eat('^') {
+            Ok(Expr::Binary(Box::new(base), '^', Box::new(self.unary()?)))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn atom(&mut self) -> anyhow::Result<Expr> {
+        self.skip_spaces();
+        let start = self.position;
+        if self.eat('(') {
+            let expr = self.sum()?;
+            if !self.eat(')') {
+                bail!("missing ')' for '(' at {}", start + 1);
+            }
+            return Ok(expr);
+        }
+        let rest = self.rest();
+        let Some(first) = rest.chars().next() else {
+            bail!("expression ends early");
+        };
+        if first.is_ascii_digit() || first == '.' {
+            let len = number_len(rest);
+            self.position += len;
+            let value = rest[..len]
+                .parse()
+                .with_context(|| format!("invalid number {:?} at {}", &rest[..len], start + 1))?;
+            return Ok(Expr::Number(value));
+        }
+        if first.is_ascii_alphabetic() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len());
+            let name = &rest[..len];
+            self.position += len;
+            return match name {
+                "x0" => Ok(Expr::Column(FormulaColumn::X0)),
+                "x1" => Ok(Expr::Column(FormulaColumn::X1)),
+                "pi" => Ok(Expr::Number(std::f64::consts::PI)),
+                _ => {
+                    let Some(function) = Function::from_name(name) else {
+                        bail!("unknown name {name:?} at {}", start + 1);
+                    };
+                    if !self.eat('(') {
+                        bail!("expected '(' after {name}");
+                    }
+                    let arg = self.sum()?;
+                    if !self.eat(')') {
+                        bail!("missing ')' after the argument of {name}");
+                    }
+                    Ok(Expr::Call(function, Box::new(arg)))
+                }
+            };
+        }
+        bail!("unexpected {first:?} at {}", start + 1)
+    }
+}
+
+/// Length of the number at the start of the text (Digits, a point and an optionally signed
+/// exponent)
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut len = 0;
+    let mut is_exponent_allowed = true;
+    while len < bytes.len() {
+        match bytes[len] {
+            b'0'..=b'9' | b'.' => len += 1,
+            b'e' | b'E' if is_exponent_allowed => {
+                is_exponent_allowed = false;
+                len += 1;
+                if matches!(bytes.get(len), Some(b'+' | b'-')) {
+                    len += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+    len
+}
+
+/// Parses `[column =] expression` returning the column if one was written before `=`
+fn parse_formula(text: &str) -> anyhow::Result<(Option<FormulaColumn>, Expr)> {
+    let (target, expression) = match text.split_once('=') {
+        Some((target, expression)) => {
+            let target = FormulaColumn::ALL
+                .into_iter()
+                .find(|x| x.name() == target.trim())
+                .with_context(|| format!("can only assign to x0 or x1 not {:?}", target.trim()))?;
+            (Some(target), expression)
+        }
+        None => (None, text),
+    };
+    Ok((target, Parser::parse(expression)?))
+}
+
+/// Settings for setting a column of the points from an expression
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct FormulaEdit {
+    /// Column written to unless the text starts with one and `=`
+    pub column: FormulaColumn,
+    pub text: String,
+    /// Only the selected points are changed (All points if there is no selection)
+    pub is_selection_only: bool,
+}
+
+impl Default for FormulaEdit {
+    fn default() -> Self {
+        Self {
+            column: Default::default(),
+            text: "x0 * 10 + 1".to_string(),
+            is_selection_only: true,
+        }
+    }
+}
+
+impl FormulaEdit {
+    /// The points at the indices with the column set to the value of the expression
+    ///
+    /// Fails without changes if the expression is invalid or is not a finite number for any point
+    fn apply(
+        &self,
+        points: &[DataPoint],
+        indices: impl IntoIterator<Item = usize>,
+    ) -> anyhow::Result<Vec<(usize, DataPoint)>> {
+        let (target, expr) = parse_formula(&self.text)?;
+        let column = target.unwrap_or(self.column);
+        indices
+            .into_iter()
+            .map(|i| {
+                let mut point = points[i];
+                let value = expr.eval(&point);
+                if !value.is_finite() {
+                    bail!("expression is {value} for row {i}");
+                }
+                column.set(&mut point, value);
+                Ok((i, point))
+            })
+            .collect()
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_menu_formula_edit(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Apply Expression…", |ui| {
+                let formula = &mut self.formula_edit;
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_source("id-formula-column")
+                        .selected_text(formula.column.name())
+                        .width(50.0)
+                        .show_ui(ui, |ui| {
+                            for column in FormulaColumn::ALL {
+                                ui.selectable_value(&mut formula.column, column, column.name());
+                            }
+                        });
+                    ui.label("=");
+                    ui.add(egui::TextEdit::singleline(&mut formula.text).desired_width(200.0))
+                        .on_hover_text("Uses x0, x1, numbers, + - * / ^, parentheses, pi and abs sqrt exp ln log10 sin cos round floor ceil (Can start with \"x1 =\" to pick the column)");
+                });
+                let error = parse_formula(&formula.text).err();
+                if let Some(e) = error.as_ref() {
+                    ui.colored_label(ui.visuals().error_fg_color, format!("{e:#}"));
+                }
+                let use_selection = formula.is_selection_only && !self.selection.is_empty();
+                ui.add_enabled(
+                    !self.selection.is_empty(),
+                    egui::Checkbox::new(&mut formula.is_selection_only, "Selection only"),
+                );
+                let count = if use_selection {
+                    self.selection.len()
+                } else {
+                    self.data.points().len()
+                };
+                if ui
+                    .add_enabled(error.is_none(), Button::new(format!("Apply to {count} points")))
+                    .on_hover_text("Evaluated for each point as a single undoable change")
+                    .clicked()
+                {
+                    let result = if use_selection {
+                        formula.apply(self.data.points(), self.selection.indices().iter().copied())
+                    } else {
+                        formula.apply(self.data.points(), 0..self.data.points().len())
+                    };
+                    match result {
+                        Ok(edits) => {
+                            self.data.edit_many(edits);
+                            self.selection.keep_for(&self.data);
+                            self.status_msg
+                                .info(format!("Applied expression to {count} points"));
+                        }
+                        Err(e) => self
+                            .status_msg
+                            .error_display(format!("Expression not applied. {e:#}")),
+                    }
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn formulas_follow_precedence() {
+        let point = DataPoint::new(2.0, 3.0, DataLabel::Normal);
+        let eval = |text: &str| parse_formula(text).map(|(_, expr)| expr.eval(&point));
+        assert_eq!(eval("x0 * 10 + 1").unwrap(), 21.0);
+        assert_eq!(eval("-x1 ^ 2").unwrap(), -9.0);
+        assert_eq!(eval("2 ^ 3 ^ 2").unwrap(), 512.0);
+        assert_eq!(eval("(x0 + x1) / 2 - sqrt(4)").unwrap(), 0.5);
+        assert_eq!(eval("1.5e2").unwrap(), 150.0);
+        assert_eq!(eval("1e-3 * 1000").unwrap(), 1.0);
+        assert_eq!(eval("2E+1-1").unwrap(), 19.0);
+        assert!(eval("x0 +").is_err());
+        assert!(eval("foo(1)").is_err());
+        assert!(eval("(1 + 2").is_err());
+        assert!(eval("label = 1").is_err());
+
+        let points = [point, DataPoint::new(0.0, -1.0, DataLabel::Anomaly)];
+        let formula = FormulaEdit {
+            text: "x1 = x1 * 100".to_string(),
+            ..Default::default()
+        };
+        let edits = formula.apply(&points, [1]).unwrap();
+        assert_eq!(
+            edits,
+            [(1, DataPoint::new(0.0, -100.0, DataLabel::Anomaly))]
+        );
+        let formula = FormulaEdit {
+            text: "ln(x0)".to_string(),
+            ..Default::default()
+        };
+        assert!(formula.apply(&points, 0..2).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::{
+    data_generation::{DataGenerator, ParamKind, ParamValue, PolarShape},
+    ui_blocks::ui_seed,
+};
+
+impl DBV {
+    /// Adds a generator to the Generate menu (Replaces any already registered with the same name)
+    pub fn register_generator(&mut self, generator: impl DataGenerator + 'static) {
+        self.generator_registry.register(generator);
+    }
+
+    pub(super) fn ui_menu_generate(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("Generate", |ui| {
+            if ui.button("Polar...").clicked() {
+                self.show_polar_generator = true;
+                ui.close_menu();
+            }
+            for name in self.generator_registry.names() {
+                if ui.button(format!("{name}...")).clicked() {
+                    self.open_generator = Some(name.to_string());
+                    ui.close_menu();
+                }
+            }
+        });
+    }
+
+    pub(super) fn ui_generator_windows(&mut self, ctx: &egui::Context) {
+        self.ui_polar_generator_window(ctx);
+        self.ui_registered_generator_window(ctx);
+    }
+
+    /// Shows a window built from the parameters of the open registered generator
+    fn ui_registered_generator_window(&mut self, ctx: &egui::Context) {
+        let Some(name) = self.open_generator.as_ref() else {
+            return;
+        };
+        let Some(generator) = self.generator_registry.get(name) else {
+            // No longer registered
+            self.open_generator = None;
+            return;
+        };
+        let specs = generator.params();
+        let params = self.generator_params.entry(name.clone()).or_default();
+        params.fill_defaults(&specs);
+        let mut is_open = true;
+        let mut should_generate = false;
+        egui::Window::new(format!("Generate {name} Data"))
+            .id(egui::Id::new("id-registered-generator"))
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                egui::Grid::new("grid-registered-generator").show(ui, |ui| {
+                    for spec in specs.iter() {
+                        ui.label(&spec.name);
+                        let Some(value) = params.get_mut(&spec.name) else {
+                            unreachable!("defaults should have been filled")
+                        };
+                        match (&spec.kind, value) {
+                            (ParamKind::Number { min, max, speed }, ParamValue::Number(x)) => {
+                                ui.add(
+                                    egui::DragValue::new(x)
+                                        .speed(*speed)
+                                        .clamp_range(*min..=*max),
+                                );
+                            }
+                            (ParamKind::Integer { min, max }, ParamValue::Integer(x)) => {
+                                ui.add(egui::DragValue::new(x).clamp_range(*min..=*max));
+                            }
+                            (ParamKind::Choice(options), ParamValue::Choice(x)) => {
+                                ui.horizontal(|ui| {
+                                    for (i, option) in options.iter().enumerate() {
+                                        ui.selectable_value(x, i, option);
+                                    }
+                                });
+                            }
+                            (ParamKind::Label, ParamValue::Label(x)) => {
+                                self.label_classes.ui_combo(
+                                    ui,
+                                    &format!("id-generator-param-{}", spec.name),
+                                    x,
+                                );
+                            }
+                            (kind, value) => {
+                                ui.label(format!(
+                                    "Default {value:?} does not match parameter type {kind:?}"
+                                ));
+                            }
+                        }
+                        ui.end_row();
+                    }
+
+                    ui_seed(ui, &mut self.data.settings.seed);
+                    ui.end_row();
+                });
+                if ui
+                    .add(Button::new("Generate"))
+                    .on_hover_text("Adds the points as a single undoable change")
+                    .clicked()
+                {
+                    should_generate = true;
+                }
+            });
+        if should_generate {
+            let points = generator.generate(params, self.data.settings.seed);
+            self.status_msg
+                .info(format!("Generated {} points", points.len()));
+            self.data.add_many(points);
+        }
+        if !is_open {
+            self.open_generator = None;
+        }
+    }
+
+    fn ui_polar_generator_window(&mut self, ctx: &egui::Context) {
+        if !self.show_polar_generator {
+            return;
+        }
+        let mut is_open = true;
+        let mut should_generate = false;
+        egui::Window::new("Generate Polar Data")
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                let generator = &mut self.polar_generator;
+                egui::Grid::new("grid-polar-generator").show(ui, |ui| {
+                    ui.label("Shape");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut generator.shape, PolarShape::Ring, "Ring");
+                        ui.selectable_value(&mut generator.shape, PolarShape::Spiral, "Spiral");
+                        ui.selectable_value(&mut generator.shape, PolarShape::Sector, "Sector");
+                    });
+                    ui.end_row();
+
+                    ui.label("Center");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut generator.center[0]).prefix("x0: "));
+                        ui.add(egui::DragValue::new(&mut generator.center[1]).prefix("x1: "));
+                    });
+                    ui.end_row();
+
+                    ui.label("Number of points");
+                    ui.add(egui::DragValue::new(&mut generator.count).clamp_range(1..=100_000));
+                    ui.end_row();
+
+                    ui.label("Radius");
+                    ui.add(
+                        egui::DragValue::new(&mut generator.radius)
+                            .speed(0.1)
+                            .clamp_range(0.0..=f64::INFINITY),
+                    );
+                    ui.end_row();
+
+                    if generator.shape != PolarShape::Ring {
+                        ui.label("Inner radius");
+                        ui.add(
+                            egui::DragValue::new(&mut generator.inner_radius)
+                                .speed(0.1)
+                                .clamp_range(0.0..=f64::INFINITY),
+                        );
+                        ui.end_row();
+                    }
+
+                    ui.label("Start angle (degrees)");
+                    ui.add(egui::DragValue::new(&mut generator.start_angle));
+                    ui.end_row();
+
+                    if generator.shape == PolarShape::Spiral {
+                        ui.label("Turns");
+                        ui.add(
+                            egui::DragValue::new(&mut generator.turns)
+                                .speed(0.1)
+                                .clamp_range(0.0..=f64::INFINITY),
+                        );
+                    } else {
+                        ui.label("End angle (degrees)");
+                        ui.add(egui::DragValue::new(&mut generator.end_angle));
+                    }
+                    ui.end_row();
+
+                    ui.label("Radial noise (std dev)");
+                    ui.add(
+                        egui::DragValue::new(&mut generator.radial_noise)
+                            .speed(0.05)
+                            .clamp_range(0.0..=f64::INFINITY),
+                    );
+                    ui.end_row();
+
+                    ui.label("Label");
+                    self.label_classes.ui_combo(
+                        ui,
+                        "id-polar-generator-label",
+                        &mut generator.label,
+                    );
+                    ui.end_row();
+
+                    ui_seed(ui, &mut self.data.settings.seed);
+                    ui.end_row();
+                });
+                if ui
+                    .add(Button::new("Generate"))
+                    .on_hover_text("Adds the points as a single undoable change")
+                    .clicked()
+                {
+                    should_generate = true;
+                }
+            });
+        if should_generate {
+            self.polar_generator.seed = self.data.settings.seed;
+            let points = self.polar_generator.generate();
+            self.status_msg
+                .info(format!("Generated {} points", points.len()));
+            self.data.add_many(points);
+        }
+        if !is_open {
+            self.show_polar_generator = false;
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::data_definition::DataPoint;
+
+/// Spacing of the grid points are snapped to (Lines go through the origin)
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct GridSnap {
+    pub spacing: [f64; 2],
+}
+
+impl Default for GridSnap {
+    fn default() -> Self {
+        Self {
+            spacing: [1.0, 1.0],
+        }
+    }
+}
+
+impl GridSnap {
+    /// The nearest grid intersection (Axes with a spacing that is not positive are left as is)
+    fn snap(&self, value: [f64; 2]) -> [f64; 2] {
+        let mut result = value;
+        for (x, spacing) in result.iter_mut().zip(self.spacing) {
+            if spacing > 0.0 {
+                *x = (*x / spacing).round() * spacing;
+            }
+        }
+        result
+    }
+
+    /// Edits that move every point that is not on the grid onto it
+    fn apply(&self, points: &[DataPoint]) -> Vec<(usize, DataPoint)> {
+        points
+            .iter()
+            .enumerate()
+            .filter_map(|(i, point)| {
+                let [x0, x1] = self.snap([point.x0, point.x1]);
+                (x0 != point.x0 || x1 != point.x1).then_some((i, DataPoint { x0, x1, ..*point }))
+            })
+            .collect()
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_menu_snap_to_grid(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Snap All Points to Grid", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Spacing");
+                    for (value, prefix) in self.grid_snap.spacing.iter_mut().zip(["x0: ", "x1: "]) {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .speed(0.01)
+                                .clamp_range(0.0..=f64::INFINITY)
+                                .prefix(prefix),
+                        );
+                    }
+                });
+                if ui
+                    .add(Button::new("Snap"))
+                    .on_hover_text("Moves every point to the nearest grid intersection as a single undoable change (0 spacing leaves that axis as is)")
+                    .clicked()
+                {
+                    let edits = self.grid_snap.apply(self.data.points());
+                    let count = edits.len();
+                    self.data.edit_many(edits);
+                    self.selection.keep_for(&self.data);
+                    self.status_msg.info(format!("Snapped {count} points to the grid"));
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn moves_only_points_off_the_grid() {
+        let points = [
+            DataPoint::new(1.26, -0.74, DataLabel::Anomaly),
+            DataPoint::new(0.5, 3.0, DataLabel::Normal),
+        ];
+        let snap = GridSnap {
+            spacing: [0.5, 0.0],
+        };
+        let edits = snap.apply(&points);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0],
+            (0, DataPoint::new(1.5, -0.74, DataLabel::Anomaly))
+        );
+    }
+}
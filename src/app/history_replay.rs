@@ -0,0 +1,119 @@
+use egui::Button;
+use egui_plot::Points;
+
+use crate::DBV;
+
+use super::data_definition::{DataTimestamp, HistoryReplay};
+
+/// Plays back the history of the points on the plot
+#[derive(PartialEq)]
+pub struct HistoryPlayer {
+    replay: HistoryReplay,
+    /// Version of the data the replay was made from (Closed if the data changes)
+    data_timestamp: DataTimestamp,
+    is_playing: bool,
+    steps_per_second: f32,
+    /// Time of the last step taken while playing
+    last_step_time: f64,
+}
+
+impl DBV {
+    pub(super) fn ui_btn_replay_history(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(self.data.has_undo(), Button::new("Replay History..."))
+            .on_hover_text("Shows how the points were built one change at a time")
+            .clicked()
+        {
+            self.history_player = Some(HistoryPlayer {
+                replay: HistoryReplay::new(&self.data),
+                data_timestamp: self.data.timestamp(),
+                is_playing: false,
+                steps_per_second: 4.0,
+                last_step_time: 0.0,
+            });
+            ui.close_menu();
+        }
+    }
+
+    pub(super) fn ui_history_replay_window(&mut self, ctx: &egui::Context) {
+        let Some(player) = self.history_player.as_mut() else {
+            return;
+        };
+        if player.data_timestamp != self.data.timestamp() {
+            self.history_player = None;
+            self.status_msg
+                .info("Replay stopped because the points were changed");
+            return;
+        }
+        let now = ctx.input(|i| i.time);
+        if player.is_playing {
+            let interval = 1.0 / f64::from(player.steps_per_second.max(0.1));
+            if now - player.last_step_time >= interval {
+                player.last_step_time = now;
+                let next = player.replay.step() + 1;
+                player.replay.seek(next);
+            }
+            if player.replay.is_at_end() {
+                player.is_playing = false;
+            } else {
+                ctx.request_repaint_after(std::time::Duration::from_secs_f64(interval));
+            }
+        }
+
+        let mut is_open = true;
+        egui::Window::new("Replay History")
+            .open(&mut is_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let replay = &mut player.replay;
+                let mut step = replay.step();
+                ui.horizontal(|ui| {
+                    if ui.button("⏮").on_hover_text("Start").clicked() {
+                        step = 0;
+                    }
+                    if ui.button("⏴").on_hover_text("Step back").clicked() {
+                        step = step.saturating_sub(1);
+                    }
+                    let play_text = if player.is_playing { "⏸" } else { "▶" };
+                    if ui.button(play_text).on_hover_text("Play / Pause").clicked() {
+                        player.is_playing = !player.is_playing;
+                        if player.is_playing && replay.is_at_end() {
+                            step = 0;
+                        }
+                        player.last_step_time = now;
+                    }
+                    if ui.button("⏵").on_hover_text("Step forward").clicked() {
+                        step += 1;
+                    }
+                    if ui.button("⏭").on_hover_text("End").clicked() {
+                        step = replay.step_count();
+                    }
+                });
+                ui.add(egui::Slider::new(&mut step, 0..=replay.step_count()).text("Step"));
+                ui.add(
+                    egui::DragValue::new(&mut player.steps_per_second)
+                        .speed(0.1)
+                        .clamp_range(0.1..=60.0)
+                        .prefix("Speed: ")
+                        .suffix(" steps/s"),
+                );
+                if step != replay.step() {
+                    replay.seek(step);
+                }
+                ui.label(format!("Points: {}", replay.points().len()));
+                ui.label(match replay.last_change() {
+                    Some(change) => format!("Last change: {change}"),
+                    None => "Before the oldest change in the history".to_string(),
+                });
+            });
+        if !is_open {
+            self.history_player = None;
+        }
+    }
+
+    /// The points at the current step of the replay (`None` if no replay is open)
+    pub(super) fn markers_history_replay(&self) -> Option<Vec<Points>> {
+        let points = self.history_player.as_ref()?.replay.points();
+        Some(self.markers_by_label(points, |_| true))
+    }
+}
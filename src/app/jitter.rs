@@ -0,0 +1,127 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::{
+    data_definition::DataPoint,
+    data_generation::{rng_from_seed, sample_normal},
+    ui_blocks::ui_seed,
+};
+
+/// Settings for moving points by random noise
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone)]
+#[serde(default)]
+pub struct Jitter {
+    /// Standard deviation of the noise added to each coordinate
+    pub sigma: f64,
+    /// Only the selected points are moved (All points if there is no selection)
+    pub is_selection_only: bool,
+}
+
+impl Default for Jitter {
+    fn default() -> Self {
+        Self {
+            sigma: 0.1,
+            is_selection_only: true,
+        }
+    }
+}
+
+impl Jitter {
+    /// The points at the indices after adding the noise (Same seed gives the same noise)
+    pub(super) fn apply(
+        &self,
+        points: &[DataPoint],
+        indices: impl IntoIterator<Item = usize>,
+        seed: u64,
+    ) -> Vec<(usize, DataPoint)> {
+        let mut rng = rng_from_seed(seed);
+        indices
+            .into_iter()
+            .map(|i| {
+                let mut point = points[i];
+                point.x0 += sample_normal(&mut rng, self.sigma);
+                point.x1 += sample_normal(&mut rng, self.sigma);
+                (i, point)
+            })
+            .collect()
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_menu_jitter(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Add Jitter…", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Sigma");
+                    ui.add(
+                        egui::DragValue::new(&mut self.jitter.sigma)
+                            .speed(0.01)
+                            .clamp_range(0.0..=f64::INFINITY),
+                    );
+                    ui_seed(ui, &mut self.data.settings.seed);
+                });
+                let use_selection = self.jitter.is_selection_only && !self.selection.is_empty();
+                ui.add_enabled(
+                    !self.selection.is_empty(),
+                    egui::Checkbox::new(&mut self.jitter.is_selection_only, "Selection only"),
+                );
+                let count = if use_selection {
+                    self.selection.len()
+                } else {
+                    self.data.points().len()
+                };
+                if ui
+                    .add(Button::new(format!("Jitter {count} points")))
+                    .on_hover_text(
+                        "Adds Gaussian noise to both coordinates as a single undoable change",
+                    )
+                    .clicked()
+                {
+                    let seed = self.data.settings.seed;
+                    let edits = if use_selection {
+                        self.jitter.apply(
+                            self.data.points(),
+                            self.selection.indices().iter().copied(),
+                            seed,
+                        )
+                    } else {
+                        self.jitter
+                            .apply(self.data.points(), 0..self.data.points().len(), seed)
+                    };
+                    self.data.edit_many(edits);
+                    self.selection.keep_for(&self.data);
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn same_seed_same_noise() {
+        let points = [
+            DataPoint::new(0.0, 0.0, DataLabel::Normal),
+            DataPoint::new(5.0, 5.0, DataLabel::Anomaly),
+        ];
+        let jitter = Jitter::default();
+        let edits = jitter.apply(&points, [1], 0);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].0, 1);
+        assert_ne!(edits[0].1, points[1]);
+        assert_eq!(edits[0].1.label, DataLabel::Anomaly);
+        assert_eq!(edits, jitter.apply(&points, [1], 0));
+
+        let none = Jitter {
+            sigma: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(none.apply(&points, [0], 0)[0].1, points[0]);
+    }
+}
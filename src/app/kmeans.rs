@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+use egui::{Button, RichText};
+use egui_plot::{MarkerShape, PlotPoint, PlotUi, Points, Text};
+use rand::Rng as _;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoint, DataTimestamp, DistanceCalculation as _, PointArray},
+    data_generation::rng_from_seed,
+    ui_blocks::ui_seed,
+};
+
+/// Settings for grouping the points with k-means to label each group at once
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct KMeansLabeling {
+    pub k: usize,
+    #[serde(skip)]
+    result: Option<KMeansResult>,
+}
+
+impl Default for KMeansLabeling {
+    fn default() -> Self {
+        Self { k: 2, result: None }
+    }
+}
+
+/// The clusters found for a version of the data
+#[derive(Debug, PartialEq)]
+struct KMeansResult {
+    timestamp: DataTimestamp,
+    centers: Vec<PointArray>,
+    /// Index of the cluster of each point
+    assignments: Vec<usize>,
+    /// Label picked for each cluster
+    labels: Vec<DataLabel>,
+}
+
+/// Index of the center closest to the point
+fn nearest_center(point: &DataPoint, centers: &[PointArray]) -> usize {
+    centers
+        .iter()
+        .enumerate()
+        .min_by(|a, b| point.distance_to(*a.1).total_cmp(&point.distance_to(*b.1)))
+        .map_or(0, |(i, _)| i)
+}
+
+/// Centers and the cluster of each point (k-means++ start then Lloyd's iterations)
+fn kmeans(points: &[DataPoint], k: usize, seed: u64) -> (Vec<PointArray>, Vec<usize>) {
+    const MAX_ITERATIONS: usize = 100;
+    let k = k.clamp(1, points.len().max(1));
+    if points.is_empty() {
+        return (vec![], vec![]);
+    }
+    let mut rng = rng_from_seed(seed);
+    let mut centers = vec![points[rng.gen_range(0..points.len())].to_array()];
+    while centers.len() < k {
+        // Points far from the existing centers are more likely to be picked
+        let weights: Vec<f64> = points
+            .iter()
+            .map(|p| p.distance_to(centers[nearest_center(p, &centers)]).powi(2))
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            break; // Fewer distinct points than clusters
+        }
+        let mut target = rng.gen_range(0.0..total);
+        let index = weights
+            .iter()
+            .position(|w| {
+                target -= w;
+                target < 0.0
+            })
+            .unwrap_or(points.len() - 1);
+        centers.push(points[index].to_array());
+    }
+
+    let mut assignments = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let new_assignments: Vec<usize> =
+            points.iter().map(|p| nearest_center(p, &centers)).collect();
+        if new_assignments == assignments {
+            break;
+        }
+        assignments = new_assignments;
+        let mut sums = vec![[0.0, 0.0, 0.0]; centers.len()];
+        for (point, &cluster) in points.iter().zip(assignments.iter()) {
+            sums[cluster][0] += point.x0;
+            sums[cluster][1] += point.x1;
+            sums[cluster][2] += 1.0;
+        }
+        for (center, [x0, x1, count]) in centers.iter_mut().zip(sums) {
+            if count > 0.0 {
+                *center = [x0 / count, x1 / count];
+            }
+        }
+    }
+    (centers, assignments)
+}
+
+impl DBV {
+    pub(super) fn ui_btn_kmeans_labeling(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(!self.data.is_empty(), Button::new("Label by Clusters…"))
+            .clicked()
+        {
+            self.show_kmeans_window = true;
+            ui.close_menu();
+        }
+    }
+
+    pub(super) fn ui_kmeans_window(&mut self, ctx: &egui::Context) {
+        if !self.show_kmeans_window {
+            self.kmeans.result = None;
+            return;
+        }
+        if self
+            .kmeans
+            .result
+            .as_ref()
+            .is_some_and(|x| x.timestamp != self.data.timestamp())
+        {
+            // The points changed so the clusters no longer match them
+            self.kmeans.result = None;
+        }
+        let mut is_open = true;
+        let mut should_apply = false;
+        egui::Window::new("Label by Clusters")
+            .open(&mut is_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Clusters (k)");
+                    ui.add(egui::DragValue::new(&mut self.kmeans.k).clamp_range(1..=20));
+                    ui_seed(ui, &mut self.data.settings.seed);
+                    if ui.button("Find Clusters").clicked() {
+                        let (centers, assignments) =
+                            kmeans(self.data.points(), self.kmeans.k, self.data.settings.seed);
+                        // Start from the label most points in the cluster already have
+                        let mut counts = vec![BTreeMap::<DataLabel, usize>::new(); centers.len()];
+                        for (point, &cluster) in self.data.points().iter().zip(assignments.iter()) {
+                            *counts[cluster].entry(point.label).or_default() += 1;
+                        }
+                        let labels = counts
+                            .into_iter()
+                            .map(|counts| {
+                                counts
+                                    .into_iter()
+                                    .max_by_key(|(_, count)| *count)
+                                    .map_or(DataLabel::Normal, |(label, _)| label)
+                            })
+                            .collect();
+                        self.kmeans.result = Some(KMeansResult {
+                            timestamp: self.data.timestamp(),
+                            centers,
+                            assignments,
+                            labels,
+                        });
+                    }
+                });
+                let Some(result) = self.kmeans.result.as_mut() else {
+                    ui.label("Find the clusters then pick a label for each");
+                    return;
+                };
+                ui.separator();
+                egui::Grid::new("grid-kmeans").striped(true).show(ui, |ui| {
+                    for (i, label) in result.labels.iter_mut().enumerate() {
+                        let count = result.assignments.iter().filter(|&&c| c == i).count();
+                        ui.label(format!("Cluster {} ({count} points)", i + 1));
+                        self.label_classes
+                            .ui_combo(ui, &format!("id-kmeans-label-{i}"), label);
+                        ui.end_row();
+                    }
+                });
+                should_apply = ui
+                    .button("Apply Labels")
+                    .on_hover_text(
+                        "Relabels every point by its cluster as a single undoable change",
+                    )
+                    .clicked();
+            });
+        if should_apply {
+            if let Some(result) = self.kmeans.result.take() {
+                let edits: Vec<_> = result
+                    .assignments
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &cluster)| {
+                        let mut point = self.data.points()[i];
+                        point.label = result.labels[cluster];
+                        (i, point)
+                    })
+                    .collect();
+                self.data.edit_many(edits);
+                self.selection.keep_for(&self.data);
+                self.status_msg
+                    .info(format!("Labeled {} clusters", result.centers.len()));
+            }
+        }
+        if !is_open {
+            self.show_kmeans_window = false;
+        }
+    }
+
+    /// Shows the centers of the clusters found while the window is open
+    pub(super) fn plot_kmeans_centers(&self, plot_ui: &mut PlotUi) {
+        let Some(result) = &self.kmeans.result else {
+            return;
+        };
+        if !self.show_kmeans_window {
+            return;
+        }
+        let color = plot_ui.ctx().style().visuals.strong_text_color();
+        plot_ui.points(
+            Points::new(result.centers.clone())
+                .name("Cluster centers")
+                .shape(MarkerShape::Cross)
+                .radius(self.marker_radius * 2.5)
+                .color(color),
+        );
+        for (i, center) in result.centers.iter().enumerate() {
+            plot_ui.text(
+                Text::new(
+                    PlotPoint::new(center[0], center[1]),
+                    RichText::new(format!("{}", i + 1)).color(color),
+                )
+                .anchor(egui::Align2::LEFT_BOTTOM),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_distant_groups() {
+        let points: Vec<DataPoint> = [[0.0, 0.0], [0.0, 1.0], [10.0, 10.0], [11.0, 10.0]]
+            .iter()
+            .map(|[x0, x1]| DataPoint::new(*x0, *x1, DataLabel::Normal))
+            .collect();
+        let (centers, assignments) = kmeans(&points, 2, 3);
+        assert_eq!(centers.len(), 2);
+        assert_eq!(assignments[0], assignments[1]);
+        assert_eq!(assignments[2], assignments[3]);
+        assert_ne!(assignments[0], assignments[2]);
+        assert_eq!(centers[assignments[2]], [10.5, 10.0]);
+        assert_eq!((centers, assignments), kmeans(&points, 2, 3));
+    }
+}
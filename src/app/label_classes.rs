@@ -0,0 +1,235 @@
+use ecolor::Color32;
+use egui::Button;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoint},
+    marker_shapes::PointShape,
+};
+
+/// A user defined class that points can be labeled with besides normal and anomaly
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone)]
+pub struct LabelClass {
+    pub name: String,
+    pub color: Color32,
+    pub shape: PointShape,
+}
+
+/// The classes that come after [`DataLabel::Normal`] and [`DataLabel::Anomaly`]
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct LabelClasses {
+    /// The first one is the class with index [`DataLabel::FIRST_CLASS`]
+    classes: Vec<LabelClass>,
+    /// Every class other than normal is saved as an anomaly so files only have 0 and 1 as labels
+    pub should_save_as_binary: bool,
+}
+
+impl LabelClasses {
+    /// Colors given to new classes in turn
+    const PALETTE: [Color32; 6] = [
+        Color32::from_rgb(90, 180, 90),
+        Color32::from_rgb(170, 100, 200),
+        Color32::from_rgb(220, 90, 90),
+        Color32::from_rgb(60, 180, 180),
+        Color32::from_rgb(200, 180, 60),
+        Color32::from_rgb(150, 150, 150),
+    ];
+
+    /// Returns the class of a user defined label (`None` for normal, anomaly and classes that
+    /// are not defined)
+    fn get(&self, label: DataLabel) -> Option<&LabelClass> {
+        match label {
+            DataLabel::Normal | DataLabel::Anomaly => None,
+            DataLabel::Class(x) => self
+                .classes
+                .get(usize::from(x.checked_sub(DataLabel::FIRST_CLASS)?)),
+        }
+    }
+
+    /// Every label that can be picked (Normal, Anomaly then the user defined classes)
+    pub fn labels(&self) -> impl Iterator<Item = DataLabel> + '_ {
+        [DataLabel::Normal, DataLabel::Anomaly].into_iter().chain(
+            (0..self.classes.len()).map(|i| DataLabel::from(i as u8 + DataLabel::FIRST_CLASS)),
+        )
+    }
+
+    pub fn name(&self, label: DataLabel) -> String {
+        match (label, self.get(label)) {
+            (DataLabel::Normal, _) => "Normal".to_string(),
+            (DataLabel::Anomaly, _) => "Anomaly".to_string(),
+            (_, Some(class)) => class.name.clone(),
+            (DataLabel::Class(x), None) => format!("Class {x}"),
+        }
+    }
+
+    /// Shape of the markers of a user defined label (Classes that are not defined use a circle)
+    pub fn shape(&self, label: DataLabel) -> PointShape {
+        self.get(label).map_or(PointShape::Circle, |x| x.shape)
+    }
+
+    /// Adds a class with the next free index and returns its label (`None` if there are no
+    /// indices left)
+    pub fn add(&mut self) -> Option<DataLabel> {
+        let index = u8::try_from(self.classes.len())
+            .ok()?
+            .checked_add(DataLabel::FIRST_CLASS)?;
+        self.classes.push(LabelClass {
+            name: format!("Class {index}"),
+            color: Self::PALETTE[self.classes.len() % Self::PALETTE.len()],
+            shape: PointShape::Circle,
+        });
+        Some(DataLabel::from(index))
+    }
+
+    /// Adds classes up to the largest class the points are labeled with so that they can all be
+    /// picked. Returns the number of classes added
+    pub fn add_missing(&mut self, points: &[DataPoint]) -> usize {
+        let Some(max) = points
+            .iter()
+            .filter_map(|point| match point.label {
+                DataLabel::Class(x) => Some(x),
+                _ => None,
+            })
+            .max()
+        else {
+            return 0;
+        };
+        let mut count = 0;
+        while self.get(DataLabel::from(max)).is_none() && self.add().is_some() {
+            count += 1;
+        }
+        count
+    }
+
+    /// Changes the labels of points about to be saved (Other classes become anomalies if saving
+    /// as binary)
+    pub fn prepare_for_save(&self, points: &mut [DataPoint]) {
+        if self.should_save_as_binary {
+            for point in points {
+                point.label = point.label.to_binary();
+            }
+        }
+    }
+
+    /// Shows a drop down to pick one of the labels
+    pub fn ui_combo(
+        &self,
+        ui: &mut egui::Ui,
+        id_source: &str,
+        label: &mut DataLabel,
+    ) -> egui::Response {
+        egui::ComboBox::from_id_source(id_source)
+            .selected_text(self.name(*label))
+            .show_ui(ui, |ui| {
+                for option in self.labels() {
+                    ui.selectable_value(label, option, self.name(option));
+                }
+            })
+            .response
+    }
+}
+
+impl DBV {
+    /// Color of the markers of points with the label (Before any results)
+    pub(super) fn label_color(&self, label: DataLabel) -> Color32 {
+        match label {
+            DataLabel::Normal => self.color_normal,
+            DataLabel::Anomaly => self.color_anom,
+            DataLabel::Class(_) => self
+                .label_classes
+                .get(label)
+                .map_or(Color32::GRAY, |x| x.color),
+        }
+    }
+
+    /// Shape of the markers of points with the label (Before any results)
+    pub(super) fn label_shape(&self, label: DataLabel) -> PointShape {
+        match label {
+            DataLabel::Normal => self.marker_shapes.normal,
+            DataLabel::Anomaly => self.marker_shapes.anomaly,
+            DataLabel::Class(_) => self.label_classes.shape(label),
+        }
+    }
+
+    pub(super) fn ui_label_classes(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            ui.strong("Classes");
+            let mut should_remove_last = false;
+            let class_count = self.label_classes.classes.len();
+            for (i, class) in self.label_classes.classes.iter_mut().enumerate() {
+                ui.separator();
+                ui.add(egui::TextEdit::singleline(&mut class.name).desired_width(70.0));
+                ui.color_edit_button_srgba(&mut class.color);
+                class.shape.ui(
+                    ui,
+                    &format!("id-shape-class-{}", i as u8 + DataLabel::FIRST_CLASS),
+                );
+                if i + 1 == class_count {
+                    let is_in_use = self
+                        .data
+                        .points()
+                        .iter()
+                        .any(|x| x.label == DataLabel::from(i as u8 + DataLabel::FIRST_CLASS));
+                    should_remove_last = ui
+                        .add_enabled(!is_in_use, Button::new("🗑"))
+                        .on_hover_text("Remove class (Only possible when no points use it)")
+                        .clicked();
+                }
+            }
+            if should_remove_last {
+                self.label_classes.classes.pop();
+                if self.label_classes.get(self.primary_click_label).is_none()
+                    && matches!(self.primary_click_label, DataLabel::Class(_))
+                {
+                    self.primary_click_label = DataLabel::Normal;
+                }
+            }
+            ui.separator();
+            if ui.button("Add class").clicked() && self.label_classes.add().is_none() {
+                self.status_msg
+                    .error_display("No more classes can be added");
+            }
+            ui.checkbox(
+                &mut self.label_classes.should_save_as_binary,
+                "Save as binary labels",
+            )
+            .on_hover_text("Files are saved with every class other than normal as an anomaly (1)");
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classes_follow_normal_and_anomaly() {
+        let mut classes = LabelClasses::default();
+        assert_eq!(classes.add(), Some(DataLabel::Class(2)));
+        let points = [DataPoint::new(0.0, 0.0, DataLabel::Class(4))];
+        assert_eq!(classes.add_missing(&points), 2);
+        assert_eq!(
+            classes.labels().collect::<Vec<_>>(),
+            [
+                DataLabel::Normal,
+                DataLabel::Anomaly,
+                DataLabel::Class(2),
+                DataLabel::Class(3),
+                DataLabel::Class(4)
+            ]
+        );
+        assert_eq!(classes.name(DataLabel::Class(3)), "Class 3");
+        assert_eq!(classes.name(DataLabel::Class(9)), "Class 9");
+
+        let mut points = points;
+        classes.should_save_as_binary = true;
+        classes.prepare_for_save(&mut points);
+        assert_eq!(points[0].label, DataLabel::Anomaly);
+        assert_eq!(
+            DataLabel::from(DataLabel::Class(7).as_int()),
+            DataLabel::Class(7)
+        );
+    }
+}
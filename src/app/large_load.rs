@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use crate::DBV;
+
+use super::{data_definition::DataPoints, data_generation::rng_from_seed};
+
+/// A load with more points than the user allows without confirmation
+#[derive(PartialEq, Debug)]
+pub struct LargeLoad {
+    points: DataPoints,
+    path: PathBuf,
+    /// Number of points to keep if the user chooses to reduce the points
+    target_count: usize,
+}
+
+impl LargeLoad {
+    /// Keeps a random subset of `target_count` points in their original order
+    fn downsample(self, seed: u64) -> (DataPoints, PathBuf) {
+        let mut rng = rng_from_seed(seed);
+        let target_count = self.target_count.min(self.points.len());
+        let mut indices =
+            rand::seq::index::sample(&mut rng, self.points.len(), target_count).into_vec();
+        indices.sort_unstable();
+        let points = indices.into_iter().map(|i| self.points[i]).collect();
+        (points, self.path)
+    }
+
+    fn keep_first(mut self) -> (DataPoints, PathBuf) {
+        self.points.truncate(self.target_count);
+        (self.points, self.path)
+    }
+}
+
+impl DBV {
+    pub(super) fn prompt_for_large_load(&mut self, points: DataPoints, path: PathBuf) {
+        self.status_msg.info(format!(
+            "Loaded file has {} points. Please confirm how many to keep",
+            points.len()
+        ));
+        self.large_load = Some(LargeLoad {
+            points,
+            path,
+            target_count: self.max_points_without_prompt,
+        });
+    }
+
+    pub(super) fn ui_large_load_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Confirm loads with more points than");
+            ui.add(
+                egui::DragValue::new(&mut self.max_points_without_prompt)
+                    .speed(1000)
+                    .clamp_range(1..=usize::MAX),
+            );
+        });
+    }
+
+    /// Shows a progress bar while an operation that reports progress is running
+    pub(super) fn ui_load_progress(&mut self, ui: &mut egui::Ui) {
+        let Some((description, fraction)) = self.op_state.progress() else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.label(description);
+            ui.add(egui::ProgressBar::new(fraction).show_percentage());
+        });
+        // Progress is updated in the background so keep repainting to show it
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_millis(100));
+    }
+
+    pub(super) fn ui_large_load_dialog(&mut self, ctx: &egui::Context) {
+        let Some(large_load) = self.large_load.as_mut() else {
+            return;
+        };
+        let mut is_open = true;
+        let mut choice = None;
+        egui::Window::new("Large File")
+            .open(&mut is_open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{:?} has {} points which is more than the {} allowed without confirmation",
+                    large_load.path,
+                    large_load.points.len(),
+                    self.max_points_without_prompt
+                ));
+                ui.horizontal(|ui| {
+                    ui.label("Points to keep");
+                    ui.add(
+                        egui::DragValue::new(&mut large_load.target_count)
+                            .speed(1000)
+                            .clamp_range(1..=large_load.points.len()),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Downsample")
+                        .on_hover_text("Keeps a random subset of the points")
+                        .clicked()
+                    {
+                        choice = Some(LargeLoadChoice::Downsample);
+                    }
+                    if ui.button("Keep First").clicked() {
+                        choice = Some(LargeLoadChoice::KeepFirst);
+                    }
+                    if ui.button("Load All").clicked() {
+                        choice = Some(LargeLoadChoice::LoadAll);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        choice = Some(LargeLoadChoice::Cancel);
+                    }
+                });
+            });
+        if !is_open {
+            choice = Some(LargeLoadChoice::Cancel);
+        }
+        let Some(choice) = choice else {
+            return;
+        };
+        let large_load = self
+            .large_load
+            .take()
+            .expect("checked at the start of the function");
+        let (points, path) = match choice {
+            LargeLoadChoice::Downsample => large_load.downsample(self.data.settings.seed),
+            LargeLoadChoice::KeepFirst => large_load.keep_first(),
+            LargeLoadChoice::LoadAll => (large_load.points, large_load.path),
+            LargeLoadChoice::Cancel => {
+                self.status_msg.info("Load cancelled");
+                return;
+            }
+        };
+        self.finish_data_load(points, path);
+    }
+}
+
+enum LargeLoadChoice {
+    Downsample,
+    KeepFirst,
+    LoadAll,
+    Cancel,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::{DataLabel, DataPoint};
+
+    use super::*;
+
+    #[test]
+    fn downsample_keeps_order_and_count() {
+        let points: DataPoints = (0..100)
+            .map(|i| DataPoint::new(i as f64, 0.0, DataLabel::Normal))
+            .collect();
+        let large_load = LargeLoad {
+            points,
+            path: PathBuf::new(),
+            target_count: 10,
+        };
+        let (actual, _) = large_load.downsample(0);
+        assert_eq!(actual.len(), 10);
+        assert!(actual.windows(2).all(|pair| pair[0].x0 < pair[1].x0));
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::Display;
+
+use super::display_precision::DisplayPrecision;
+
+/// What is shown after the name of each series in the plot legend
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct LegendSummary {
+    pub show_count: bool,
+    /// Percentage of all the points
+    pub show_percentage: bool,
+    /// Only shown when the points have scores from a trained model
+    pub show_mean_score: bool,
+}
+
+impl Default for LegendSummary {
+    fn default() -> Self {
+        Self {
+            show_count: true,
+            show_percentage: false,
+            show_mean_score: false,
+        }
+    }
+}
+
+impl LegendSummary {
+    /// Returns the name followed by the enabled statistics e.g. "Normal (12, 40.0%, mean 0.532)"
+    pub fn series_name(
+        &self,
+        name: impl Display,
+        count: usize,
+        total: usize,
+        mean_score: Option<f64>,
+        precision: &DisplayPrecision,
+    ) -> String {
+        let mut parts = vec![];
+        if self.show_count {
+            parts.push(count.to_string());
+        }
+        if self.show_percentage {
+            parts.push(format!(
+                "{:.1}%",
+                count as f64 * 100.0 / total.max(1) as f64
+            ));
+        }
+        if let Some(mean) = mean_score.filter(|_| self.show_mean_score) {
+            parts.push(format!("mean {}", precision.format(mean)));
+        }
+        if parts.is_empty() {
+            name.to_string()
+        } else {
+            format!("{name} ({})", parts.join(", "))
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Legend shows");
+            ui.checkbox(&mut self.show_count, "Count");
+            ui.checkbox(&mut self.show_percentage, "Percentage");
+            ui.checkbox(&mut self.show_mean_score, "Mean score")
+                .on_hover_text("Only available when the model is trained on the current points");
+        });
+    }
+}
+
+/// Average of the values or `None` if there are none
+pub fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_includes_enabled_statistics() {
+        let precision = DisplayPrecision::default();
+        let mut summary = LegendSummary::default();
+        assert_eq!(
+            summary.series_name("Normal", 3, 12, Some(0.5), &precision),
+            "Normal (3)"
+        );
+        summary.show_percentage = true;
+        summary.show_mean_score = true;
+        assert_eq!(
+            summary.series_name("Normal", 3, 12, Some(0.5), &precision),
+            "Normal (3, 25.0%, mean 0.500)"
+        );
+        assert_eq!(
+            summary.series_name("Normal", 0, 0, None, &precision),
+            "Normal (0, 0.0%)"
+        );
+        summary = LegendSummary {
+            show_count: false,
+            show_percentage: false,
+            show_mean_score: false,
+        };
+        assert_eq!(
+            summary.series_name("Normal", 3, 12, None, &precision),
+            "Normal"
+        );
+    }
+}
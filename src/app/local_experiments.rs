@@ -1,18 +1,23 @@
+use std::time::Duration;
+
+use web_time::Instant;
+
 use super::{
-    data_definition::{DataLabel, DataPoints, DataTimestamp},
+    data_definition::{DataLabel, DataPoint, DataPoints, DataTimestamp, PointArray},
     status_msg::StatusMsg,
 };
 
 mod proximity_score;
 mod singlemax;
 
-pub use proximity_score::ProximityScore;
+pub use proximity_score::{ProximityScore, ThresholdPreset};
 pub use singlemax::SingleMax;
 
 pub type Scores = Vec<f64>;
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq)]
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Default)]
 pub enum LocalExperiment {
+    #[default]
     None,
     ProximityScoreUntrained(ProximityScore<UnTrained>),
     ProximityScoreTrained(ProximityScore<Trained>),
@@ -26,6 +31,16 @@ pub struct TrainResults {
     data_timestamp_at_start: DataTimestamp,
 }
 
+/// Tracks changes to the data so that work (Such as training) is only started after the data
+/// stops changing
+#[derive(Debug, Default, PartialEq)]
+pub struct IdleDebounce {
+    /// The latest timestamp of the data seen and when it was first seen
+    pending: Option<(DataTimestamp, Instant)>,
+    /// Used to prevent retrying on each frame if training fails
+    last_attempted: Option<DataTimestamp>,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct UnTrained;
 #[derive(Debug, PartialEq)]
@@ -67,6 +82,35 @@ pub trait ModelInference {
     /// # PANICS
     /// If index is not within the scores during training
     fn score_for_training_data(&self, index: usize) -> f64;
+
+    /// Returns `true` if the point is close enough to the threshold to need a closer look
+    /// (Only models with a borderline band set return `true`)
+    ///
+    /// # PANICS
+    /// If index is not within the points during training
+    fn is_borderline_on_training_data(&self, _index: usize) -> bool {
+        false
+    }
+
+    /// Scores a point that was not in the training data
+    ///
+    /// ASSUMPTION: `training_points` are the points the model was trained on
+    fn score(&self, point: PointArray, training_points: &[DataPoint]) -> f64;
+
+    /// Scores each of the points (See [`Self::score`])
+    fn score_batch(&self, points: &[PointArray], training_points: &[DataPoint]) -> Vec<f64> {
+        points
+            .iter()
+            .map(|&point| self.score(point, training_points))
+            .collect()
+    }
+
+    /// Gives the prediction for a point with the score (Used with [`Self::score`] to predict
+    /// points that were not in the training data)
+    fn prediction_for_score(&self, score: f64) -> DataLabel;
+
+    /// The score where the prediction changes between normal and anomaly
+    fn decision_threshold(&self) -> f64;
 }
 
 pub trait ModelInferenceConfig: ModelInference {
@@ -128,6 +172,28 @@ impl LocalExperiment {
         }
     }
 
+    /// Returns the auto retrain setting of the model if there is a model
+    pub(crate) fn auto_retrain_mut(&mut self) -> Option<&mut bool> {
+        match self {
+            LocalExperiment::None => None,
+            LocalExperiment::ProximityScoreUntrained(x) => Some(x.auto_retrain_mut()),
+            LocalExperiment::ProximityScoreTrained(x) => Some(x.auto_retrain_mut()),
+            LocalExperiment::SingleMaxUntrained(x) => Some(x.auto_retrain_mut()),
+            LocalExperiment::SingleMaxTrained(x) => Some(x.auto_retrain_mut()),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            LocalExperiment::None => "None",
+            LocalExperiment::ProximityScoreUntrained(_)
+            | LocalExperiment::ProximityScoreTrained(_) => "Proximity Score",
+            LocalExperiment::SingleMaxUntrained(_) | LocalExperiment::SingleMaxTrained(_) => {
+                "Single Max"
+            }
+        }
+    }
+
     pub(crate) fn description(&self) -> &str {
         match self {
             LocalExperiment::None => "N/A",
@@ -142,8 +208,33 @@ impl LocalExperiment {
     }
 }
 
-impl Default for LocalExperiment {
-    fn default() -> Self {
-        Self::None
+impl IdleDebounce {
+    const DELAY: Duration = Duration::from_millis(500);
+
+    /// Returns `true` if the work should be started now
+    ///
+    /// If still waiting for the data to stop changing a repaint is requested for when the wait is over
+    pub fn poll(&mut self, data_timestamp: DataTimestamp, ctx: &egui::Context) -> bool {
+        if self.last_attempted == Some(data_timestamp) {
+            return false;
+        }
+        match self.pending {
+            Some((timestamp, first_seen)) if timestamp == data_timestamp => {
+                let elapsed = first_seen.elapsed();
+                if elapsed >= Self::DELAY {
+                    self.pending = None;
+                    self.last_attempted = Some(data_timestamp);
+                    true
+                } else {
+                    ctx.request_repaint_after(Self::DELAY - elapsed);
+                    false
+                }
+            }
+            _ => {
+                self.pending = Some((data_timestamp, Instant::now()));
+                ctx.request_repaint_after(Self::DELAY);
+                false
+            }
+        }
     }
 }
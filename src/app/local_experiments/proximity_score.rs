@@ -3,7 +3,10 @@ use std::marker::PhantomData;
 use anyhow::bail;
 
 use crate::app::{
-    data_definition::{DataLabel, DataPoints, DataTimestamp, DistanceCalculations as _},
+    data_definition::{
+        DataLabel, DataPoint, DataPoints, DataTimestamp, DistanceCalculation as _,
+        DistanceCalculations as _, PointArray,
+    },
     status_msg::StatusMsg,
 };
 
@@ -12,17 +15,42 @@ use super::{ModelInference, ModelInferenceConfig, ModelTrain, TrainResults, Trai
 #[derive(serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct ProximityScore<State = UnTrained> {
     train_data: Option<TrainingInfo>,
+    /// If the model should be retrained automatically when the data changes
+    #[serde(default)]
+    auto_retrain: bool,
+    /// Named thresholds that are kept when the model is retrained
+    #[serde(default)]
+    threshold_presets: Vec<ThresholdPreset>,
     state: PhantomData<State>, // This doesn't take up space at runtime
 }
 impl ProximityScore {
     pub(crate) fn new() -> ProximityScore {
         ProximityScore::<UnTrained> {
             train_data: None,
+            auto_retrain: false,
+            threshold_presets: vec![],
             state: PhantomData,
         }
     }
 }
 
+impl<T> ProximityScore<T> {
+    pub(crate) fn auto_retrain_mut(&mut self) -> &mut bool {
+        &mut self.auto_retrain
+    }
+
+    pub(crate) fn threshold_presets_mut(&mut self) -> &mut Vec<ThresholdPreset> {
+        &mut self.threshold_presets
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Debug)]
+pub struct ThresholdPreset {
+    pub name: String,
+    pub threshold: f64,
+    pub borderline_threshold: Option<f64>,
+}
+
 #[derive(serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct TrainingInfo {
     results: TrainResults,
@@ -34,6 +62,9 @@ pub struct PredictConfig {
     pub min_score: f64,
     pub max_score: f64,
     pub threshold: f64,
+    /// Scores from this up to the threshold are borderline instead of normal (Off if `None`)
+    #[serde(default)]
+    pub borderline_threshold: Option<f64>,
 }
 
 impl<T> ModelTrain for &ProximityScore<T> {
@@ -52,13 +83,19 @@ impl<T> ModelTrain for &ProximityScore<T> {
 
     #[allow(refining_impl_trait)] // Makes it easier to know what type is returned and resolves error encountered using opaque return type
     fn to_inference(&self, results: TrainResults) -> ProximityScore<Trained> {
-        let predict_config = PredictConfig::from(&results);
+        let mut predict_config = PredictConfig::from(&results);
+        if let Some(previous) = self.train_data.as_ref() {
+            // Retraining so keep the operating point that was tuned
+            predict_config.carry_over_thresholds(&previous.predict_config);
+        }
         let train_data = TrainingInfo {
             results,
             predict_config,
         };
         ProximityScore::<Trained> {
             train_data: Some(train_data),
+            auto_retrain: self.auto_retrain,
+            threshold_presets: self.threshold_presets.clone(),
             state: PhantomData,
         }
     }
@@ -76,11 +113,24 @@ impl<T> ModelTrain for ProximityScore<T> {
         if points.is_empty() {
             bail!("no points found");
         }
+        let is_training: Vec<bool> = points.iter().map(DataPoint::is_used_for_training).collect();
+        let training_count = is_training.iter().filter(|&&x| x).count();
+        if training_count == 0 {
+            bail!("all points are excluded from training or in the test split");
+        }
         let pairwise_distances = points.pairwise_distances();
-        let n = points.len() as f64;
+        let n = training_count as f64;
+        // Every point is scored but only against the points used for training
         let scores = pairwise_distances
             .into_iter()
-            .map(|distances| distances.into_iter().sum::<f64>() / n)
+            .map(|distances| {
+                distances
+                    .into_iter()
+                    .zip(is_training.iter())
+                    .filter_map(|(distance, &is_training)| is_training.then_some(distance))
+                    .sum::<f64>()
+                    / n
+            })
             .collect();
         Ok(TrainResults {
             scores,
@@ -126,6 +176,49 @@ impl ModelInference for &ProximityScore<Trained> {
             .expect("expected to only be called if this is set (checked by type)");
         training_info.results.scores[index]
     }
+
+    fn is_borderline_on_training_data(&self, index: usize) -> bool {
+        let training_info = self
+            .train_data
+            .as_ref()
+            .expect("expected to only be called if this is set (checked by type)");
+        let config = &training_info.predict_config;
+        let score = training_info.results.scores[index];
+        config
+            .borderline_threshold
+            .is_some_and(|lower| lower <= score && score < config.threshold)
+    }
+
+    fn score(&self, point: PointArray, training_points: &[DataPoint]) -> f64 {
+        // Same as training (Sum of the distances to the training points over their count)
+        let (sum, count) = training_points
+            .iter()
+            .filter(|x| x.is_used_for_training())
+            .fold((0.0, 0usize), |(sum, count), x| {
+                (sum + x.distance_to(point), count + 1)
+            });
+        sum / count.max(1) as f64
+    }
+
+    fn prediction_for_score(&self, score: f64) -> DataLabel {
+        let training_info = self
+            .train_data
+            .as_ref()
+            .expect("expected to only be called if this is set (checked by type)");
+        if score < training_info.predict_config.threshold {
+            DataLabel::Normal
+        } else {
+            DataLabel::Anomaly
+        }
+    }
+
+    fn decision_threshold(&self) -> f64 {
+        self.train_data
+            .as_ref()
+            .expect("expected to only be called if this is set (checked by type)")
+            .predict_config
+            .threshold
+    }
 }
 
 impl ModelInference for ProximityScore<Trained> {
@@ -140,6 +233,22 @@ impl ModelInference for ProximityScore<Trained> {
     fn score_for_training_data(&self, index: usize) -> f64 {
         (&self).score_for_training_data(index)
     }
+
+    fn is_borderline_on_training_data(&self, index: usize) -> bool {
+        (&self).is_borderline_on_training_data(index)
+    }
+
+    fn score(&self, point: PointArray, training_points: &[DataPoint]) -> f64 {
+        (&self).score(point, training_points)
+    }
+
+    fn prediction_for_score(&self, score: f64) -> DataLabel {
+        (&self).prediction_for_score(score)
+    }
+
+    fn decision_threshold(&self) -> f64 {
+        (&self).decision_threshold()
+    }
 }
 
 impl ModelInferenceConfig for ProximityScore<Trained> {
@@ -177,10 +286,134 @@ impl From<&TrainResults> for PredictConfig {
             min_score,
             max_score,
             threshold,
+            borderline_threshold: None,
         }
     }
 }
 
 impl PredictConfig {
     const THRESHOLD_RATIO: f64 = 3. / 4.; // Set to 75% NB: code assumes this is between 0 and 1
+
+    /// Moves the thresholds to the same fractions of the score range as they are in `previous`
+    ///
+    /// Nothing is changed if all the previous scores were equal as there is no fraction to keep
+    fn carry_over_thresholds(&mut self, previous: &PredictConfig) {
+        let previous_range = previous.max_score - previous.min_score;
+        if previous_range <= 0. {
+            return;
+        }
+        let (min_score, range) = (self.min_score, self.max_score - self.min_score);
+        let rescale = |value: f64| {
+            let ratio = ((value - previous.min_score) / previous_range).clamp(0., 1.);
+            min_score + ratio * range
+        };
+        self.borderline_threshold = previous.borderline_threshold.map(rescale);
+        self.threshold = rescale(previous.threshold);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::{tests::generate_data_points, Data, DataPoint};
+    use crate::app::local_experiments::ModelInferenceConfig as _;
+
+    use super::*;
+
+    #[test]
+    fn excluded_points_are_scored_but_not_used_for_training() {
+        let mut points = generate_data_points();
+        let far_away = DataPoint {
+            is_excluded_from_training: true,
+            ..DataPoint::new(1000.0, 1000.0, DataLabel::Anomaly)
+        };
+        let train = |points: DataPoints| {
+            crate::background_worker::create_runtime()
+                .block_on(ProximityScore::<UnTrained>::train(
+                    (),
+                    points,
+                    Data::default().timestamp(),
+                    &mut Default::default(),
+                ))
+                .unwrap()
+                .scores
+        };
+        let expected = train(points.clone());
+        points.push(far_away);
+        let actual = train(points);
+        assert_eq!(actual.len(), expected.len() + 1);
+        assert_eq!(actual[..expected.len()], expected);
+        assert!(actual[expected.len()] > 100.0);
+    }
+
+    #[test]
+    fn borderline_band_is_below_threshold() {
+        let results = crate::background_worker::create_runtime()
+            .block_on(ProximityScore::<UnTrained>::train(
+                (),
+                generate_data_points(),
+                Data::default().timestamp(),
+                &mut Default::default(),
+            ))
+            .unwrap();
+        let mut model = ProximityScore::new().to_inference(results);
+        let config = model.predict_config_mut();
+        config.borderline_threshold = Some(config.min_score);
+        let threshold = config.threshold;
+        for i in 0..generate_data_points().len() {
+            let is_below = model.score_for_training_data(i) < threshold;
+            assert_eq!(model.is_borderline_on_training_data(i), is_below);
+            assert_eq!(model.prediction_on_training_data(i).is_normal(), is_below);
+        }
+    }
+
+    #[test]
+    fn scoring_a_training_point_matches_training() {
+        let points = generate_data_points();
+        let results = crate::background_worker::create_runtime()
+            .block_on(ProximityScore::<UnTrained>::train(
+                (),
+                points.clone(),
+                Data::default().timestamp(),
+                &mut Default::default(),
+            ))
+            .unwrap();
+        let model = ProximityScore::new().to_inference(results);
+        for (i, point) in points.iter().enumerate() {
+            let score = model.score(point.to_array(), &points);
+            assert!((score - model.score_for_training_data(i)).abs() < 1e-9);
+            assert_eq!(
+                model.prediction_for_score(score),
+                model.prediction_on_training_data(i)
+            );
+        }
+    }
+
+    #[test]
+    fn retrain_keeps_threshold_ratio() {
+        let train = |points: DataPoints| {
+            crate::background_worker::create_runtime()
+                .block_on(ProximityScore::<UnTrained>::train(
+                    (),
+                    points,
+                    Data::default().timestamp(),
+                    &mut Default::default(),
+                ))
+                .unwrap()
+        };
+        let mut points = generate_data_points();
+        let mut model = ProximityScore::new().to_inference(train(points.clone()));
+        let config = model.predict_config_mut();
+        let ratio_of = |config: &PredictConfig, value: f64| {
+            (value - config.min_score) / (config.max_score - config.min_score)
+        };
+        config.threshold = config.min_score + 0.4 * (config.max_score - config.min_score);
+        config.borderline_threshold = Some(config.min_score);
+
+        points.push(DataPoint::new(50.0, -20.0, DataLabel::Anomaly));
+        let mut retrained = model.to_inference(train(points));
+        let config = *retrained.predict_config_mut();
+        assert_ne!(config.max_score, model.predict_config_mut().max_score);
+        assert!((ratio_of(&config, config.threshold) - 0.4).abs() < 1e-9);
+        assert_eq!(config.borderline_threshold, Some(config.min_score));
+    }
 }
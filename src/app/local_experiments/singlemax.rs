@@ -3,7 +3,10 @@ use std::marker::PhantomData;
 use anyhow::bail;
 
 use crate::app::{
-    data_definition::{DataLabel, DataPoints, DataTimestamp, DistanceCalculations as _},
+    data_definition::{
+        DataLabel, DataPoint, DataPoints, DataTimestamp, DistanceCalculation as _,
+        DistanceCalculations as _, PointArray,
+    },
     status_msg::StatusMsg,
 };
 
@@ -12,17 +15,27 @@ use super::{ModelInference, ModelInferenceConfig, ModelTrain, TrainResults, Trai
 #[derive(serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct SingleMax<State = UnTrained> {
     train_data: Option<TrainingInfo>,
+    /// If the model should be retrained automatically when the data changes
+    #[serde(default)]
+    auto_retrain: bool,
     state: PhantomData<State>, // This doesn't take up space at runtime
 }
 impl SingleMax {
     pub(crate) fn new() -> SingleMax {
         SingleMax::<UnTrained> {
             train_data: None,
+            auto_retrain: false,
             state: PhantomData,
         }
     }
 }
 
+impl<T> SingleMax<T> {
+    pub(crate) fn auto_retrain_mut(&mut self) -> &mut bool {
+        &mut self.auto_retrain
+    }
+}
+
 #[derive(serde::Deserialize, serde::Serialize, PartialEq)]
 pub struct TrainingInfo {
     results: TrainResults,
@@ -52,6 +65,7 @@ impl<T> ModelTrain for &SingleMax<T> {
         };
         SingleMax::<Trained> {
             train_data: Some(train_data),
+            auto_retrain: self.auto_retrain,
             state: PhantomData,
         }
     }
@@ -69,6 +83,9 @@ impl<T> ModelTrain for SingleMax<T> {
         if points.is_empty() {
             bail!("no points found");
         }
+        if !points.iter().any(DataPoint::is_used_for_training) {
+            bail!("all points are excluded from training or in the test split");
+        }
         let pairwise_distances = points.pairwise_distances();
         let scores = pairwise_distances
             .into_iter()
@@ -78,8 +95,10 @@ impl<T> ModelTrain for SingleMax<T> {
                     .into_iter()
                     .enumerate()
                     .fold(f64::INFINITY, |acc, (other_index, elem)| {
-                        if score_for_index == other_index {
-                            // Skip distance to itself when getting minimum
+                        if score_for_index == other_index
+                            || !points[other_index].is_used_for_training()
+                        {
+                            // Skip distance to itself and to points held out when getting minimum
                             acc
                         } else {
                             acc.min(elem)
@@ -130,6 +149,37 @@ impl ModelInference for &SingleMax<Trained> {
             .expect("expected to only be called if this is set (checked by type)");
         training_info.results.scores[index]
     }
+
+    fn score(&self, point: PointArray, training_points: &[DataPoint]) -> f64 {
+        // Same as training (Distance to the nearest training point)
+        training_points
+            .iter()
+            .filter(|x| x.is_used_for_training())
+            .map(|x| x.distance_to(point))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Anomaly if the point would replace the outlier by being further from its nearest neighbour
+    fn prediction_for_score(&self, score: f64) -> DataLabel {
+        let training_info = self
+            .train_data
+            .as_ref()
+            .expect("expected to only be called if this is set (checked by type)");
+        if score > training_info.results.scores[training_info.outlier_index] {
+            DataLabel::Anomaly
+        } else {
+            DataLabel::Normal
+        }
+    }
+
+    /// The score of the outlier
+    fn decision_threshold(&self) -> f64 {
+        let training_info = self
+            .train_data
+            .as_ref()
+            .expect("expected to only be called if this is set (checked by type)");
+        training_info.results.scores[training_info.outlier_index]
+    }
 }
 
 impl ModelInference for SingleMax<Trained> {
@@ -144,6 +194,18 @@ impl ModelInference for SingleMax<Trained> {
     fn score_for_training_data(&self, index: usize) -> f64 {
         (&self).score_for_training_data(index)
     }
+
+    fn score(&self, point: PointArray, training_points: &[DataPoint]) -> f64 {
+        (&self).score(point, training_points)
+    }
+
+    fn prediction_for_score(&self, score: f64) -> DataLabel {
+        (&self).prediction_for_score(score)
+    }
+
+    fn decision_threshold(&self) -> f64 {
+        (&self).decision_threshold()
+    }
 }
 
 impl ModelInferenceConfig for SingleMax<Trained> {
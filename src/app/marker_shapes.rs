@@ -0,0 +1,117 @@
+use egui_plot::MarkerShape;
+
+/// Same as [`MarkerShape`] but can be saved with the app state
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PointShape {
+    Circle,
+    Diamond,
+    Square,
+    Cross,
+    Plus,
+    Up,
+    Down,
+    Left,
+    Right,
+    Asterisk,
+}
+
+impl PointShape {
+    const ALL: [Self; 10] = [
+        Self::Circle,
+        Self::Diamond,
+        Self::Square,
+        Self::Cross,
+        Self::Plus,
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::Asterisk,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Circle => "Circle",
+            Self::Diamond => "Diamond",
+            Self::Square => "Square",
+            Self::Cross => "Cross",
+            Self::Plus => "Plus",
+            Self::Up => "Up",
+            Self::Down => "Down",
+            Self::Left => "Left",
+            Self::Right => "Right",
+            Self::Asterisk => "Asterisk",
+        }
+    }
+
+    /// Shows a drop down to pick the shape
+    pub fn ui(&mut self, ui: &mut egui::Ui, id_source: &str) {
+        egui::ComboBox::from_id_source(id_source)
+            .width(80.0)
+            .selected_text(self.name())
+            .show_ui(ui, |ui| {
+                for shape in Self::ALL {
+                    ui.selectable_value(self, shape, shape.name());
+                }
+            });
+    }
+}
+
+impl From<PointShape> for MarkerShape {
+    fn from(value: PointShape) -> Self {
+        match value {
+            PointShape::Circle => MarkerShape::Circle,
+            PointShape::Diamond => MarkerShape::Diamond,
+            PointShape::Square => MarkerShape::Square,
+            PointShape::Cross => MarkerShape::Cross,
+            PointShape::Plus => MarkerShape::Plus,
+            PointShape::Up => MarkerShape::Up,
+            PointShape::Down => MarkerShape::Down,
+            PointShape::Left => MarkerShape::Left,
+            PointShape::Right => MarkerShape::Right,
+            PointShape::Asterisk => MarkerShape::Asterisk,
+        }
+    }
+}
+
+/// Marker shape used for each kind of point on the plot
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct MarkerShapes {
+    pub normal: PointShape,
+    pub anomaly: PointShape,
+    pub true_positives: PointShape,
+    pub false_positives: PointShape,
+    pub true_negatives: PointShape,
+    pub false_negatives: PointShape,
+    pub borderline: PointShape,
+}
+
+impl Default for MarkerShapes {
+    fn default() -> Self {
+        Self {
+            normal: PointShape::Plus,
+            anomaly: PointShape::Asterisk,
+            true_positives: PointShape::Asterisk,
+            false_positives: PointShape::Plus,
+            true_negatives: PointShape::Plus,
+            false_negatives: PointShape::Asterisk,
+            borderline: PointShape::Diamond,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_marker_shape_can_be_picked() {
+        let mut picked: Vec<MarkerShape> = PointShape::ALL.map(MarkerShape::from).to_vec();
+        picked.dedup();
+        assert_eq!(picked.len(), MarkerShape::all().count());
+        for shape in MarkerShape::all() {
+            assert!(picked.contains(&shape), "{shape:?}");
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+
+use crate::DBV;
+
+use super::data_definition::PendingMatLoad;
+
+/// A MAT-file waiting for the user to select the variables to load
+#[derive(PartialEq, Debug)]
+pub struct MatVariablesDialog {
+    pending: PendingMatLoad,
+    path: PathBuf,
+    /// Use the selected names for later loads and saves
+    should_remember: bool,
+}
+
+impl DBV {
+    pub(super) fn ui_mat_variable_names(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("grid-mat-variable-names").show(ui, |ui| {
+            ui.label("Points (n x 2)");
+            ui.text_edit_singleline(&mut self.mat_variable_names.points);
+            ui.end_row();
+            ui.label("Labels (n x 1)");
+            ui.text_edit_singleline(&mut self.mat_variable_names.labels);
+            ui.end_row();
+        });
+        if ui.button("Reset to X and y").clicked() {
+            self.mat_variable_names = Default::default();
+        }
+    }
+
+    pub(super) fn prompt_for_mat_variables(&mut self, pending: PendingMatLoad, path: PathBuf) {
+        self.status_msg.info(format!(
+            "{:?} or {:?} not found in MAT-file. Please select the variables to use",
+            self.mat_variable_names.points, self.mat_variable_names.labels
+        ));
+        self.mat_variables_dialog = Some(MatVariablesDialog {
+            pending,
+            path,
+            should_remember: false,
+        });
+    }
+
+    pub(super) fn ui_mat_variables_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.mat_variables_dialog.as_mut() else {
+            return;
+        };
+        let mut is_open = true;
+        let mut should_load = false;
+        let mut should_cancel = false;
+        egui::Window::new("MAT-file Variables")
+            .open(&mut is_open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!("File: {:?}", dialog.path));
+                let names = &mut dialog.pending.names;
+                for (field_name, name) in [
+                    ("Points (n x 2)", &mut names.points),
+                    ("Labels (n x 1)", &mut names.labels),
+                ] {
+                    egui::ComboBox::new(format!("id-mat-variables-{field_name}"), field_name)
+                        .selected_text(name.as_str())
+                        .show_ui(ui, |ui| {
+                            for available in dialog.pending.available.iter() {
+                                ui.selectable_value(name, available.clone(), available);
+                            }
+                        });
+                }
+                ui.checkbox(
+                    &mut dialog.should_remember,
+                    "Use these names for future loads and saves",
+                );
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Load").clicked() {
+                        should_load = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_load {
+            match dialog
+                .pending
+                .load()
+                .context("failed to load using selected variables")
+            {
+                Ok(loaded_data) => {
+                    let MatVariablesDialog {
+                        pending,
+                        path,
+                        should_remember,
+                    } = self
+                        .mat_variables_dialog
+                        .take()
+                        .expect("dialog should be open to be able to load from it");
+                    if should_remember {
+                        self.mat_variable_names = pending.names;
+                    }
+                    self.on_data_loaded(loaded_data, path);
+                }
+                Err(e) => self.status_msg.error_debug(e),
+            }
+        } else if !is_open || should_cancel {
+            self.mat_variables_dialog = None;
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use egui_plot::{Line, MarkerShape, PlotPoint, PlotPoints, PlotUi, Points, Text};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataPoint, DistanceCalculation as _, PointArray},
+    display_precision::DisplayPrecision,
+};
+
+/// Two locations clicked in measure mode (Kept until cleared or a new one is started)
+#[derive(Debug, PartialEq, Default)]
+pub struct Measurement {
+    start: Option<PointArray>,
+    end: Option<PointArray>,
+}
+
+impl Measurement {
+    /// Starts a new measurement or finishes the one in progress
+    fn click(&mut self, position: PointArray) {
+        if self.start.is_some() && self.end.is_none() {
+            self.end = Some(position);
+        } else {
+            *self = Self {
+                start: Some(position),
+                end: None,
+            };
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn summary(start: PointArray, end: PointArray, precision: &DisplayPrecision) -> String {
+        format!(
+            "Distance: {}\nΔx0: {}\nΔx1: {}",
+            precision.format(DataPoint::calculate_distance(start, end)),
+            precision.format(end[0] - start[0]),
+            precision.format(end[1] - start[1]),
+        )
+    }
+}
+
+impl DBV {
+    pub(super) fn measure_on_click(
+        &mut self,
+        pointer_coordinate: Option<PlotPoint>,
+        is_primary: bool,
+    ) {
+        if !is_primary {
+            self.measurement.clear();
+            return;
+        }
+        if let Some(pos) = pointer_coordinate {
+            self.measurement.click([pos.x, pos.y]);
+            if let (Some(start), Some(end)) = (self.measurement.start, self.measurement.end) {
+                self.status_msg.info(
+                    Measurement::summary(start, end, &self.display_precision).replace('\n', ", "),
+                );
+            }
+        }
+    }
+
+    pub(super) fn ui_btn_clear_measurement(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(
+                self.measurement.start.is_some(),
+                egui::Button::new("Clear Measurement"),
+            )
+            .clicked()
+        {
+            self.measurement.clear();
+        }
+    }
+
+    /// Draws the last measurement or the one in progress up to the pointer
+    pub(super) fn plot_measurement(&self, plot_ui: &mut PlotUi) {
+        let Some(start) = self.measurement.start else {
+            return;
+        };
+        let end = match self.measurement.end {
+            Some(end) => end,
+            None if self.click_mode.is_measure() => match plot_ui.pointer_coordinate() {
+                Some(pointer) => [pointer.x, pointer.y],
+                None => start,
+            },
+            None => return,
+        };
+        plot_ui.line(
+            Line::new(PlotPoints::new(vec![start, end]))
+                .name("Measurement")
+                .color(self.color_selection)
+                .width(1.5),
+        );
+        plot_ui.points(
+            Points::new(vec![start, end])
+                .name("Measurement")
+                .shape(MarkerShape::Cross)
+                .radius(self.marker_radius * 1.5)
+                .color(self.color_selection),
+        );
+        plot_ui.text(
+            Text::new(
+                PlotPoint::new((start[0] + end[0]) / 2.0, (start[1] + end[1]) / 2.0),
+                Measurement::summary(start, end, &self.display_precision),
+            )
+            .anchor(egui::Align2::LEFT_BOTTOM)
+            .color(self.color_selection),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_click_finishes_and_third_restarts() {
+        let mut measurement = Measurement::default();
+        measurement.click([0.0, 0.0]);
+        measurement.click([3.0, -4.0]);
+        assert_eq!(measurement.end, Some([3.0, -4.0]));
+        let precision = DisplayPrecision {
+            decimal_places: Some(1),
+        };
+        assert_eq!(
+            Measurement::summary([0.0, 0.0], [3.0, -4.0], &precision),
+            "Distance: 5.0\nΔx0: 3.0\nΔx1: -4.0"
+        );
+
+        measurement.click([1.0, 1.0]);
+        assert_eq!(measurement.start, Some([1.0, 1.0]));
+        assert_eq!(measurement.end, None);
+    }
+}
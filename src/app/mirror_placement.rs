@@ -0,0 +1,137 @@
+use egui_plot::{HLine, LineStyle, MarkerShape, PlotPoint, PlotUi, Points, VLine};
+
+use crate::DBV;
+
+use super::data_definition::{DataLabel, DataPoint, PointArray};
+
+/// What new points are reflected across
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum MirrorAxis {
+    #[default]
+    Off,
+    /// Line where x0 is the x0 of the center
+    Vertical,
+    /// Line where x1 is the x1 of the center
+    Horizontal,
+    /// Reflected through the center
+    Point,
+}
+
+/// Adds the reflection of each point added by clicking
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug, Default)]
+#[serde(default)]
+pub struct MirrorPlacement {
+    pub axis: MirrorAxis,
+    /// Position of the line or point reflected across
+    pub center: PointArray,
+}
+
+impl MirrorPlacement {
+    /// Returns the reflection of the position (`None` if off or it is its own reflection)
+    fn mirror(&self, [x0, x1]: PointArray) -> Option<PointArray> {
+        let [c0, c1] = self.center;
+        let result = match self.axis {
+            MirrorAxis::Off => return None,
+            MirrorAxis::Vertical => [2.0 * c0 - x0, x1],
+            MirrorAxis::Horizontal => [x0, 2.0 * c1 - x1],
+            MirrorAxis::Point => [2.0 * c0 - x0, 2.0 * c1 - x1],
+        };
+        (result != [x0, x1]).then_some(result)
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_mirror_options(&mut self, ui: &mut egui::Ui) {
+        let mirror = &mut self.mirror_placement;
+        ui.label("Mirror")
+            .on_hover_text("Also adds the reflection of each point clicked (Undone together)");
+        egui::ComboBox::from_id_source("mirror axis")
+            .selected_text(format!("{:?}", mirror.axis))
+            .show_ui(ui, |ui| {
+                for axis in [
+                    MirrorAxis::Off,
+                    MirrorAxis::Vertical,
+                    MirrorAxis::Horizontal,
+                    MirrorAxis::Point,
+                ] {
+                    ui.selectable_value(&mut mirror.axis, axis, format!("{axis:?}"));
+                }
+            });
+        if matches!(mirror.axis, MirrorAxis::Vertical | MirrorAxis::Point) {
+            ui.add(
+                egui::DragValue::new(&mut mirror.center[0])
+                    .speed(0.1)
+                    .prefix("x0: "),
+            );
+        }
+        if matches!(mirror.axis, MirrorAxis::Horizontal | MirrorAxis::Point) {
+            ui.add(
+                egui::DragValue::new(&mut mirror.center[1])
+                    .speed(0.1)
+                    .prefix("x1: "),
+            );
+        }
+    }
+
+    /// Adds a point where clicked along with its reflection if mirroring is on
+    pub(super) fn add_on_click(&mut self, pointer_coordinate: Option<PlotPoint>, label: DataLabel) {
+        let mirrored = pointer_coordinate.and_then(|x| self.mirror_placement.mirror([x.x, x.y]));
+        match (pointer_coordinate, mirrored) {
+            (Some(pos), Some([x0, x1])) => self.data.add_many_in_bounds(
+                vec![
+                    DataPoint::new(pos.x, pos.y, label),
+                    DataPoint::new(x0, x1, label),
+                ],
+                &mut self.status_msg,
+            ),
+            _ => self
+                .data
+                .add(pointer_coordinate, label, &mut self.status_msg),
+        }
+    }
+
+    /// Shows what points are reflected across while adding points
+    pub(super) fn plot_mirror_guide(&self, plot_ui: &mut PlotUi) {
+        if self.click_mode != super::ClickMode::AddPoints {
+            return;
+        }
+        let color = self.color_selection.gamma_multiply(0.6);
+        let [c0, c1] = self.mirror_placement.center;
+        match self.mirror_placement.axis {
+            MirrorAxis::Off => {}
+            MirrorAxis::Vertical => {
+                plot_ui.vline(VLine::new(c0).color(color).style(LineStyle::dashed_dense()))
+            }
+            MirrorAxis::Horizontal => {
+                plot_ui.hline(HLine::new(c1).color(color).style(LineStyle::dashed_dense()))
+            }
+            MirrorAxis::Point => plot_ui.points(
+                Points::new(vec![[c0, c1]])
+                    .shape(MarkerShape::Plus)
+                    .radius(self.marker_radius * 2.0)
+                    .color(color),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reflections_across_each_axis() {
+        let mut mirror = MirrorPlacement {
+            axis: MirrorAxis::Off,
+            center: [1.0, 2.0],
+        };
+        assert_eq!(mirror.mirror([3.0, 5.0]), None);
+        mirror.axis = MirrorAxis::Vertical;
+        assert_eq!(mirror.mirror([3.0, 5.0]), Some([-1.0, 5.0]));
+        assert_eq!(mirror.mirror([1.0, 5.0]), None, "on the line");
+        mirror.axis = MirrorAxis::Horizontal;
+        assert_eq!(mirror.mirror([3.0, 5.0]), Some([3.0, -1.0]));
+        mirror.axis = MirrorAxis::Point;
+        assert_eq!(mirror.mirror([3.0, 5.0]), Some([-1.0, -1.0]));
+    }
+}
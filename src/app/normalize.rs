@@ -0,0 +1,184 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::data_definition::DataPoint;
+
+/// How the coordinates are rescaled
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum NormalizeMethod {
+    /// Each axis gets a mean of 0 and a standard deviation of 1
+    #[default]
+    Standardize,
+    /// Each axis is scaled to the target range
+    MinMax,
+}
+
+/// Settings for rescaling every point
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct Normalize {
+    pub method: NormalizeMethod,
+    /// Range used by [`NormalizeMethod::MinMax`]
+    pub target_range: [f64; 2],
+    /// Show the statistics used for each axis in the status messages
+    pub should_log_params: bool,
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Self {
+            method: Default::default(),
+            target_range: [0.0, 1.0],
+            should_log_params: true,
+        }
+    }
+}
+
+/// Maps a coordinate to `value * scale + offset`
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct AxisTransform {
+    scale: f64,
+    offset: f64,
+    /// The statistics the transform was derived from for the status log
+    description: [(&'static str, f64); 2],
+}
+
+impl Normalize {
+    /// The transform of an axis given its values (Constant axes are only shifted)
+    fn axis_transform(&self, values: &[f64]) -> AxisTransform {
+        let count = values.len().max(1) as f64;
+        match self.method {
+            NormalizeMethod::Standardize => {
+                let mean = values.iter().sum::<f64>() / count;
+                let std_dev =
+                    (values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count).sqrt();
+                let scale = if std_dev > 0.0 { 1.0 / std_dev } else { 1.0 };
+                AxisTransform {
+                    scale,
+                    offset: -mean * scale,
+                    description: [("mean", mean), ("std dev", std_dev)],
+                }
+            }
+            NormalizeMethod::MinMax => {
+                let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let [low, high] = self.target_range;
+                if max > min {
+                    let scale = (high - low) / (max - min);
+                    AxisTransform {
+                        scale,
+                        offset: low - min * scale,
+                        description: [("min", min), ("max", max)],
+                    }
+                } else {
+                    // Nothing to stretch so put it in the middle of the range
+                    AxisTransform {
+                        scale: 1.0,
+                        offset: (low + high) / 2.0 - min,
+                        description: [("min", min), ("max", max)],
+                    }
+                }
+            }
+        }
+    }
+
+    /// The rescaled points and the transforms used for each axis
+    fn apply(&self, points: &[DataPoint]) -> (Vec<(usize, DataPoint)>, [AxisTransform; 2]) {
+        let x0: Vec<f64> = points.iter().map(|p| p.x0).collect();
+        let x1: Vec<f64> = points.iter().map(|p| p.x1).collect();
+        let transforms = [self.axis_transform(&x0), self.axis_transform(&x1)];
+        let edits = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let mut point = *point;
+                point.x0 = point.x0 * transforms[0].scale + transforms[0].offset;
+                point.x1 = point.x1 * transforms[1].scale + transforms[1].offset;
+                (i, point)
+            })
+            .collect();
+        (edits, transforms)
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_menu_normalize(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Normalize…", |ui| {
+                ui.radio_value(
+                    &mut self.normalize.method,
+                    NormalizeMethod::Standardize,
+                    "Standardize (z-score)",
+                );
+                ui.radio_value(
+                    &mut self.normalize.method,
+                    NormalizeMethod::MinMax,
+                    "Min-max scaling",
+                );
+                if self.normalize.method == NormalizeMethod::MinMax {
+                    ui.horizontal(|ui| {
+                        ui.label("Target range");
+                        let [low, high] = &mut self.normalize.target_range;
+                        ui.add(egui::DragValue::new(low).speed(0.1));
+                        ui.add(egui::DragValue::new(high).speed(0.1));
+                    });
+                }
+                ui.checkbox(
+                    &mut self.normalize.should_log_params,
+                    "Show parameters in status",
+                );
+                if ui
+                    .add(Button::new(format!(
+                        "Rescale {} points",
+                        self.data.points().len()
+                    )))
+                    .on_hover_text("Each axis is rescaled separately as a single undoable change")
+                    .clicked()
+                {
+                    let (edits, transforms) = self.normalize.apply(self.data.points());
+                    self.data.edit_many(edits);
+                    self.selection.keep_for(&self.data);
+                    if self.normalize.should_log_params {
+                        for (axis, transform) in ["x0", "x1"].iter().zip(transforms) {
+                            let [(name_a, a), (name_b, b)] = transform.description;
+                            self.status_msg.info(format!(
+                                "{axis}: {name_a} {a}, {name_b} {b} (new = old * {} + {})",
+                                transform.scale, transform.offset
+                            ));
+                        }
+                    }
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn rescales_each_axis() {
+        let points = [
+            DataPoint::new(1.0, 5.0, DataLabel::Normal),
+            DataPoint::new(3.0, 5.0, DataLabel::Anomaly),
+        ];
+        let mut normalize = Normalize::default();
+        let (edits, transforms) = normalize.apply(&points);
+        assert_eq!([edits[0].1.x0, edits[1].1.x0], [-1.0, 1.0]);
+        // Constant axis is only centered
+        assert_eq!([edits[0].1.x1, edits[1].1.x1], [0.0, 0.0]);
+        assert_eq!(transforms[0].description, [("mean", 2.0), ("std dev", 1.0)]);
+        assert_eq!(edits[1].1.label, DataLabel::Anomaly);
+
+        normalize.method = NormalizeMethod::MinMax;
+        normalize.target_range = [-2.0, 2.0];
+        let (edits, _) = normalize.apply(&points);
+        assert_eq!([edits[0].1.x0, edits[1].1.x0], [-2.0, 2.0]);
+        assert_eq!(edits[0].1.x1, 0.0);
+    }
+}
@@ -1,6 +1,17 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
-use super::{data_definition::DataPoints, local_experiments::TrainResults};
+use super::{
+    data_definition::{DataPoints, ParsedPoints, PendingCsvLoad, PendingMatLoad},
+    local_experiments::TrainResults,
+    project::Project,
+    settings_transfer::AppSettings,
+};
 
 pub type AwaitingType = poll_promise::Promise<OperationOutcome>;
 
@@ -11,10 +22,43 @@ pub enum OperationalState {
     #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
     RunningPyExperiment(AwaitingType),
     Saving(AwaitingType),
-    Loading(AwaitingType),
+    Loading(AwaitingType, LoadProgress),
+    /// Writing several files (Progress counts the files written)
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    Exporting(AwaitingType, LoadProgress),
     RunningLocExperiment(AwaitingType),
 }
 
+/// Progress of a load running in the background shared with the UI (Cheap to clone)
+#[derive(Clone, Default, Debug)]
+pub struct LoadProgress(Arc<LoadProgressCounts>);
+
+#[derive(Default, Debug)]
+struct LoadProgressCounts {
+    processed: AtomicUsize,
+    total: AtomicUsize,
+}
+
+impl LoadProgress {
+    pub fn set_total(&self, total: usize) {
+        self.0.total.store(total, Ordering::Relaxed);
+    }
+
+    pub fn set_processed(&self, processed: usize) {
+        self.0.processed.store(processed, Ordering::Relaxed);
+    }
+
+    /// Returns the fraction completed or `None` if the total is not known yet
+    pub fn fraction(&self) -> Option<f32> {
+        let total = self.0.total.load(Ordering::Relaxed);
+        if total == 0 {
+            return None;
+        }
+        let processed = self.0.processed.load(Ordering::Relaxed).min(total);
+        Some(processed as f32 / total as f32)
+    }
+}
+
 #[derive(Debug)]
 pub enum OperationOutcome {
     Cancelled,
@@ -30,8 +74,45 @@ pub enum Payload {
         loaded_data: DataPoints,
         path: PathBuf,
     },
-    Save(PathBuf),
+    LoadNeedsCsvMapping {
+        pending: PendingCsvLoad,
+        path: PathBuf,
+    },
+    LoadNeedsMatVariables {
+        pending: PendingMatLoad,
+        path: PathBuf,
+    },
+    /// Path saved to and the content hash of the points saved
+    Save(PathBuf, String),
+    /// Path exported to and the content hash of the points exported
+    Export(PathBuf, String),
+    LoadProject {
+        project: Box<Project>,
+        path: PathBuf,
+    },
+    SaveProject(PathBuf),
+    ExportSettings(PathBuf),
+    ImportSettings {
+        settings: Box<AppSettings>,
+        path: PathBuf,
+    },
     Train(TrainResults),
+    /// Points read from a file to be scored by the model without adding them to the data
+    ScoreExternal {
+        parsed: ParsedPoints,
+        path: PathBuf,
+    },
+    /// Folder the variants were written to and how many
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    ExportVariants {
+        folder: PathBuf,
+        count: usize,
+    },
+    /// Points read from a file to be drawn behind the data for comparison
+    LoadReference {
+        parsed: ParsedPoints,
+        path: PathBuf,
+    },
 }
 
 impl PartialEq for OperationalState {
@@ -58,6 +139,16 @@ impl OperationalState {
         matches!(self, Self::RunningPyExperiment(..))
     }
 
+    /// Returns what is running and the fraction completed if the progress is known
+    #[must_use]
+    pub fn progress(&self) -> Option<(&'static str, f32)> {
+        match self {
+            Self::Loading(_, progress) => Some(("Loading...", progress.fraction()?)),
+            Self::Exporting(_, progress) => Some(("Exporting...", progress.fraction()?)),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if the operational state is [`RunningLocExperiment`].
     ///
     /// [`RunningLocExperiment`]: OperationalState::RunningLocExperiment
@@ -0,0 +1,182 @@
+use egui::Button;
+use egui_plot::{MarkerShape, PlotUi, Points};
+
+use crate::DBV;
+
+use super::data_definition::{DataPoint, DataTimestamp, DistanceCalculation as _, PointArray};
+
+/// Settings for removing points that are far from all the others
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct OutlierPruning {
+    /// Which nearest neighbor the distance is measured to
+    pub k: usize,
+    /// Points whose distance is above this quantile (0 to 1) of all the distances are removed
+    pub quantile: f64,
+    /// Points to remove for the data version and settings they were found with
+    #[serde(skip)]
+    preview: Option<(DataTimestamp, usize, f64, Vec<usize>)>,
+}
+
+impl Default for OutlierPruning {
+    fn default() -> Self {
+        Self {
+            k: 3,
+            quantile: 0.95,
+            preview: None,
+        }
+    }
+}
+
+impl OutlierPruning {
+    /// Indices of the points whose distance to their k-th nearest neighbor is above the quantile
+    fn outliers(&self, points: &[DataPoint]) -> Vec<usize> {
+        if self.k == 0 || points.len() <= self.k {
+            return vec![];
+        }
+        let distances: Vec<f64> = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let mut others: Vec<f64> = points
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, other)| point.distance_to(other.to_array()))
+                    .collect();
+                let (_, kth, _) = others.select_nth_unstable_by(self.k - 1, f64::total_cmp);
+                *kth
+            })
+            .collect();
+        let mut sorted = distances.clone();
+        sorted.sort_by(f64::total_cmp);
+        let rank = (self.quantile.clamp(0.0, 1.0) * (sorted.len() - 1) as f64).round() as usize;
+        let threshold = sorted[rank];
+        distances
+            .iter()
+            .enumerate()
+            .filter(|(_, distance)| **distance > threshold)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_btn_prune_outliers(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(!self.data.is_empty(), Button::new("Prune Outliers…"))
+            .clicked()
+        {
+            self.show_outlier_pruning_window = true;
+            ui.close_menu();
+        }
+    }
+
+    /// The points that would be removed (Recalculated only when the data or settings change)
+    fn outliers_to_prune(&mut self) -> &[usize] {
+        let pruning = &mut self.outlier_pruning;
+        let key = (self.data.timestamp(), pruning.k, pruning.quantile);
+        if !pruning
+            .preview
+            .as_ref()
+            .is_some_and(|(timestamp, k, quantile, _)| (*timestamp, *k, *quantile) == key)
+        {
+            let outliers = pruning.outliers(self.data.points());
+            pruning.preview = Some((key.0, key.1, key.2, outliers));
+        }
+        &pruning.preview.as_ref().expect("set above").3
+    }
+
+    pub(super) fn ui_outlier_pruning_window(&mut self, ctx: &egui::Context) {
+        if !self.show_outlier_pruning_window {
+            self.outlier_pruning.preview = None;
+            return;
+        }
+        let mut is_open = true;
+        egui::Window::new("Prune Outliers")
+            .open(&mut is_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Neighbor (k)");
+                    ui.add(egui::DragValue::new(&mut self.outlier_pruning.k).clamp_range(1..=100));
+                    ui.label("Quantile");
+                    ui.add(
+                        egui::DragValue::new(&mut self.outlier_pruning.quantile)
+                            .speed(0.005)
+                            .clamp_range(0.0..=1.0),
+                    );
+                });
+                let outliers = self.outliers_to_prune().to_vec();
+                if ui
+                    .add_enabled(
+                        !outliers.is_empty(),
+                        Button::new(format!("Remove {} points", outliers.len())),
+                    )
+                    .on_hover_text(
+                        "Removes the points marked on the plot whose distance to their k-th nearest neighbor is above the quantile",
+                    )
+                    .clicked()
+                {
+                    let count_before = self.data.points().len();
+                    self.data.delete_many(outliers);
+                    self.selection.keep_for(&self.data);
+                    self.status_msg.info(format!(
+                        "Removed {} outliers",
+                        count_before - self.data.points().len()
+                    ));
+                }
+            });
+        if !is_open {
+            self.show_outlier_pruning_window = false;
+        }
+    }
+
+    /// Marks the points that would be removed while the window is open
+    pub(super) fn plot_outlier_pruning_preview(&self, plot_ui: &mut PlotUi) {
+        let Some((timestamp, _, _, outliers)) = &self.outlier_pruning.preview else {
+            return;
+        };
+        if !self.show_outlier_pruning_window || *timestamp != self.data.timestamp() {
+            return;
+        }
+        let targets: Vec<PointArray> = outliers
+            .iter()
+            .map(|&i| self.data.points()[i].to_array())
+            .collect();
+        plot_ui.points(
+            Points::new(targets)
+                .name("To be pruned")
+                .shape(MarkerShape::Cross)
+                .radius(self.marker_radius * 2.0)
+                .color(self.color_anom),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn far_point_is_an_outlier() {
+        let mut points: Vec<DataPoint> = (0..10)
+            .map(|i| DataPoint::new(i as f64, 0.0, DataLabel::Normal))
+            .collect();
+        points.push(DataPoint::new(50.0, 50.0, DataLabel::Normal));
+        let pruning = OutlierPruning {
+            k: 2,
+            quantile: 0.9,
+            ..Default::default()
+        };
+        assert_eq!(pruning.outliers(&points), [10]);
+        assert!(OutlierPruning {
+            k: 20,
+            ..Default::default()
+        }
+        .outliers(&points)
+        .is_empty());
+    }
+}
@@ -0,0 +1,334 @@
+use ecolor::Color32;
+use egui::{ColorImage, TextureHandle, TextureOptions};
+use egui_plot::{Arrows, PlotImage, PlotPoint, PlotPoints, PlotUi};
+use poll_promise::Promise;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataPoint, DataTimestamp, DistanceCalculation as _},
+    local_experiments::IdleDebounce,
+    plot_zoom_reset::MinMaxPair,
+};
+
+/// Results of the expensive overlays for one version of the data
+pub struct Overlays {
+    data_timestamp: DataTimestamp,
+    /// Index of the nearest other point for each point
+    nearest_neighbors: Vec<Option<usize>>,
+    /// Area covered by the grids
+    extent: MinMaxPair,
+    /// Estimated density of points for each cell normalized so the largest value is 1
+    ///
+    /// Cells are in rows starting from the top left (The same as images)
+    density: Vec<f32>,
+    /// Index of the point nearest to the center of each cell (Same layout as `density`)
+    voronoi: Vec<usize>,
+}
+
+/// Keeps the overlays for the latest data computed in the background so they are ready to show
+#[derive(Default)]
+pub struct OverlayCache {
+    overlays: Option<Overlays>,
+    pending: Option<Promise<Overlays>>,
+    /// Used to wait for the data to stop changing before precomputing
+    debounce: IdleDebounce,
+    /// Built from `overlays` the first time they are shown
+    density_texture: Option<TextureHandle>,
+    /// Built from `overlays` the first time they are shown along with the label colors used
+    voronoi_texture: Option<(TextureHandle, Vec<Color32>)>,
+}
+
+impl PartialEq for OverlayCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.overlays.as_ref().map(|x| x.data_timestamp)
+            == other.overlays.as_ref().map(|x| x.data_timestamp)
+    }
+}
+
+impl Overlays {
+    /// Cells per side of the grids
+    const GRID_RESOLUTION: usize = 128;
+
+    fn compute(points: &[DataPoint], extent: MinMaxPair, data_timestamp: DataTimestamp) -> Self {
+        let nearest_neighbors = (0..points.len())
+            .map(|i| {
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(j, other)| (j, other.distance_to(points[i].to_array())))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(j, _)| j)
+            })
+            .collect();
+
+        let bandwidth = Self::bandwidth(points, &extent);
+        let cell_count = Self::GRID_RESOLUTION * Self::GRID_RESOLUTION;
+        let mut density = Vec::with_capacity(cell_count);
+        let mut voronoi = Vec::with_capacity(cell_count);
+        for cell in 0..cell_count {
+            let center = Self::cell_center(&extent, cell);
+            let mut cell_density = 0.0;
+            let mut nearest = (0, f64::INFINITY);
+            for (i, point) in points.iter().enumerate() {
+                let distance = point.distance_to(center);
+                cell_density += (-0.5 * (distance / bandwidth).powi(2)).exp();
+                if distance < nearest.1 {
+                    nearest = (i, distance);
+                }
+            }
+            density.push(cell_density as f32);
+            voronoi.push(nearest.0);
+        }
+        let max_density = density.iter().copied().fold(0.0f32, f32::max);
+        if max_density > 0.0 {
+            density.iter_mut().for_each(|x| *x /= max_density);
+        }
+
+        Self {
+            data_timestamp,
+            nearest_neighbors,
+            extent,
+            density,
+            voronoi,
+        }
+    }
+
+    /// Uses Scott's rule on the average standard deviation of the two axes and falls back to a
+    /// fraction of the extent if all the points are at the same place
+    fn bandwidth(points: &[DataPoint], extent: &MinMaxPair) -> f64 {
+        let n = points.len() as f64;
+        let std_dev = |values: &dyn Fn(&DataPoint) -> f64| {
+            let mean = points.iter().map(values).sum::<f64>() / n;
+            (points
+                .iter()
+                .map(|p| (values(p) - mean).powi(2))
+                .sum::<f64>()
+                / n)
+                .sqrt()
+        };
+        let result = (std_dev(&|p| p.x0) + std_dev(&|p| p.x1)) / 2.0 * n.powf(-1.0 / 6.0);
+        if result > 0.0 {
+            result
+        } else {
+            (extent.max[0] - extent.min[0]).max(extent.max[1] - extent.min[1]) / 20.0
+        }
+    }
+
+    fn cell_center(extent: &MinMaxPair, cell: usize) -> [f64; 2] {
+        let resolution = Self::GRID_RESOLUTION as f64;
+        let (row, col) = (
+            (cell / Self::GRID_RESOLUTION) as f64,
+            (cell % Self::GRID_RESOLUTION) as f64,
+        );
+        let cell_width = (extent.max[0] - extent.min[0]) / resolution;
+        let cell_height = (extent.max[1] - extent.min[1]) / resolution;
+        [
+            extent.min[0] + (col + 0.5) * cell_width,
+            extent.max[1] - (row + 0.5) * cell_height,
+        ]
+    }
+
+    fn image(&self, ctx: &egui::Context, name: &str, pixels: Vec<Color32>) -> TextureHandle {
+        let size = [Self::GRID_RESOLUTION, Self::GRID_RESOLUTION];
+        let image = ColorImage { size, pixels };
+        ctx.load_texture(name, image, TextureOptions::NEAREST)
+    }
+
+    fn plot_image(&self, texture: &TextureHandle) -> PlotImage {
+        let MinMaxPair { min, max } = self.extent;
+        PlotImage::new(
+            texture,
+            PlotPoint::new((min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0),
+            [(max[0] - min[0]) as f32, (max[1] - min[1]) as f32],
+        )
+    }
+}
+
+impl DBV {
+    /// Above this the overlays are not computed because they take too long even in the background
+    const OVERLAYS_MAX_POINTS: usize = 20_000;
+
+    pub(super) fn ui_overlay_options(&mut self, ui: &mut egui::Ui) {
+        let is_too_many = self.data.points().len() > Self::OVERLAYS_MAX_POINTS;
+        ui.add_enabled_ui(!is_too_many, |ui| {
+            ui.checkbox(&mut self.show_nearest_neighbors, "Show nearest neighbours")
+                .on_hover_text("Arrow from each point to its nearest point");
+            ui.checkbox(&mut self.show_density, "Show density");
+            ui.checkbox(&mut self.show_voronoi, "Show Voronoi cells")
+                .on_hover_text("Area closest to each point colored by its label");
+        })
+        .response
+        .on_disabled_hover_text(format!(
+            "Only available with up to {} points",
+            Self::OVERLAYS_MAX_POINTS
+        ));
+        ui.checkbox(
+            &mut self.precompute_overlays_when_idle,
+            "Precompute when idle",
+        )
+        .on_hover_text("Calculates the overlays in the background after the data stops changing so they show immediately when turned on");
+        if self.overlay_cache.pending.is_some() {
+            ui.label("Calculating...");
+        }
+    }
+
+    fn is_any_overlay_shown(&self) -> bool {
+        self.show_nearest_neighbors || self.show_density || self.show_voronoi
+    }
+
+    /// Starts calculating the overlays in the background if they are shown or the app is idle
+    pub(super) fn precompute_overlays(&mut self, ctx: &egui::Context) {
+        let cache = &mut self.overlay_cache;
+        if cache.pending.as_ref().is_some_and(|x| x.ready().is_some()) {
+            let promise = cache.pending.take().expect("checked above");
+            cache.overlays = Some(promise.block_and_take());
+            cache.density_texture = None;
+            cache.voronoi_texture = None;
+        }
+
+        let data_timestamp = self.data.timestamp();
+        let point_count = self.data.points().len();
+        let cache = &mut self.overlay_cache;
+        let is_current = cache
+            .overlays
+            .as_ref()
+            .is_some_and(|x| x.data_timestamp == data_timestamp);
+        // Waits for a calculation for older data to finish instead of starting another one
+        let is_running = cache.pending.is_some();
+        if is_current || is_running || point_count == 0 || point_count > Self::OVERLAYS_MAX_POINTS {
+            return;
+        }
+        let is_idle = self.op_state.is_normal()
+            && !ctx.input(|i| i.pointer.any_down())
+            && cache.debounce.poll(data_timestamp, ctx);
+        let should_start =
+            self.is_any_overlay_shown() || (self.precompute_overlays_when_idle && is_idle);
+        if !should_start {
+            return;
+        }
+
+        let points = self.data.clone_points();
+        let extent = self.data.get_points_min_max_w_margin();
+        let ctx = ctx.clone();
+        let compute = move || {
+            let result = Overlays::compute(&points, extent, data_timestamp);
+            ctx.request_repaint();
+            result
+        };
+        // Threads are not available in WASM so it is done on the UI thread instead
+        #[cfg(not(target_arch = "wasm32"))]
+        let promise = Promise::spawn_blocking(compute);
+        #[cfg(target_arch = "wasm32")]
+        let promise = Promise::from_ready(compute());
+        self.overlay_cache.pending = Some(promise);
+    }
+
+    /// Draws the overlays that are shown if they are up to date with the data
+    pub(super) fn plot_overlays_from_cache(&mut self, plot_ui: &mut PlotUi) {
+        if !self.is_any_overlay_shown() {
+            return;
+        }
+        let data_timestamp = self.data.timestamp();
+        let points = self.data.points();
+        // Indexed by the label of the points
+        let colors: Vec<Color32> = self
+            .label_classes
+            .labels()
+            .map(|label| self.label_color(label))
+            .collect();
+        let cache = &mut self.overlay_cache;
+        let Some(overlays) = cache
+            .overlays
+            .as_ref()
+            .filter(|x| x.data_timestamp == data_timestamp)
+        else {
+            return;
+        };
+
+        if self.show_density {
+            let texture = cache.density_texture.get_or_insert_with(|| {
+                let pixels = overlays
+                    .density
+                    .iter()
+                    .map(|&x| Color32::from_rgba_unmultiplied(255, 140, 0, (x * 160.0) as u8))
+                    .collect();
+                overlays.image(plot_ui.ctx(), "overlay-density", pixels)
+            });
+            plot_ui.image(overlays.plot_image(texture).name("Density"));
+        }
+
+        if self.show_voronoi {
+            if !matches!(&cache.voronoi_texture, Some((_, used)) if *used == colors) {
+                let resolution = Overlays::GRID_RESOLUTION;
+                let pixels = (0..overlays.voronoi.len())
+                    .map(|cell| {
+                        let nearest = overlays.voronoi[cell];
+                        // Cells next to a cell of a different point are on the border
+                        let is_border = (cell % resolution + 1 < resolution
+                            && overlays.voronoi[cell + 1] != nearest)
+                            || overlays
+                                .voronoi
+                                .get(cell + resolution)
+                                .is_some_and(|x| *x != nearest);
+                        let color = colors
+                            .get(usize::from(points[nearest].label.as_int()))
+                            .copied()
+                            .unwrap_or(Color32::GRAY);
+                        color.gamma_multiply(if is_border { 0.6 } else { 0.2 })
+                    })
+                    .collect();
+                let texture = overlays.image(plot_ui.ctx(), "overlay-voronoi", pixels);
+                cache.voronoi_texture = Some((texture, colors));
+            }
+            let (texture, _) = cache.voronoi_texture.as_ref().expect("set above");
+            plot_ui.image(overlays.plot_image(texture).name("Voronoi cells"));
+        }
+
+        if self.show_nearest_neighbors {
+            let (origins, tips): (Vec<[f64; 2]>, Vec<[f64; 2]>) = overlays
+                .nearest_neighbors
+                .iter()
+                .enumerate()
+                .filter_map(|(i, nearest)| {
+                    Some((points[i].to_array(), points[(*nearest)?].to_array()))
+                })
+                .unzip();
+            plot_ui.arrows(
+                Arrows::new(PlotPoints::new(origins), PlotPoints::new(tips))
+                    .color(Color32::GRAY)
+                    .name("Nearest neighbours"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::app::data_definition::{Data, DataLabel};
+
+    #[test]
+    fn overlays_use_nearest_points() {
+        let points = vec![
+            DataPoint::new(0.0, 0.0, DataLabel::Normal),
+            DataPoint::new(1.0, 0.0, DataLabel::Normal),
+            DataPoint::new(10.0, 10.0, DataLabel::Anomaly),
+        ];
+        let extent = MinMaxPair {
+            min: [0.0, 0.0],
+            max: [10.0, 10.0],
+        };
+        let actual = Overlays::compute(&points, extent, Data::default().timestamp());
+        assert_eq!(actual.nearest_neighbors, [Some(1), Some(0), Some(1)]);
+
+        let last_cell = Overlays::GRID_RESOLUTION * Overlays::GRID_RESOLUTION - 1;
+        let top_right = Overlays::GRID_RESOLUTION - 1;
+        assert_eq!(actual.voronoi[last_cell - top_right], 0, "bottom left");
+        assert_eq!(actual.voronoi[top_right], 2, "top right");
+        assert!(actual.density[last_cell - top_right] > actual.density[top_right]);
+    }
+}
@@ -0,0 +1,136 @@
+use egui::Button;
+use rand::Rng as _;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoint, DataPoints, DistanceCalculation as _},
+    data_generation::rng_from_seed,
+    ui_blocks::ui_seed,
+};
+
+/// Settings for adding points between existing points of a label (SMOTE)
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct Oversample {
+    pub label: DataLabel,
+    /// Number of points to add
+    pub count: usize,
+    /// Number of nearest neighbors of the same label to interpolate towards
+    pub k: usize,
+}
+
+impl Default for Oversample {
+    fn default() -> Self {
+        Self {
+            label: DataLabel::Anomaly,
+            count: 20,
+            k: 5,
+        }
+    }
+}
+
+impl Oversample {
+    /// New points each on the segment between a random point of the label and one of its `k`
+    /// nearest neighbors of the same label (Empty if there are less than 2 such points)
+    fn synthesize(&self, points: &[DataPoint], seed: u64) -> DataPoints {
+        let members: Vec<&DataPoint> = points.iter().filter(|x| x.label == self.label).collect();
+        if members.len() < 2 {
+            return vec![];
+        }
+        let k = self.k.clamp(1, members.len() - 1);
+        let neighbors: Vec<Vec<usize>> = members
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let mut others: Vec<(f64, usize)> = members
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(j, other)| (point.distance_to(other.to_array()), j))
+                    .collect();
+                others.sort_by(|a, b| a.0.total_cmp(&b.0));
+                others.into_iter().take(k).map(|(_, j)| j).collect()
+            })
+            .collect();
+        let mut rng = rng_from_seed(seed);
+        (0..self.count)
+            .map(|_| {
+                let i = rng.gen_range(0..members.len());
+                let neighbor = members[neighbors[i][rng.gen_range(0..k)]];
+                let gap: f64 = rng.gen();
+                let base = members[i];
+                DataPoint::new(
+                    base.x0 + gap * (neighbor.x0 - base.x0),
+                    base.x1 + gap * (neighbor.x1 - base.x1),
+                    self.label,
+                )
+            })
+            .collect()
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_menu_oversample(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Oversample (SMOTE)", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Label");
+                    self.label_classes
+                        .ui_combo(ui, "id-oversample-label", &mut self.oversample.label);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Points to add");
+                    ui.add(egui::DragValue::new(&mut self.oversample.count).clamp_range(1..=100_000));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Neighbors (k)");
+                    ui.add(egui::DragValue::new(&mut self.oversample.k).clamp_range(1..=100));
+                    ui_seed(ui, &mut self.data.settings.seed);
+                });
+                if ui
+                    .add(Button::new("Add Points"))
+                    .on_hover_text("Each new point is placed between a point of the label and one of its nearest neighbors with the same label")
+                    .clicked()
+                {
+                    let points = self
+                        .oversample
+                        .synthesize(self.data.points(), self.data.settings.seed);
+                    if points.is_empty() {
+                        self.status_msg.error_display(format!(
+                            "At least 2 points labeled {} are needed to oversample",
+                            self.label_classes.name(self.oversample.label)
+                        ));
+                    } else {
+                        self.status_msg
+                            .info(format!("Added {} synthetic points", points.len()));
+                        self.data.add_many(points);
+                    }
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_points_lie_between_neighbors() {
+        let points = [
+            DataPoint::new(0.0, 0.0, DataLabel::Anomaly),
+            DataPoint::new(2.0, 0.0, DataLabel::Anomaly),
+            DataPoint::new(1.0, 5.0, DataLabel::Normal),
+        ];
+        let oversample = Oversample::default();
+        let added = oversample.synthesize(&points, 0);
+        assert_eq!(added.len(), 20);
+        assert!(added
+            .iter()
+            .all(|p| p.label == DataLabel::Anomaly && p.x1 == 0.0 && (0.0..=2.0).contains(&p.x0)));
+        assert_eq!(added, oversample.synthesize(&points, 0));
+        assert!(oversample.synthesize(&points[1..], 0).is_empty());
+    }
+}
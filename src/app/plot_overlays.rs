@@ -0,0 +1,143 @@
+use ecolor::Color32;
+use egui::{Pos2, Rect, Sense, Stroke, Vec2};
+use egui_plot::{Line, PlotBounds, PlotPoints, PlotUi};
+
+use crate::DBV;
+
+use super::plot_zoom_reset::MinMaxPair;
+
+impl DBV {
+    /// Number of radial lines drawn by the polar grid
+    const POLAR_GRID_SPOKES: usize = 12;
+    /// Largest side of the mini-map in screen points
+    const MINIMAP_SIZE: f32 = 150.0;
+    /// Limit on points drawn in the mini-map to keep it cheap (Evenly skips points above this)
+    const MINIMAP_MAX_POINTS: usize = 5_000;
+
+    /// Moves the plot to center on the position selected in the mini-map (Needs to be run inside the plot)
+    pub(super) fn apply_minimap_jump(&mut self, plot_ui: &mut PlotUi) {
+        if let Some([x, y]) = self.minimap_jump.take() {
+            let bounds = plot_ui.plot_bounds();
+            let [half_width, half_height] = [bounds.width() / 2.0, bounds.height() / 2.0];
+            plot_ui.set_plot_bounds(PlotBounds::from_min_max(
+                [x - half_width, y - half_height],
+                [x + half_width, y + half_height],
+            ));
+        }
+    }
+
+    /// Draws a mini-map of all the data and the visible area in the bottom right of the plot.
+    /// Clicking or dragging on it moves the visible area.
+    pub(super) fn ui_minimap(&mut self, ui: &mut egui::Ui, plot_rect: Rect) {
+        let Some(viewport) = self.plot_bounds else {
+            return;
+        };
+        let data_extent = self.data.get_points_min_max_w_margin();
+        // Include the viewport so its rectangle is always visible
+        let extent = MinMaxPair {
+            min: [
+                data_extent.min[0].min(viewport.min()[0]),
+                data_extent.min[1].min(viewport.min()[1]),
+            ],
+            max: [
+                data_extent.max[0].max(viewport.max()[0]),
+                data_extent.max[1].max(viewport.max()[1]),
+            ],
+        };
+        let width = extent.max[0] - extent.min[0];
+        let height = extent.max[1] - extent.min[1];
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        let scale = Self::MINIMAP_SIZE as f64 / width.max(height);
+        let size = Vec2::new((width * scale) as f32, (height * scale) as f32);
+        let margin = 8.0;
+        let rect = Rect::from_min_size(plot_rect.max - size - Vec2::splat(margin), size);
+
+        let to_screen = |[x, y]: [f64; 2]| {
+            Pos2::new(
+                rect.min.x + ((x - extent.min[0]) * scale) as f32,
+                rect.max.y - ((y - extent.min[1]) * scale) as f32,
+            )
+        };
+        let to_plot = |pos: Pos2| {
+            [
+                extent.min[0] + (pos.x - rect.min.x) as f64 / scale,
+                extent.min[1] + (rect.max.y - pos.y) as f64 / scale,
+            ]
+        };
+
+        let response = ui.interact(rect, ui.id().with("minimap"), Sense::click_and_drag());
+        if response.clicked() || response.dragged() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                self.minimap_jump = Some(to_plot(rect.clamp(pos)));
+            }
+        }
+
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        painter.rect(
+            rect,
+            2.0,
+            visuals.extreme_bg_color.gamma_multiply(0.9),
+            visuals.widgets.noninteractive.bg_stroke,
+        );
+        let points = self.data.points();
+        let step = (points.len() / Self::MINIMAP_MAX_POINTS).max(1);
+        for point in points.iter().step_by(step) {
+            painter.circle_filled(
+                to_screen([point.x0, point.x1]),
+                1.5,
+                self.label_color(point.label),
+            );
+        }
+        let viewport_rect =
+            Rect::from_two_pos(to_screen(viewport.min()), to_screen(viewport.max()));
+        painter.rect_stroke(
+            viewport_rect,
+            0.0,
+            Stroke::new(1.5, visuals.strong_text_color()),
+        );
+    }
+
+    /// Draws concentric circles and radial lines around the origin covering the visible area
+    pub(super) fn plot_polar_grid(&self, plot_ui: &mut PlotUi) {
+        let bounds = plot_ui.plot_bounds();
+        let spacing = self.polar_grid_spacing;
+        if spacing <= 0.0 {
+            return;
+        }
+        // Farthest visible distance from the origin
+        let max_radius = [bounds.min(), bounds.max()]
+            .into_iter()
+            .flat_map(|x| [x[0].abs(), x[1].abs()])
+            .fold(0.0f64, f64::max)
+            * std::f64::consts::SQRT_2;
+        let ring_count = (max_radius / spacing).ceil() as usize;
+        if ring_count > 500 {
+            return; // Too dense to be useful and slows down the plot
+        }
+        let color = Color32::GRAY.gamma_multiply(0.5);
+        for i in 1..=ring_count {
+            let r = spacing * i as f64;
+            plot_ui.line(
+                Line::new(PlotPoints::from_parametric_callback(
+                    |t| (r * t.cos(), r * t.sin()),
+                    0.0..=std::f64::consts::TAU,
+                    128,
+                ))
+                .color(color),
+            );
+        }
+        for i in 0..Self::POLAR_GRID_SPOKES {
+            let theta = std::f64::consts::TAU * i as f64 / Self::POLAR_GRID_SPOKES as f64;
+            plot_ui.line(
+                Line::new(PlotPoints::new(vec![
+                    [0.0, 0.0],
+                    [max_radius * theta.cos(), max_radius * theta.sin()],
+                ]))
+                .color(color),
+            );
+        }
+    }
+}
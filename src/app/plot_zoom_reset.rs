@@ -2,7 +2,17 @@ use egui_plot::PlotBounds;
 use log::{info, warn};
 use std::fmt::Debug;
 
-use super::data_definition::PointArray;
+use super::data_definition::{DataLabel, PointArray};
+
+/// The points that a zoom reset should fit in the plot
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub enum ZoomTarget {
+    #[default]
+    AllPoints,
+    Label(DataLabel),
+    /// A fixed area such as the default view of the dataset
+    View(MinMaxPair),
+}
 
 #[derive(Debug, PartialEq, Default)]
 pub enum StatePlotResetZoom {
@@ -0,0 +1,201 @@
+use egui::Button;
+use egui_plot::{MarkerShape, PlotUi, Points};
+
+use crate::DBV;
+
+use super::data_definition::{DistanceCalculation as _, GroupId, PointArray};
+
+impl DBV {
+    pub(super) fn ui_btn_show_groups(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.show_groups_window, "Show Groups");
+    }
+
+    pub(super) fn ui_groups_window(&mut self, ctx: &egui::Context) {
+        if !self.show_groups_window {
+            return;
+        }
+        let mut is_open = true;
+        egui::Window::new("Groups")
+            .open(&mut is_open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                self.ui_selection_summary(ui);
+                ui.separator();
+                self.ui_new_group(ui);
+                ui.separator();
+                self.ui_groups_list(ui);
+            });
+        if !is_open {
+            self.show_groups_window = false;
+        }
+    }
+
+    fn ui_selection_summary(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("Selected points: {}", self.selection.len()));
+            if ui
+                .add_enabled(!self.selection.is_empty(), Button::new("Clear Selection"))
+                .clicked()
+            {
+                self.selection.clear();
+            }
+            if ui
+                .add_enabled(!self.selection.is_empty(), Button::new("Remove From Group"))
+                .on_hover_text("Removes the selected points from any group they are in")
+                .clicked()
+            {
+                self.set_group_of_selection(None);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.show_group_outlines, "Show group outlines on plot");
+            ui.label("Selection color");
+            ui.color_edit_button_srgba(&mut self.color_selection);
+        });
+    }
+
+    fn ui_new_group(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(&mut self.new_group_name);
+            let name = self.new_group_name.trim();
+            if ui
+                .add_enabled(!name.is_empty(), Button::new("Add Group"))
+                .on_hover_text("Creates the group and assigns the selected points to it")
+                .clicked()
+            {
+                match self.data.groups.add(name.to_string()) {
+                    Ok(id) => {
+                        self.new_group_name.clear();
+                        if !self.selection.is_empty() {
+                            self.set_group_of_selection(Some(id));
+                        }
+                    }
+                    Err(e) => self
+                        .status_msg
+                        .error_display(format!("Group not added. {e:#}")),
+                }
+            }
+        });
+    }
+
+    fn ui_groups_list(&mut self, ui: &mut egui::Ui) {
+        if self.data.groups.is_empty() {
+            ui.label("No groups defined");
+            return;
+        }
+        let ids: Vec<GroupId> = self.data.groups.iter().map(|(id, _)| id).collect();
+        egui::Grid::new("grid-groups").striped(true).show(ui, |ui| {
+            for id in ids {
+                let indices = self.data.indices_in_group(id);
+                if let Some(group) = self.data.groups.get_mut(id) {
+                    ui.color_edit_button_srgba(&mut group.color);
+                    ui.add(egui::TextEdit::singleline(&mut group.name).desired_width(120.0));
+                }
+                ui.label(format!("{} points", indices.len()));
+                if ui
+                    .add_enabled(!indices.is_empty(), Button::new("Select"))
+                    .clicked()
+                {
+                    self.selection.set(&self.data, indices.iter().copied());
+                }
+                if ui
+                    .add_enabled(!self.selection.is_empty(), Button::new("Assign Selected"))
+                    .clicked()
+                {
+                    self.set_group_of_selection(Some(id));
+                }
+                if ui
+                    .add_enabled(
+                        !indices.is_empty() && self.op_state.is_normal(),
+                        Button::new("Export..."),
+                    )
+                    .on_hover_text("Saves only the points in this group to a new file")
+                    .clicked()
+                {
+                    let points = indices.iter().map(|&i| self.data.points()[i]).collect();
+                    self.export_points(ui.ctx().clone(), points);
+                }
+                if ui
+                    .button("Delete")
+                    .on_hover_text("Deletes the group but keeps its points")
+                    .clicked()
+                {
+                    self.data.delete_group(id);
+                    self.selection.keep_for(&self.data);
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    fn set_group_of_selection(&mut self, group: Option<GroupId>) {
+        self.data
+            .set_group(self.selection.indices().iter().copied(), group);
+        self.selection.keep_for(&self.data);
+    }
+
+    /// Draws a square around each locked point
+    pub(super) fn plot_locked_indicators(&self, plot_ui: &mut PlotUi) {
+        let locked: Vec<PointArray> = self
+            .data
+            .points()
+            .iter()
+            .filter(|point| point.is_locked)
+            .map(|point| point.to_array())
+            .collect();
+        if locked.is_empty() {
+            return;
+        }
+        plot_ui.points(
+            Points::new(locked)
+                .name("Locked")
+                .shape(MarkerShape::Square)
+                .filled(false)
+                .radius(self.marker_radius * 1.8)
+                .color(plot_ui.ctx().style().visuals.strong_text_color()),
+        );
+    }
+
+    /// Draws rings around the points that are in a group and around the selected points
+    pub(super) fn plot_group_and_selection_outlines(&self, plot_ui: &mut PlotUi) {
+        let points = self.data.points();
+        if self.show_group_outlines {
+            for (id, group) in self.data.groups.iter() {
+                let members: Vec<PointArray> = points
+                    .iter()
+                    .filter(|point| point.group == Some(id))
+                    .map(|point| point.to_array())
+                    .collect();
+                if members.is_empty() {
+                    continue;
+                }
+                plot_ui.points(
+                    Points::new(members)
+                        .name(&group.name)
+                        .shape(MarkerShape::Circle)
+                        .filled(false)
+                        .radius(self.marker_radius * 1.3)
+                        .color(group.color),
+                );
+            }
+        }
+        if !self.selection.is_empty() {
+            let selected: Vec<PointArray> = self
+                .selection
+                .indices()
+                .iter()
+                .filter_map(|&i| points.get(i))
+                .map(|point| point.to_array())
+                .collect();
+            plot_ui.points(
+                Points::new(selected)
+                    .name("Selected")
+                    .shape(MarkerShape::Square)
+                    .filled(false)
+                    .radius(self.marker_radius * 1.6)
+                    .color(self.color_selection),
+            );
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use egui_plot::PlotUi;
+
+use crate::DBV;
+
+use super::{data_definition::DataPoint, prediction_classification::prediction_classification};
+
+impl DBV {
+    /// Shows the details of the point under the pointer in a tooltip (Not while a button is
+    /// held so it does not get in the way of dragging)
+    pub(super) fn plot_point_tooltip(&self, plot_ui: &PlotUi) {
+        if !self.show_point_tooltip || !plot_ui.response().hovered() {
+            return;
+        }
+        let ctx = plot_ui.ctx().clone();
+        if ctx.input(|i| i.pointer.any_down()) {
+            return;
+        }
+        let Some(pointer) = plot_ui.pointer_coordinate() else {
+            return;
+        };
+        let units_per_screen_point =
+            plot_ui.plot_bounds().width() / plot_ui.response().rect.width() as f64;
+        let Some(index) = self.data.nearest_within(
+            [pointer.x, pointer.y],
+            self.marker_radius as f64 * units_per_screen_point,
+            |i| self.is_point_shown(i),
+        ) else {
+            return;
+        };
+        let DataPoint {
+            x0,
+            x1,
+            label,
+            is_excluded_from_training,
+            is_in_test_split,
+            is_locked,
+            ..
+        } = self.data.points()[index];
+        let model = self.loc_inference_model();
+        egui::show_tooltip_at_pointer(&ctx, egui::Id::new("point-tooltip"), |ui| {
+            egui::Grid::new("grid-point-tooltip").show(ui, |ui| {
+                ui.label("Row #");
+                ui.label(index.to_string());
+                ui.end_row();
+                ui.label("Position");
+                ui.label(self.display_precision.format_pair([x0, x1]));
+                ui.end_row();
+                ui.label("Label");
+                ui.label(self.label_classes.name(label));
+                ui.end_row();
+                if let Some(model) = model {
+                    let predicted = model.prediction_on_training_data(index);
+                    ui.label("Score");
+                    ui.label(
+                        self.display_precision
+                            .format(model.score_for_training_data(index)),
+                    );
+                    ui.end_row();
+                    ui.label("Prediction");
+                    ui.label(predicted.to_string());
+                    ui.end_row();
+                    ui.label("Classification");
+                    ui.label(prediction_classification(label, predicted).to_string());
+                    ui.end_row();
+                }
+            });
+            if is_excluded_from_training {
+                ui.weak("Held out from training");
+            }
+            if is_in_test_split {
+                ui.weak("In the test split");
+            }
+            if is_locked {
+                ui.weak("Locked");
+            }
+        });
+    }
+}
@@ -2,6 +2,9 @@ use std::fmt::Display;
 
 use super::data_definition::DataLabel;
 
+#[derive(
+    serde::Deserialize, serde::Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug,
+)]
 pub enum Classification {
     FalseNegative,
     FalsePositive,
@@ -11,25 +14,112 @@ pub enum Classification {
 
 /// This function exists to keep the logic for this in one place so it doesn't get mixed up
 pub fn prediction_classification(ground_truth: DataLabel, predicted: DataLabel) -> Classification {
-    match (ground_truth, predicted) {
-        (DataLabel::Normal, DataLabel::Normal) => Classification::TrueNegative,
-        (DataLabel::Normal, DataLabel::Anomaly) => Classification::FalsePositive,
-        (DataLabel::Anomaly, DataLabel::Normal) => Classification::FalseNegative,
-        (DataLabel::Anomaly, DataLabel::Anomaly) => Classification::TruePositive,
+    // Models only predict normal or anomaly so other classes count as anomalies
+    match (
+        ground_truth.to_binary().is_anomaly(),
+        predicted.to_binary().is_anomaly(),
+    ) {
+        (false, false) => Classification::TrueNegative,
+        (false, true) => Classification::FalsePositive,
+        (true, false) => Classification::FalseNegative,
+        (true, true) => Classification::TruePositive,
+    }
+}
+
+impl Classification {
+    /// Abbreviation used in the table and exports (Avoids allocating like `to_string`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Classification::FalseNegative => "FN",
+            Classification::FalsePositive => "FP",
+            Classification::TrueNegative => "TN",
+            Classification::TruePositive => "TP",
+        }
     }
 }
 
 impl Display for Classification {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Classification::FalseNegative => "FN",
-                Classification::FalsePositive => "FP",
-                Classification::TrueNegative => "TN",
-                Classification::TruePositive => "TP",
-            }
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Number of points in each classification
+#[derive(serde::Serialize, PartialEq, Eq, Default, Debug, Clone, Copy)]
+pub struct ConfusionCounts {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub true_negatives: usize,
+    pub false_negatives: usize,
+}
+
+impl ConfusionCounts {
+    pub fn add(&mut self, classification: Classification) {
+        match classification {
+            Classification::FalseNegative => self.false_negatives += 1,
+            Classification::FalsePositive => self.false_positives += 1,
+            Classification::TrueNegative => self.true_negatives += 1,
+            Classification::TruePositive => self.true_positives += 1,
+        }
+    }
+
+    /// Returns `None` if nothing was predicted to be an anomaly
+    pub fn precision(&self) -> Option<f64> {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_positives,
+        )
+    }
+
+    /// Returns `None` if there are no anomalies
+    pub fn recall(&self) -> Option<f64> {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_negatives,
         )
     }
+
+    /// Returns `None` if there are no anomalies and none were predicted
+    pub fn f1(&self) -> Option<f64> {
+        ratio(
+            2 * self.true_positives,
+            2 * self.true_positives + self.false_positives + self.false_negatives,
+        )
+    }
+}
+
+impl FromIterator<Classification> for ConfusionCounts {
+    fn from_iter<T: IntoIterator<Item = Classification>>(iter: T) -> Self {
+        let mut result = Self::default();
+        for classification in iter {
+            result.add(classification);
+        }
+        result
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> Option<f64> {
+    (denominator > 0).then(|| numerator as f64 / denominator as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_from_counts() {
+        let counts: ConfusionCounts = [
+            Classification::TruePositive,
+            Classification::TruePositive,
+            Classification::FalsePositive,
+            Classification::FalseNegative,
+            Classification::TrueNegative,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(counts.precision(), Some(2.0 / 3.0));
+        assert_eq!(counts.recall(), Some(2.0 / 3.0));
+        assert_eq!(counts.f1(), Some(4.0 / 6.0));
+        assert_eq!(ConfusionCounts::default().f1(), None);
+    }
 }
@@ -0,0 +1,51 @@
+use egui::{Button, Key, KeyboardShortcut, Modifiers};
+
+use crate::DBV;
+
+impl DBV {
+    const SHORTCUT_PRESENTATION: KeyboardShortcut =
+        KeyboardShortcut::new(Modifiers::NONE, Key::F11);
+
+    /// Returns `true` if only the plot is shown (Full screen without menus or panels)
+    pub(super) fn is_presenting(&self) -> bool {
+        self.show_data_only_before_presentation.is_some()
+    }
+
+    pub(super) fn ui_btn_presentation_mode(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add(
+                Button::new("Presentation Mode")
+                    .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_PRESENTATION)),
+            )
+            .on_hover_text("Shows only the plot in full screen (Press again or Escape to leave)")
+            .clicked()
+        {
+            self.set_presenting(ui.ctx(), true);
+            ui.close_menu();
+        }
+    }
+
+    /// Enters or leaves presentation mode when its shortcut (or Escape while presenting) is pressed
+    pub(super) fn handle_presentation_shortcut(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|i| i.consume_shortcut(&Self::SHORTCUT_PRESENTATION)) {
+            self.set_presenting(ctx, !self.is_presenting());
+        } else if self.is_presenting()
+            && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape))
+        {
+            self.set_presenting(ctx, false);
+        }
+    }
+
+    fn set_presenting(&mut self, ctx: &egui::Context, is_presenting: bool) {
+        if is_presenting == self.is_presenting() {
+            return;
+        }
+        if is_presenting {
+            self.show_data_only_before_presentation = Some(self.show_data_only);
+            self.show_data_only = true;
+        } else if let Some(show_data_only) = self.show_data_only_before_presentation.take() {
+            self.show_data_only = show_data_only;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(is_presenting));
+    }
+}
@@ -0,0 +1,304 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context};
+use ecolor::Color32;
+
+use crate::DBV;
+
+use super::{
+    data_definition::Data,
+    execute, file_handle_to_path,
+    local_experiments::LocalExperiment,
+    operational_state::{OperationOutcome, OperationalState, Payload},
+    plot_zoom_reset::ZoomTarget,
+};
+
+/// Everything needed to continue working on a dataset, saved as a single `.dbv` file
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Project {
+    /// Used to detect files written by newer versions that may not load correctly
+    format_version: u32,
+    /// Points along with their undo history
+    data: Data,
+    /// Content hash of the points when saved, used to detect changes made outside the app
+    #[serde(default)]
+    data_hash: Option<String>,
+    /// Includes the results of training so the model does not need to be retrained
+    loc_experiment: LocalExperiment,
+    #[serde(default)]
+    display: DisplaySettings,
+}
+
+/// Settings that control how the data is shown
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
+struct DisplaySettings {
+    marker_radius: Option<f32>,
+    colors: Option<[Color32; 7]>,
+    show_plot_legend: Option<bool>,
+    show_plot_grid_lines: Option<bool>,
+    show_polar_grid: Option<bool>,
+    polar_grid_spacing: Option<f64>,
+}
+
+impl std::fmt::Debug for Project {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Project")
+            .field("format_version", &self.format_version)
+            .field("points", &self.data.points().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Project {
+    const FORMAT_VERSION: u32 = 1;
+    pub const EXTENSION: &'static str = "dbv";
+    /// Number of recently used projects remembered
+    #[cfg(not(target_arch = "wasm32"))]
+    const MAX_RECENT: usize = 10;
+
+    fn from_text(text: &[u8]) -> anyhow::Result<Self> {
+        let text = std::str::from_utf8(text).context("project file is not valid UTF-8")?;
+        let result: Self = ron::from_str(text).context("failed to parse project file")?;
+        if result.format_version > Self::FORMAT_VERSION {
+            bail!(
+                "project file format version {} is newer than the supported version {}",
+                result.format_version,
+                Self::FORMAT_VERSION
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_project_persistence(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(self.op_state.is_normal(), |ui| {
+            if ui.button("Load Project...").clicked() {
+                self.load_project(ui.ctx().clone());
+                ui.close_menu();
+            }
+            if ui.button("Save Project as...").clicked() {
+                self.save_project(ui.ctx().clone());
+                ui.close_menu();
+            }
+        });
+    }
+
+    /// Serializes the current state as a project. The data is moved into the project temporarily
+    /// to avoid having to clone it
+    fn project_text(&mut self) -> anyhow::Result<String> {
+        let project = Project {
+            format_version: Project::FORMAT_VERSION,
+            data_hash: Some(self.data.content_hash()),
+            data: std::mem::take(&mut self.data),
+            loc_experiment: std::mem::take(&mut self.loc_experiment),
+            display: DisplaySettings {
+                marker_radius: Some(self.marker_radius),
+                colors: Some(self.project_colors()),
+                show_plot_legend: Some(self.show_plot_legend),
+                show_plot_grid_lines: Some(self.show_plot_grid_lines),
+                show_polar_grid: Some(self.show_polar_grid),
+                polar_grid_spacing: Some(self.polar_grid_spacing),
+            },
+        };
+        let result = ron::ser::to_string_pretty(&project, Default::default())
+            .context("failed to serialize project");
+        self.data = project.data;
+        self.loc_experiment = project.loc_experiment;
+        result
+    }
+
+    fn project_colors(&self) -> [Color32; 7] {
+        [
+            self.color_normal,
+            self.color_anom,
+            self.color_results_false_negatives,
+            self.color_results_false_positives,
+            self.color_results_true_negatives,
+            self.color_results_true_positives,
+            self.color_selection,
+        ]
+    }
+
+    /// Moves the path to the top of the recently used projects
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn remember_recent_project(&mut self, path: PathBuf) {
+        self.recent_projects.retain(|x| x != &path);
+        self.recent_projects.insert(0, path);
+        self.recent_projects.truncate(Project::MAX_RECENT);
+    }
+
+    /// Replaces the current data, model and display settings with those from the project
+    pub(super) fn on_project_loaded(&mut self, project: Project, path: PathBuf) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.remember_recent_project(path.clone());
+        let Project {
+            format_version: _,
+            data,
+            data_hash,
+            loc_experiment,
+            display,
+        } = project;
+        let actual_hash = data.content_hash();
+        self.data = data;
+        self.loc_experiment = loc_experiment;
+        self.selection.clear();
+        if let Some(value) = display.marker_radius {
+            self.marker_radius = value;
+        }
+        if let Some(colors) = display.colors {
+            [
+                self.color_normal,
+                self.color_anom,
+                self.color_results_false_negatives,
+                self.color_results_false_positives,
+                self.color_results_true_negatives,
+                self.color_results_true_positives,
+                self.color_selection,
+            ] = colors;
+        }
+        if let Some(value) = display.show_plot_legend {
+            self.show_plot_legend = value;
+        }
+        if let Some(value) = display.show_plot_grid_lines {
+            self.show_plot_grid_lines = value;
+        }
+        if let Some(value) = display.show_polar_grid {
+            self.show_polar_grid = value;
+        }
+        if let Some(value) = display.polar_grid_spacing {
+            self.polar_grid_spacing = value;
+        }
+        match data_hash {
+            Some(expected) if expected != actual_hash => self.status_msg.error_display(format!(
+                "Project loaded from {path:?} but the points do not match the dataset hash \
+                 recorded when it was saved. Expected {expected} but found {actual_hash}"
+            )),
+            _ => self.status_msg.info(format!(
+                "Project loaded from {path:?} (Dataset hash: {actual_hash})"
+            )),
+        }
+        if self.on_load_reset_plot_zoom {
+            let target = match self.data.settings.default_view {
+                Some(view) => ZoomTarget::View(view),
+                None => ZoomTarget::AllPoints,
+            };
+            self.start_zoom_reset(target);
+        }
+    }
+
+    fn save_project(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let text = match self.project_text() {
+            Ok(text) => text,
+            Err(e) => {
+                self.status_msg.error_debug(e);
+                return;
+            }
+        };
+        self.op_state = OperationalState::Saving(execute(async move {
+            let dialog = rfd::AsyncFileDialog::new()
+                .set_title("Save Project as")
+                .add_filter("DBV Project", &[Project::EXTENSION]);
+            #[cfg(target_arch = "wasm32")]
+            let dialog = dialog.set_file_name("project.dbv");
+            let Some(file) = dialog.save_file().await else {
+                // user canceled
+                ctx.request_repaint();
+                return OperationOutcome::Cancelled;
+            };
+            let path = file_handle_to_path(&file);
+            let result = match file
+                .write(text.as_bytes())
+                .await
+                .context("failed to save project")
+            {
+                Ok(()) => OperationOutcome::Success(Payload::SaveProject(path)),
+                Err(e) => OperationOutcome::Failed(e),
+            };
+
+            ctx.request_repaint();
+
+            result
+        }));
+    }
+
+    fn load_project(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let promise = execute(async move {
+            let dialog = rfd::AsyncFileDialog::new()
+                .set_title("Load Project")
+                .add_filter("DBV Project", &[Project::EXTENSION]);
+            let Some(file) = dialog.pick_file().await else {
+                // user canceled
+                ctx.request_repaint();
+                return OperationOutcome::Cancelled;
+            };
+            let result = read_project(&file).await;
+
+            ctx.request_repaint();
+
+            result
+        });
+        // Progress is not tracked because project files are parsed in one step
+        self.op_state = OperationalState::Loading(promise, Default::default());
+    }
+
+    /// Loads the project without asking for the file (Used for recent projects)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn load_project_from_path(&mut self, path: PathBuf, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        if !path.is_file() {
+            // Checked first because reading a missing file through the handle panics
+            self.status_msg
+                .error_display(format!("Project file not found at {path:?}"));
+            self.recent_projects.retain(|x| x != &path);
+            return;
+        }
+        let promise = execute(async move {
+            let result = read_project(&rfd::FileHandle::from(path)).await;
+
+            ctx.request_repaint();
+
+            result
+        });
+        self.op_state = OperationalState::Loading(promise, Default::default());
+    }
+}
+
+async fn read_project(file: &rfd::FileHandle) -> OperationOutcome {
+    let path = file_handle_to_path(file);
+    match Project::from_text(&file.read().await).context("failed to load project") {
+        Ok(project) => OperationOutcome::Success(Payload::LoadProject {
+            project: Box::new(project),
+            path,
+        }),
+        Err(e) => OperationOutcome::Failed(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_round_trip() {
+        let mut data = Data::default();
+        data.add_many(crate::app::data_definition::tests::generate_data_points());
+        data.settings.axis_labels = ["Temperature".to_string(), "Pressure".to_string()];
+        let expected_timestamp = data.timestamp();
+        let project = Project {
+            format_version: Project::FORMAT_VERSION,
+            data_hash: Some(data.content_hash()),
+            data,
+            loc_experiment: Default::default(),
+            display: Default::default(),
+        };
+        let text = ron::to_string(&project).unwrap();
+        let actual = Project::from_text(text.as_bytes()).unwrap();
+        assert!(actual.data == project.data);
+        assert_eq!(actual.data.timestamp(), expected_timestamp);
+        assert_eq!(actual.data_hash, Some(actual.data.content_hash()));
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     DBV,
 };
 
-use super::{data_definition::DataPoint, status_msg::StatusMsg};
+use super::{backups::Backups, data_definition::DataPoint, status_msg::StatusMsg};
 
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone)]
 pub struct PyExperiment {
@@ -91,9 +91,12 @@ impl PyExperiment {
         result
     }
 
+    /// The seed is passed to the script in the `DBV_SEED` environment variable
     pub async fn run(
         &self,
         points: &[DataPoint],
+        seed: u64,
+        backups: Backups,
         status_msg: &mut StatusMsg,
     ) -> anyhow::Result<()> {
         // Check if everything is ready to run
@@ -106,8 +109,15 @@ impl PyExperiment {
 
         // Save File
         let path = data_path.join(self.data_filename().expect("required to be ready"));
+        if let Some(backup_path) = backups.backup_before_overwrite(&path)? {
+            status_msg.info(format!("Backup saved to {backup_path:?}"));
+        }
         let file = rfd::FileHandle::from(path);
-        points.save_to_file(&file).await.context("save failed")?;
+        // The script expects the default variable names
+        points
+            .save_to_file(&file, &Default::default())
+            .await
+            .context("save failed")?;
         status_msg.info(format!("Saved data before calling script to {file:?}"));
 
         // Send Command
@@ -135,6 +145,7 @@ impl PyExperiment {
 
         command
             .env("PYTHONPATH", working_dir.as_os_str())
+            .env("DBV_SEED", seed.to_string())
             .current_dir(&working_dir)
             .arg("-c")
             .arg(cmd_str);
@@ -409,10 +420,16 @@ impl DBV {
         debug_assert!(self.op_state.is_normal());
         let mut status_msg = self.status_msg.clone(); // Clone is cheap because type uses an arc internally
         let py_experiment = self.py_experiment.clone();
-        let points = self.data.clone_points();
+        let mut points = self.data.clone_points();
+        // The script only knows normal and anomalous points
+        for point in points.iter_mut() {
+            point.label = point.label.to_binary();
+        }
+        let backups = self.backups;
+        let seed = self.data.settings.seed;
         self.op_state = OperationalState::RunningPyExperiment(execute(async move {
             let result = match py_experiment
-                .run(&points, &mut status_msg)
+                .run(&points, seed, backups, &mut status_msg)
                 .await
                 .context("python experiment run failed")
             {
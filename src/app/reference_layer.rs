@@ -0,0 +1,196 @@
+use std::path::PathBuf;
+
+use ecolor::Color32;
+use egui_plot::{MarkerShape, PlotUi, Points};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{parse_points_text, DataLabel, DataPoints, ParsedPoints, PointArray},
+    execute, file_handle_to_path,
+    operational_state::{OperationOutcome, OperationalState, Payload},
+};
+
+/// Points from another file drawn faded behind the data for comparison (Never edited)
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct ReferenceLayer {
+    #[serde(skip)]
+    points: DataPoints,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    pub is_visible: bool,
+    pub color: Color32,
+}
+
+impl Default for ReferenceLayer {
+    fn default() -> Self {
+        Self {
+            points: Default::default(),
+            path: None,
+            is_visible: true,
+            color: Color32::from_rgb(140, 140, 140),
+        }
+    }
+}
+
+impl ReferenceLayer {
+    /// Opacity of the markers so they stay behind the data visually
+    const FADE: f32 = 0.35;
+
+    fn is_loaded(&self) -> bool {
+        self.path.is_some()
+    }
+
+    fn clear(&mut self) {
+        self.points.clear();
+        self.path = None;
+    }
+
+    /// Positions split into normal points and every other label
+    fn positions_by_label(&self) -> (Vec<PointArray>, Vec<PointArray>) {
+        let mut normal = vec![];
+        let mut others = vec![];
+        for point in &self.points {
+            if point.label == DataLabel::Normal {
+                normal.push([point.x0, point.x1]);
+            } else {
+                others.push([point.x0, point.x1]);
+            }
+        }
+        (normal, others)
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_btns_reference_layer(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .button("Load Reference Dataset...")
+            .on_hover_text("Shows the points in a CSV file (label optional) faded behind the data without adding them to it")
+            .clicked()
+        {
+            self.load_reference_points(ui.ctx().clone());
+            ui.close_menu();
+        }
+        if self.reference_layer.is_loaded() && ui.button("Clear Reference Dataset").clicked() {
+            self.reference_layer.clear();
+            ui.close_menu();
+        }
+    }
+
+    pub(super) fn ui_reference_layer_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let hover_text = match &self.reference_layer.path {
+                Some(path) => format!("{} points from {path:?}", self.reference_layer.points.len()),
+                None => "No reference dataset loaded (See the File menu)".to_string(),
+            };
+            ui.checkbox(
+                &mut self.reference_layer.is_visible,
+                "Show reference dataset",
+            )
+            .on_hover_text(hover_text);
+            ui.color_edit_button_srgba(&mut self.reference_layer.color);
+        });
+    }
+
+    fn load_reference_points(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let progress = Default::default();
+        self.op_state = OperationalState::Loading(
+            execute(async move {
+                let Some(file) = rfd::AsyncFileDialog::new()
+                    .set_title("Load Reference Dataset")
+                    .add_filter("CSV", &["csv", "txt"])
+                    .pick_file()
+                    .await
+                else {
+                    // user canceled
+                    ctx.request_repaint();
+                    return OperationOutcome::Cancelled;
+                };
+                let text = String::from_utf8_lossy(&file.read().await).into_owned();
+                let parsed = parse_points_text(&text, DataLabel::Normal);
+                ctx.request_repaint();
+                OperationOutcome::Success(Payload::LoadReference {
+                    parsed,
+                    path: file_handle_to_path(&file),
+                })
+            }),
+            progress,
+        );
+    }
+
+    pub(super) fn on_reference_points_loaded(&mut self, parsed: ParsedPoints, path: PathBuf) {
+        let ParsedPoints { points, errors } = parsed;
+        for error in errors {
+            self.status_msg
+                .error_display(format!("Unable to read reference point. {error}"));
+        }
+        if points.is_empty() {
+            self.status_msg
+                .error_display(format!("No points found in {path:?}"));
+            return;
+        }
+        self.status_msg.info(format!(
+            "Loaded {} reference points from {path:?}",
+            points.len()
+        ));
+        self.reference_layer.points = points;
+        self.reference_layer.path = Some(path);
+        self.reference_layer.is_visible = true;
+    }
+
+    /// Draws the reference points faded (Called before the data so they end up behind it)
+    pub(super) fn plot_reference_layer(&self, plot_ui: &mut PlotUi) {
+        if !self.reference_layer.is_visible || !self.reference_layer.is_loaded() {
+            return;
+        }
+        let color = self
+            .reference_layer
+            .color
+            .gamma_multiply(ReferenceLayer::FADE);
+        let (normal, others) = self.reference_layer.positions_by_label();
+        for (points, name, shape) in [
+            (normal, "Reference Normal", MarkerShape::Circle),
+            (others, "Reference Anomalies", MarkerShape::Diamond),
+        ] {
+            if points.is_empty() {
+                continue;
+            }
+            let len = points.len();
+            plot_ui.points(
+                Points::new(points)
+                    .name(format!("{name} ({len})"))
+                    .radius(self.marker_radius)
+                    .shape(shape)
+                    .color(color),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::data_definition::DataPoint;
+
+    #[test]
+    fn splits_reference_points_by_label() {
+        let mut layer = ReferenceLayer {
+            points: vec![
+                DataPoint::new(0.0, 1.0, DataLabel::Normal),
+                DataPoint::new(2.0, 3.0, DataLabel::Anomaly),
+                DataPoint::new(4.0, 5.0, DataLabel::Class(2)),
+            ],
+            path: Some(PathBuf::from("ref.csv")),
+            ..Default::default()
+        };
+        assert!(layer.is_loaded());
+        let (normal, others) = layer.positions_by_label();
+        assert_eq!(normal, [[0.0, 1.0]]);
+        assert_eq!(others, [[2.0, 3.0], [4.0, 5.0]]);
+        layer.clear();
+        assert!(!layer.is_loaded());
+        assert!(layer.points.is_empty());
+    }
+}
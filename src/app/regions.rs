@@ -0,0 +1,174 @@
+use egui::{PointerButton, RichText};
+use egui_plot::{PlotPoint, PlotPoints, PlotUi, Polygon, Text};
+
+use crate::DBV;
+
+use super::data_definition::Region;
+
+impl DBV {
+    pub(super) fn ui_btn_show_regions(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.show_regions_window, "Show Regions");
+    }
+
+    /// Draws the regions and, in region mode, adds one for each rectangle dragged
+    pub(super) fn plot_regions(&mut self, plot_ui: &mut PlotUi) {
+        for region in self.data.regions.iter() {
+            let color = self.label_color(region.label);
+            plot_ui.polygon(
+                Polygon::new(PlotPoints::new(region.outline()))
+                    .stroke(egui::Stroke::new(1.5, color))
+                    .fill_color(color.gamma_multiply(0.08)),
+            );
+            plot_ui.text(
+                Text::new(
+                    PlotPoint::new(region.min[0], region.max[1]),
+                    RichText::new(&region.name).color(color),
+                )
+                .anchor(egui::Align2::LEFT_BOTTOM),
+            );
+        }
+
+        if !self.click_mode.is_draw_region() {
+            self.drawing_region = None;
+            return;
+        }
+        let response = plot_ui.response();
+        let pointer = plot_ui
+            .pointer_coordinate()
+            .or(self.last_cursor_pos)
+            .map(|x| [x.x, x.y]);
+        if response.drag_started_by(PointerButton::Primary) {
+            self.drawing_region = pointer;
+        }
+        let is_finished = response.drag_stopped_by(PointerButton::Primary);
+        let Some(start) = self.drawing_region else {
+            return;
+        };
+        let region = Region::from_corners(
+            format!("Region {}", self.data.regions.len() + 1),
+            self.primary_click_label,
+            start,
+            pointer.unwrap_or(start),
+        );
+        if is_finished {
+            self.drawing_region = None;
+            if region.min[0] < region.max[0] && region.min[1] < region.max[1] {
+                self.data.regions.add(region);
+                self.show_regions_window = true;
+            }
+            return;
+        }
+        plot_ui.polygon(
+            Polygon::new(PlotPoints::new(region.outline()))
+                .name("New Region")
+                .stroke(egui::Stroke::new(1.0, self.color_selection))
+                .fill_color(self.color_selection.gamma_multiply(0.1)),
+        );
+    }
+
+    pub(super) fn ui_regions_window(&mut self, ctx: &egui::Context) {
+        if !self.show_regions_window {
+            return;
+        }
+        let mut is_open = true;
+        egui::Window::new("Regions")
+            .open(&mut is_open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label("Drag on the plot in Region mode to add a region");
+                ui.separator();
+                self.ui_regions_list(ui);
+            });
+        if !is_open {
+            self.show_regions_window = false;
+        }
+    }
+
+    fn ui_regions_list(&mut self, ui: &mut egui::Ui) {
+        if self.data.regions.is_empty() {
+            ui.label("No regions defined");
+            return;
+        }
+        let mut to_remove = None;
+        let mut to_select = None;
+        let mut to_relabel = None;
+        egui::Grid::new("regions grid")
+            .striped(true)
+            .show(ui, |ui| {
+                for (i, region) in self.data.regions.iter_mut().enumerate() {
+                    ui.add(egui::TextEdit::singleline(&mut region.name).desired_width(100.0));
+                    self.label_classes.ui_combo(
+                        ui,
+                        &format!("id-region-label-{i}"),
+                        &mut region.label,
+                    );
+                    ui.label(format!(
+                        "({}, {}) to ({}, {})",
+                        self.display_precision.format(region.min[0]),
+                        self.display_precision.format(region.min[1]),
+                        self.display_precision.format(region.max[0]),
+                        self.display_precision.format(region.max[1]),
+                    ));
+                    if ui
+                        .button("Select Inside")
+                        .on_hover_text("Replaces the selection with the points inside")
+                        .clicked()
+                    {
+                        to_select = Some(i);
+                    }
+                    ui.menu_button("Relabel Inside", |ui| {
+                        for label in self.label_classes.labels() {
+                            let mut text = format!("As {}", self.label_classes.name(label));
+                            if label == region.label {
+                                text.push_str(" (Region label)");
+                            }
+                            if ui.button(text).clicked() {
+                                to_relabel = Some((i, label));
+                                ui.close_menu();
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(
+                        "Gives the points inside the label picked as a single undoable change",
+                    );
+                    if ui.button("Delete").clicked() {
+                        to_remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(region) = to_select.and_then(|i| self.data.regions.get(i)) {
+            let indices = self.data.indices_in_region(region);
+            self.status_msg.info(format!(
+                "Selected {} point(s) in {:?}",
+                indices.len(),
+                region.name
+            ));
+            self.selection.set(&self.data, indices);
+        }
+        if let Some((region, label)) =
+            to_relabel.and_then(|(i, label)| Some((self.data.regions.get(i)?.clone(), label)))
+        {
+            let indices: Vec<usize> = self
+                .data
+                .indices_in_region(&region)
+                .into_iter()
+                .filter(|&i| self.data.points()[i].label != label)
+                .collect();
+            self.status_msg.info(format!(
+                "Relabeled {} point(s) in {:?} as {}",
+                indices.len(),
+                region.name,
+                self.label_classes.name(label)
+            ));
+            if !indices.is_empty() {
+                self.data.set_label(indices, label);
+                self.selection.keep_for(&self.data);
+            }
+        }
+        if let Some(i) = to_remove {
+            self.data.regions.remove(i);
+        }
+    }
+}
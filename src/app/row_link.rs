@@ -0,0 +1,119 @@
+use egui_plot::{MarkerShape, PlotPoint, PlotUi, Points};
+
+use crate::DBV;
+
+use super::{
+    plot_zoom_reset::{MinMaxPair, ZoomTarget},
+    DisplayMode,
+};
+
+/// Links a row of the table with its point on the plot
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct RowLink {
+    /// Point highlighted on the plot and in the table
+    #[serde(skip)]
+    pub index: Option<usize>,
+    /// The table scrolls to the linked row the next time it is shown
+    #[serde(skip)]
+    pub should_scroll: bool,
+    /// Clicking a row also switches to the plot centered on its point
+    pub should_center_on_click: bool,
+}
+
+impl RowLink {
+    /// Screen distance from the cursor that the clicked point must be within
+    const PICK_RADIUS: f64 = 10.0;
+
+    /// Position of the linked point in the rows shown (Rows can be sorted by `order`)
+    pub fn scroll_target(&mut self, order: Option<&[usize]>, row_count: usize) -> Option<usize> {
+        if !std::mem::take(&mut self.should_scroll) {
+            return None;
+        }
+        let index = self.index?;
+        match order {
+            Some(order) => order.iter().position(|&i| i == index),
+            None => (index < row_count).then_some(index),
+        }
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_row_link_options(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(
+            &mut self.row_link.should_center_on_click,
+            "Center plot on clicked row",
+        )
+        .on_hover_text("Clicking a row switches to the plot centered on its point");
+    }
+
+    /// Links the row clicked in the table with its point
+    pub(super) fn link_row(&mut self, index: usize) {
+        self.row_link.index = Some(index);
+        if !self.row_link.should_center_on_click {
+            return;
+        }
+        let Some(point) = self.data.points().get(index) else {
+            return;
+        };
+        self.display_mode = DisplayMode::Plot;
+        if let Some(bounds) = self.plot_bounds {
+            let [half_width, half_height] = [bounds.width() / 2.0, bounds.height() / 2.0];
+            self.start_zoom_reset(ZoomTarget::View(MinMaxPair {
+                min: [point.x0 - half_width, point.x1 - half_height],
+                max: [point.x0 + half_width, point.x1 + half_height],
+            }));
+        }
+    }
+
+    /// Links the point under the cursor so the table scrolls to its row when shown
+    pub(super) fn link_point_on_click(
+        &mut self,
+        response: &egui::Response,
+        pointer_coordinate: Option<PlotPoint>,
+    ) {
+        let (Some(coord), Some(bounds)) = (pointer_coordinate, self.plot_bounds) else {
+            return;
+        };
+        let units_per_screen_point = bounds.width() / response.rect.width() as f64;
+        self.row_link.index = self.data.nearest_within(
+            [coord.x, coord.y],
+            RowLink::PICK_RADIUS * units_per_screen_point,
+            |i| self.is_point_shown(i),
+        );
+        self.row_link.should_scroll = self.row_link.index.is_some();
+    }
+
+    /// Highlights the point of the linked row
+    pub(super) fn plot_linked_row(&self, plot_ui: &mut PlotUi) {
+        let Some(point) = self.row_link.index.and_then(|i| self.data.points().get(i)) else {
+            return;
+        };
+        plot_ui.points(
+            Points::new(vec![[point.x0, point.x1]])
+                .name("Linked Row")
+                .shape(MarkerShape::Circle)
+                .filled(false)
+                .radius(self.marker_radius * 2.2)
+                .color(self.color_selection),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrolls_once_to_the_sorted_row() {
+        let mut link = RowLink {
+            index: Some(2),
+            should_scroll: true,
+            ..Default::default()
+        };
+        assert_eq!(link.scroll_target(Some(&[2, 0, 1]), 3), Some(0));
+        assert_eq!(link.scroll_target(Some(&[2, 0, 1]), 3), None);
+        link.should_scroll = true;
+        assert_eq!(link.scroll_target(None, 2), None);
+    }
+}
@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use ecolor::Color32;
+use egui::Button;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataPoint, DataTimestamp},
+    export_results::results_csv,
+    prediction_classification::{prediction_classification, Classification, ConfusionCounts},
+};
+
+/// Settings for writing the outputs of each local experiment run to its own folder
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct RunArtifacts {
+    pub is_enabled: bool,
+    /// Folder that the run folders are created in
+    pub workspace: Option<String>,
+    /// Folder of the most recent run
+    #[serde(skip)]
+    last_run_dir: Option<PathBuf>,
+}
+
+/// Summary of a run written next to the other artifacts
+#[derive(serde::Serialize)]
+struct RunInfo<'a> {
+    model: &'a str,
+    points: usize,
+    data_hash: String,
+    data_timestamp: DataTimestamp,
+    counts: ConfusionCounts,
+    precision: Option<f64>,
+    recall: Option<f64>,
+    f1: Option<f64>,
+}
+
+impl RunArtifacts {
+    const SNAPSHOT_SIZE: f64 = 600.0;
+
+    /// Creates a new folder for the run (Named with the time and model)
+    fn create_run_dir(workspace: &Path, model_name: &str) -> anyhow::Result<PathBuf> {
+        let base_name = format!(
+            "{}_{}",
+            chrono::Local::now().format("%Y-%m-%d_%H-%M-%S"),
+            model_name.to_lowercase().replace(' ', "_")
+        );
+        let mut result = workspace.join(&base_name);
+        let mut suffix = 1;
+        while result.exists() {
+            suffix += 1;
+            result = workspace.join(format!("{base_name}_{suffix}"));
+        }
+        std::fs::create_dir_all(&result)
+            .with_context(|| format!("failed to create run folder {result:?}"))?;
+        Ok(result)
+    }
+
+    /// Draws the points as an SVG image using the color and tooltip returned for each point
+    fn plot_snapshot(
+        points: &[DataPoint],
+        style_of: impl Fn(usize, &DataPoint) -> (Color32, String),
+    ) -> String {
+        let size = Self::SNAPSHOT_SIZE;
+        let margin = 10.0;
+        let (mut min, mut max) = ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]);
+        for point in points {
+            min = [min[0].min(point.x0), min[1].min(point.x1)];
+            max = [max[0].max(point.x0), max[1].max(point.x1)];
+        }
+        let extent = (max[0] - min[0]).max(max[1] - min[1]).max(f64::EPSILON);
+        let scale = (size - 2.0 * margin) / extent;
+        let mut result = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n"
+        );
+        for (i, point) in points.iter().enumerate() {
+            let (color, title) = style_of(i, point);
+            result.push_str(&format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"#{:02x}{:02x}{:02x}\"><title>{title}</title></circle>\n",
+                margin + (point.x0 - min[0]) * scale,
+                size - margin - (point.x1 - min[1]) * scale, // SVG y axis points down
+                color.r(),
+                color.g(),
+                color.b()
+            ));
+        }
+        result.push_str("</svg>\n");
+        result
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_run_artifacts(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut self.run_artifacts.is_enabled,
+                "Save artifacts for each run",
+            )
+            .on_hover_text("Writes the scores, model, metrics and a plot snapshot to a new folder after training");
+            if !self.run_artifacts.is_enabled {
+                return;
+            }
+            ui.label(
+                self.run_artifacts
+                    .workspace
+                    .as_deref()
+                    .unwrap_or(Self::NOT_SET),
+            );
+            if ui.button("Browse...").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Select Workspace Folder for Runs")
+                    .pick_folder()
+                {
+                    self.run_artifacts.workspace = Some(path.to_string_lossy().into_owned());
+                }
+            }
+            let last_run_dir = self.run_artifacts.last_run_dir.clone();
+            if ui
+                .add_enabled(last_run_dir.is_some(), Button::new("Open Last Run Folder"))
+                .clicked()
+            {
+                if let Some(dir) = last_run_dir {
+                    if let Err(e) = opener::open(&dir).context("failed to open run folder") {
+                        self.status_msg.error_debug(e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Writes the artifacts for the model that just finished training if enabled
+    pub(super) fn save_run_artifacts(&mut self) {
+        if !self.run_artifacts.is_enabled {
+            return;
+        }
+        let Some(workspace) = self.run_artifacts.workspace.clone() else {
+            self.status_msg
+                .error_display("Unable to save run artifacts. No workspace folder selected");
+            return;
+        };
+        match self
+            .write_run_artifacts(Path::new(&workspace))
+            .context("failed to save run artifacts")
+        {
+            Ok(dir) => {
+                self.status_msg
+                    .info(format!("Run artifacts saved to {dir:?}"));
+                self.run_artifacts.last_run_dir = Some(dir);
+            }
+            Err(e) => self.status_msg.error_debug(e),
+        }
+    }
+
+    fn write_run_artifacts(&self, workspace: &Path) -> anyhow::Result<PathBuf> {
+        let model = self
+            .loc_inference_model()
+            .context("model is not trained on the current data")?;
+        let points = self.data.points();
+        let dir = RunArtifacts::create_run_dir(workspace, self.loc_experiment.name())?;
+        let write = |name: &str, contents: &[u8]| {
+            std::fs::write(dir.join(name), contents)
+                .with_context(|| format!("failed to write {name}"))
+        };
+
+        write("scores.csv", &results_csv(points, model)?)?;
+
+        let model_text = ron::ser::to_string_pretty(&self.loc_experiment, Default::default())
+            .context("failed to serialize model")?;
+        write("model.ron", model_text.as_bytes())?;
+
+        let counts: ConfusionCounts = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                prediction_classification(point.label, model.prediction_on_training_data(i))
+            })
+            .collect();
+        let info = RunInfo {
+            model: self.loc_experiment.name(),
+            points: points.len(),
+            data_hash: self.data.content_hash(),
+            data_timestamp: self.data.timestamp(),
+            counts,
+            precision: counts.precision(),
+            recall: counts.recall(),
+            f1: counts.f1(),
+        };
+        let info_text = ron::ser::to_string_pretty(&info, Default::default())
+            .context("failed to serialize metrics")?;
+        write("metrics.ron", info_text.as_bytes())?;
+
+        let snapshot = self.plot_snapshot_svg();
+        write("plot.svg", snapshot.as_bytes())?;
+
+        Ok(dir)
+    }
+
+    /// Draws the points as an SVG image colored by the results of the model if it is trained on
+    /// the current data and by their labels otherwise
+    pub(super) fn plot_snapshot_svg(&self) -> String {
+        match self.loc_inference_model() {
+            Some(model) => RunArtifacts::plot_snapshot(self.data.points(), |i, point| {
+                let classification =
+                    prediction_classification(point.label, model.prediction_on_training_data(i));
+                let color = match classification {
+                    Classification::FalseNegative => self.color_results_false_negatives,
+                    Classification::FalsePositive => self.color_results_false_positives,
+                    Classification::TrueNegative => self.color_results_true_negatives,
+                    Classification::TruePositive => self.color_results_true_positives,
+                };
+                (color, classification.to_string())
+            }),
+            None => RunArtifacts::plot_snapshot(self.data.points(), |_, point| {
+                (
+                    self.label_color(point.label),
+                    self.label_classes.name(point.label),
+                )
+            }),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use ecolor::Color32;
+use egui_plot::{Line, PlotPoints, PlotUi};
+
+use super::{
+    data_definition::{DataTimestamp, PointArray},
+    score_heatmap::ScoreHeatmap,
+};
+
+/// Lines joining the places with the same score drawn over the plot
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct ScoreContours {
+    pub is_enabled: bool,
+    /// Number of evenly spaced levels between the lowest and highest visible score (A line is
+    /// always drawn at the threshold)
+    pub level_count: u8,
+    #[serde(skip)]
+    cache: Option<ContoursCache>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ContoursCache {
+    /// Same as the key of the heatmap the lines are traced from along with the levels used
+    key: ((DataTimestamp, [f64; 4]), Vec<f64>),
+    /// Lines of each level, the last one is the threshold
+    lines: Vec<Vec<Vec<PointArray>>>,
+}
+
+impl Default for ScoreContours {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            level_count: 5,
+            cache: None,
+        }
+    }
+}
+
+/// Edge between two neighbouring cells identified by the row and column of the top or left cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GridEdge {
+    Horizontal(usize, usize),
+    Vertical(usize, usize),
+}
+
+impl ScoreContours {
+    pub fn ui_options(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.is_enabled, "Contours").on_hover_text(
+            "Draws lines through places with the same score (Including the threshold)",
+        );
+        if self.is_enabled {
+            ui.add(
+                egui::DragValue::new(&mut self.level_count)
+                    .clamp_range(0..=20)
+                    .prefix("Levels: "),
+            )
+            .on_hover_text("Number of lines besides the threshold");
+        }
+    }
+
+    /// Draws the contours of the heatmap's grid of scores (Traced again only when the grid or
+    /// levels change)
+    pub fn plot(
+        &mut self,
+        plot_ui: &mut PlotUi,
+        grid_key: (DataTimestamp, [f64; 4]),
+        scores: &[f64],
+        threshold: f64,
+        threshold_color: Color32,
+    ) {
+        let (min, max) = scores
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &x| {
+                (min.min(x), max.max(x))
+            });
+        let step = (max - min) / (f64::from(self.level_count) + 1.0);
+        let mut levels: Vec<f64> = (1..=self.level_count)
+            .map(|i| min + step * f64::from(i))
+            .collect();
+        levels.push(threshold);
+        let key = (grid_key, levels);
+        if self.cache.as_ref().map(|x| &x.key) != Some(&key) {
+            let area = grid_key.1;
+            let lines = key
+                .1
+                .iter()
+                .map(|&level| {
+                    contour_lines(
+                        scores,
+                        ScoreHeatmap::RESOLUTION,
+                        |row, col| ScoreHeatmap::cell_center(area, row, col),
+                        level,
+                    )
+                })
+                .collect();
+            self.cache = Some(ContoursCache { key, lines });
+        }
+
+        let Some(cache) = self.cache.as_ref() else {
+            return;
+        };
+        let level_color = plot_ui.ctx().style().visuals.weak_text_color();
+        let (threshold_lines, level_lines) = cache.lines.split_last().expect("threshold is last");
+        for line in level_lines.iter().flatten() {
+            // Same name for all so they share one entry in the legend
+            plot_ui.line(
+                Line::new(PlotPoints::new(line.clone()))
+                    .name("Score contours")
+                    .color(level_color),
+            );
+        }
+        for line in threshold_lines {
+            plot_ui.line(
+                Line::new(PlotPoints::new(line.clone()))
+                    .name("Threshold contour")
+                    .color(threshold_color)
+                    .width(2.0),
+            );
+        }
+    }
+}
+
+/// Traces the lines where the scores cross `level` on a grid of `resolution` by `resolution`
+/// cells (Marching squares)
+///
+/// Segments are joined into as few lines as possible so that they can be drawn as polylines
+fn contour_lines(
+    scores: &[f64],
+    resolution: usize,
+    position: impl Fn(usize, usize) -> PointArray,
+    level: f64,
+) -> Vec<Vec<PointArray>> {
+    debug_assert_eq!(scores.len(), resolution * resolution);
+    let score = |row: usize, col: usize| scores[row * resolution + col];
+    let is_above = |row: usize, col: usize| score(row, col) >= level;
+
+    // Find the segments in each square formed by the centers of four neighbouring cells
+    let mut segments: Vec<[GridEdge; 2]> = vec![];
+    for row in 0..resolution.saturating_sub(1) {
+        for col in 0..resolution - 1 {
+            let top = GridEdge::Horizontal(row, col);
+            let bottom = GridEdge::Horizontal(row + 1, col);
+            let left = GridEdge::Vertical(row, col);
+            let right = GridEdge::Vertical(row, col + 1);
+            let corners = [
+                is_above(row, col),
+                is_above(row, col + 1),
+                is_above(row + 1, col + 1),
+                is_above(row + 1, col),
+            ];
+            let crossed: Vec<GridEdge> = [top, right, bottom, left]
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| corners[*i] != corners[(i + 1) % 4])
+                .map(|(_, edge)| edge)
+                .collect();
+            match crossed[..] {
+                [a, b] => segments.push([a, b]),
+                [_, _, _, _] => {
+                    // Saddle: use the average of the corners to decide which corners are joined
+                    let center = (score(row, col)
+                        + score(row, col + 1)
+                        + score(row + 1, col + 1)
+                        + score(row + 1, col))
+                        / 4.0;
+                    if (center >= level) == corners[0] {
+                        segments.extend([[top, right], [left, bottom]]);
+                    } else {
+                        segments.extend([[top, left], [right, bottom]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let edge_point = |edge: GridEdge| {
+        let (start, end) = match edge {
+            GridEdge::Horizontal(row, col) => ((row, col), (row, col + 1)),
+            GridEdge::Vertical(row, col) => ((row, col), (row + 1, col)),
+        };
+        let (start_score, end_score) = (score(start.0, start.1), score(end.0, end.1));
+        let t = ((level - start_score) / (end_score - start_score)).clamp(0.0, 1.0);
+        let (start, end) = (position(start.0, start.1), position(end.0, end.1));
+        [
+            start[0] + (end[0] - start[0]) * t,
+            start[1] + (end[1] - start[1]) * t,
+        ]
+    };
+
+    // Join the segments that share an edge (Each edge is shared by at most two squares)
+    let mut segments_at_edge: HashMap<GridEdge, Vec<usize>> = HashMap::new();
+    for (i, segment) in segments.iter().enumerate() {
+        for edge in segment {
+            segments_at_edge.entry(*edge).or_default().push(i);
+        }
+    }
+    let mut is_used = vec![false; segments.len()];
+    let mut result = vec![];
+    for first in 0..segments.len() {
+        if is_used[first] {
+            continue;
+        }
+        is_used[first] = true;
+        let mut edges = std::collections::VecDeque::from(segments[first]);
+        for is_forward in [true, false] {
+            loop {
+                let end = if is_forward {
+                    *edges.back().expect("not empty")
+                } else {
+                    *edges.front().expect("not empty")
+                };
+                let Some(&next) = segments_at_edge[&end].iter().find(|&&i| !is_used[i]) else {
+                    break;
+                };
+                is_used[next] = true;
+                let [a, b] = segments[next];
+                let other = if a == end { b } else { a };
+                if is_forward {
+                    edges.push_back(other);
+                } else {
+                    edges.push_front(other);
+                }
+            }
+        }
+        result.push(edges.into_iter().map(edge_point).collect());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contour_around_peak_is_one_closed_line() {
+        // Distance from the center of a 5 by 5 grid
+        let resolution = 5;
+        let scores: Vec<f64> = (0..resolution * resolution)
+            .map(|i| {
+                let (row, col) = ((i / resolution) as f64, (i % resolution) as f64);
+                ((row - 2.0).powi(2) + (col - 2.0).powi(2)).sqrt()
+            })
+            .collect();
+        let position = |row: usize, col: usize| [col as f64, -(row as f64)];
+
+        let lines = contour_lines(&scores, resolution, position, 1.5);
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line.first(), line.last());
+        for point in line {
+            let distance = (point[0] - 2.0).hypot(point[1] + 2.0);
+            assert!((1.0..=2.0).contains(&distance), "{point:?}");
+        }
+
+        assert!(contour_lines(&scores, resolution, position, 10.0).is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+use ecolor::Color32;
+use egui::Button;
+use egui_plot::{MarkerShape, Points};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataTimestamp, DistanceCalculation as _, PointArray},
+    local_experiments::Scores,
+};
+
+/// Scores of a trained model kept to compare against the scores of another model
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Debug)]
+pub struct ReferenceScores {
+    /// Name of the model that produced the scores
+    name: String,
+    data_timestamp: DataTimestamp,
+    scores: Scores,
+}
+
+/// Scales the scores to be between 0 and 1 so models with different ranges can be compared
+fn normalize(scores: &[f64]) -> Vec<f64> {
+    let min = scores.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    scores
+        .iter()
+        .map(|score| {
+            if range > 0.0 {
+                (score - min) / range
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Returns the difference between the normalized scores (current - reference) for each point.
+/// Values are between -1 and 1
+fn normalized_score_deltas(current: &[f64], reference: &[f64]) -> Vec<f64> {
+    debug_assert_eq!(current.len(), reference.len());
+    normalize(current)
+        .into_iter()
+        .zip(normalize(reference))
+        .map(|(current, reference)| current - reference)
+        .collect()
+}
+
+impl DBV {
+    /// Number of color steps used to show the deltas (Odd so that no difference has its own step)
+    const SCORE_DELTA_BINS: usize = 9;
+
+    pub(super) fn ui_score_delta(&mut self, ui: &mut egui::Ui) {
+        let current_scores = self.current_scores();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(current_scores.is_some(), Button::new("Keep Scores for Comparison"))
+                .on_hover_text("Stores the current model's scores so another model can be compared against them")
+                .clicked()
+            {
+                self.reference_scores = current_scores.map(|scores| ReferenceScores {
+                    name: self.loc_experiment.name().to_string(),
+                    data_timestamp: self.data.timestamp(),
+                    scores,
+                });
+            }
+            let Some(reference) = &self.reference_scores else {
+                return;
+            };
+            let is_comparable = self.score_deltas().is_some();
+            ui.add_enabled(
+                is_comparable,
+                egui::Checkbox::new(
+                    &mut self.show_score_delta,
+                    format!("Color by score delta vs kept {} scores", reference.name),
+                ),
+            )
+            .on_disabled_hover_text(
+                "Requires the current model to be trained on the same version of the data as the kept scores",
+            );
+            if ui.button("Discard Kept Scores").clicked() {
+                self.reference_scores = None;
+                self.show_score_delta = false;
+            }
+        });
+    }
+
+    /// The scores of the current model for each point if it is trained on the current data
+    pub(super) fn current_scores(&self) -> Option<Scores> {
+        let model = self.loc_inference_model()?;
+        Some(
+            (0..self.data.points().len())
+                .map(|i| model.score_for_training_data(i))
+                .collect(),
+        )
+    }
+
+    /// The deltas if both the current model and the kept scores are for the current data
+    fn score_deltas(&self) -> Option<Vec<f64>> {
+        let reference = self.reference_scores.as_ref()?;
+        if reference.data_timestamp != self.data.timestamp() {
+            return None;
+        }
+        let current = self.current_scores()?;
+        Some(normalized_score_deltas(&current, &reference.scores))
+    }
+
+    /// Markers colored by how much more (red) or less (blue) anomalous the current model finds
+    /// each point than the kept scores. Returns `None` if the delta cannot be calculated
+    pub(super) fn markers_score_delta(&self) -> Option<Vec<Points>> {
+        if !self.show_score_delta {
+            return None;
+        }
+        let deltas = self.score_deltas()?;
+        let mut bins: Vec<Vec<PointArray>> = vec![vec![]; Self::SCORE_DELTA_BINS];
+        for (point, delta) in self.data.points().iter().zip(deltas) {
+            // Map -1..=1 onto the bins
+            let bin = ((delta + 1.0) / 2.0 * (Self::SCORE_DELTA_BINS - 1) as f64).round() as usize;
+            bins[bin.min(Self::SCORE_DELTA_BINS - 1)].push(point.to_array());
+        }
+        let step = 2.0 / (Self::SCORE_DELTA_BINS - 1) as f64;
+        Some(
+            bins.into_iter()
+                .enumerate()
+                .filter(|(_, points)| !points.is_empty())
+                .map(|(i, points)| {
+                    let center = -1.0 + i as f64 * step;
+                    self.data_points_to_egui_points(
+                        points,
+                        format!("Δ {center:+.2}"),
+                        None,
+                        MarkerShape::Circle,
+                        delta_color(center),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Blue for negative, grey for no difference and red for positive deltas
+fn delta_color(delta: f64) -> Color32 {
+    let neutral = Color32::GRAY;
+    let end = if delta < 0.0 {
+        Color32::from_rgb(0, 90, 255)
+    } else {
+        Color32::from_rgb(230, 20, 20)
+    };
+    let t = delta.abs().min(1.0) as f32;
+    let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color32::from_rgb(
+        lerp(neutral.r(), end.r()),
+        lerp(neutral.g(), end.g()),
+        lerp(neutral.b(), end.b()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deltas_ignore_score_range() {
+        let current = [0.0, 5.0, 10.0];
+        let reference = [100.0, 100.5, 101.0];
+        assert_eq!(normalized_score_deltas(&current, &reference), [0.0; 3]);
+
+        let reference = [1.0, 0.5, 0.0];
+        assert_eq!(
+            normalized_score_deltas(&current, &reference),
+            [-1.0, 0.0, 1.0]
+        );
+    }
+}
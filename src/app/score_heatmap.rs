@@ -0,0 +1,206 @@
+use ecolor::Color32;
+use egui::{ColorImage, TextureHandle, TextureOptions};
+use egui_plot::{PlotImage, PlotPoint, PlotUi};
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataTimestamp, PointArray},
+    local_experiments::ModelInference,
+};
+
+/// Scores of the trained model over the visible area drawn behind the points
+#[derive(Default)]
+pub struct ScoreHeatmap {
+    /// Training state of the model and the plot area (min x0, min x1, max x0, max x1) the scores
+    /// are for
+    key: Option<(DataTimestamp, [f64; 4])>,
+    /// Cells are in rows starting from the top left (The same as images)
+    scores: Vec<f64>,
+    /// Built from the scores along with the predictions and colors used
+    texture: Option<(TextureHandle, Vec<DataLabel>, [Color32; 2])>,
+}
+
+impl PartialEq for ScoreHeatmap {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl ScoreHeatmap {
+    /// Cells per side of the grid
+    pub(super) const RESOLUTION: usize = 64;
+
+    /// Returns the center of the cell in the grid covering the area
+    pub(super) fn cell_center(area: [f64; 4], row: usize, col: usize) -> PointArray {
+        let [min_x0, min_x1, max_x0, max_x1] = area;
+        let resolution = Self::RESOLUTION as f64;
+        let cell_width = (max_x0 - min_x0) / resolution;
+        let cell_height = (max_x1 - min_x1) / resolution;
+        [
+            min_x0 + (col as f64 + 0.5) * cell_width,
+            max_x1 - (row as f64 + 0.5) * cell_height,
+        ]
+    }
+
+    /// Returns the centers of the cells covering the area
+    fn cell_centers(area: [f64; 4]) -> Vec<PointArray> {
+        (0..Self::RESOLUTION * Self::RESOLUTION)
+            .map(|cell| Self::cell_center(area, cell / Self::RESOLUTION, cell % Self::RESOLUTION))
+            .collect()
+    }
+}
+
+impl DBV {
+    /// Above this the heatmap is not shown because scoring the grid is too slow to do on the UI thread
+    const HEATMAP_MAX_POINTS: usize = 5_000;
+
+    pub(super) fn ui_score_heatmap_toggle(&mut self, ui: &mut egui::Ui) {
+        let is_too_many = self.data.points().len() > Self::HEATMAP_MAX_POINTS;
+        let disabled_text = format!(
+            "Only available with up to {} points",
+            Self::HEATMAP_MAX_POINTS
+        );
+        ui.add_enabled(
+            !is_too_many,
+            egui::Checkbox::new(&mut self.show_score_heatmap, "Decision background"),
+        )
+        .on_hover_text("Colors the plot by the prediction of the model (Darker for higher scores)")
+        .on_disabled_hover_text(&disabled_text);
+        ui.add_enabled_ui(!is_too_many, |ui| self.score_contours.ui_options(ui))
+            .response
+            .on_disabled_hover_text(disabled_text);
+    }
+
+    /// Draws the model's predictions over the visible area behind the points and the contours of
+    /// its scores (Each is shown only if turned on)
+    ///
+    /// The grid is scored again when the model is retrained or the view changes (Not while the
+    /// pointer is down so panning stays smooth) and only recolored when the threshold changes
+    pub(super) fn plot_score_heatmap(&mut self, plot_ui: &mut PlotUi) {
+        if !(self.show_score_heatmap || self.score_contours.is_enabled)
+            || self.data.points().len() > Self::HEATMAP_MAX_POINTS
+        {
+            return;
+        }
+        // Borrowed from the field so the heatmap can be updated at the same time
+        let Some(model) = self.loc_experiment.model_inference() else {
+            return;
+        };
+        if !self
+            .loc_experiment
+            .is_at_timestamp(self.data.training_timestamp())
+        {
+            return;
+        }
+        let bounds = plot_ui.plot_bounds();
+        let area = [
+            bounds.min()[0],
+            bounds.min()[1],
+            bounds.max()[0],
+            bounds.max()[1],
+        ];
+        let heatmap = &mut self.score_heatmap;
+        let key = (model.data_timestamp_at_training(), area);
+        let is_pointer_down = plot_ui.ctx().input(|i| i.pointer.any_down());
+        let is_stale = heatmap.key.is_some_and(|(timestamp, _)| timestamp != key.0);
+        if heatmap.key != Some(key) && (!is_pointer_down || is_stale || heatmap.key.is_none()) {
+            heatmap.scores =
+                model.score_batch(&ScoreHeatmap::cell_centers(area), self.data.points());
+            heatmap.key = Some(key);
+            heatmap.texture = None;
+        }
+        let Some(key) = heatmap.key else {
+            return;
+        };
+        let colors = [self.color_normal, self.color_anom];
+        if self.show_score_heatmap {
+            heatmap.plot_image(plot_ui, model, colors);
+        }
+        if self.score_contours.is_enabled {
+            self.score_contours.plot(
+                plot_ui,
+                key,
+                &heatmap.scores,
+                model.decision_threshold(),
+                self.color_anom,
+            );
+        }
+    }
+}
+
+impl ScoreHeatmap {
+    fn plot_image(
+        &mut self,
+        plot_ui: &mut PlotUi,
+        model: &dyn ModelInference,
+        colors: [Color32; 2],
+    ) {
+        let Some((_, area)) = self.key else {
+            return;
+        };
+        let predictions: Vec<DataLabel> = self
+            .scores
+            .iter()
+            .map(|&x| model.prediction_for_score(x))
+            .collect();
+        if !matches!(&self.texture, Some((_, used, used_colors)) if *used == predictions && *used_colors == colors)
+        {
+            let (min, max) = self
+                .scores
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &x| {
+                    (min.min(x), max.max(x))
+                });
+            let range = (max - min).max(f64::EPSILON);
+            let pixels = self
+                .scores
+                .iter()
+                .zip(predictions.iter())
+                .map(|(&score, prediction)| {
+                    let color = match prediction {
+                        DataLabel::Normal => colors[0],
+                        DataLabel::Anomaly | DataLabel::Class(_) => colors[1],
+                    };
+                    color.gamma_multiply(0.1 + 0.25 * ((score - min) / range) as f32)
+                })
+                .collect();
+            let image = ColorImage {
+                size: [ScoreHeatmap::RESOLUTION, ScoreHeatmap::RESOLUTION],
+                pixels,
+            };
+            let texture =
+                plot_ui
+                    .ctx()
+                    .load_texture("score-heatmap", image, TextureOptions::LINEAR);
+            self.texture = Some((texture, predictions, colors));
+        }
+        let (texture, ..) = self.texture.as_ref().expect("set above");
+        let [min_x0, min_x1, max_x0, max_x1] = area;
+        plot_ui.image(
+            PlotImage::new(
+                texture,
+                PlotPoint::new((min_x0 + max_x0) / 2.0, (min_x1 + max_x1) / 2.0),
+                [(max_x0 - min_x0) as f32, (max_x1 - min_x1) as f32],
+            )
+            .name("Decision background"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_centers_start_at_top_left() {
+        let centers = ScoreHeatmap::cell_centers([0.0, 0.0, 64.0, 128.0]);
+        assert_eq!(
+            centers.len(),
+            ScoreHeatmap::RESOLUTION * ScoreHeatmap::RESOLUTION
+        );
+        assert_eq!(centers[0], [0.5, 127.0]);
+        assert_eq!(centers[ScoreHeatmap::RESOLUTION - 1], [63.5, 127.0]);
+        assert_eq!(*centers.last().unwrap(), [63.5, 1.0]);
+    }
+}
@@ -0,0 +1,167 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use egui::{Button, ColorImage, Key, KeyboardShortcut, Modifiers, Rect};
+
+use crate::DBV;
+
+/// Settings for saving pictures of the plot with a shortcut
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct Screenshots {
+    /// Where the pictures are saved (Relative paths are from the working directory)
+    pub folder: PathBuf,
+    /// Area of the plot on screen the last time it was drawn
+    #[serde(skip)]
+    pub plot_rect: Option<Rect>,
+    #[serde(skip)]
+    is_requested: bool,
+}
+
+impl Default for Screenshots {
+    fn default() -> Self {
+        Self {
+            folder: PathBuf::from("screenshots"),
+            plot_rect: None,
+            is_requested: false,
+        }
+    }
+}
+
+impl Screenshots {
+    const SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::NONE, Key::F12);
+
+    /// Name for a screenshot taken at the time given
+    fn file_name(time: chrono::DateTime<chrono::Local>) -> String {
+        format!("plot_{}.png", time.format("%Y%m%d_%H%M%S_%3f"))
+    }
+
+    /// Saves the part of the screen image covered by `rect` (in points) as a PNG in the folder
+    fn save(
+        &self,
+        image: &ColorImage,
+        rect: Rect,
+        pixels_per_point: f32,
+    ) -> anyhow::Result<PathBuf> {
+        let [width, height] = image.size;
+        let to_pixel =
+            |x: f32, max: usize| ((x * pixels_per_point).round().max(0.0) as usize).min(max);
+        let (min_x, max_x) = (to_pixel(rect.min.x, width), to_pixel(rect.max.x, width));
+        let (min_y, max_y) = (to_pixel(rect.min.y, height), to_pixel(rect.max.y, height));
+        if min_x >= max_x || min_y >= max_y {
+            anyhow::bail!("plot is not on the screen");
+        }
+        let mut bytes = Vec::with_capacity((max_x - min_x) * (max_y - min_y) * 4);
+        for row in min_y..max_y {
+            for pixel in &image.pixels[row * width + min_x..row * width + max_x] {
+                bytes.extend(pixel.to_srgba_unmultiplied());
+            }
+        }
+
+        std::fs::create_dir_all(&self.folder)
+            .with_context(|| format!("failed to create screenshots folder {:?}", self.folder))?;
+        let path = self.folder.join(Self::file_name(chrono::Local::now()));
+        image::save_buffer(
+            &path,
+            &bytes,
+            (max_x - min_x) as u32,
+            (max_y - min_y) as u32,
+            image::ColorType::Rgba8,
+        )
+        .with_context(|| format!("failed to write screenshot to {path:?}"))?;
+        Ok(path)
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_screenshot_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Screenshots folder");
+            let mut folder = self.screenshots.folder.display().to_string();
+            if ui
+                .add(egui::TextEdit::singleline(&mut folder).desired_width(200.0))
+                .on_hover_text(format!(
+                    "Press {} to save the plot as a PNG in this folder",
+                    ui.ctx().format_shortcut(&Screenshots::SHORTCUT)
+                ))
+                .changed()
+            {
+                self.screenshots.folder = PathBuf::from(folder);
+            }
+            if ui
+                .add_enabled(
+                    Path::new(&self.screenshots.folder).is_dir(),
+                    Button::new("Open"),
+                )
+                .clicked()
+            {
+                if let Err(e) = opener::open(&self.screenshots.folder) {
+                    self.status_msg
+                        .error_display(format!("failed to open screenshots folder: {e}"));
+                }
+            }
+        });
+    }
+
+    /// Requests a picture of the window when the shortcut is pressed and saves the plot part of
+    /// it once the picture arrives (A frame or more later)
+    pub(super) fn handle_screenshots(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|i| i.consume_shortcut(&Screenshots::SHORTCUT)) {
+            self.screenshots.is_requested = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+        if !self.screenshots.is_requested {
+            return;
+        }
+        let Some(image) = ctx.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        }) else {
+            return;
+        };
+        self.screenshots.is_requested = false;
+        let Some(rect) = self.screenshots.plot_rect else {
+            self.status_msg
+                .error_display("Screenshot not saved. The plot is not shown");
+            return;
+        };
+        match self.screenshots.save(&image, rect, ctx.pixels_per_point()) {
+            Ok(path) => self
+                .status_msg
+                .info(format!("Screenshot saved to {path:?}")),
+            Err(e) => self.status_msg.error_display(format!("{e:#}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+    use ecolor::Color32;
+
+    use super::*;
+
+    #[test]
+    fn saves_the_plot_part_of_the_screen() {
+        let time = chrono::Local
+            .with_ymd_and_hms(2024, 3, 9, 14, 5, 7)
+            .unwrap();
+        assert_eq!(Screenshots::file_name(time), "plot_20240309_140507_000.png");
+
+        let folder = tempfile::tempdir().unwrap();
+        let screenshots = Screenshots {
+            folder: folder.path().join("shots"),
+            ..Default::default()
+        };
+        let mut image = ColorImage::new([8, 6], Color32::BLACK);
+        image.pixels[2 * 8 + 2] = Color32::RED;
+        // Half size in points and partly off the screen
+        let rect = Rect::from_min_max(egui::pos2(1.0, 1.0), egui::pos2(10.0, 2.0));
+        let path = screenshots.save(&image, rect, 2.0).unwrap();
+        let saved = image::open(path).unwrap().to_rgba8();
+        assert_eq!(saved.dimensions(), (6, 2));
+        assert_eq!(saved.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    }
+}
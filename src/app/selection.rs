@@ -0,0 +1,63 @@
+use std::collections::BTreeSet;
+
+use super::data_definition::{Data, DataTimestamp};
+
+/// Indices of the points that are currently selected.
+/// The indices are only meaningful for the version of the data they were selected on so the
+/// selection is cleared if the data changes in a way the selection was not updated for.
+#[derive(Default, Debug, PartialEq)]
+pub struct Selection {
+    timestamp: Option<DataTimestamp>,
+    indices: BTreeSet<usize>,
+}
+
+impl Selection {
+    /// Clears the selection if the data has changed since it was made
+    pub fn sync(&mut self, data: &Data) {
+        if self.timestamp.is_some() && self.timestamp != Some(data.timestamp()) {
+            self.clear();
+        }
+    }
+
+    /// Marks the selection as still valid for the current version of the data.
+    /// Should only be used after changes that do not move or remove points
+    pub fn keep_for(&mut self, data: &Data) {
+        if !self.indices.is_empty() {
+            self.timestamp = Some(data.timestamp());
+        }
+    }
+
+    pub fn set(&mut self, data: &Data, indices: impl IntoIterator<Item = usize>) {
+        self.indices = indices.into_iter().collect();
+        self.timestamp = Some(data.timestamp());
+    }
+
+    pub fn toggle(&mut self, data: &Data, index: usize) {
+        self.sync(data);
+        if !self.indices.remove(&index) {
+            self.indices.insert(index);
+        }
+        self.timestamp = Some(data.timestamp());
+    }
+
+    pub fn clear(&mut self) {
+        self.indices.clear();
+        self.timestamp = None;
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.indices.contains(&index)
+    }
+
+    pub fn indices(&self) -> &BTreeSet<usize> {
+        &self.indices
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
@@ -0,0 +1,223 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::data_definition::DataLabel;
+
+impl DBV {
+    pub(super) fn ui_menu_duplicate_selection(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.selection.is_empty(), |ui| {
+            ui.menu_button("Duplicate Selection", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Offset");
+                    ui.add(egui::DragValue::new(&mut self.duplicate_offset[0]).prefix("x0: "));
+                    ui.add(egui::DragValue::new(&mut self.duplicate_offset[1]).prefix("x1: "));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Copies");
+                    ui.add(egui::DragValue::new(&mut self.duplicate_count).clamp_range(1..=100))
+                        .on_hover_text("Each copy is moved by the offset from the one before it");
+                });
+                ui.checkbox(
+                    &mut self.duplicate_should_flip_label,
+                    "Flip label of copies",
+                );
+                if ui
+                    .add(Button::new(format!(
+                        "Duplicate {} points",
+                        self.selection.len() * self.duplicate_count
+                    )))
+                    .on_hover_text(
+                        "The copies become the selection so they can be duplicated again",
+                    )
+                    .clicked()
+                {
+                    self.duplicate_selection();
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+
+    pub(super) fn ui_menu_relabel_selection(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.selection.is_empty(), |ui| {
+            ui.menu_button("Relabel Selection", |ui| {
+                let labels: Vec<DataLabel> = self.label_classes.labels().collect();
+                for label in labels {
+                    let name = self.label_classes.name(label);
+                    if ui.button(format!("Set to {name}")).clicked() {
+                        self.data
+                            .set_label(self.selection.indices().iter().copied(), label);
+                        self.selection.keep_for(&self.data);
+                        ui.close_menu();
+                    }
+                }
+                if ui.button("Flip labels").clicked() {
+                    let edits: Vec<_> = self
+                        .selection
+                        .indices()
+                        .iter()
+                        .map(|&i| {
+                            let mut point = self.data.points()[i];
+                            point.label = point.label.opposite();
+                            (i, point)
+                        })
+                        .collect();
+                    self.data.edit_many(edits);
+                    self.selection.keep_for(&self.data);
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+
+    pub(super) fn ui_menu_color_selection(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.selection.is_empty(), |ui| {
+            ui.menu_button("Color Selection", |ui| {
+                ui.horizontal(|ui| {
+                    ui.color_edit_button_srgba(&mut self.custom_point_color);
+                    if ui
+                        .button("Set Color")
+                        .on_hover_text(
+                            "Shown instead of the color of their label or classification",
+                        )
+                        .clicked()
+                    {
+                        self.data.set_color(
+                            self.selection.indices().iter().copied(),
+                            Some(self.custom_point_color),
+                        );
+                        self.selection.keep_for(&self.data);
+                        ui.close_menu();
+                    }
+                });
+                if ui.button("Clear Color").clicked() {
+                    self.data
+                        .set_color(self.selection.indices().iter().copied(), None);
+                    self.selection.keep_for(&self.data);
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+
+    pub(super) fn ui_btn_delete_selection(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(
+                !self.selection.is_empty(),
+                Button::new(format!("Delete Selection ({})", self.selection.len())),
+            )
+            .on_hover_text("Deletes the selected points as a single undoable change")
+            .clicked()
+        {
+            self.data
+                .delete_many(self.selection.indices().iter().copied());
+            self.selection.clear();
+            ui.close_menu();
+        }
+    }
+
+    pub(super) fn ui_btns_training_exclusion(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.selection.is_empty(), |ui| {
+            for (text, value) in [
+                ("Exclude Selection from Training", true),
+                ("Include Selection in Training", false),
+            ] {
+                if ui
+                    .button(text)
+                    .on_hover_text("Excluded points are still shown and scored")
+                    .clicked()
+                {
+                    self.data.set_excluded_from_training(
+                        self.selection.indices().iter().copied(),
+                        value,
+                    );
+                    self.selection.keep_for(&self.data);
+                    ui.close_menu();
+                }
+            }
+        });
+    }
+
+    pub(super) fn ui_btns_lock_selection(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.selection.is_empty(), |ui| {
+            for (text, value) in [("Lock Selection", true), ("Unlock Selection", false)] {
+                if ui
+                    .button(text)
+                    .on_hover_text("Locked points are skipped when deleting")
+                    .clicked()
+                {
+                    self.data
+                        .set_locked(self.selection.indices().iter().copied(), value);
+                    self.selection.keep_for(&self.data);
+                    ui.close_menu();
+                }
+            }
+        });
+    }
+
+    fn duplicate_selection(&mut self) {
+        let copies = self.data.duplicate(
+            self.selection.indices().iter().copied(),
+            self.duplicate_offset,
+            self.duplicate_should_flip_label,
+            self.duplicate_count,
+        );
+        self.selection.set(&self.data, copies);
+    }
+
+    /// Swaps normal and anomaly labels of the selection (or all points if nothing is selected)
+    pub(super) fn ui_btn_swap_labels(&mut self, ui: &mut egui::Ui) {
+        let is_selection = !self.selection.is_empty();
+        let text = if is_selection {
+            format!(
+                "Swap Normal/Anomaly in Selection ({})",
+                self.selection.len()
+            )
+        } else {
+            "Swap Normal/Anomaly for All Points".to_string()
+        };
+        if ui
+            .add_enabled(!self.data.is_empty(), Button::new(text))
+            .on_hover_text("Single undoable change (Other classes are not changed)")
+            .clicked()
+        {
+            let indices: Vec<usize> = if is_selection {
+                self.selection.indices().iter().copied().collect()
+            } else {
+                (0..self.data.points().len()).collect()
+            };
+            let edits: Vec<_> = indices
+                .into_iter()
+                .map(|i| {
+                    let mut point = self.data.points()[i];
+                    point.label = swapped_label(point.label);
+                    (i, point)
+                })
+                .collect();
+            self.data.edit_many(edits);
+            self.selection.keep_for(&self.data);
+            ui.close_menu();
+        }
+    }
+}
+
+fn swapped_label(label: DataLabel) -> DataLabel {
+    match label {
+        DataLabel::Normal => DataLabel::Anomaly,
+        DataLabel::Anomaly => DataLabel::Normal,
+        DataLabel::Class(_) => label,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_keeps_other_classes() {
+        assert_eq!(swapped_label(DataLabel::Normal), DataLabel::Anomaly);
+        assert_eq!(swapped_label(DataLabel::Anomaly), DataLabel::Normal);
+        assert_eq!(swapped_label(DataLabel::Class(3)), DataLabel::Class(3));
+    }
+}
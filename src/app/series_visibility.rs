@@ -0,0 +1,135 @@
+use std::collections::BTreeSet;
+
+use crate::DBV;
+
+use super::{
+    data_definition::DataLabel,
+    local_experiments::ModelInference,
+    prediction_classification::{prediction_classification, Classification},
+};
+
+/// A series of markers on the plot that a point belongs to
+#[derive(
+    serde::Deserialize, serde::Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug,
+)]
+pub enum PlotSeries {
+    /// Points shown by their label (No results)
+    Label(DataLabel),
+    Classification(Classification),
+    Borderline,
+}
+
+/// The series that are hidden on the plot (Hidden points are also not hovered or deleted)
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct SeriesVisibility {
+    hidden: BTreeSet<PlotSeries>,
+}
+
+impl SeriesVisibility {
+    pub fn is_visible(&self, series: PlotSeries) -> bool {
+        !self.hidden.contains(&series)
+    }
+
+    fn set_visible(&mut self, series: PlotSeries, is_visible: bool) {
+        if is_visible {
+            self.hidden.remove(&series);
+        } else {
+            self.hidden.insert(series);
+        }
+    }
+
+    /// Shows only the series given
+    fn isolate(&mut self, series: PlotSeries, all: &[PlotSeries]) {
+        self.hidden = all.iter().copied().filter(|x| *x != series).collect();
+    }
+}
+
+impl DBV {
+    /// The series the point is drawn in given the model whose results are shown (if any)
+    pub(super) fn point_series(
+        &self,
+        index: usize,
+        model: Option<&dyn ModelInference>,
+    ) -> PlotSeries {
+        match model {
+            Some(model) if model.is_borderline_on_training_data(index) => PlotSeries::Borderline,
+            Some(model) => PlotSeries::Classification(prediction_classification(
+                self.data.points()[index].label,
+                model.prediction_on_training_data(index),
+            )),
+            None => PlotSeries::Label(self.data.points()[index].label),
+        }
+    }
+
+    /// Returns `true` if the point is in a series that is not hidden
+    pub(super) fn is_point_shown(&self, index: usize) -> bool {
+        self.series_visibility
+            .is_visible(self.point_series(index, self.loc_inference_model()))
+    }
+
+    pub(super) fn ui_menu_series_visibility(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("Series", |ui| {
+            let all: Vec<(PlotSeries, String)> = if self.loc_inference_model().is_some() {
+                [
+                    Classification::TruePositive,
+                    Classification::FalsePositive,
+                    Classification::TrueNegative,
+                    Classification::FalseNegative,
+                ]
+                .into_iter()
+                .map(|x| (PlotSeries::Classification(x), x.to_string()))
+                .chain([(PlotSeries::Borderline, "Borderline".to_string())])
+                .collect()
+            } else {
+                self.label_classes
+                    .labels()
+                    .map(|x| (PlotSeries::Label(x), self.label_classes.name(x)))
+                    .collect()
+            };
+            let series: Vec<PlotSeries> = all.iter().map(|x| x.0).collect();
+            for (value, name) in all {
+                ui.horizontal(|ui| {
+                    let mut is_visible = self.series_visibility.is_visible(value);
+                    if ui.checkbox(&mut is_visible, name).changed() {
+                        self.series_visibility.set_visible(value, is_visible);
+                    }
+                    if ui
+                        .small_button("Only")
+                        .on_hover_text("Hides every other series")
+                        .clicked()
+                    {
+                        self.series_visibility.isolate(value, &series);
+                    }
+                });
+            }
+            ui.separator();
+            if ui.button("Show All").clicked() {
+                self.series_visibility = Default::default();
+                ui.close_menu();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isolate_hides_the_others() {
+        let all = [
+            PlotSeries::Label(DataLabel::Normal),
+            PlotSeries::Label(DataLabel::Anomaly),
+            PlotSeries::Classification(Classification::FalseNegative),
+        ];
+        let mut visibility = SeriesVisibility::default();
+        assert!(all.iter().all(|x| visibility.is_visible(*x)));
+        visibility.isolate(all[2], &all);
+        assert!(!visibility.is_visible(all[0]));
+        assert!(!visibility.is_visible(all[1]));
+        assert!(visibility.is_visible(all[2]));
+        visibility.set_visible(all[0], true);
+        assert!(visibility.is_visible(all[0]));
+    }
+}
@@ -0,0 +1,281 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use ecolor::Color32;
+
+use crate::DBV;
+
+use super::{
+    data_definition::DataLabel,
+    delete_picker::DeleteTieBreak,
+    execute, file_handle_to_path,
+    operational_state::{OperationOutcome, OperationalState, Payload},
+};
+
+/// The parts of the app state that are about how the user likes to work rather than the data,
+/// written to a TOML file so they can be copied to other machines.
+///
+/// Every field is optional so that files with only some of the settings can be imported
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug, PartialEq)]
+#[serde(default)]
+pub struct AppSettings {
+    marker_radius: Option<f32>,
+    display_decimal_places: Option<u8>,
+    color_normal: Option<Color32>,
+    color_anom: Option<Color32>,
+    color_results_false_negatives: Option<Color32>,
+    color_results_false_positives: Option<Color32>,
+    color_results_true_negatives: Option<Color32>,
+    color_results_true_positives: Option<Color32>,
+    color_results_borderline: Option<Color32>,
+    color_selection: Option<Color32>,
+    primary_click_label: Option<DataLabel>,
+    suggest_label_from_neighbors: Option<bool>,
+    label_suggestion_k: Option<usize>,
+    delete_tie_break: Option<DeleteTieBreak>,
+    delete_pick_radius: Option<f32>,
+    round_new_points: Option<bool>,
+    rounding_decimal_places: Option<u8>,
+    /// `false` means the history size is unlimited
+    limit_history: Option<bool>,
+    max_history: Option<u16>,
+    allow_boxed_zoom: Option<bool>,
+    show_plot_legend: Option<bool>,
+    show_plot_grid_lines: Option<bool>,
+    on_load_reset_plot_zoom: Option<bool>,
+}
+
+impl AppSettings {
+    pub const EXTENSION: &'static str = "toml";
+
+    fn from_app(app: &DBV) -> Self {
+        Self {
+            marker_radius: Some(app.marker_radius),
+            display_decimal_places: app.display_precision.decimal_places,
+            color_normal: Some(app.color_normal),
+            color_anom: Some(app.color_anom),
+            color_results_false_negatives: Some(app.color_results_false_negatives),
+            color_results_false_positives: Some(app.color_results_false_positives),
+            color_results_true_negatives: Some(app.color_results_true_negatives),
+            color_results_true_positives: Some(app.color_results_true_positives),
+            color_results_borderline: Some(app.color_results_borderline),
+            color_selection: Some(app.color_selection),
+            primary_click_label: Some(app.primary_click_label),
+            suggest_label_from_neighbors: Some(app.suggest_label_from_neighbors),
+            label_suggestion_k: Some(app.label_suggestion_k),
+            delete_tie_break: Some(app.delete_tie_break),
+            delete_pick_radius: Some(app.delete_pick_radius),
+            round_new_points: Some(app.data.is_rounding_enabled()),
+            rounding_decimal_places: app.data.rounding_decimal_places,
+            limit_history: Some(app.data.max_history_size().is_some()),
+            max_history: app.data.max_history_size(),
+            allow_boxed_zoom: Some(app.allow_boxed_zoom),
+            show_plot_legend: Some(app.show_plot_legend),
+            show_plot_grid_lines: Some(app.show_plot_grid_lines),
+            on_load_reset_plot_zoom: Some(app.on_load_reset_plot_zoom),
+        }
+    }
+
+    /// Sets the values that are present in the file
+    fn apply_to(self, app: &mut DBV) {
+        fn set<T>(target: &mut T, value: Option<T>) {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+        set(&mut app.marker_radius, self.marker_radius);
+        if self.display_decimal_places.is_some() {
+            app.display_precision.decimal_places = self.display_decimal_places;
+        }
+        set(&mut app.color_normal, self.color_normal);
+        set(&mut app.color_anom, self.color_anom);
+        set(
+            &mut app.color_results_false_negatives,
+            self.color_results_false_negatives,
+        );
+        set(
+            &mut app.color_results_false_positives,
+            self.color_results_false_positives,
+        );
+        set(
+            &mut app.color_results_true_negatives,
+            self.color_results_true_negatives,
+        );
+        set(
+            &mut app.color_results_true_positives,
+            self.color_results_true_positives,
+        );
+        set(
+            &mut app.color_results_borderline,
+            self.color_results_borderline,
+        );
+        set(&mut app.color_selection, self.color_selection);
+        set(&mut app.primary_click_label, self.primary_click_label);
+        set(
+            &mut app.suggest_label_from_neighbors,
+            self.suggest_label_from_neighbors,
+        );
+        set(&mut app.label_suggestion_k, self.label_suggestion_k);
+        set(&mut app.delete_tie_break, self.delete_tie_break);
+        set(&mut app.delete_pick_radius, self.delete_pick_radius);
+        if let Some(is_enabled) = self.round_new_points {
+            app.data.set_rounding_enabled(is_enabled);
+        }
+        if let Some(decimal_places) = self.rounding_decimal_places {
+            if app.data.is_rounding_enabled() {
+                *app.data.rounding_decimal_places_mut() = decimal_places;
+            }
+        }
+        match (self.limit_history, self.max_history) {
+            (Some(false), _) => app.data.set_history_size(None),
+            (Some(true) | None, Some(size)) => app.data.set_history_size(Some(size)),
+            (Some(true) | None, None) => {}
+        }
+        set(&mut app.allow_boxed_zoom, self.allow_boxed_zoom);
+        set(&mut app.show_plot_legend, self.show_plot_legend);
+        set(&mut app.show_plot_grid_lines, self.show_plot_grid_lines);
+        set(
+            &mut app.on_load_reset_plot_zoom,
+            self.on_load_reset_plot_zoom,
+        );
+    }
+
+    fn from_text(text: &[u8]) -> anyhow::Result<Self> {
+        let text = std::str::from_utf8(text).context("settings file is not valid UTF-8")?;
+        toml::from_str(text).context("failed to parse settings file")
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_settings_transfer(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(self.op_state.is_normal(), |ui| {
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Export Settings...")
+                    .on_hover_text("Saves colors, click behaviour, rounding and similar settings (Not the data)")
+                    .clicked()
+                {
+                    self.export_settings(ui.ctx().clone());
+                    ui.close_menu();
+                }
+                if ui.button("Import Settings...").clicked() {
+                    self.import_settings(ui.ctx().clone());
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+
+    pub(super) fn on_settings_imported(&mut self, settings: AppSettings, path: PathBuf) {
+        settings.apply_to(self);
+        self.status_msg
+            .info(format!("Settings imported from {path:?}"));
+    }
+
+    fn export_settings(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let text = match toml::to_string_pretty(&AppSettings::from_app(self))
+            .context("failed to serialize settings")
+        {
+            Ok(text) => text,
+            Err(e) => {
+                self.status_msg.error_debug(e);
+                return;
+            }
+        };
+        self.op_state = OperationalState::Saving(execute(async move {
+            let dialog = rfd::AsyncFileDialog::new()
+                .set_title("Export Settings as")
+                .add_filter("Settings", &[AppSettings::EXTENSION]);
+            #[cfg(target_arch = "wasm32")]
+            let dialog = dialog.set_file_name("dbv_settings.toml");
+            let Some(file) = dialog.save_file().await else {
+                // user canceled
+                ctx.request_repaint();
+                return OperationOutcome::Cancelled;
+            };
+            let path = file_handle_to_path(&file);
+            let result = match file
+                .write(text.as_bytes())
+                .await
+                .context("failed to export settings")
+            {
+                Ok(()) => OperationOutcome::Success(Payload::ExportSettings(path)),
+                Err(e) => OperationOutcome::Failed(e),
+            };
+
+            ctx.request_repaint();
+
+            result
+        }));
+    }
+
+    fn import_settings(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let promise = execute(async move {
+            let dialog = rfd::AsyncFileDialog::new()
+                .set_title("Import Settings")
+                .add_filter("Settings", &[AppSettings::EXTENSION]);
+            let Some(file) = dialog.pick_file().await else {
+                // user canceled
+                ctx.request_repaint();
+                return OperationOutcome::Cancelled;
+            };
+            let path = file_handle_to_path(&file);
+            let result = match AppSettings::from_text(&file.read().await)
+                .context("failed to import settings")
+            {
+                Ok(settings) => OperationOutcome::Success(Payload::ImportSettings {
+                    settings: Box::new(settings),
+                    path,
+                }),
+                Err(e) => OperationOutcome::Failed(e),
+            };
+
+            ctx.request_repaint();
+
+            result
+        });
+        self.op_state = OperationalState::Loading(promise, Default::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_round_trip_through_toml() {
+        let mut expected = DBV {
+            marker_radius: 3.5,
+            color_anom: Color32::from_rgb(1, 2, 3),
+            primary_click_label: DataLabel::Anomaly,
+            delete_tie_break: DeleteTieBreak::DeleteAll,
+            ..Default::default()
+        };
+        expected.data.set_rounding_enabled(true);
+        *expected.data.rounding_decimal_places_mut() = 2;
+        expected.data.set_history_size(None);
+
+        let text = toml::to_string_pretty(&AppSettings::from_app(&expected)).unwrap();
+        let mut actual = DBV::default();
+        AppSettings::from_text(text.as_bytes())
+            .unwrap()
+            .apply_to(&mut actual);
+        assert_eq!(
+            AppSettings::from_app(&actual),
+            AppSettings::from_app(&expected)
+        );
+    }
+
+    #[test]
+    fn missing_settings_are_unchanged() {
+        let mut actual = DBV::default();
+        AppSettings::from_text(b"marker_radius = 2.0")
+            .unwrap()
+            .apply_to(&mut actual);
+        let mut expected = AppSettings::from_app(&DBV::default());
+        expected.marker_radius = Some(2.0);
+        assert_eq!(AppSettings::from_app(&actual), expected);
+    }
+}
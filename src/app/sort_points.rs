@@ -0,0 +1,190 @@
+use std::cmp::Ordering;
+
+use egui::Button;
+
+use crate::DBV;
+
+use super::data_definition::DataPoint;
+
+/// A value the points can be ordered by
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SortField {
+    X0,
+    X1,
+    Label,
+    /// Score from the current model (Requires it to be trained on the current points)
+    Score,
+}
+
+impl SortField {
+    const ALL: [Self; 4] = [Self::X0, Self::X1, Self::Label, Self::Score];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::X0 => "x0",
+            Self::X1 => "x1",
+            Self::Label => "Label",
+            Self::Score => "Score",
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SortKey {
+    pub field: SortField,
+    pub is_descending: bool,
+}
+
+/// Keys the points are sorted by, later keys only break ties of earlier ones
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct SortPoints {
+    pub keys: Vec<SortKey>,
+}
+
+impl Default for SortPoints {
+    fn default() -> Self {
+        Self {
+            keys: vec![SortKey {
+                field: SortField::X0,
+                is_descending: false,
+            }],
+        }
+    }
+}
+
+impl SortPoints {
+    /// Limit to keep the menu short
+    const MAX_KEYS: usize = 4;
+
+    fn uses_scores(&self) -> bool {
+        self.keys.iter().any(|x| x.field == SortField::Score)
+    }
+
+    /// Indices of the points in their sorted order (Stable so equal points keep their order)
+    fn sorted_order(&self, points: &[DataPoint], scores: Option<&[f64]>) -> Vec<usize> {
+        let value = |i: usize, field: SortField| match field {
+            SortField::X0 => points[i].x0,
+            SortField::X1 => points[i].x1,
+            SortField::Label => f64::from(points[i].label.as_int()),
+            SortField::Score => scores.map_or(0.0, |x| x[i]),
+        };
+        let mut result: Vec<usize> = (0..points.len()).collect();
+        result.sort_by(|&a, &b| {
+            self.keys
+                .iter()
+                .map(|key| {
+                    let ordering = value(a, key.field).total_cmp(&value(b, key.field));
+                    if key.is_descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+                .find(|x| x.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        result
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_menu_sort_points(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Sort Points", |ui| {
+                let mut to_remove = None;
+                let key_count = self.sort_points.keys.len();
+                for (i, key) in self.sort_points.keys.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(if i == 0 { "By" } else { "Then by" });
+                        egui::ComboBox::from_id_source(format!("id-sort-key-{i}"))
+                            .selected_text(key.field.name())
+                            .show_ui(ui, |ui| {
+                                for field in SortField::ALL {
+                                    ui.selectable_value(&mut key.field, field, field.name());
+                                }
+                            });
+                        ui.checkbox(&mut key.is_descending, "Descending");
+                        if key_count > 1 && ui.small_button("🗑").clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = to_remove {
+                    self.sort_points.keys.remove(i);
+                }
+                if key_count < SortPoints::MAX_KEYS && ui.button("Add Key").clicked() {
+                    self.sort_points.keys.push(SortKey {
+                        field: SortField::X1,
+                        is_descending: false,
+                    });
+                }
+                let scores = self.current_scores();
+                let can_sort = !self.sort_points.uses_scores() || scores.is_some();
+                if ui
+                    .add_enabled(can_sort, Button::new("Sort"))
+                    .on_hover_text("Reorders the rows as a single undoable change")
+                    .on_disabled_hover_text(
+                        "Sorting by score requires a model trained on the current points",
+                    )
+                    .clicked()
+                {
+                    let order = self
+                        .sort_points
+                        .sorted_order(self.data.points(), scores.as_deref());
+                    let edits: Vec<_> = order
+                        .iter()
+                        .enumerate()
+                        .map(|(new_index, &old_index)| (new_index, self.data.points()[old_index]))
+                        .collect();
+                    // The selection follows the points to their new rows
+                    let selected: Vec<usize> = order
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, old_index)| self.selection.contains(**old_index))
+                        .map(|(new_index, _)| new_index)
+                        .collect();
+                    self.data.edit_many(edits);
+                    self.selection.set(&self.data, selected);
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn later_keys_break_ties() {
+        let points = [
+            DataPoint::new(1.0, 5.0, DataLabel::Normal),
+            DataPoint::new(0.0, 5.0, DataLabel::Anomaly),
+            DataPoint::new(1.0, 7.0, DataLabel::Anomaly),
+            DataPoint::new(1.0, 5.0, DataLabel::Anomaly),
+        ];
+        let mut sort = SortPoints::default();
+        assert_eq!(sort.sorted_order(&points, None), [1, 0, 2, 3]);
+        sort.keys.push(SortKey {
+            field: SortField::X1,
+            is_descending: true,
+        });
+        assert_eq!(sort.sorted_order(&points, None), [1, 2, 0, 3]);
+
+        let sort = SortPoints {
+            keys: vec![SortKey {
+                field: SortField::Score,
+                is_descending: true,
+            }],
+        };
+        assert!(sort.uses_scores());
+        assert_eq!(
+            sort.sorted_order(&points, Some(&[0.1, 0.9, 0.5, 0.9])),
+            [1, 3, 2, 0]
+        );
+    }
+}
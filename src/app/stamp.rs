@@ -0,0 +1,207 @@
+use egui_plot::{MarkerShape, PlotPoint, PlotUi, Points};
+
+use crate::DBV;
+
+use super::data_definition::{DataLabel, DataPoint, PointArray};
+
+/// Arrangement of points placed by one click in stamp mode
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum StampShape {
+    #[default]
+    Ring,
+    /// Points spread evenly over a filled circle
+    Disc,
+    /// Horizontal line segment rotated by the angle
+    Line,
+    /// Square patch of evenly spaced points
+    Grid,
+}
+
+impl StampShape {
+    const ALL: [Self; 4] = [Self::Ring, Self::Disc, Self::Line, Self::Grid];
+}
+
+/// Settings for placing a shape of points with each click in stamp mode
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(default)]
+pub struct Stamp {
+    pub shape: StampShape,
+    /// Half the width of the shape in plot units
+    pub radius: f64,
+    /// Number of points in the shape (Points per side for the grid)
+    pub count: u16,
+    /// Rotation of the line in degrees
+    pub angle: f64,
+}
+
+impl Default for Stamp {
+    fn default() -> Self {
+        Self {
+            shape: Default::default(),
+            radius: 1.0,
+            count: 12,
+            angle: 0.0,
+        }
+    }
+}
+
+impl Stamp {
+    /// Positions of the points of the shape centered at `center`
+    fn positions(&self, [c0, c1]: PointArray) -> Vec<PointArray> {
+        let count = usize::from(self.count.max(1));
+        let radius = self.radius;
+        let tau = std::f64::consts::TAU;
+        match self.shape {
+            StampShape::Ring => (0..count)
+                .map(|i| {
+                    let angle = tau * i as f64 / count as f64;
+                    [c0 + radius * angle.cos(), c1 + radius * angle.sin()]
+                })
+                .collect(),
+            StampShape::Disc => {
+                // Sunflower spiral spreads the points evenly without needing randomness
+                let golden_angle = std::f64::consts::PI * (3.0 - 5f64.sqrt());
+                (0..count)
+                    .map(|i| {
+                        let distance = radius * ((i as f64 + 0.5) / count as f64).sqrt();
+                        let angle = golden_angle * i as f64;
+                        [c0 + distance * angle.cos(), c1 + distance * angle.sin()]
+                    })
+                    .collect()
+            }
+            StampShape::Line => {
+                let (sin, cos) = self.angle.to_radians().sin_cos();
+                (0..count)
+                    .map(|i| {
+                        let t = if count == 1 {
+                            0.0
+                        } else {
+                            radius * (2.0 * i as f64 / (count - 1) as f64 - 1.0)
+                        };
+                        [c0 + t * cos, c1 + t * sin]
+                    })
+                    .collect()
+            }
+            StampShape::Grid => {
+                let offset = |i: usize| {
+                    if count == 1 {
+                        0.0
+                    } else {
+                        radius * (2.0 * i as f64 / (count - 1) as f64 - 1.0)
+                    }
+                };
+                (0..count)
+                    .flat_map(|row| (0..count).map(move |col| (row, col)))
+                    .map(|(row, col)| [c0 + offset(col), c1 + offset(row)])
+                    .collect()
+            }
+        }
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_stamp_options(&mut self, ui: &mut egui::Ui) {
+        let stamp = &mut self.stamp;
+        egui::ComboBox::from_id_source("stamp shape")
+            .selected_text(format!("{:?}", stamp.shape))
+            .show_ui(ui, |ui| {
+                for shape in StampShape::ALL {
+                    ui.selectable_value(&mut stamp.shape, shape, format!("{shape:?}"));
+                }
+            });
+        ui.add(
+            egui::DragValue::new(&mut stamp.radius)
+                .speed(0.05)
+                .clamp_range(0.0..=f64::INFINITY)
+                .prefix("Radius: "),
+        );
+        let (count_text, max_count) = if stamp.shape == StampShape::Grid {
+            ("Per side: ", 100)
+        } else {
+            ("Count: ", 1000)
+        };
+        ui.add(
+            egui::DragValue::new(&mut stamp.count)
+                .clamp_range(1..=max_count)
+                .prefix(count_text),
+        );
+        if stamp.shape == StampShape::Line {
+            ui.add(
+                egui::DragValue::new(&mut stamp.angle)
+                    .clamp_range(-180.0..=180.0)
+                    .suffix("°")
+                    .prefix("Angle: "),
+            );
+        }
+    }
+
+    /// Adds the points of the stamp centered where clicked as a single undoable change
+    pub(super) fn stamp_on_click(
+        &mut self,
+        pointer_coordinate: Option<PlotPoint>,
+        label: DataLabel,
+    ) {
+        let Some(pos) = pointer_coordinate else {
+            self.status_msg
+                .error_display("Unable to stamp. Cursor not detected over the plot");
+            return;
+        };
+        let points = self
+            .stamp
+            .positions([pos.x, pos.y])
+            .into_iter()
+            .map(|[x0, x1]| DataPoint::new(x0, x1, label))
+            .collect();
+        self.data.add_many_in_bounds(points, &mut self.status_msg);
+    }
+
+    /// Shows where the stamp would place points
+    pub(super) fn plot_stamp_preview(&self, plot_ui: &mut PlotUi) {
+        if !self.click_mode.is_stamp() || !plot_ui.response().hovered() {
+            return;
+        }
+        let Some(pointer) = plot_ui.pointer_coordinate() else {
+            return;
+        };
+        plot_ui.points(
+            Points::new(self.stamp.positions([pointer.x, pointer.y]))
+                .name("Stamp")
+                .shape(MarkerShape::Circle)
+                .filled(false)
+                .radius(self.marker_radius)
+                .color(self.color_selection.gamma_multiply(0.6)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_stay_within_radius() {
+        let center = [1.0, -2.0];
+        for shape in StampShape::ALL {
+            let stamp = Stamp {
+                shape,
+                radius: 2.0,
+                count: 5,
+                angle: 30.0,
+            };
+            let positions = stamp.positions(center);
+            let expected_count = if shape == StampShape::Grid { 25 } else { 5 };
+            assert_eq!(positions.len(), expected_count, "{shape:?}");
+            for [x0, x1] in positions {
+                // Grid corners are the farthest at radius times root 2
+                let distance = (x0 - center[0]).hypot(x1 - center[1]);
+                assert!(
+                    distance <= 2.0 * std::f64::consts::SQRT_2 + 1e-9,
+                    "{shape:?}"
+                );
+                if shape == StampShape::Ring {
+                    assert!((distance - 2.0).abs() < 1e-9);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use egui::Button;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoints},
+    data_generation::{PolarGenerator, PolarShape},
+    local_experiments::LocalExperiment,
+    plot_zoom_reset::ZoomTarget,
+};
+
+type Example = (&'static str, fn() -> DataPoints);
+
+/// Small datasets that can be used to try out the app
+const EXAMPLES: [Example; 3] = [
+    (
+        "Ring with anomalies in the center",
+        ring_with_center_anomalies,
+    ),
+    (
+        "Spiral with scattered anomalies",
+        spiral_with_scattered_anomalies,
+    ),
+    ("Two clusters", two_clusters),
+];
+
+fn ring_with_center_anomalies() -> DataPoints {
+    let mut result = PolarGenerator::default().generate();
+    result.extend(
+        PolarGenerator {
+            shape: PolarShape::Sector,
+            count: 10,
+            radius: 3.0,
+            radial_noise: 0.0,
+            label: DataLabel::Anomaly,
+            ..Default::default()
+        }
+        .generate(),
+    );
+    result
+}
+
+fn spiral_with_scattered_anomalies() -> DataPoints {
+    let mut result = PolarGenerator {
+        shape: PolarShape::Spiral,
+        count: 150,
+        inner_radius: 1.0,
+        radial_noise: 0.2,
+        ..Default::default()
+    }
+    .generate();
+    result.extend(
+        PolarGenerator {
+            shape: PolarShape::Sector,
+            count: 15,
+            radius: 12.0,
+            radial_noise: 0.0,
+            label: DataLabel::Anomaly,
+            seed: 1,
+            ..Default::default()
+        }
+        .generate(),
+    );
+    result
+}
+
+fn two_clusters() -> DataPoints {
+    let cluster = |center, seed| PolarGenerator {
+        shape: PolarShape::Sector,
+        center,
+        count: 60,
+        radius: 3.0,
+        radial_noise: 0.0,
+        seed,
+        ..Default::default()
+    };
+    let mut result = cluster([-6.0, 0.0], 0).generate();
+    result.extend(cluster([6.0, 2.0], 1).generate());
+    result.extend(
+        PolarGenerator {
+            label: DataLabel::Anomaly,
+            count: 5,
+            ..cluster([0.0, 8.0], 2)
+        }
+        .generate(),
+    );
+    result
+}
+
+impl DBV {
+    pub(super) fn ui_startup_chooser(&mut self, ctx: &egui::Context) {
+        if !self.is_startup_chooser_open {
+            return;
+        }
+        let mut should_close = false;
+        egui::Window::new("Welcome")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                if ui
+                    .add(Button::new(format!(
+                        "Continue where you left off ({} points)",
+                        self.data.points().len()
+                    )))
+                    .clicked()
+                {
+                    should_close = true;
+                }
+                if ui
+                    .add(Button::new("Blank canvas"))
+                    .on_hover_text(
+                        "Clears the points and the model (Clearing the points can be undone)",
+                    )
+                    .clicked()
+                {
+                    self.data.clear_points();
+                    self.loc_experiment = LocalExperiment::None;
+                    self.selection.clear();
+                    should_close = true;
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if !self.recent_projects.is_empty() {
+                    ui.separator();
+                    ui.strong("Recent projects");
+                    let mut selected = None;
+                    for path in self.recent_projects.iter() {
+                        let name = path
+                            .file_name()
+                            .map(|x| x.to_string_lossy())
+                            .unwrap_or_default();
+                        if ui
+                            .add_enabled(self.op_state.is_normal(), Button::new(name))
+                            .on_hover_text(path.to_string_lossy())
+                            .clicked()
+                        {
+                            selected = Some(path.clone());
+                        }
+                    }
+                    if let Some(path) = selected {
+                        self.load_project_from_path(path, ctx.clone());
+                        should_close = true;
+                    }
+                }
+
+                ui.separator();
+                ui.strong("Examples");
+                for (name, generate) in EXAMPLES {
+                    if ui
+                        .button(name)
+                        .on_hover_text("Replaces the points (Can be undone like any other load)")
+                        .clicked()
+                    {
+                        self.data.replace_with_loaded_data(generate());
+                        self.loc_experiment = LocalExperiment::None;
+                        self.selection.clear();
+                        self.start_zoom_reset(ZoomTarget::AllPoints);
+                        should_close = true;
+                    }
+                }
+
+                ui.separator();
+                ui.checkbox(&mut self.show_startup_chooser, "Show at startup")
+                    .on_hover_text("When off, the app continues where it left off");
+            });
+        if should_close {
+            self.is_startup_chooser_open = false;
+        }
+    }
+}
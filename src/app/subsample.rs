@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+
+use egui::Button;
+use rand::seq::SliceRandom as _;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoint},
+    data_generation::rng_from_seed,
+    ui_blocks::ui_seed,
+};
+
+/// How many points are kept
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum SubsampleSize {
+    #[default]
+    Count,
+    Percent,
+}
+
+/// Settings for randomly keeping part of the points
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone)]
+#[serde(default)]
+pub struct Subsample {
+    pub size: SubsampleSize,
+    pub count: usize,
+    pub percent: f64,
+    /// Keep the same share of each label
+    pub is_stratified: bool,
+}
+
+impl Default for Subsample {
+    fn default() -> Self {
+        Self {
+            size: Default::default(),
+            count: 100,
+            percent: 50.0,
+            is_stratified: true,
+        }
+    }
+}
+
+impl Subsample {
+    /// Number of points to keep out of `total`
+    fn target(&self, total: usize) -> usize {
+        match self.size {
+            SubsampleSize::Count => self.count.min(total),
+            SubsampleSize::Percent => ((total as f64 * self.percent.clamp(0.0, 100.0) / 100.0)
+                .round() as usize)
+                .min(total),
+        }
+    }
+
+    /// Indices of the points that are not kept (Sorted)
+    pub(super) fn indices_to_remove(&self, points: &[DataPoint], seed: u64) -> Vec<usize> {
+        let mut rng = rng_from_seed(seed);
+        let target = self.target(points.len());
+        let groups: Vec<Vec<usize>> = if self.is_stratified {
+            let mut by_label: BTreeMap<DataLabel, Vec<usize>> = BTreeMap::new();
+            for (i, point) in points.iter().enumerate() {
+                by_label.entry(point.label).or_default().push(i);
+            }
+            by_label.into_values().collect()
+        } else {
+            vec![(0..points.len()).collect()]
+        };
+        let sizes: Vec<usize> = groups.iter().map(Vec::len).collect();
+        let mut result = vec![];
+        for (mut group, keep) in groups.into_iter().zip(shares(&sizes, target)) {
+            group.shuffle(&mut rng);
+            result.extend_from_slice(&group[keep..]);
+        }
+        result.sort_unstable();
+        result
+    }
+}
+
+/// Splits `target` between the groups in proportion to their sizes so the shares add up to
+/// `target` (Largest remainder method, ties go to the earlier group)
+fn shares(sizes: &[usize], target: usize) -> Vec<usize> {
+    let total: usize = sizes.iter().sum();
+    if total == 0 {
+        return vec![0; sizes.len()];
+    }
+    let quota = |size: usize| (size as u128 * target as u128, total as u128);
+    let mut result: Vec<usize> = sizes
+        .iter()
+        .map(|&size| {
+            let (numerator, denominator) = quota(size);
+            (numerator / denominator) as usize
+        })
+        .collect();
+    let mut by_remainder: Vec<usize> = (0..sizes.len()).collect();
+    by_remainder.sort_by_key(|&i| {
+        let (numerator, denominator) = quota(sizes[i]);
+        std::cmp::Reverse(numerator % denominator)
+    });
+    let leftover = target - result.iter().sum::<usize>();
+    for &i in &by_remainder[..leftover] {
+        result[i] += 1;
+    }
+    result
+}
+
+impl DBV {
+    pub(super) fn ui_menu_subsample(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Random Subsample", |ui| {
+                let total = self.data.points().len();
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.subsample.size, SubsampleSize::Count, "Keep");
+                    ui.add(egui::DragValue::new(&mut self.subsample.count).clamp_range(0..=total));
+                    ui.label("points");
+                });
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.subsample.size, SubsampleSize::Percent, "Keep");
+                    ui.add(
+                        egui::DragValue::new(&mut self.subsample.percent)
+                            .clamp_range(0.0..=100.0)
+                            .suffix("%"),
+                    );
+                });
+                ui.checkbox(&mut self.subsample.is_stratified, "Stratified by label")
+                    .on_hover_text("Each label keeps the same share of its points");
+                ui.horizontal(|ui| {
+                    ui_seed(ui, &mut self.data.settings.seed);
+                });
+                if ui
+                    .add(Button::new(format!(
+                        "Keep {} of {total} points",
+                        self.subsample.target(total)
+                    )))
+                    .on_hover_text(
+                        "Deletes the rest as a single undoable change (Locked points are kept)",
+                    )
+                    .clicked()
+                {
+                    let to_remove = self
+                        .subsample
+                        .indices_to_remove(self.data.points(), self.data.settings.seed);
+                    self.data.delete_many(to_remove);
+                    self.selection.keep_for(&self.data);
+                    self.status_msg.info(format!(
+                        "Kept {} of {total} points",
+                        self.data.points().len()
+                    ));
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stratified_keeps_label_shares() {
+        let points: Vec<DataPoint> = (0..20)
+            .map(|i| {
+                let label = if i < 5 {
+                    DataLabel::Anomaly
+                } else {
+                    DataLabel::Normal
+                };
+                DataPoint::new(i as f64, 0.0, label)
+            })
+            .collect();
+        let subsample = Subsample {
+            size: SubsampleSize::Percent,
+            percent: 40.0,
+            ..Default::default()
+        };
+        let removed = subsample.indices_to_remove(&points, 0);
+        assert_eq!(removed.len(), 12);
+        assert_eq!(removed.iter().filter(|&&i| i < 5).count(), 3);
+        assert_eq!(removed, subsample.indices_to_remove(&points, 0));
+        assert!(removed.windows(2).all(|x| x[0] < x[1]));
+    }
+
+    #[test]
+    fn stratified_keeps_exactly_the_target_with_three_labels() {
+        // Rounding each label's share of 5 / 3 on its own would keep 6 points
+        let points: Vec<DataPoint> = (0..9)
+            .map(|i| DataPoint::new(i as f64, 0.0, DataLabel::from(i as u8 / 3)))
+            .collect();
+        let subsample = Subsample {
+            size: SubsampleSize::Count,
+            count: 5,
+            ..Default::default()
+        };
+        let removed = subsample.indices_to_remove(&points, 0);
+        assert_eq!(points.len() - removed.len(), 5);
+        for label in 0..3 {
+            let kept = (label * 3..label * 3 + 3)
+                .filter(|i| !removed.contains(i))
+                .count();
+            assert!((1..=2).contains(&kept), "label {label} kept {kept}");
+        }
+        assert_eq!(shares(&[3, 3, 3], 5), [2, 2, 1]);
+        assert_eq!(shares(&[1, 10, 89], 10), [0, 1, 9]);
+    }
+}
@@ -0,0 +1,52 @@
+use egui::Key;
+use egui_plot::PlotPoint;
+
+use crate::DBV;
+
+use super::data_definition::DataLabel;
+
+/// Values typed into the row above the table for appending a point
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct TableAddRow {
+    pub coordinates: [f64; 2],
+    pub label: DataLabel,
+}
+
+impl Default for TableAddRow {
+    fn default() -> Self {
+        Self {
+            coordinates: [0.0, 0.0],
+            label: DataLabel::Normal,
+        }
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_table_add_row(&mut self, ui: &mut egui::Ui) {
+        let mut should_add = false;
+        ui.horizontal(|ui| {
+            ui.strong("New point");
+            let add_row = &mut self.table_add_row;
+            for (i, value) in add_row.coordinates.iter_mut().enumerate() {
+                ui.label(format!("x{i}"));
+                let response = ui.add(egui::DragValue::new(value).speed(0.01));
+                should_add |= response.lost_focus() && ui.input(|x| x.key_pressed(Key::Enter));
+            }
+            self.label_classes
+                .ui_combo(ui, "id-table-add-row-label", &mut add_row.label);
+            should_add |= ui
+                .button("Append")
+                .on_hover_text("Adds the point as the last row (Press Enter in a field to add)")
+                .clicked();
+        });
+        if should_add {
+            let [x0, x1] = self.table_add_row.coordinates;
+            self.data.add(
+                Some(PlotPoint::new(x0, x1)),
+                self.table_add_row.label,
+                &mut self.status_msg,
+            );
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::Write as _;
+
+use egui::{Button, Sense};
+use log::debug;
+
+use crate::DBV;
+
+use super::{
+    prediction_classification::prediction_classification,
+    table_columns::{TableColumn, TableSortColumn},
+    DuringEditPoint,
+};
+
+/// Drag value for editing a coordinate (Steps by the rounding precision if there is one)
+fn edit_num<Num: egui::emath::Numeric>(
+    ui: &mut egui::Ui,
+    value: &mut Num,
+    rounding_decimal_places: Option<u8>,
+) -> egui::Response {
+    let mut drag_value = egui::DragValue::new(value);
+    if let Some(precision) = rounding_decimal_places {
+        drag_value = drag_value.speed(0.1f64.powi(precision as i32));
+    }
+    ui.add(drag_value)
+}
+
+impl DBV {
+    /// Label for a number in a cell (Written into the reused cell text buffer)
+    fn ui_number_cell(&mut self, ui: &mut egui::Ui, value: f64) {
+        let text = &mut self.table_columns.cell_text;
+        self.display_precision.format_into(text, value);
+        ui.label(text.as_str());
+    }
+
+    pub(super) fn ui_table_header(&mut self, ui: &mut egui::Ui, column: TableColumn) {
+        match column {
+            TableColumn::Score => {
+                self.ui_sortable_header(ui, column.name(), TableSortColumn::Score)
+            }
+            TableColumn::NearestDistance => {
+                self.ui_sortable_header(ui, column.name(), TableSortColumn::NearestDistance)
+            }
+            TableColumn::Color => {
+                ui.strong(column.name())
+                    .on_hover_text("Shown instead of the color of the label or classification");
+            }
+            TableColumn::Train => {
+                ui.strong(column.name())
+                    .on_hover_text("Unchecked points are held out from training but still scored");
+            }
+            TableColumn::Lock => {
+                ui.strong(column.name())
+                    .on_hover_text("Locked points are skipped when deleting");
+            }
+            _ => {
+                ui.strong(column.name());
+            }
+        }
+    }
+
+    /// Contents of one cell (Editable for the row being edited)
+    pub(super) fn ui_table_cell(
+        &mut self,
+        ui: &mut egui::Ui,
+        column: TableColumn,
+        row_index: usize,
+        text_height: f32,
+        nearest_distances: Option<&[Option<f64>]>,
+    ) {
+        // The row can be gone if it was deleted by an earlier cell
+        let Some(&point) = self.data.points().get(row_index) else {
+            return;
+        };
+        if self.is_table_cursor(row_index, column) {
+            ui.painter()
+                .rect_stroke(ui.max_rect(), 2.0, ui.visuals().selection.stroke);
+        }
+        let rounding_decimal_places = self.data.rounding_decimal_places;
+        let edit = self
+            .edit_point
+            .as_mut()
+            .filter(|x| x.index == row_index)
+            .map(|x| &mut x.point);
+        let mut focus_response = None;
+        match (column, edit) {
+            (TableColumn::Selected, _) => {
+                let mut is_selected = self.selection.contains(row_index);
+                if ui.checkbox(&mut is_selected, "").changed() {
+                    self.selection.toggle(&self.data, row_index);
+                }
+            }
+            (TableColumn::RowNumber, _) => {
+                let text = &mut self.table_columns.cell_text;
+                text.clear();
+                write!(text, "{row_index}").expect("writing to a String does not fail");
+                ui.label(text.as_str());
+            }
+            (TableColumn::X0, Some(edit)) => {
+                focus_response = Some(edit_num(ui, &mut edit.x0, rounding_decimal_places));
+            }
+            (TableColumn::X1, Some(edit)) => {
+                focus_response = Some(edit_num(ui, &mut edit.x1, rounding_decimal_places));
+            }
+            (TableColumn::Label, Some(edit)) => {
+                focus_response = Some(self.label_classes.ui_combo(
+                    ui,
+                    "id-table-cell-label",
+                    &mut edit.label,
+                ));
+            }
+            (TableColumn::Group, Some(edit)) => {
+                egui::ComboBox::new("id-table-cell-group", "")
+                    .selected_text(self.data.group_name(edit.group))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut edit.group, None, "None");
+                        for (id, group) in self.data.groups.iter() {
+                            ui.selectable_value(&mut edit.group, Some(id), &group.name);
+                        }
+                    });
+            }
+            (TableColumn::Color, Some(edit)) => {
+                let mut is_custom = edit.color.is_some();
+                ui.checkbox(&mut is_custom, "");
+                match (is_custom, edit.color.as_mut()) {
+                    (true, Some(color)) => {
+                        ui.color_edit_button_srgba(color);
+                    }
+                    (true, None) => edit.color = Some(self.color_selection),
+                    (false, _) => edit.color = None,
+                }
+            }
+            (TableColumn::X0, None) => {
+                self.ui_number_cell(ui, point.x0);
+            }
+            (TableColumn::X1, None) => {
+                self.ui_number_cell(ui, point.x1);
+            }
+            (TableColumn::Label, None) => {
+                ui.label(point.label.short_name());
+            }
+            (TableColumn::Group, None) => {
+                ui.label(self.data.group_name(point.group));
+            }
+            (TableColumn::Color, None) => {
+                if let Some(color) = point.color {
+                    let (rect, _) = ui
+                        .allocate_exact_size(egui::Vec2::splat(text_height * 0.8), Sense::hover());
+                    ui.painter().rect_filled(rect, 2.0, color);
+                }
+            }
+            (TableColumn::Train, _) => {
+                let mut is_training = !point.is_excluded_from_training;
+                if ui.checkbox(&mut is_training, "").changed() {
+                    self.data
+                        .set_excluded_from_training([row_index], !is_training);
+                }
+            }
+            (TableColumn::Lock, _) => {
+                let mut is_locked = point.is_locked;
+                if ui.checkbox(&mut is_locked, "").changed() {
+                    self.data.set_locked([row_index], is_locked);
+                }
+            }
+            (TableColumn::Actions, Some(edit)) => {
+                let edited = *edit;
+                if ui.button("Save").clicked() {
+                    self.data.edit(row_index, edited);
+                    self.edit_point = None;
+                }
+                if ui.button("Cancel").clicked() {
+                    self.edit_point = None;
+                }
+            }
+            (TableColumn::Actions, None) => {
+                if self.edit_point.is_some() {
+                    // No buttons if not on the row being edited
+                } else if ui.button("Edit").clicked() {
+                    self.edit_point = Some(DuringEditPoint {
+                        index: row_index,
+                        point,
+                    });
+                } else if ui
+                    .add_enabled(!point.is_locked, Button::new("Delete"))
+                    .on_disabled_hover_text("Locked")
+                    .clicked()
+                {
+                    debug!("Delete clicked on row_index: {row_index}");
+                    self.data.delete_by_index(row_index);
+                }
+            }
+            (TableColumn::Prediction | TableColumn::Classification | TableColumn::Score, _) => {
+                let Some(model) = self.loc_inference_model() else {
+                    return;
+                };
+                match column {
+                    TableColumn::Prediction => {
+                        ui.label(model.prediction_on_training_data(row_index).short_name());
+                    }
+                    TableColumn::Classification => {
+                        let predicted = model.prediction_on_training_data(row_index);
+                        ui.label(prediction_classification(point.label, predicted).as_str());
+                    }
+                    _ => {
+                        let score = model.score_for_training_data(row_index);
+                        self.ui_number_cell(ui, score);
+                    }
+                }
+            }
+            (TableColumn::NearestDistance, _) => {
+                match nearest_distances.and_then(|x| x.get(row_index).copied().flatten()) {
+                    Some(distance) => self.ui_number_cell(ui, distance),
+                    None => {
+                        ui.label("-");
+                    }
+                }
+            }
+        }
+        if let Some(response) = focus_response {
+            self.table_edit_focus(&response, column);
+        }
+    }
+}
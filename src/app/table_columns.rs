@@ -0,0 +1,562 @@
+use std::{cmp::Ordering, ops::Range, sync::Arc};
+
+use poll_promise::Promise;
+
+use crate::DBV;
+
+use super::data_definition::{DataPoint, DataTimestamp, DistanceCalculation as _};
+
+/// A column of the table
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TableColumn {
+    Selected,
+    RowNumber,
+    X0,
+    X1,
+    Label,
+    Group,
+    Color,
+    Train,
+    Lock,
+    /// Buttons for editing and deleting the row
+    Actions,
+    Prediction,
+    Classification,
+    Score,
+    NearestDistance,
+}
+
+impl TableColumn {
+    const ALL: [Self; 14] = [
+        Self::Selected,
+        Self::RowNumber,
+        Self::X0,
+        Self::X1,
+        Self::Label,
+        Self::Group,
+        Self::Color,
+        Self::Train,
+        Self::Lock,
+        Self::Actions,
+        Self::Prediction,
+        Self::Classification,
+        Self::Score,
+        Self::NearestDistance,
+    ];
+
+    /// Text of the header
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Selected => "sel",
+            Self::RowNumber => "row #",
+            Self::X0 => "x0",
+            Self::X1 => "x1",
+            Self::Label => "label",
+            Self::Group => "group",
+            Self::Color => "color",
+            Self::Train => "train",
+            Self::Lock => "lock",
+            Self::Actions => "",
+            Self::Prediction => "prediction",
+            Self::Classification => "classification",
+            Self::Score => "score",
+            Self::NearestDistance => "nn distance",
+        }
+    }
+
+    /// Name in the columns menu (The actions column has no header)
+    fn menu_name(&self) -> &'static str {
+        match self {
+            Self::Actions => "edit / delete",
+            _ => self.name(),
+        }
+    }
+
+    /// Only shown while a model trained on the data is available
+    fn needs_model(&self) -> bool {
+        matches!(self, Self::Prediction | Self::Classification | Self::Score)
+    }
+}
+
+/// Whether a column is shown (The order of the settings is the order of the columns)
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ColumnSetting {
+    pub column: TableColumn,
+    pub is_visible: bool,
+}
+
+/// A computed column the table rows can be ordered by
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TableSortColumn {
+    /// Score from the current model (Unsorted if there is no model)
+    Score,
+    NearestDistance,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct TableSort {
+    pub column: TableSortColumn,
+    pub is_descending: bool,
+}
+
+/// What the cached row order was calculated for
+#[derive(PartialEq, Clone, Copy, Debug)]
+struct RowOrderKey {
+    sort: TableSort,
+    data_timestamp: DataTimestamp,
+    /// Data the model was trained on (`None` if there is no model)
+    model_timestamp: Option<DataTimestamp>,
+}
+
+/// Extra columns of the table and the order rows are shown in (The points are not moved)
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct TableColumns {
+    /// Order and visibility of the columns
+    pub layout: Vec<ColumnSetting>,
+    pub sort: Option<TableSort>,
+    /// Only one page of rows is shown at a time
+    pub is_paginated: bool,
+    pub page_size: usize,
+    #[serde(skip)]
+    pub page: usize,
+    #[serde(skip)]
+    nearest_distances: NearestDistanceCache,
+    /// Reused for the text of number cells so drawing them does not allocate
+    #[serde(skip)]
+    pub(super) cell_text: String,
+    /// Sorting is only redone when the data, the model or the sort changes
+    #[serde(skip)]
+    row_order: Option<(RowOrderKey, Arc<[usize]>)>,
+}
+
+impl Default for TableColumns {
+    fn default() -> Self {
+        Self {
+            layout: Self::default_layout(),
+            sort: None,
+            is_paginated: false,
+            page_size: 1_000,
+            page: 0,
+            nearest_distances: Default::default(),
+            cell_text: String::new(),
+            row_order: None,
+        }
+    }
+}
+
+/// Nearest distances for the version of the data they were calculated for (Done in the background)
+#[derive(Default)]
+struct NearestDistanceCache {
+    distances: Option<TimestampedDistances>,
+    pending: Option<Promise<TimestampedDistances>>,
+}
+
+/// Nearest distance of each point along with the data they were calculated for
+type TimestampedDistances = (DataTimestamp, Arc<[Option<f64>]>);
+
+impl PartialEq for NearestDistanceCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.distances.as_ref().map(|(timestamp, _)| *timestamp)
+            == other.distances.as_ref().map(|(timestamp, _)| *timestamp)
+    }
+}
+
+impl std::fmt::Debug for NearestDistanceCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NearestDistanceCache")
+            .field("timestamp", &self.distances.as_ref().map(|(x, _)| x))
+            .field("is_pending", &self.pending.is_some())
+            .finish()
+    }
+}
+
+/// Distance from each point to its nearest other point (`None` if it is the only point)
+fn nearest_distances(points: &[DataPoint]) -> Vec<Option<f64>> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| point.distance_to(other.to_array()))
+                .min_by(f64::total_cmp)
+        })
+        .collect()
+}
+
+/// Indices of the rows ordered by the values (Stable and rows without a value go last)
+fn sorted_order(values: &[Option<f64>], is_descending: bool) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| match (values[a], values[b]) {
+        (Some(a), Some(b)) if is_descending => b.total_cmp(&a),
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    order
+}
+
+impl TableColumns {
+    /// Above this the nearest distances are not calculated because it takes too long
+    pub const NEAREST_DISTANCE_MAX_POINTS: usize = 20_000;
+
+    /// Marker added to the header of the column the rows are sorted by
+    pub fn sort_marker(&self, column: TableSortColumn) -> &'static str {
+        match self.sort {
+            Some(sort) if sort.column == column && sort.is_descending => " ⬇",
+            Some(sort) if sort.column == column => " ⬆",
+            _ => "",
+        }
+    }
+
+    /// Cycles the column through ascending, descending and not sorted
+    pub fn toggle_sort(&mut self, column: TableSortColumn) {
+        self.sort = match self.sort {
+            Some(sort) if sort.column == column && sort.is_descending => None,
+            Some(sort) if sort.column == column => Some(TableSort {
+                column,
+                is_descending: true,
+            }),
+            _ => Some(TableSort {
+                column,
+                is_descending: false,
+            }),
+        };
+    }
+
+    /// Every column in the original order with only the nearest distance hidden
+    fn default_layout() -> Vec<ColumnSetting> {
+        TableColumn::ALL
+            .into_iter()
+            .map(|column| ColumnSetting {
+                column,
+                is_visible: column != TableColumn::NearestDistance,
+            })
+            .collect()
+    }
+
+    fn is_visible(&self, column: TableColumn) -> bool {
+        !self
+            .layout
+            .iter()
+            .any(|x| x.column == column && !x.is_visible)
+    }
+
+    /// Adds any columns missing from a layout saved by an older version (at the end)
+    fn fill_layout(&mut self) {
+        for column in TableColumn::ALL {
+            if !self.layout.iter().any(|x| x.column == column) {
+                self.layout.push(ColumnSetting {
+                    column,
+                    is_visible: true,
+                });
+            }
+        }
+    }
+
+    /// The columns shown in order (Result columns only if there is a model)
+    pub fn shown_columns(
+        &self,
+        has_model: bool,
+        can_show_nearest_distances: bool,
+    ) -> Vec<TableColumn> {
+        self.layout
+            .iter()
+            .filter(|x| x.is_visible)
+            .map(|x| x.column)
+            .filter(|column| has_model || !column.needs_model())
+            .filter(|column| can_show_nearest_distances || *column != TableColumn::NearestDistance)
+            .collect()
+    }
+
+    /// Forces the rows to be sorted again (The scores change when the model is retrained)
+    pub fn invalidate_row_order(&mut self) {
+        self.row_order = None;
+    }
+
+    fn page_count(&self, row_count: usize) -> usize {
+        row_count.div_ceil(self.page_size.max(1)).max(1)
+    }
+
+    /// Rows shown (All rows unless paginated)
+    pub fn visible_rows(&self, row_count: usize) -> Range<usize> {
+        if !self.is_paginated {
+            return 0..row_count;
+        }
+        let page_size = self.page_size.max(1);
+        let page = self.page.min(self.page_count(row_count) - 1);
+        page * page_size..((page + 1) * page_size).min(row_count)
+    }
+
+    /// Changes to the page with the row and returns its position on the page
+    pub fn show_row(&mut self, row: usize) -> usize {
+        if !self.is_paginated {
+            return row;
+        }
+        let page_size = self.page_size.max(1);
+        self.page = row / page_size;
+        row % page_size
+    }
+
+    /// Nearest distances for the points if they are up to date with the data (`None` while they
+    /// are recalculated in the background)
+    fn nearest_distances(
+        &mut self,
+        points: &[DataPoint],
+        data_timestamp: DataTimestamp,
+        ctx: &egui::Context,
+    ) -> Option<Arc<[Option<f64>]>> {
+        if points.len() > Self::NEAREST_DISTANCE_MAX_POINTS {
+            return None;
+        }
+        let cache = &mut self.nearest_distances;
+        if cache.pending.as_ref().is_some_and(|x| x.ready().is_some()) {
+            let promise = cache.pending.take().expect("checked above");
+            cache.distances = Some(promise.block_and_take());
+        }
+        match cache.distances.as_ref() {
+            Some((timestamp, distances)) if *timestamp == data_timestamp => {
+                return Some(Arc::clone(distances));
+            }
+            // Waits for a calculation for older data to finish instead of starting another one
+            _ if cache.pending.is_some() => return None,
+            _ => {}
+        }
+
+        let points = points.to_vec();
+        let ctx = ctx.clone();
+        let compute = move || {
+            let result: Arc<[Option<f64>]> = nearest_distances(&points).into();
+            ctx.request_repaint();
+            (data_timestamp, result)
+        };
+        // Threads are not available in WASM so it is done on the UI thread instead
+        #[cfg(not(target_arch = "wasm32"))]
+        let promise = Promise::spawn_blocking(compute);
+        #[cfg(target_arch = "wasm32")]
+        let promise = Promise::from_ready(compute());
+        cache.pending = Some(promise);
+        None
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_table_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            self.ui_menu_table_columns(ui);
+            self.ui_menu_formula_edit(ui);
+            if self.table_columns.sort.is_some() && ui.button("Unsort").clicked() {
+                self.table_columns.sort = None;
+            }
+            ui.separator();
+            self.ui_table_pages(ui);
+            ui.separator();
+            self.ui_row_link_options(ui);
+            ui.separator();
+            self.ui_btn_copy_rows(ui);
+        });
+    }
+
+    /// Header of a column that sorts the rows when clicked
+    pub(super) fn ui_sortable_header(
+        &mut self,
+        ui: &mut egui::Ui,
+        name: &str,
+        column: TableSortColumn,
+    ) {
+        let text = format!("{name}{}", self.table_columns.sort_marker(column));
+        if ui
+            .add(egui::Label::new(egui::RichText::new(text).strong()).sense(egui::Sense::click()))
+            .on_hover_text("Click to sort the rows (ascending, descending then unsorted)")
+            .clicked()
+        {
+            self.table_columns.toggle_sort(column);
+        }
+    }
+
+    fn ui_menu_table_columns(&mut self, ui: &mut egui::Ui) {
+        let is_too_many = self.data.points().len() > TableColumns::NEAREST_DISTANCE_MAX_POINTS;
+        ui.menu_button("Columns", |ui| {
+            let columns = &mut self.table_columns;
+            columns.fill_layout();
+            let count = columns.layout.len();
+            let mut swap = None;
+            for i in 0..count {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(i > 0, egui::Button::new("⏴").small())
+                        .on_hover_text("Move left")
+                        .clicked()
+                    {
+                        swap = Some(i - 1);
+                    }
+                    if ui
+                        .add_enabled(i + 1 < count, egui::Button::new("⏵").small())
+                        .on_hover_text("Move right")
+                        .clicked()
+                    {
+                        swap = Some(i);
+                    }
+                    let setting = &mut columns.layout[i];
+                    let response =
+                        ui.checkbox(&mut setting.is_visible, setting.column.menu_name());
+                    match setting.column {
+                        TableColumn::NearestDistance => response.on_hover_text(format!(
+                            "Distance from each point to its nearest other point (Only calculated with up to {} points)",
+                            TableColumns::NEAREST_DISTANCE_MAX_POINTS
+                        )),
+                        column if column.needs_model() => {
+                            response.on_hover_text("Only shown when a model is trained on the data")
+                        }
+                        _ => response,
+                    };
+                });
+            }
+            if let Some(i) = swap {
+                columns.layout.swap(i, i + 1);
+            }
+            ui.separator();
+            if ui.button("Reset Columns").clicked() {
+                columns.layout = TableColumns::default_layout();
+                ui.close_menu();
+            }
+        })
+        .response
+        .on_hover_text(if is_too_many {
+            "Show, hide and reorder the columns (Too many points for the nearest distance)"
+        } else {
+            "Show, hide and reorder the columns"
+        });
+    }
+
+    fn ui_table_pages(&mut self, ui: &mut egui::Ui) {
+        let columns = &mut self.table_columns;
+        ui.checkbox(&mut columns.is_paginated, "Pages")
+            .on_hover_text("Shows one page of rows at a time (For very large datasets)");
+        if !columns.is_paginated {
+            return;
+        }
+        ui.add(
+            egui::DragValue::new(&mut columns.page_size)
+                .clamp_range(10..=100_000)
+                .suffix(" rows"),
+        );
+        let page_count = columns.page_count(self.data.points().len());
+        columns.page = columns.page.min(page_count - 1);
+        if ui
+            .add_enabled(columns.page > 0, egui::Button::new("⏴"))
+            .clicked()
+        {
+            columns.page -= 1;
+        }
+        ui.label(format!("Page {} of {page_count}", columns.page + 1));
+        if ui
+            .add_enabled(columns.page + 1 < page_count, egui::Button::new("⏵"))
+            .clicked()
+        {
+            columns.page += 1;
+        }
+    }
+
+    /// The nearest distances if the column is shown and they have been calculated
+    pub(super) fn table_nearest_distances(
+        &mut self,
+        ctx: &egui::Context,
+    ) -> Option<Arc<[Option<f64>]>> {
+        if !self.table_columns.is_visible(TableColumn::NearestDistance) {
+            return None;
+        }
+        let data_timestamp = self.data.timestamp();
+        self.table_columns
+            .nearest_distances(self.data.points(), data_timestamp, ctx)
+    }
+
+    /// Data index of each table row (`None` if the rows are in the order of the points)
+    pub(super) fn table_row_order(
+        &mut self,
+        nearest_distances: Option<&[Option<f64>]>,
+    ) -> Option<Arc<[usize]>> {
+        let sort = self.table_columns.sort?;
+        let key = RowOrderKey {
+            sort,
+            data_timestamp: self.data.timestamp(),
+            model_timestamp: self
+                .loc_inference_model()
+                .map(|x| x.data_timestamp_at_training()),
+        };
+        if let Some((cached_key, order)) = self.table_columns.row_order.as_ref() {
+            if *cached_key == key {
+                return Some(Arc::clone(order));
+            }
+        }
+        let values: Vec<Option<f64>> = match sort.column {
+            TableSortColumn::Score => self.current_scores()?.into_iter().map(Some).collect(),
+            TableSortColumn::NearestDistance => nearest_distances?.to_vec(),
+        };
+        let order: Arc<[usize]> = sorted_order(&values, sort.is_descending).into();
+        self.table_columns.row_order = Some((key, Arc::clone(&order)));
+        Some(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn rows_sorted_by_nearest_distance() {
+        let points = [
+            DataPoint::new(0.0, 0.0, DataLabel::Normal),
+            DataPoint::new(1.0, 0.0, DataLabel::Normal),
+            DataPoint::new(5.0, 0.0, DataLabel::Anomaly),
+        ];
+        let distances = nearest_distances(&points);
+        assert_eq!(distances, [Some(1.0), Some(1.0), Some(4.0)]);
+        assert_eq!(sorted_order(&distances, false), [0, 1, 2]);
+        assert_eq!(sorted_order(&distances, true), [2, 0, 1]);
+        assert_eq!(sorted_order(&[None, Some(2.0)], false), [1, 0]);
+        assert_eq!(nearest_distances(&points[..1]), [None]);
+
+        let mut columns = TableColumns::default();
+        columns.toggle_sort(TableSortColumn::Score);
+        assert_eq!(columns.sort_marker(TableSortColumn::Score), " ⬆");
+        columns.toggle_sort(TableSortColumn::Score);
+        columns.toggle_sort(TableSortColumn::Score);
+        assert_eq!(columns.sort, None);
+
+        assert_eq!(
+            columns.shown_columns(false, true)[..3],
+            [
+                TableColumn::Selected,
+                TableColumn::RowNumber,
+                TableColumn::X0
+            ]
+        );
+        assert!(!columns
+            .shown_columns(true, false)
+            .contains(&TableColumn::NearestDistance));
+        columns.layout.swap(0, 12);
+        columns.layout.truncate(13);
+        columns.fill_layout();
+        assert_eq!(
+            columns.shown_columns(true, true)[..2],
+            [TableColumn::Score, TableColumn::RowNumber]
+        );
+        assert_eq!(
+            columns.shown_columns(true, true).last(),
+            Some(&TableColumn::NearestDistance)
+        );
+
+        columns.is_paginated = true;
+        columns.page_size = 10;
+        assert_eq!(columns.show_row(23), 3);
+        assert_eq!(columns.visible_rows(25), 20..25);
+        assert_eq!(columns.visible_rows(5), 0..5);
+    }
+}
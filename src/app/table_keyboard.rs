@@ -0,0 +1,203 @@
+use egui::{Key, Modifiers};
+
+use crate::DBV;
+
+use super::{table_columns::TableColumn, DisplayMode, DuringEditPoint};
+
+/// State of editing the table with the keyboard (The cursor row is the linked row)
+#[derive(Debug, PartialEq, Default)]
+pub struct TableKeyboard {
+    /// Column of the cursor
+    column: Option<TableColumn>,
+    /// Field of the edited row to give keyboard focus to when it is next drawn
+    focus_field: Option<TableColumn>,
+    /// Field of the edited row that last had keyboard focus
+    edit_field: Option<TableColumn>,
+    /// Tab presses taken from the input while editing (`true` for Shift+Tab)
+    pending_tabs: Vec<bool>,
+}
+
+impl TableKeyboard {
+    /// Fields that Tab moves between while editing a row
+    const EDIT_FIELDS: [TableColumn; 3] = [TableColumn::X0, TableColumn::X1, TableColumn::Label];
+
+    /// The field after (or before) the one given, wrapping around
+    fn next_field(current: Option<TableColumn>, is_backwards: bool) -> TableColumn {
+        let count = Self::EDIT_FIELDS.len();
+        let position = current.and_then(|x| Self::EDIT_FIELDS.iter().position(|&y| y == x));
+        let next = match (position, is_backwards) {
+            (None, false) => 0,
+            (None, true) => count - 1,
+            (Some(i), false) => (i + 1) % count,
+            (Some(i), true) => (i + count - 1) % count,
+        };
+        Self::EDIT_FIELDS[next]
+    }
+}
+
+/// Position moved by `delta` and kept within `0..len`
+fn moved(position: usize, delta: isize, len: usize) -> usize {
+    position
+        .saturating_add_signed(delta)
+        .min(len.saturating_sub(1))
+}
+
+impl DBV {
+    /// Takes Tab presses out of the input while a table row is edited so that egui does not
+    /// move the focus to the next widget (They move between the fields of the row instead)
+    pub(super) fn take_table_tab_presses(&mut self, raw_input: &mut egui::RawInput) {
+        if self.display_mode != DisplayMode::Table || self.edit_point.is_none() {
+            return;
+        }
+        let pending_tabs = &mut self.table_keyboard.pending_tabs;
+        raw_input.events.retain(|event| match event {
+            egui::Event::Key {
+                key: Key::Tab,
+                pressed,
+                modifiers,
+                ..
+            } => {
+                if *pressed {
+                    pending_tabs.push(modifiers.shift);
+                }
+                false
+            }
+            _ => true,
+        });
+    }
+
+    /// Returns `true` if the cell has the keyboard cursor
+    pub(super) fn is_table_cursor(&self, row_index: usize, column: TableColumn) -> bool {
+        self.edit_point.is_none()
+            && self.row_link.index == Some(row_index)
+            && self.table_keyboard.column == Some(column)
+    }
+
+    /// Gives a field of the edited row focus if it was moved to and keeps track of which has it
+    pub(super) fn table_edit_focus(&mut self, response: &egui::Response, column: TableColumn) {
+        if self.table_keyboard.focus_field == Some(column) {
+            self.table_keyboard.focus_field = None;
+            response.request_focus();
+        }
+        if response.has_focus() {
+            self.table_keyboard.edit_field = Some(column);
+        }
+    }
+
+    /// Arrow keys move the cursor, Enter starts and saves edits, Escape cancels and Tab moves
+    /// between the fields of the edited row
+    ///
+    /// Called after the rows are drawn so values typed this frame are saved
+    pub(super) fn handle_table_keys(
+        &mut self,
+        ctx: &egui::Context,
+        row_order: Option<&[usize]>,
+        columns: &[TableColumn],
+    ) {
+        for is_backwards in std::mem::take(&mut self.table_keyboard.pending_tabs) {
+            if self.edit_point.is_some() {
+                let field = TableKeyboard::next_field(self.table_keyboard.edit_field, is_backwards);
+                self.table_keyboard.focus_field = Some(field);
+                self.table_keyboard.edit_field = Some(field);
+            }
+        }
+
+        if let Some(edit) = self.edit_point {
+            if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+                self.edit_point = None;
+            } else if !ctx.memory(|m| m.any_popup_open())
+                && ctx.input(|i| i.key_pressed(Key::Enter))
+            {
+                self.data.edit(edit.index, edit.point);
+                self.edit_point = None;
+                self.row_link.index = Some(edit.index);
+            }
+            return;
+        }
+
+        // Keys are left to any widget that has focus (eg. a text field)
+        if ctx.memory(|m| m.focused().is_some()) || ctx.wants_keyboard_input() {
+            return;
+        }
+        let row_count = self.data.points().len();
+        if row_count == 0 || columns.is_empty() {
+            return;
+        }
+        let [up, down, left, right, enter] = [
+            Key::ArrowUp,
+            Key::ArrowDown,
+            Key::ArrowLeft,
+            Key::ArrowRight,
+            Key::Enter,
+        ]
+        .map(|key| ctx.input_mut(|i| i.consume_key(Modifiers::NONE, key)));
+
+        let to_position = |index: usize| match row_order {
+            Some(order) => order.iter().position(|&i| i == index),
+            None => Some(index),
+        };
+        let to_index = |position: usize| match row_order {
+            Some(order) => order.get(position).copied(),
+            None => Some(position),
+        };
+        let position = self
+            .row_link
+            .index
+            .filter(|&i| i < row_count)
+            .and_then(to_position);
+        let column = self
+            .table_keyboard
+            .column
+            .and_then(|x| columns.iter().position(|&y| y == x));
+
+        if up || down || left || right {
+            let row_delta = if up { -1 } else { isize::from(down) };
+            let column_delta = if left { -1 } else { isize::from(right) };
+            let position = position.map_or(0, |x| moved(x, row_delta, row_count));
+            let column = column.map_or(0, |x| moved(x, column_delta, columns.len()));
+            self.row_link.index = to_index(position);
+            self.row_link.should_scroll = true;
+            self.table_keyboard.column = Some(columns[column]);
+        } else if enter {
+            let Some(index) = self.row_link.index.filter(|&i| i < row_count) else {
+                return;
+            };
+            self.edit_point = Some(DuringEditPoint {
+                index,
+                point: self.data.points()[index],
+            });
+            let field = self
+                .table_keyboard
+                .column
+                .filter(|x| TableKeyboard::EDIT_FIELDS.contains(x))
+                .unwrap_or(TableColumn::X0);
+            self.table_keyboard.focus_field = Some(field);
+            self.table_keyboard.edit_field = Some(field);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_wraps_around_the_fields() {
+        assert_eq!(TableKeyboard::next_field(None, false), TableColumn::X0);
+        assert_eq!(
+            TableKeyboard::next_field(Some(TableColumn::X0), false),
+            TableColumn::X1
+        );
+        assert_eq!(
+            TableKeyboard::next_field(Some(TableColumn::Label), false),
+            TableColumn::X0
+        );
+        assert_eq!(
+            TableKeyboard::next_field(Some(TableColumn::X0), true),
+            TableColumn::Label
+        );
+        assert_eq!(moved(0, -1, 5), 0);
+        assert_eq!(moved(4, 1, 5), 4);
+        assert_eq!(moved(2, 1, 5), 3);
+    }
+}
@@ -0,0 +1,103 @@
+use egui::Button;
+
+use super::local_experiments::{
+    ModelInferenceConfig as _, ProximityScore, ThresholdPreset, Trained,
+};
+
+/// Shows the threshold, the optional borderline band and the saved presets of the model
+///
+/// `preset_name` is the name typed for the next preset to be saved
+pub(super) fn ui_threshold_config(
+    ui: &mut egui::Ui,
+    model: &mut ProximityScore<Trained>,
+    preset_name: &mut String,
+) {
+    // Taken out while editing so the config can be borrowed at the same time
+    let mut presets = std::mem::take(model.threshold_presets_mut());
+    let config = model.predict_config_mut();
+    let score_range = config.min_score..=config.max_score;
+    ui.horizontal(|ui| {
+        ui.label("Threshold: ");
+        ui.add(egui::Slider::new(
+            &mut config.threshold,
+            score_range.clone(),
+        ));
+        // TODO 4: Add button to set threshold to best value based on F1
+    });
+    ui.horizontal(|ui| {
+        let mut is_band_enabled = config.borderline_threshold.is_some();
+        if ui
+            .checkbox(&mut is_band_enabled, "Borderline band")
+            .on_hover_text("Points scored from the lower threshold up to the threshold are shown as borderline")
+            .changed()
+        {
+            config.borderline_threshold = is_band_enabled.then(|| {
+                // Start with the lower quarter of the distance from the minimum to the threshold
+                config.threshold - (config.threshold - config.min_score) / 4.0
+            });
+        }
+        if let Some(lower) = config.borderline_threshold.as_mut() {
+            ui.label("Lower threshold: ");
+            ui.add(egui::Slider::new(lower, config.min_score..=config.threshold));
+        }
+    });
+    // Keep the band below the threshold if the threshold was moved below it
+    if let Some(lower) = config.borderline_threshold.as_mut() {
+        *lower = lower.min(config.threshold);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Presets: ");
+        let mut to_remove = None;
+        for (i, preset) in presets.iter().enumerate() {
+            let response =
+                ui.button(&preset.name)
+                    .on_hover_text(match preset.borderline_threshold {
+                        Some(lower) => {
+                            format!("Threshold: {} (Borderline from {lower})", preset.threshold)
+                        }
+                        None => format!("Threshold: {}", preset.threshold),
+                    });
+            if response.clicked() {
+                config.threshold = preset.threshold.clamp(config.min_score, config.max_score);
+                config.borderline_threshold = preset
+                    .borderline_threshold
+                    .map(|x| x.clamp(config.min_score, config.threshold));
+            }
+            response.context_menu(|ui| {
+                if ui.button("Remove preset").clicked() {
+                    to_remove = Some(i);
+                    ui.close_menu();
+                }
+            });
+        }
+        if let Some(i) = to_remove {
+            presets.remove(i);
+        }
+        ui.add(
+            egui::TextEdit::singleline(preset_name)
+                .desired_width(80.0)
+                .hint_text("Name"),
+        );
+        let name = preset_name.trim();
+        if ui
+            .add_enabled(!name.is_empty(), Button::new("Save preset"))
+            .on_hover_text(
+                "Replaces any preset with the same name. Right click a preset to remove it",
+            )
+            .clicked()
+        {
+            let preset = ThresholdPreset {
+                name: name.to_string(),
+                threshold: config.threshold,
+                borderline_threshold: config.borderline_threshold,
+            };
+            match presets.iter_mut().find(|x| x.name == preset.name) {
+                Some(existing) => *existing = preset,
+                None => presets.push(preset),
+            }
+            preset_name.clear();
+        }
+    });
+    *model.threshold_presets_mut() = presets;
+}
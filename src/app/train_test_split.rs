@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+
+use egui::Button;
+use egui_plot::{MarkerShape, PlotUi, Points};
+use rand::seq::SliceRandom as _;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataLabel, DataPoint, DistanceCalculation as _, PointArray},
+    data_generation::rng_from_seed,
+    prediction_classification::{prediction_classification, ConfusionCounts},
+    ui_blocks::ui_seed,
+};
+
+/// Settings for splitting the points into a train and a test split
+///
+/// Separate from excluding points so a split does not change which points are held out
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct TrainTestSplit {
+    /// Share of the points put in the test split by a random split
+    pub test_percent: f64,
+    /// Each label gets the same share of test points
+    pub is_stratified: bool,
+    /// Mark the test points on the plot
+    pub show_test_points: bool,
+}
+
+impl Default for TrainTestSplit {
+    fn default() -> Self {
+        Self {
+            test_percent: 25.0,
+            is_stratified: true,
+            show_test_points: true,
+        }
+    }
+}
+
+impl TrainTestSplit {
+    /// Whether each point should be in the test split
+    fn random_split(&self, points: &[DataPoint], seed: u64) -> Vec<bool> {
+        let mut rng = rng_from_seed(seed);
+        let groups: Vec<Vec<usize>> = if self.is_stratified {
+            let mut by_label: BTreeMap<DataLabel, Vec<usize>> = BTreeMap::new();
+            for (i, point) in points.iter().enumerate() {
+                by_label.entry(point.label).or_default().push(i);
+            }
+            by_label.into_values().collect()
+        } else {
+            vec![(0..points.len()).collect()]
+        };
+        let fraction = self.test_percent.clamp(0.0, 100.0) / 100.0;
+        let mut result = vec![false; points.len()];
+        for mut group in groups {
+            group.shuffle(&mut rng);
+            let test_count = (group.len() as f64 * fraction).round() as usize;
+            for &i in &group[..test_count] {
+                result[i] = true;
+            }
+        }
+        result
+    }
+}
+
+impl DBV {
+    /// Sets which points are in the test split as a single undoable change
+    fn set_test_split(&mut self, is_test: impl Fn(usize) -> bool) {
+        let edits: Vec<_> = (0..self.data.points().len())
+            .map(|i| {
+                let mut point = self.data.points()[i];
+                point.is_in_test_split = is_test(i);
+                (i, point)
+            })
+            .collect();
+        self.data.edit_many(edits);
+        self.selection.keep_for(&self.data);
+    }
+
+    pub(super) fn ui_menu_train_test_split(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(!self.data.is_empty(), |ui| {
+            ui.menu_button("Train/Test Split", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Test share");
+                    ui.add(
+                        egui::DragValue::new(&mut self.train_test_split.test_percent)
+                            .clamp_range(0.0..=100.0)
+                            .suffix("%"),
+                    );
+                    ui_seed(ui, &mut self.data.settings.seed);
+                });
+                ui.checkbox(
+                    &mut self.train_test_split.is_stratified,
+                    "Stratified by label",
+                );
+                if ui
+                    .button("Random Split")
+                    .on_hover_text("Replaces the current split")
+                    .clicked()
+                {
+                    let is_test = self
+                        .train_test_split
+                        .random_split(self.data.points(), self.data.settings.seed);
+                    self.set_test_split(|i| is_test[i]);
+                    ui.close_menu();
+                }
+                if ui
+                    .add_enabled(
+                        !self.selection.is_empty(),
+                        Button::new("Selection Is Test Split"),
+                    )
+                    .on_hover_text("All other points are put in the train split")
+                    .clicked()
+                {
+                    let selection = self.selection.indices().clone();
+                    self.set_test_split(|i| selection.contains(&i));
+                    ui.close_menu();
+                }
+                if ui.button("Train on All Points").clicked() {
+                    self.set_test_split(|_| false);
+                    ui.close_menu();
+                }
+                ui.separator();
+                ui.checkbox(
+                    &mut self.train_test_split.show_test_points,
+                    "Mark test points on plot",
+                );
+            });
+        });
+    }
+
+    /// Shows the metrics of the current model for each split (Excluded points in the train split
+    /// are not counted)
+    pub(super) fn ui_split_metrics(&self, ui: &mut egui::Ui) {
+        let Some(model) = self.loc_inference_model() else {
+            return;
+        };
+        let mut counts = [ConfusionCounts::default(); 2];
+        for (i, point) in self.data.points().iter().enumerate() {
+            if point.is_excluded_from_training && !point.is_in_test_split {
+                continue;
+            }
+            counts[usize::from(point.is_in_test_split)].add(prediction_classification(
+                point.label,
+                model.prediction_on_training_data(i),
+            ));
+        }
+        let format =
+            |x: Option<f64>| x.map_or("-".to_string(), |x| self.display_precision.format(x));
+        ui.horizontal(|ui| {
+            for (name, counts) in ["Train", "Test"].iter().zip(counts) {
+                let total = counts.true_positives
+                    + counts.false_positives
+                    + counts.true_negatives
+                    + counts.false_negatives;
+                if total == 0 {
+                    continue;
+                }
+                ui.label(format!(
+                    "{name} ({total}): Precision {}, Recall {}, F1 {}",
+                    format(counts.precision()),
+                    format(counts.recall()),
+                    format(counts.f1())
+                ));
+                ui.separator();
+            }
+        });
+    }
+
+    pub(super) fn plot_test_split_indicators(&self, plot_ui: &mut PlotUi) {
+        if !self.train_test_split.show_test_points {
+            return;
+        }
+        let test: Vec<PointArray> = self
+            .data
+            .points()
+            .iter()
+            .enumerate()
+            .filter(|(i, point)| point.is_in_test_split && self.is_point_shown(*i))
+            .map(|(_, point)| point.to_array())
+            .collect();
+        if test.is_empty() {
+            return;
+        }
+        plot_ui.points(
+            Points::new(test)
+                .name("Test Split")
+                .shape(MarkerShape::Diamond)
+                .filled(false)
+                .radius(self.marker_radius * 1.8)
+                .color(plot_ui.ctx().style().visuals.weak_text_color()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stratified_split_takes_share_of_each_label() {
+        let points: Vec<DataPoint> = (0..12)
+            .map(|i| {
+                let label = if i < 4 {
+                    DataLabel::Anomaly
+                } else {
+                    DataLabel::Normal
+                };
+                DataPoint::new(i as f64, 0.0, label)
+            })
+            .collect();
+        let split = TrainTestSplit::default();
+        let is_test = split.random_split(&points, 0);
+        assert_eq!(is_test.iter().filter(|x| **x).count(), 3);
+        assert_eq!(is_test[..4].iter().filter(|x| **x).count(), 1);
+        assert_eq!(is_test, split.random_split(&points, 0));
+    }
+
+    #[test]
+    fn split_keeps_points_held_out_from_training() {
+        let mut app = DBV::default();
+        app.data.add_many(
+            (0..4)
+                .map(|i| DataPoint::new(i as f64, 0.0, DataLabel::Normal))
+                .collect(),
+        );
+        app.data.set_excluded_from_training([1], true);
+        app.set_test_split(|i| i >= 2);
+        app.set_test_split(|_| false);
+        let points = app.data.points();
+        assert!(points[1].is_excluded_from_training);
+        assert!(!points.iter().any(|x| x.is_in_test_split));
+        app.set_test_split(|i| i >= 2);
+        let trained_on: Vec<bool> = app
+            .data
+            .points()
+            .iter()
+            .map(DataPoint::is_used_for_training)
+            .collect();
+        assert_eq!(trained_on, [true, false, false, false]);
+    }
+}
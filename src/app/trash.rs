@@ -0,0 +1,84 @@
+use egui::Button;
+
+use crate::DBV;
+
+impl DBV {
+    pub(super) fn ui_btn_show_trash(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(
+            &mut self.show_trash_window,
+            format!("Show Trash ({})", self.data.trash.len()),
+        );
+    }
+
+    pub(super) fn ui_trash_window(&mut self, ctx: &egui::Context) {
+        if !self.show_trash_window {
+            return;
+        }
+        let mut is_open = true;
+        egui::Window::new("Trash")
+            .open(&mut is_open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.checkbox(
+                    &mut self.data.trash.is_enabled,
+                    "Soft delete (Move deleted points to the trash)",
+                )
+                .on_hover_text("Points in the trash are not shown or used for training");
+                ui.horizontal(|ui| {
+                    let has_points = !self.data.trash.is_empty();
+                    if ui
+                        .add_enabled(has_points, Button::new("Restore All"))
+                        .clicked()
+                    {
+                        let indices: Vec<usize> = (0..self.data.trash.len()).collect();
+                        self.data.restore_from_trash(&indices);
+                    }
+                    if ui
+                        .add_enabled(has_points, Button::new("Empty Trash"))
+                        .on_hover_text("Permanently removes all points in the trash")
+                        .clicked()
+                    {
+                        self.data.trash.empty();
+                    }
+                });
+                ui.separator();
+                self.ui_trash_points(ui);
+            });
+        if !is_open {
+            self.show_trash_window = false;
+        }
+    }
+
+    fn ui_trash_points(&mut self, ui: &mut egui::Ui) {
+        if self.data.trash.is_empty() {
+            ui.label("Trash is empty");
+            return;
+        }
+        let mut to_restore = None;
+        let mut to_purge = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("grid-trash").striped(true).show(ui, |ui| {
+                for (i, point) in self.data.trash.points().iter().enumerate() {
+                    ui.label(format!(
+                        "{} {}",
+                        self.display_precision.format_pair([point.x0, point.x1]),
+                        point.label
+                    ));
+                    if ui.button("Restore").clicked() {
+                        to_restore = Some(i);
+                    }
+                    if ui.button("Purge").clicked() {
+                        to_purge = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+        if let Some(i) = to_restore {
+            self.data.restore_from_trash(&[i]);
+        }
+        if let Some(i) = to_purge {
+            self.data.trash.purge(&[i]);
+        }
+    }
+}
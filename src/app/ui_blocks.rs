@@ -1,6 +1,9 @@
 use std::ops::RangeInclusive;
 
 use egui::Button;
+use egui_plot::PlotUi;
+
+use super::data_definition::PointArray;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct OptionEditNumeric<T>
@@ -81,3 +84,21 @@ where
         }
     }
 }
+
+/// Edits the seed shared by the tools that use random numbers
+pub fn ui_seed(ui: &mut egui::Ui, seed: &mut u64) {
+    ui.label("Seed");
+    ui.add(egui::DragValue::new(seed)).on_hover_text(
+        "Shared by every tool that uses random numbers and saved with the project (Also in Options)",
+    );
+}
+
+/// Where the primary button was pressed in plot coordinates (Drags only start after the pointer
+/// moves so this is where a drag that just started was begun)
+pub fn drag_start_position(plot_ui: &PlotUi) -> Option<PointArray> {
+    plot_ui
+        .ctx()
+        .input(|i| i.pointer.press_origin())
+        .map(|pos| plot_ui.plot_from_screen(pos))
+        .map(|point| [point.x, point.y])
+}
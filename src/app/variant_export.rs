@@ -0,0 +1,196 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+
+use crate::DBV;
+
+use super::{
+    data_definition::{DataPoint, DataPoints, Save as _},
+    execute,
+    jitter::Jitter,
+    operational_state::{LoadProgress, OperationOutcome, OperationalState, Payload},
+    subsample::Subsample,
+};
+
+/// Settings for writing randomly changed copies of the data to a folder in one go
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone)]
+#[serde(default)]
+pub struct VariantExport {
+    /// Number of files written
+    pub count: usize,
+    /// Each variant keeps a random part of the points (Uses the Random Subsample settings)
+    pub should_subsample: bool,
+    /// Each variant has noise added to the points (Uses the Add Jitter sigma)
+    pub should_jitter: bool,
+    /// Start of the file names (Followed by the number of the variant)
+    pub file_stem: String,
+}
+
+impl Default for VariantExport {
+    fn default() -> Self {
+        Self {
+            count: 10,
+            should_subsample: true,
+            should_jitter: true,
+            file_stem: "variant".to_string(),
+        }
+    }
+}
+
+impl VariantExport {
+    /// Name of the file for the variant at `index` (Numbered from 1 with enough digits to sort)
+    fn file_name(&self, index: usize) -> String {
+        let width = self.count.to_string().len();
+        format!("{}_{:0width$}.csv", self.file_stem, index + 1)
+    }
+
+    /// The points of the variant at `index` (Each variant uses the seed plus its index)
+    fn variant(
+        &self,
+        points: &[DataPoint],
+        jitter: &Jitter,
+        subsample: &Subsample,
+        seed: u64,
+        index: usize,
+    ) -> DataPoints {
+        let seed = seed.wrapping_add(index as u64);
+        let mut result: DataPoints = if self.should_subsample {
+            let to_remove = subsample.indices_to_remove(points, seed);
+            points
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| to_remove.binary_search(i).is_err())
+                .map(|(_, point)| *point)
+                .collect()
+        } else {
+            points.to_vec()
+        };
+        if self.should_jitter {
+            result = jitter
+                .apply(&result, 0..result.len(), seed)
+                .into_iter()
+                .map(|(_, point)| point)
+                .collect();
+        }
+        result
+    }
+}
+
+impl DBV {
+    pub(super) fn ui_menu_variant_export(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(self.op_state.is_normal() && !self.data.is_empty(), |ui| {
+            ui.menu_button("Export Perturbed Variants…", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Variants");
+                    ui.add(
+                        egui::DragValue::new(&mut self.variant_export.count)
+                            .clamp_range(1..=10_000),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("File name");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.variant_export.file_stem)
+                            .desired_width(100.0),
+                    );
+                    ui.label(self.variant_export.file_name(0));
+                });
+                ui.checkbox(&mut self.variant_export.should_subsample, "Subsample")
+                    .on_hover_text("Uses the settings of Edit > Random Subsample");
+                ui.checkbox(&mut self.variant_export.should_jitter, "Jitter")
+                    .on_hover_text(format!(
+                        "Adds noise with sigma {} (Set in Edit > Add Jitter)",
+                        self.jitter.sigma
+                    ));
+                if ui
+                    .button("Choose Folder and Export...")
+                    .on_hover_text(
+                        "Variant N uses the dataset seed plus N so exports can be repeated",
+                    )
+                    .clicked()
+                {
+                    self.export_variants(ui.ctx().clone());
+                    ui.close_menu();
+                }
+            });
+        });
+    }
+
+    fn export_variants(&mut self, ctx: egui::Context) {
+        debug_assert!(self.op_state.is_normal());
+        let mut points = self.data.clone_points();
+        self.label_classes.prepare_for_save(&mut points);
+        let settings = self.variant_export.clone();
+        let (jitter, subsample) = (self.jitter.clone(), self.subsample.clone());
+        let seed = self.data.settings.seed;
+        let mat_names = self.mat_variable_names.clone();
+        let progress = LoadProgress::default();
+        let progress_for_ui = progress.clone();
+        let promise = execute(async move {
+            let Some(folder) = rfd::AsyncFileDialog::new()
+                .set_title("Export Perturbed Variants to")
+                .pick_folder()
+                .await
+            else {
+                // user canceled
+                ctx.request_repaint();
+                return OperationOutcome::Cancelled;
+            };
+            let folder = folder.path().to_path_buf();
+            progress.set_total(settings.count);
+            for index in 0..settings.count {
+                let variant = settings.variant(&points, &jitter, &subsample, seed, index);
+                let path = folder.join(settings.file_name(index));
+                if let Err(e) = variant
+                    .save_to_file(&rfd::FileHandle::from(path.clone()), &mat_names)
+                    .await
+                    .with_context(|| format!("failed to export variant to {path:?}"))
+                {
+                    ctx.request_repaint();
+                    return OperationOutcome::Failed(e);
+                }
+                progress.set_processed(index + 1);
+                ctx.request_repaint();
+            }
+            OperationOutcome::Success(Payload::ExportVariants {
+                folder,
+                count: settings.count,
+            })
+        });
+        self.op_state = OperationalState::Exporting(promise, progress_for_ui);
+    }
+
+    pub(super) fn on_variants_exported(&mut self, folder: PathBuf, count: usize) {
+        self.status_msg
+            .info(format!("Exported {count} perturbed variants to {folder:?}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::data_definition::DataLabel;
+
+    use super::*;
+
+    #[test]
+    fn variants_differ_but_repeat() {
+        let points: Vec<DataPoint> = (0..10)
+            .map(|i| DataPoint::new(i as f64, 0.0, DataLabel::Normal))
+            .collect();
+        let settings = VariantExport {
+            count: 12,
+            ..Default::default()
+        };
+        assert_eq!(settings.file_name(0), "variant_01.csv");
+        assert_eq!(settings.file_name(11), "variant_12.csv");
+        let subsample = Subsample {
+            count: 4,
+            ..Default::default()
+        };
+        let jitter = Jitter::default();
+        let first = settings.variant(&points, &jitter, &subsample, 7, 0);
+        assert_eq!(first.len(), 4);
+        assert_eq!(first, settings.variant(&points, &jitter, &subsample, 7, 0));
+        assert_ne!(first, settings.variant(&points, &jitter, &subsample, 7, 1));
+    }
+}
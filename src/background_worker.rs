@@ -1,4 +1,7 @@
 // TODO 4: Remove this file and just use the tokio macro
+#[cfg(not(target_arch = "wasm32"))]
+use std::{path::PathBuf, sync::Mutex, time::SystemTime};
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn create_runtime() -> tokio::runtime::Runtime {
     tokio::runtime::Builder::new_current_thread()
@@ -7,6 +10,11 @@ pub fn create_runtime() -> tokio::runtime::Runtime {
         .expect("Unable to create Runtime")
 }
 
+/// How often the worker writes pending autosaves and checks the watched file (Kept at or below the
+/// smallest autosave interval allowed)
+#[cfg(not(target_arch = "wasm32"))]
+const WORKER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn start_background_worker(rt: tokio::runtime::Runtime) {
     // Execute the runtime in its own thread.
@@ -15,8 +23,219 @@ pub fn start_background_worker(rt: tokio::runtime::Runtime) {
         rt.block_on(async {
             loop {
                 // Can use this loop for background tasks
-                tokio::time::sleep(std::time::Duration::from_secs(300)).await;
+                write_pending_autosave();
+                check_watched_file();
+                tokio::time::sleep(WORKER_INTERVAL).await;
             }
         })
     });
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct AutosaveState {
+    /// The latest snapshot that has not been written yet
+    pending: Option<(PathBuf, String)>,
+    /// Set once the app is closing so no snapshot is written after the recovery file is removed
+    is_stopped: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct FileWatchState {
+    path: Option<PathBuf>,
+    /// Modification time when the file was last loaded or saved by the app
+    last_modified: Option<SystemTime>,
+    has_changed: bool,
+    /// Used to wake up the UI when a change is found
+    ctx: Option<egui::Context>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct UpdateCheckState {
+    is_in_progress: bool,
+    /// Tag of the latest release found by the last check
+    result: Option<anyhow::Result<String>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+lazy_static::lazy_static! {
+    static ref AUTOSAVE: Mutex<AutosaveState> = Default::default();
+    /// Held for the duration of a write (Separate from `AUTOSAVE` so queueing never waits on the disk)
+    static ref AUTOSAVE_WRITE: Mutex<()> = Default::default();
+    static ref FILE_WATCH: Mutex<FileWatchState> = Default::default();
+    static ref UPDATE_CHECK: Mutex<UpdateCheckState> = Default::default();
+}
+
+/// Queues the contents to be written to the path by the background worker (Replaces any snapshot not written yet)
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn queue_autosave(path: PathBuf, contents: String) {
+    let mut state = AUTOSAVE.lock().expect("autosave lock poisoned");
+    if !state.is_stopped {
+        state.pending = Some((path, contents));
+    }
+}
+
+/// Stops any further autosaves and removes the file (Used to mark a clean exit)
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn stop_autosave_and_remove(path: &std::path::Path) {
+    {
+        let mut state = AUTOSAVE.lock().expect("autosave lock poisoned");
+        state.is_stopped = true;
+        state.pending = None;
+    }
+    // Waiting for the write lock ensures a write in progress finishes before the file is removed
+    let _write_guard = AUTOSAVE_WRITE.lock().expect("autosave write lock poisoned");
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(path) {
+            log::error!("failed to remove recovery file at {path:?}. Error: {e}");
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_pending_autosave() {
+    let _write_guard = AUTOSAVE_WRITE.lock().expect("autosave write lock poisoned");
+    // Only held while taking the snapshot so queueing from the UI never waits on the disk
+    let Some((path, contents)) = AUTOSAVE
+        .lock()
+        .expect("autosave lock poisoned")
+        .pending
+        .take()
+    else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            log::error!("failed to create folder for recovery file at {parent:?}. Error: {e}");
+            return;
+        }
+    }
+    // Write to a temporary file first so a crash during the write cannot corrupt the last snapshot
+    let temp_path = path.with_extension("tmp");
+    match std::fs::write(&temp_path, contents).and_then(|()| std::fs::rename(&temp_path, &path)) {
+        Ok(()) => log::debug!("Autosave written to {path:?}"),
+        Err(e) => log::error!("failed to write recovery file at {path:?}. Error: {e}"),
+    }
+}
+
+/// Sets the file checked for changes by the background worker (`None` stops watching)
+///
+/// Does nothing if the path is already being watched
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn watch_file(path: Option<&std::path::Path>, ctx: &egui::Context) {
+    let mut state = FILE_WATCH.lock().expect("file watch lock poisoned");
+    if state.path.as_deref() == path {
+        return;
+    }
+    state.path = path.map(|x| x.to_path_buf());
+    state.last_modified = path.and_then(modified_time);
+    state.has_changed = false;
+    state.ctx = Some(ctx.clone());
+}
+
+/// Treats the current version of the watched file as seen (Used after the app writes or reads it)
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn mark_watched_file_seen() {
+    let mut state = FILE_WATCH.lock().expect("file watch lock poisoned");
+    state.last_modified = state.path.as_deref().and_then(modified_time);
+    state.has_changed = false;
+}
+
+/// Returns the path of the watched file if it changed since last checked
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn take_watched_file_change() -> Option<PathBuf> {
+    let mut state = FILE_WATCH.lock().expect("file watch lock poisoned");
+    if std::mem::take(&mut state.has_changed) {
+        state.path.clone()
+    } else {
+        None
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn modified_time(path: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|x| x.modified()).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn check_watched_file() {
+    let mut state = FILE_WATCH.lock().expect("file watch lock poisoned");
+    let Some(path) = state.path.as_deref() else {
+        return;
+    };
+    // A missing file is not reported as a change because scripts often delete before rewriting
+    let Some(modified) = modified_time(path) else {
+        return;
+    };
+    if state.last_modified != Some(modified) {
+        log::info!("Watched file changed on disk: {path:?}");
+        state.last_modified = Some(modified);
+        state.has_changed = true;
+        if let Some(ctx) = state.ctx.as_ref() {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Starts looking up the latest release as a task on the background worker's runtime (The
+/// result is taken with [`take_update_check_result`])
+///
+/// Does nothing if a check is already in progress
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn request_update_check(ctx: &egui::Context) {
+    let mut state = UPDATE_CHECK.lock().expect("update check lock poisoned");
+    if state.is_in_progress {
+        return;
+    }
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        state.result = Some(Err(anyhow::anyhow!(
+            "unable to check for updates without the background worker"
+        )));
+        return;
+    };
+    state.is_in_progress = true;
+    let ctx = ctx.clone();
+    // Blocking task so a slow network does not delay the autosaves done by the worker loop
+    runtime.spawn_blocking(move || {
+        let result = fetch_latest_release_tag();
+        log::info!("Update check finished: {result:?}");
+        let mut state = UPDATE_CHECK.lock().expect("update check lock poisoned");
+        state.is_in_progress = false;
+        state.result = Some(result);
+        ctx.request_repaint();
+    });
+}
+
+/// Returns the tag of the latest release once a requested check finishes
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn take_update_check_result() -> Option<anyhow::Result<String>> {
+    UPDATE_CHECK
+        .lock()
+        .expect("update check lock poisoned")
+        .result
+        .take()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(serde::Deserialize)]
+struct ReleaseInfo {
+    tag_name: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_latest_release_tag() -> anyhow::Result<String> {
+    use anyhow::Context as _;
+    let response = ureq::get(crate::LATEST_RELEASE_API_URL)
+        .timeout(std::time::Duration::from_secs(10))
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .context("request for the latest release failed")?;
+    let body = response
+        .into_string()
+        .context("failed to read the latest release response")?;
+    let release: ReleaseInfo =
+        serde_json::from_str(&body).context("no release tag found in the response")?;
+    Ok(release.tag_name)
+}
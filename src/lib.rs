@@ -3,3 +3,25 @@
 mod app;
 pub mod background_worker;
 pub use app::DBV;
+
+/// Types needed to add generators to the Generate menu from downstream builds
+pub mod generators {
+    pub use crate::app::{
+        data_definition::{DataLabel, DataPoint, DataPoints},
+        data_generation::{DataGenerator, GeneratorParams, ParamKind, ParamSpec, ParamValue},
+    };
+}
+
+#[cfg(all(feature = "automation", not(target_arch = "wasm32")))]
+pub use app::automation;
+
+/// Name of the app also used by eframe to choose where to store the app's state
+pub const APP_NAME: &str = "DBV - Data Builder Viewer";
+
+/// Where the source code and releases are published
+pub const REPOSITORY_URL: &str = "https://github.com/uruth-lab/dbv";
+
+/// Used to check if there is a newer release
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const LATEST_RELEASE_API_URL: &str =
+    "https://api.github.com/repos/uruth-lab/dbv/releases/latest";
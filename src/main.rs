@@ -23,7 +23,7 @@ fn main() -> eframe::Result<()> {
 
     // TODO 5: Find a way to delete saved data and not save on that close to get back to defaults
     eframe::run_native(
-        "DBV - Data Builder Viewer",
+        dbv::APP_NAME,
         native_options,
         Box::new(|cc| Box::new(dbv::DBV::new(cc))),
     )